@@ -0,0 +1,166 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Benchmarks use synthetic beacon chain data signed by generated sync committees.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use crate::fixtures::{self, BeaconBlock, BeaconState, Committee, PERIOD_START_SLOT};
+use crate::Pallet as BeaconLightClient;
+use bridge_types::beacon::{AncestryProof, CompactExecutionHeader};
+use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_support::assert_ok;
+use frame_system::{self, RawOrigin};
+use sp_runtime::traits::{Hash, Keccak256};
+
+const CHECKPOINT_SLOT: u64 = PERIOD_START_SLOT + 100;
+
+fn network_id() -> EVMChainId {
+    NetworkConfig::BeaconMainnet.chain_id()
+}
+
+fn initialize<T: Config>(committee: &Committee) -> BeaconBlock {
+    let (checkpoint, block) = fixtures::checkpoint(CHECKPOINT_SLOT, committee);
+    assert_ok!(BeaconLightClient::<T>::initialize(
+        RawOrigin::Root.into(),
+        NetworkConfig::BeaconMainnet,
+        BeaconNetworkConfig::mainnet(),
+        checkpoint
+    ));
+    block
+}
+
+fn finalized_block(slot: u64, block_roots_root: H256) -> (BeaconBlock, BeaconState) {
+    let state = BeaconState {
+        slot,
+        block_roots_root,
+        ..Default::default()
+    };
+    let block = BeaconBlock::new(
+        slot,
+        state.root(),
+        fixtures::execution_header(slot, H256::zero()),
+    );
+    (block, state)
+}
+
+fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
+    frame_system::Pallet::<T>::assert_last_event(generic_event.into());
+}
+
+benchmarks! {
+    initialize {
+        let (checkpoint, block) = fixtures::checkpoint(CHECKPOINT_SLOT, &Committee::new(1));
+    }: _(RawOrigin::Root, NetworkConfig::BeaconMainnet, BeaconNetworkConfig::mainnet(), checkpoint)
+    verify {
+        assert_last_event::<T>(Event::Initialized {
+            network_id: network_id(),
+            block_root: block.root(),
+            slot: CHECKPOINT_SLOT,
+        }.into());
+    }
+
+    // Worst case: the whole sync committee signs the update which also
+    // proves the next sync committee.
+    submit_update {
+        let caller: T::AccountId = whitelisted_caller();
+        let config = BeaconNetworkConfig::mainnet();
+        let current = Committee::new(1);
+        let next = Committee::new(2);
+        initialize::<T>(&current);
+        let (finalized, state) = finalized_block(CHECKPOINT_SLOT + 64, H256::zero());
+        let update = fixtures::update(
+            &config,
+            &current,
+            beacon::SYNC_COMMITTEE_SIZE,
+            &finalized,
+            &state,
+            Some(&next),
+        );
+    }: _(RawOrigin::Signed(caller), network_id(), update)
+    verify {
+        assert_last_event::<T>(Event::BeaconHeaderFinalized {
+            network_id: network_id(),
+            block_root: finalized.root(),
+            slot: finalized.header.slot,
+        }.into());
+    }
+
+    // Worst case: the header is proven to be an ancestor of a finalized one.
+    import_execution_header {
+        let caller: T::AccountId = whitelisted_caller();
+        let config = BeaconNetworkConfig::mainnet();
+        let committee = Committee::new(1);
+        initialize::<T>(&committee);
+
+        let (ancestor, _) = finalized_block(CHECKPOINT_SLOT + 10, H256::zero());
+        let mut block_roots = vec![H256::zero(); beacon::SLOTS_PER_HISTORICAL_ROOT as usize];
+        let index = (ancestor.header.slot % beacon::SLOTS_PER_HISTORICAL_ROOT) as usize;
+        block_roots[index] = ancestor.root();
+        let (block_roots_root, header_branch) = fixtures::merkle_proof(block_roots, index);
+        let (finalized, state) = finalized_block(CHECKPOINT_SLOT + 64, block_roots_root);
+        assert_ok!(BeaconLightClient::<T>::submit_update(
+            RawOrigin::Signed(caller.clone()).into(),
+            network_id(),
+            fixtures::update(&config, &committee, beacon::SYNC_COMMITTEE_SIZE, &finalized, &state, None)
+        ));
+        let mut update = ancestor.execution_update();
+        update.ancestry_proof = Some(AncestryProof {
+            header_branch,
+            finalized_block_root: finalized.root(),
+        });
+        let block_hash = ancestor.execution_header.block_hash;
+    }: _(RawOrigin::Signed(caller), network_id(), update)
+    verify {
+        assert_last_event::<T>(Event::ExecutionHeaderImported {
+            network_id: network_id(),
+            block_hash,
+            block_number: ancestor.execution_header.block_number,
+        }.into());
+    }
+
+    verifier_verify {
+        let proof = BeaconLightClient::<T>::valid_proof().expect("proof is provided");
+        let receipts_root = fixtures::receipts_root();
+        ExecutionHeaders::<T>::insert(network_id(), proof.block_hash, CompactExecutionHeader {
+            receipts_root,
+            ..Default::default()
+        });
+        let receipt = bridge_types::header::check_receipt_proof(receipts_root, &proof.data)
+            .expect("proof is valid")
+            .expect("receipt is valid");
+        let message = Keccak256::hash_of(&receipt.logs[0]);
+    }: {
+        assert_ok!(BeaconLightClient::<T>::verify(network_id().into(), message, &proof));
+    }
+
+    impl_benchmark_test_suite!(BeaconLightClient, crate::mock::new_test_ext(), crate::mock::Test);
+}
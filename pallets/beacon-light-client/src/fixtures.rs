@@ -0,0 +1,305 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Synthetic beacon chain data signed by generated sync committees.
+
+use bridge_types::beacon::{
+    self, compute_signing_root, BeaconHeader, BeaconNetworkConfig, CheckpointUpdate,
+    ExecutionHeaderUpdate, ExecutionPayloadHeader, ForkVersions, LightClientUpdate,
+    NextSyncCommitteeUpdate, PublicKey, Signature, StateGeneralizedIndices, SyncAggregate,
+    SyncCommittee,
+};
+use bridge_types::H256;
+use hex_literal::hex;
+use milagro_bls::{AggregateSignature, SecretKey};
+use sp_io::hashing::sha2_256;
+use sp_std::prelude::*;
+
+/// Number of distinct keys in a generated sync committee, committee members reuse them.
+const KEYS_PER_COMMITTEE: usize = 4;
+
+/// Slot at the start of a sync committee period after the Deneb fork on mainnet.
+pub const PERIOD_START_SLOT: u64 = 1100 * 8192;
+
+/// Slot at the start of a sync committee period after the Electra fork on mainnet.
+pub const ELECTRA_PERIOD_START_SLOT: u64 = 1430 * 8192;
+
+fn hash_pair(left: H256, right: H256) -> H256 {
+    let mut data = [0u8; 64];
+    data[..32].copy_from_slice(left.as_bytes());
+    data[32..].copy_from_slice(right.as_bytes());
+    sha2_256(&data).into()
+}
+
+/// Root and branch of the leaf at `index` of the tree built from `leaves`.
+pub fn merkle_proof(mut leaves: Vec<H256>, mut index: usize) -> (H256, Vec<H256>) {
+    assert!(leaves.len().is_power_of_two());
+    let mut branch = Vec::new();
+    while leaves.len() > 1 {
+        branch.push(leaves[index ^ 1]);
+        leaves = leaves
+            .chunks(2)
+            .map(|pair| hash_pair(pair[0], pair[1]))
+            .collect();
+        index /= 2;
+    }
+    (leaves[0], branch)
+}
+
+pub struct Committee {
+    pub keys: Vec<SecretKey>,
+    pub sync_committee: SyncCommittee,
+}
+
+impl Committee {
+    pub fn new(seed: u8) -> Self {
+        let keys: Vec<SecretKey> = (0..KEYS_PER_COMMITTEE)
+            .map(|i| {
+                let mut bytes = [0u8; 32];
+                bytes[30] = seed;
+                bytes[31] = i as u8 + 1;
+                SecretKey::from_bytes(&bytes).unwrap()
+            })
+            .collect();
+        let pubkeys: Vec<PublicKey> = keys
+            .iter()
+            .map(|key| PublicKey(milagro_bls::PublicKey::from_secret_key(key).as_bytes()))
+            .collect();
+        let sync_committee = SyncCommittee {
+            pubkeys: (0..beacon::SYNC_COMMITTEE_SIZE)
+                .map(|i| pubkeys[i % KEYS_PER_COMMITTEE])
+                .collect(),
+            aggregate_pubkey: pubkeys[0],
+        };
+        Committee {
+            keys,
+            sync_committee,
+        }
+    }
+
+    /// Aggregate signature of the first `participants` committee members.
+    pub fn sign(&self, message: H256, participants: usize) -> SyncAggregate {
+        let mut sync_committee_bits = vec![0u8; beacon::SYNC_COMMITTEE_SIZE / 8];
+        let mut signature = AggregateSignature::new();
+        for i in 0..participants {
+            sync_committee_bits[i / 8] |= 1 << (i % 8);
+            let key = &self.keys[i % KEYS_PER_COMMITTEE];
+            signature.add(&milagro_bls::Signature::new(message.as_bytes(), key));
+        }
+        SyncAggregate {
+            sync_committee_bits,
+            sync_committee_signature: Signature(signature.as_bytes()),
+        }
+    }
+}
+
+/// Fields of a beacon state proven by the light client.
+#[derive(Default)]
+pub struct BeaconState {
+    /// Slot of the state, selects the mainnet state layout.
+    pub slot: u64,
+    pub block_roots_root: H256,
+    pub finalized_root: H256,
+    pub current_sync_committee: SyncCommittee,
+    pub next_sync_committee: SyncCommittee,
+}
+
+impl BeaconState {
+    pub fn gindices(&self) -> StateGeneralizedIndices {
+        ForkVersions::mainnet().state_gindices(self.slot)
+    }
+
+    fn leaves(&self) -> Vec<H256> {
+        let gindices = self.gindices();
+        let mut leaves = vec![H256::zero(); 1 << gindices.block_roots.depth];
+        leaves[gindices.block_roots.index as usize] = self.block_roots_root;
+        // `finalized_checkpoint` container of zero epoch and the finalized root
+        leaves[gindices.finalized_root.index as usize / 2] =
+            hash_pair(H256::zero(), self.finalized_root);
+        leaves[gindices.current_sync_committee.index as usize] =
+            self.current_sync_committee.hash_tree_root();
+        leaves[gindices.next_sync_committee.index as usize] =
+            self.next_sync_committee.hash_tree_root();
+        leaves
+    }
+
+    pub fn root(&self) -> H256 {
+        merkle_proof(self.leaves(), 0).0
+    }
+
+    pub fn branch(&self, index: u64) -> Vec<H256> {
+        merkle_proof(self.leaves(), index as usize).1
+    }
+
+    pub fn current_sync_committee_branch(&self) -> Vec<H256> {
+        self.branch(self.gindices().current_sync_committee.index)
+    }
+
+    pub fn next_sync_committee_branch(&self) -> Vec<H256> {
+        self.branch(self.gindices().next_sync_committee.index)
+    }
+
+    pub fn block_roots_branch(&self) -> Vec<H256> {
+        self.branch(self.gindices().block_roots.index)
+    }
+
+    pub fn finality_branch(&self) -> Vec<H256> {
+        let mut branch = vec![H256::zero()];
+        branch.extend(self.branch(self.gindices().finalized_root.index / 2));
+        branch
+    }
+}
+
+/// Beacon block with the execution payload header proven in its body.
+pub struct BeaconBlock {
+    pub header: BeaconHeader,
+    pub execution_header: ExecutionPayloadHeader,
+    pub execution_branch: Vec<H256>,
+}
+
+impl BeaconBlock {
+    pub fn new(slot: u64, state_root: H256, execution_header: ExecutionPayloadHeader) -> Self {
+        let mut leaves = vec![H256::zero(); 16];
+        leaves[beacon::EXECUTION_PAYLOAD_INDEX as usize] = execution_header.hash_tree_root();
+        let (body_root, execution_branch) =
+            merkle_proof(leaves, beacon::EXECUTION_PAYLOAD_INDEX as usize);
+        BeaconBlock {
+            header: BeaconHeader {
+                slot,
+                proposer_index: slot % 1000,
+                parent_root: H256::repeat_byte(slot as u8),
+                state_root,
+                body_root,
+            },
+            execution_header,
+            execution_branch,
+        }
+    }
+
+    pub fn root(&self) -> H256 {
+        self.header.hash_tree_root()
+    }
+
+    pub fn execution_update(&self) -> ExecutionHeaderUpdate {
+        ExecutionHeaderUpdate {
+            header: self.header.clone(),
+            ancestry_proof: None,
+            execution_header: self.execution_header.clone(),
+            execution_branch: self.execution_branch.clone(),
+        }
+    }
+}
+
+pub fn execution_header(block_number: u64, receipts_root: H256) -> ExecutionPayloadHeader {
+    ExecutionPayloadHeader {
+        block_number,
+        receipts_root,
+        block_hash: H256::from_low_u64_be(block_number),
+        base_fee_per_gas: 7_000_000_000u64.into(),
+        ..Default::default()
+    }
+}
+
+pub fn checkpoint(slot: u64, committee: &Committee) -> (CheckpointUpdate, BeaconBlock) {
+    let state = BeaconState {
+        slot,
+        current_sync_committee: committee.sync_committee.clone(),
+        ..Default::default()
+    };
+    let block = BeaconBlock::new(slot, state.root(), execution_header(slot, H256::zero()));
+    let checkpoint = CheckpointUpdate {
+        header: block.header.clone(),
+        current_sync_committee: committee.sync_committee.clone(),
+        current_sync_committee_branch: state.current_sync_committee_branch(),
+        block_roots_root: state.block_roots_root,
+        block_roots_branch: state.block_roots_branch(),
+    };
+    (checkpoint, block)
+}
+
+/// Update finalizing `finalized` which is attested one epoch later and signed by `signer`.
+pub fn update(
+    config: &BeaconNetworkConfig,
+    signer: &Committee,
+    participants: usize,
+    finalized: &BeaconBlock,
+    finalized_state: &BeaconState,
+    next_sync_committee: Option<&Committee>,
+) -> LightClientUpdate {
+    let attested_slot = finalized.header.slot + beacon::SLOTS_PER_EPOCH;
+    let mut attested_state = BeaconState {
+        slot: attested_slot,
+        finalized_root: finalized.root(),
+        ..Default::default()
+    };
+    if let Some(next) = next_sync_committee {
+        attested_state.next_sync_committee = next.sync_committee.clone();
+    }
+    let attested = BeaconBlock::new(
+        attested_slot,
+        attested_state.root(),
+        execution_header(attested_slot, H256::zero()),
+    );
+    let signature_slot = attested_slot + 1;
+    let signing_root = compute_signing_root(
+        attested.root(),
+        config.sync_committee_domain(signature_slot),
+    );
+    LightClientUpdate {
+        attested_header: attested.header.clone(),
+        sync_aggregate: signer.sign(signing_root, participants),
+        signature_slot,
+        next_sync_committee_update: next_sync_committee.map(|next| NextSyncCommitteeUpdate {
+            next_sync_committee: next.sync_committee.clone(),
+            next_sync_committee_branch: attested_state.next_sync_committee_branch(),
+        }),
+        finalized_header: finalized.header.clone(),
+        finality_branch: attested_state.finality_branch(),
+        block_roots_root: finalized_state.block_roots_root,
+        block_roots_branch: finalized_state.block_roots_branch(),
+    }
+}
+
+/// Receipts root of the block containing the receipt proven by [`receipt_proof`].
+pub fn receipts_root() -> H256 {
+    hex!("fd5e397a84884641f53c496804f24b5276cbb8c5c9cfc2342246be8e3ce5ad02").into()
+}
+
+/// Proof of the receipt at index 5 of the block with [`receipts_root`].
+pub fn receipt_proof() -> Vec<Vec<u8>> {
+    vec![
+        hex!("f90131a0b5ba404eb5a6a88e56579f4d37ef9813b5ad7f86f0823ff3b407ac5a6bb465eca0398ead2655e78e03c127ce22c5830e90f18b1601ec055f938336c084feb915a9a026d322c26e46c50942c1aabde50e36df5cde572aed650ce73ea3182c6e90a02ca00600a356135f4db1db0d9842264cdff2652676f881669e91e316c0b6dd783011a0837f1deb4075336da320388c1edfffc56c448a43f4a5ba031300d32a7b509fc5a01c3ac82fd65b4aba7f9afaf604d9c82ec7e2deb573a091ae235751bc5c0c288da05d454159d9071b0f68b6e0503d290f23ac7602c1db0c569dee4605d8f5298f09a00bbed10350ec954448df795f6fd46e3faefc800ede061b3840eedc6e2b07a74da0acb02d26a3650f2064c14a435fdf1f668d8655daf455ebdf671713a7c089b3898080808080808080")
+        .to_vec(),
+        hex!("f901f180a00046a08d4f0bdbdc6b31903086ce323182bce6725e7d9415f7ff91ee8f4820bda0e7cd26ad5f3d2771e4b5ab788e268a14a10209f94ee918eb6c829d21d3d11c1da00d4a56d9e9a6751874fd86c7e3cb1c6ad5a848da62751325f478978a00ea966ea064b81920c8f04a8a1e21f53a8280e739fbb7b00b2ab92493ca3f610b70e8ac85a0b1040ed4c55a73178b76abb16f946ce5bebd6b93ab873c83327df54047d12c27a0de6485e9ac58dc6e2b04b4bb38f562684f0b1a2ee586cc11079e7d9a9dc40b32a0d394f4d3532c3124a65fa36e69147e04fd20453a72ee9c50660f17e13ce9df48a066501003fc3e3478efd2803cd0eded6bbe9243ca01ba754d6327071ddbcbc649a0b2684e518f325fee39fc8ea81b68f3f5c785be00d087f3bed8857ae2ee8da26ea071060a5c52042e8d7ce21092f8ecf06053beb9a0b773a6f91a30c4220aa276b2a0fc22436632574ccf6043d0986dede27ea94c9ca9a3bb5ec03ce776a4ddef24a9a05a8a1d6698c4e7d8cc3a2506cb9b12ea9a079c9c7099bc919dc804033cc556e4a0170c468b0716fd36d161f0bf05875f15756a2976de92f9efe7716320509d79c9a0182f909a90cab169f3efb62387f9cccdd61440acc4deec42f68a4f7ca58075c7a055cf0e9202ac75689b76318f1171f3a44465eddc06aae0713bfb6b34fdd27b7980")
+        .to_vec(),
+        hex!("f904de20b904daf904d701830652f0b9010004200000000000000000000080020000000000010000000000010000000000000000000000000000000000000000000002000000080000000000000000200000000000000000000000000008000000220000000000400010000000000000000000000000000000000000000000000000000000000000040000000010000100000000000800000000004000000000000000000000000000080000004000000000020000000000020000000000000000000000000000000000000000000004000000000002000000000100000000000000000000000000001000000002000020000010200000000000010000000000000000000000000000000000000010000000f903ccf89b9421130f34829b4c343142047a28ce96ec07814b15f863a0ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3efa00000000000000000000000007d843005c7433c16b27ff939cb37471541561ebda0000000000000000000000000e9c1281aae66801fa35ec404d5f2aea393ff6988a000000000000000000000000000000000000000000000000000000005d09b7380f89b9421130f34829b4c343142047a28ce96ec07814b15f863a08c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925a00000000000000000000000007d843005c7433c16b27ff939cb37471541561ebda00000000000000000000000007a250d5630b4cf539739df2c5dacb4c659f2488da0ffffffffffffffffffffffffffffffffffffffffffffffffffffffcc840c6920f89b94c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2f863a0ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3efa0000000000000000000000000e9c1281aae66801fa35ec404d5f2aea393ff6988a00000000000000000000000007a250d5630b4cf539739df2c5dacb4c659f2488da000000000000000000000000000000000000000000000000003e973b5a5d1078ef87994e9c1281aae66801fa35ec404d5f2aea393ff6988e1a01c411e9a96e071241c2f21f7726b17ae89e3cab4c78be50e062b03a9fffbbad1b840000000000000000000000000000000000000000000000000000001f1420ad1d40000000000000000000000000000000000000000000000014ad400879d159a38f8fc94e9c1281aae66801fa35ec404d5f2aea393ff6988f863a0d78ad95fa46c994b6551d0da85fc275fe613ce37657fb8d5e3d130840159d822a00000000000000000000000007a250d5630b4cf539739df2c5dacb4c659f2488da00000000000000000000000007a250d5630b4cf539739df2c5dacb4c659f2488db88000000000000000000000000000000000000000000000000000000005d415f3320000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000003e973b5a5d1078ef87a94c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2f842a07fcf532c15f0a6db0bd6d0e038bea71d30d808c7d98cb3bf7268a95bf5081b65a00000000000000000000000007a250d5630b4cf539739df2c5dacb4c659f2488da000000000000000000000000000000000000000000000000003e973b5a5d1078e")
+        .to_vec(),
+    ]
+}
@@ -0,0 +1,665 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Beacon chain light client.
+//!
+//! Follows the beacon chain of post-merge EVM networks using Altair sync
+//! committees: finalized header updates are accepted when signed by a
+//! supermajority of the current (or next) sync committee, committees are
+//! rotated every sync committee period.
+//!
+//! Execution payload headers of finalized beacon blocks are stored and
+//! used to verify receipt inclusion proofs for inbound EVM messages
+//! (see [`bridge_types::traits::Verifier`]).
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use bridge_types::beacon::{
+    self, compute_period, compute_signing_root, is_valid_merkle_branch, BeaconHeader,
+    BeaconNetworkConfig, CheckpointUpdate, CompactExecutionHeader, ExecutionHeaderUpdate,
+    GeneralizedIndex, LightClientUpdate, PublicKey, Signature, SyncCommittee,
+};
+use bridge_types::network_config::{Consensus, NetworkConfig};
+use bridge_types::traits::{EthereumGasPriceOracle, Verifier};
+use bridge_types::{EVMChainId, GenericNetworkId, H256, U256};
+use codec::{Decode, Encode};
+use frame_support::dispatch::{DispatchError, DispatchResult};
+use frame_support::ensure;
+use frame_support::weights::Weight;
+use frame_support::RuntimeDebug;
+use sp_std::prelude::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(any(test, feature = "runtime-benchmarks"))]
+mod fixtures;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+use weights::WeightInfo;
+
+pub use pallet::*;
+
+/// Data of a finalized beacon header required to prove its ancestors.
+#[derive(
+    Clone, Copy, Default, Encode, Decode, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo,
+)]
+pub struct FinalizedHeaderState {
+    pub slot: u64,
+    /// Root of the `block_roots` vector in the header state.
+    pub block_roots_root: H256,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use frame_support::pallet_prelude::*;
+    use frame_support::{fail, Identity};
+    use frame_system::pallet_prelude::*;
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+        type WeightInfo: WeightInfo;
+    }
+
+    #[pallet::pallet]
+    #[pallet::generate_store(pub(super) trait Store)]
+    #[pallet::without_storage_info]
+    pub struct Pallet<T>(_);
+
+    /// Beacon chain configurations of the registered networks.
+    #[pallet::storage]
+    #[pallet::getter(fn beacon_config)]
+    pub type BeaconConfig<T> =
+        StorageMap<_, Identity, EVMChainId, BeaconNetworkConfig, OptionQuery>;
+
+    /// Latest finalized beacon header and its root.
+    #[pallet::storage]
+    #[pallet::getter(fn latest_finalized_header)]
+    pub type LatestFinalizedHeader<T> =
+        StorageMap<_, Identity, EVMChainId, (H256, BeaconHeader), OptionQuery>;
+
+    /// Finalized beacon headers by block root.
+    #[pallet::storage]
+    #[pallet::getter(fn finalized_header)]
+    pub type FinalizedHeaders<T> = StorageDoubleMap<
+        _,
+        Identity,
+        EVMChainId,
+        Identity,
+        H256,
+        FinalizedHeaderState,
+        OptionQuery,
+    >;
+
+    /// Sync committee of the latest finalized header period.
+    #[pallet::storage]
+    #[pallet::getter(fn current_sync_committee)]
+    pub type CurrentSyncCommittee<T> =
+        StorageMap<_, Identity, EVMChainId, SyncCommittee, OptionQuery>;
+
+    /// Sync committee of the period following the latest finalized header period.
+    #[pallet::storage]
+    #[pallet::getter(fn next_sync_committee)]
+    pub type NextSyncCommittee<T> = StorageMap<_, Identity, EVMChainId, SyncCommittee, OptionQuery>;
+
+    /// Execution headers of the finalized beacon blocks by block hash.
+    #[pallet::storage]
+    #[pallet::getter(fn execution_header)]
+    pub type ExecutionHeaders<T> = StorageDoubleMap<
+        _,
+        Identity,
+        EVMChainId,
+        Identity,
+        H256,
+        CompactExecutionHeader,
+        OptionQuery,
+    >;
+
+    /// Hash of the imported execution header with the highest number.
+    #[pallet::storage]
+    #[pallet::getter(fn latest_execution_header)]
+    pub type LatestExecutionHeader<T> = StorageMap<_, Identity, EVMChainId, H256, OptionQuery>;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        Initialized {
+            network_id: EVMChainId,
+            block_root: H256,
+            slot: u64,
+        },
+        BeaconHeaderFinalized {
+            network_id: EVMChainId,
+            block_root: H256,
+            slot: u64,
+        },
+        SyncCommitteeUpdated {
+            network_id: EVMChainId,
+            period: u64,
+        },
+        ExecutionHeaderImported {
+            network_id: EVMChainId,
+            block_hash: H256,
+            block_number: u64,
+        },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Network with given chain id is not registered.
+        NetworkNotFound,
+        /// Network with given chain id is already registered.
+        NetworkAlreadyExists,
+        /// Network id is not an EVM network id.
+        InvalidNetwork,
+        /// Network is not finalized by the beacon chain.
+        ConsensusNotSupported,
+        /// Sync committee has unexpected size.
+        InvalidSyncCommittee,
+        /// Sync committee Merkle branch is invalid.
+        InvalidSyncCommitteeBranch,
+        /// Next sync committee differs from the known one.
+        InvalidSyncCommitteeUpdate,
+        /// Sync committee participation bits have unexpected size.
+        InvalidSyncCommitteeBits,
+        /// Less than 2/3 of the sync committee signed the update.
+        InsufficientSyncCommitteeParticipants,
+        /// Block roots Merkle branch is invalid.
+        InvalidBlockRootsBranch,
+        /// Finality Merkle branch is invalid.
+        InvalidFinalityBranch,
+        /// Update slots are inconsistent.
+        InvalidUpdateSlot,
+        /// Update is signed by a sync committee which is not known yet.
+        SkippedSyncCommitteePeriod,
+        /// Update doesn't advance the light client state.
+        IrrelevantUpdate,
+        /// Sync committee signature is invalid.
+        InvalidSignature,
+        /// Beacon header is not finalized.
+        HeaderNotFinalized,
+        /// Proof of the beacon header ancestry is invalid.
+        InvalidAncestryProof,
+        /// Execution header Merkle branch is invalid.
+        InvalidExecutionHeaderProof,
+        /// Execution header is already imported.
+        DuplicateExecutionHeader,
+        /// Execution header is not found.
+        HeaderNotFound,
+        /// Receipt proof is invalid.
+        InvalidProof,
+        /// Failed to decode the proven receipt.
+        DecodeFailed,
+        /// Proven receipt doesn't contain the message log.
+        LogNotFound,
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Initialize the light client of the network with a trusted checkpoint.
+        #[pallet::call_index(0)]
+        #[pallet::weight(<T as Config>::WeightInfo::initialize())]
+        pub fn initialize(
+            origin: OriginFor<T>,
+            network_config: NetworkConfig,
+            beacon_config: BeaconNetworkConfig,
+            checkpoint: CheckpointUpdate,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            ensure!(
+                network_config.consensus() == Consensus::ProofOfStake,
+                Error::<T>::ConsensusNotSupported
+            );
+            let network_id = network_config.chain_id();
+            ensure!(
+                !BeaconConfig::<T>::contains_key(network_id),
+                Error::<T>::NetworkAlreadyExists
+            );
+            let gindices = beacon_config
+                .fork_versions
+                .state_gindices(checkpoint.header.slot);
+            Self::verify_sync_committee(
+                &checkpoint.current_sync_committee,
+                &checkpoint.current_sync_committee_branch,
+                gindices.current_sync_committee,
+                checkpoint.header.state_root,
+            )?;
+            ensure!(
+                is_valid_merkle_branch(
+                    checkpoint.block_roots_root,
+                    &checkpoint.block_roots_branch,
+                    gindices.block_roots.depth,
+                    gindices.block_roots.index,
+                    checkpoint.header.state_root,
+                ),
+                Error::<T>::InvalidBlockRootsBranch
+            );
+
+            let block_root = checkpoint.header.hash_tree_root();
+            let slot = checkpoint.header.slot;
+            BeaconConfig::<T>::insert(network_id, beacon_config);
+            CurrentSyncCommittee::<T>::insert(network_id, checkpoint.current_sync_committee);
+            Self::store_finalized_header(
+                network_id,
+                block_root,
+                checkpoint.header,
+                checkpoint.block_roots_root,
+            );
+            Self::deposit_event(Event::Initialized {
+                network_id,
+                block_root,
+                slot,
+            });
+            Ok(().into())
+        }
+
+        /// Submit a finalized header update signed by the sync committee.
+        #[pallet::call_index(1)]
+        #[pallet::weight(<T as Config>::WeightInfo::submit_update())]
+        pub fn submit_update(
+            origin: OriginFor<T>,
+            network_id: EVMChainId,
+            update: LightClientUpdate,
+        ) -> DispatchResultWithPostInfo {
+            ensure_signed(origin)?;
+            Self::process_update(network_id, update)?;
+            Ok(().into())
+        }
+
+        /// Import execution header of a finalized beacon block.
+        #[pallet::call_index(2)]
+        #[pallet::weight(<T as Config>::WeightInfo::import_execution_header())]
+        pub fn import_execution_header(
+            origin: OriginFor<T>,
+            network_id: EVMChainId,
+            update: ExecutionHeaderUpdate,
+        ) -> DispatchResultWithPostInfo {
+            ensure_signed(origin)?;
+            ensure!(
+                BeaconConfig::<T>::contains_key(network_id),
+                Error::<T>::NetworkNotFound
+            );
+            let block_root = update.header.hash_tree_root();
+            match &update.ancestry_proof {
+                None => ensure!(
+                    FinalizedHeaders::<T>::contains_key(network_id, block_root),
+                    Error::<T>::HeaderNotFinalized
+                ),
+                Some(proof) => {
+                    let Some(finalized) = FinalizedHeaders::<T>::get(network_id, proof.finalized_block_root) else {
+                        fail!(Error::<T>::HeaderNotFinalized)
+                    };
+                    ensure!(
+                        update.header.slot < finalized.slot
+                            && finalized.slot
+                                <= update.header.slot + beacon::SLOTS_PER_HISTORICAL_ROOT,
+                        Error::<T>::InvalidAncestryProof
+                    );
+                    ensure!(
+                        is_valid_merkle_branch(
+                            block_root,
+                            &proof.header_branch,
+                            beacon::HISTORICAL_ROOT_DEPTH,
+                            update.header.slot % beacon::SLOTS_PER_HISTORICAL_ROOT,
+                            finalized.block_roots_root,
+                        ),
+                        Error::<T>::InvalidAncestryProof
+                    );
+                }
+            }
+            ensure!(
+                is_valid_merkle_branch(
+                    update.execution_header.hash_tree_root(),
+                    &update.execution_branch,
+                    beacon::EXECUTION_PAYLOAD_DEPTH,
+                    beacon::EXECUTION_PAYLOAD_INDEX,
+                    update.header.body_root,
+                ),
+                Error::<T>::InvalidExecutionHeaderProof
+            );
+
+            let block_hash = update.execution_header.block_hash;
+            ensure!(
+                !ExecutionHeaders::<T>::contains_key(network_id, block_hash),
+                Error::<T>::DuplicateExecutionHeader
+            );
+            let header = CompactExecutionHeader::from(&update.execution_header);
+            let block_number = header.block_number;
+            let is_latest = LatestExecutionHeader::<T>::get(network_id)
+                .and_then(|hash| ExecutionHeaders::<T>::get(network_id, hash))
+                .map(|latest| latest.block_number < block_number)
+                .unwrap_or(true);
+            ExecutionHeaders::<T>::insert(network_id, block_hash, header);
+            if is_latest {
+                LatestExecutionHeader::<T>::insert(network_id, block_hash);
+            }
+            Self::deposit_event(Event::ExecutionHeaderImported {
+                network_id,
+                block_hash,
+                block_number,
+            });
+            Ok(().into())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        pub(crate) fn process_update(
+            network_id: EVMChainId,
+            update: LightClientUpdate,
+        ) -> DispatchResult {
+            let Some(config) = BeaconConfig::<T>::get(network_id) else {
+                fail!(Error::<T>::NetworkNotFound)
+            };
+            let Some((_, finalized_header)) = LatestFinalizedHeader::<T>::get(network_id) else {
+                fail!(Error::<T>::NetworkNotFound)
+            };
+            let next_sync_committee = NextSyncCommittee::<T>::get(network_id);
+
+            let sync_aggregate = &update.sync_aggregate;
+            ensure!(
+                sync_aggregate.sync_committee_bits.len() == beacon::SYNC_COMMITTEE_SIZE / 8,
+                Error::<T>::InvalidSyncCommitteeBits
+            );
+            ensure!(
+                sync_aggregate.participants() * 3 >= beacon::SYNC_COMMITTEE_SIZE * 2,
+                Error::<T>::InsufficientSyncCommitteeParticipants
+            );
+            ensure!(
+                update.signature_slot > update.attested_header.slot
+                    && update.attested_header.slot >= update.finalized_header.slot,
+                Error::<T>::InvalidUpdateSlot
+            );
+
+            let store_period = compute_period(finalized_header.slot);
+            let signature_period = compute_period(update.signature_slot);
+            let attested_period = compute_period(update.attested_header.slot);
+            let finalized_period = compute_period(update.finalized_header.slot);
+            if next_sync_committee.is_some() {
+                ensure!(
+                    signature_period == store_period || signature_period == store_period + 1,
+                    Error::<T>::SkippedSyncCommitteePeriod
+                );
+            } else {
+                ensure!(
+                    signature_period == store_period && finalized_period == store_period,
+                    Error::<T>::SkippedSyncCommitteePeriod
+                );
+            }
+
+            // Next sync committee is learned from an update attested in the store period
+            let learns_next_sync_committee = next_sync_committee.is_none()
+                && update.next_sync_committee_update.is_some()
+                && attested_period == store_period;
+            ensure!(
+                update.finalized_header.slot > finalized_header.slot || learns_next_sync_committee,
+                Error::<T>::IrrelevantUpdate
+            );
+
+            // State layout depends on the fork of the block the state belongs to
+            let attested_gindices = config
+                .fork_versions
+                .state_gindices(update.attested_header.slot);
+            let finalized_gindices = config
+                .fork_versions
+                .state_gindices(update.finalized_header.slot);
+            let finalized_block_root = update.finalized_header.hash_tree_root();
+            ensure!(
+                is_valid_merkle_branch(
+                    finalized_block_root,
+                    &update.finality_branch,
+                    attested_gindices.finalized_root.depth,
+                    attested_gindices.finalized_root.index,
+                    update.attested_header.state_root,
+                ),
+                Error::<T>::InvalidFinalityBranch
+            );
+            ensure!(
+                is_valid_merkle_branch(
+                    update.block_roots_root,
+                    &update.block_roots_branch,
+                    finalized_gindices.block_roots.depth,
+                    finalized_gindices.block_roots.index,
+                    update.finalized_header.state_root,
+                ),
+                Error::<T>::InvalidBlockRootsBranch
+            );
+
+            if let Some(next_update) = &update.next_sync_committee_update {
+                if attested_period == store_period {
+                    if let Some(next_sync_committee) = &next_sync_committee {
+                        ensure!(
+                            *next_sync_committee == next_update.next_sync_committee,
+                            Error::<T>::InvalidSyncCommitteeUpdate
+                        );
+                    }
+                }
+                Self::verify_sync_committee(
+                    &next_update.next_sync_committee,
+                    &next_update.next_sync_committee_branch,
+                    attested_gindices.next_sync_committee,
+                    update.attested_header.state_root,
+                )?;
+            }
+
+            let sync_committee = if signature_period == store_period {
+                CurrentSyncCommittee::<T>::get(network_id).ok_or(Error::<T>::NetworkNotFound)?
+            } else {
+                next_sync_committee
+                    .clone()
+                    .ok_or(Error::<T>::SkippedSyncCommitteePeriod)?
+            };
+            let participants: Vec<PublicKey> = sync_committee
+                .pubkeys
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| sync_aggregate.is_participant(*i))
+                .map(|(_, pubkey)| *pubkey)
+                .collect();
+            let signing_root = compute_signing_root(
+                update.attested_header.hash_tree_root(),
+                config.sync_committee_domain(update.signature_slot),
+            );
+            ensure!(
+                verify_signature(
+                    &participants,
+                    signing_root,
+                    &sync_aggregate.sync_committee_signature
+                ),
+                Error::<T>::InvalidSignature
+            );
+
+            if learns_next_sync_committee {
+                if let Some(next_update) = update.next_sync_committee_update.clone() {
+                    NextSyncCommittee::<T>::insert(network_id, next_update.next_sync_committee);
+                    Self::deposit_event(Event::SyncCommitteeUpdated {
+                        network_id,
+                        period: store_period + 1,
+                    });
+                }
+            } else if finalized_period == store_period + 1 {
+                let Some(next_sync_committee) = next_sync_committee else {
+                    fail!(Error::<T>::SkippedSyncCommitteePeriod)
+                };
+                CurrentSyncCommittee::<T>::insert(network_id, next_sync_committee);
+                match update.next_sync_committee_update.clone() {
+                    Some(next_update) if attested_period == finalized_period => {
+                        NextSyncCommittee::<T>::insert(network_id, next_update.next_sync_committee);
+                    }
+                    _ => NextSyncCommittee::<T>::remove(network_id),
+                }
+                Self::deposit_event(Event::SyncCommitteeUpdated {
+                    network_id,
+                    period: finalized_period,
+                });
+            }
+
+            if update.finalized_header.slot > finalized_header.slot {
+                let slot = update.finalized_header.slot;
+                Self::store_finalized_header(
+                    network_id,
+                    finalized_block_root,
+                    update.finalized_header,
+                    update.block_roots_root,
+                );
+                Self::deposit_event(Event::BeaconHeaderFinalized {
+                    network_id,
+                    block_root: finalized_block_root,
+                    slot,
+                });
+            }
+            Ok(())
+        }
+
+        fn verify_sync_committee(
+            sync_committee: &SyncCommittee,
+            branch: &[H256],
+            gindex: GeneralizedIndex,
+            state_root: H256,
+        ) -> DispatchResult {
+            ensure!(
+                sync_committee.pubkeys.len() == beacon::SYNC_COMMITTEE_SIZE,
+                Error::<T>::InvalidSyncCommittee
+            );
+            ensure!(
+                is_valid_merkle_branch(
+                    sync_committee.hash_tree_root(),
+                    branch,
+                    gindex.depth,
+                    gindex.index,
+                    state_root,
+                ),
+                Error::<T>::InvalidSyncCommitteeBranch
+            );
+            Ok(())
+        }
+
+        fn store_finalized_header(
+            network_id: EVMChainId,
+            block_root: H256,
+            header: BeaconHeader,
+            block_roots_root: H256,
+        ) {
+            FinalizedHeaders::<T>::insert(
+                network_id,
+                block_root,
+                FinalizedHeaderState {
+                    slot: header.slot,
+                    block_roots_root,
+                },
+            );
+            LatestFinalizedHeader::<T>::insert(network_id, (block_root, header));
+        }
+    }
+}
+
+/// BLS fast aggregate verification of the sync committee signature.
+fn verify_signature(pubkeys: &[PublicKey], message: H256, signature: &Signature) -> bool {
+    let Ok(signature) = milagro_bls::Signature::from_bytes(&signature.0) else {
+        return false;
+    };
+    let Ok(pubkeys) = pubkeys
+        .iter()
+        .map(|pubkey| milagro_bls::PublicKey::from_bytes_unchecked(&pubkey.0))
+        .collect::<Result<Vec<_>, _>>() else {
+        return false;
+    };
+    let pubkeys: Vec<&milagro_bls::PublicKey> = pubkeys.iter().collect();
+    milagro_bls::AggregateSignature::from_signature(&signature)
+        .fast_aggregate_verify(message.as_bytes(), &pubkeys)
+}
+
+impl<T: Config> Verifier for Pallet<T> {
+    type Proof = bridge_types::evm::Proof;
+
+    /// Verifies that the receipt proven by `proof` is included in an imported
+    /// execution header at `tx_index` and contains the log with `message` hash.
+    fn verify(network_id: GenericNetworkId, message: H256, proof: &Self::Proof) -> DispatchResult {
+        let network_id = network_id.evm().ok_or(Error::<T>::InvalidNetwork)?;
+        let header = ExecutionHeaders::<T>::get(network_id, proof.block_hash)
+            .ok_or(Error::<T>::HeaderNotFound)?;
+        let receipt = match bridge_types::header::check_receipt_proof_at(
+            header.receipts_root,
+            proof.tx_index.into(),
+            &proof.data,
+        ) {
+            Some(Ok(receipt)) => receipt,
+            Some(Err(err)) => {
+                frame_support::log::debug!("Failed to decode receipt: {:?}", err);
+                return Err(Error::<T>::DecodeFailed.into());
+            }
+            None => return Err(Error::<T>::InvalidProof.into()),
+        };
+        ensure!(
+            receipt.contains_hashed_log(message),
+            Error::<T>::LogNotFound
+        );
+        Ok(())
+    }
+
+    fn verify_weight(_proof: &Self::Proof) -> Weight {
+        <T as Config>::WeightInfo::verifier_verify()
+    }
+
+    /// Proof of the fixture receipt, the execution header with its receipts
+    /// root is expected to be imported under the `block_hash`.
+    #[cfg(feature = "runtime-benchmarks")]
+    fn valid_proof() -> Option<Self::Proof> {
+        Some(bridge_types::evm::Proof {
+            block_hash: H256::repeat_byte(1),
+            tx_index: 5,
+            data: fixtures::receipt_proof(),
+        })
+    }
+}
+
+impl<T: Config> EthereumGasPriceOracle for Pallet<T> {
+    fn get_base_fee(
+        network_id: EVMChainId,
+        header_hash: H256,
+    ) -> Result<Option<U256>, DispatchError> {
+        let header = ExecutionHeaders::<T>::get(network_id, header_hash)
+            .ok_or(Error::<T>::HeaderNotFound)?;
+        Ok(Some(header.base_fee))
+    }
+
+    fn get_best_block_base_fee(network_id: EVMChainId) -> Result<Option<U256>, DispatchError> {
+        let hash = LatestExecutionHeader::<T>::get(network_id).ok_or(Error::<T>::HeaderNotFound)?;
+        Self::get_base_fee(network_id, hash)
+    }
+}
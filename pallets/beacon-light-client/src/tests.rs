@@ -0,0 +1,592 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::fixtures::{
+    self, BeaconBlock, BeaconState, Committee, ELECTRA_PERIOD_START_SLOT, PERIOD_START_SLOT,
+};
+use crate::mock::*;
+use crate::{
+    CurrentSyncCommittee, Error, Event, ExecutionHeaders, FinalizedHeaders, LatestExecutionHeader,
+    LatestFinalizedHeader, NextSyncCommittee,
+};
+use bridge_types::beacon::{self, AncestryProof, BeaconNetworkConfig};
+use bridge_types::evm::Proof;
+use bridge_types::network_config::NetworkConfig;
+use bridge_types::traits::{EthereumGasPriceOracle, Verifier};
+use bridge_types::{EVMChainId, GenericNetworkId, SubNetworkId, H256, U256};
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::traits::{Hash, Keccak256};
+use sp_runtime::DispatchError;
+
+const CHECKPOINT_SLOT: u64 = PERIOD_START_SLOT + 100;
+
+fn network_id() -> EVMChainId {
+    NetworkConfig::BeaconMainnet.chain_id()
+}
+
+fn initialize(committee: &Committee) -> BeaconBlock {
+    let (checkpoint, block) = fixtures::checkpoint(CHECKPOINT_SLOT, committee);
+    assert_ok!(BeaconLightClient::initialize(
+        RuntimeOrigin::root(),
+        NetworkConfig::BeaconMainnet,
+        BeaconNetworkConfig::mainnet(),
+        checkpoint
+    ));
+    block
+}
+
+fn finalized_block(slot: u64, receipts_root: H256) -> (BeaconBlock, BeaconState) {
+    let state = BeaconState {
+        slot,
+        block_roots_root: H256::repeat_byte(slot as u8),
+        ..Default::default()
+    };
+    let block = BeaconBlock::new(
+        slot,
+        state.root(),
+        fixtures::execution_header(slot, receipts_root),
+    );
+    (block, state)
+}
+
+#[test]
+fn it_works_initialize() {
+    new_test_ext().execute_with(|| {
+        let committee = Committee::new(1);
+        let block = initialize(&committee);
+        assert_eq!(
+            BeaconLightClient::beacon_config(network_id()),
+            Some(BeaconNetworkConfig::mainnet())
+        );
+        assert_eq!(
+            LatestFinalizedHeader::<Test>::get(network_id()),
+            Some((block.root(), block.header.clone()))
+        );
+        assert!(FinalizedHeaders::<Test>::contains_key(
+            network_id(),
+            block.root()
+        ));
+        assert_eq!(
+            CurrentSyncCommittee::<Test>::get(network_id()),
+            Some(committee.sync_committee)
+        );
+        assert_eq!(NextSyncCommittee::<Test>::get(network_id()), None);
+        System::assert_last_event(RuntimeEvent::BeaconLightClient(Event::Initialized {
+            network_id: network_id(),
+            block_root: block.root(),
+            slot: CHECKPOINT_SLOT,
+        }));
+    });
+}
+
+#[test]
+fn it_fails_initialize_invalid_checkpoint() {
+    new_test_ext().execute_with(|| {
+        let committee = Committee::new(1);
+        let (checkpoint, _) = fixtures::checkpoint(CHECKPOINT_SLOT, &committee);
+        assert_noop!(
+            BeaconLightClient::initialize(
+                RuntimeOrigin::signed(1),
+                NetworkConfig::BeaconMainnet,
+                BeaconNetworkConfig::mainnet(),
+                checkpoint.clone()
+            ),
+            DispatchError::BadOrigin
+        );
+        for network_config in [NetworkConfig::Classic, NetworkConfig::Mainnet] {
+            assert_noop!(
+                BeaconLightClient::initialize(
+                    RuntimeOrigin::root(),
+                    network_config,
+                    BeaconNetworkConfig::mainnet(),
+                    checkpoint.clone()
+                ),
+                Error::<Test>::ConsensusNotSupported
+            );
+        }
+        let mut invalid = checkpoint.clone();
+        invalid.current_sync_committee = Committee::new(2).sync_committee;
+        assert_noop!(
+            BeaconLightClient::initialize(
+                RuntimeOrigin::root(),
+                NetworkConfig::BeaconMainnet,
+                BeaconNetworkConfig::mainnet(),
+                invalid
+            ),
+            Error::<Test>::InvalidSyncCommitteeBranch
+        );
+        let mut invalid = checkpoint.clone();
+        invalid.current_sync_committee.pubkeys.pop();
+        assert_noop!(
+            BeaconLightClient::initialize(
+                RuntimeOrigin::root(),
+                NetworkConfig::BeaconMainnet,
+                BeaconNetworkConfig::mainnet(),
+                invalid
+            ),
+            Error::<Test>::InvalidSyncCommittee
+        );
+        let mut invalid = checkpoint.clone();
+        invalid.block_roots_root = H256::repeat_byte(1);
+        assert_noop!(
+            BeaconLightClient::initialize(
+                RuntimeOrigin::root(),
+                NetworkConfig::BeaconMainnet,
+                BeaconNetworkConfig::mainnet(),
+                invalid
+            ),
+            Error::<Test>::InvalidBlockRootsBranch
+        );
+
+        initialize(&committee);
+        assert_noop!(
+            BeaconLightClient::initialize(
+                RuntimeOrigin::root(),
+                NetworkConfig::BeaconMainnet,
+                BeaconNetworkConfig::mainnet(),
+                checkpoint
+            ),
+            Error::<Test>::NetworkAlreadyExists
+        );
+    });
+}
+
+#[test]
+fn it_works_submit_update() {
+    new_test_ext().execute_with(|| {
+        let config = BeaconNetworkConfig::mainnet();
+        let current = Committee::new(1);
+        let next = Committee::new(2);
+        initialize(&current);
+
+        let (finalized, state) = finalized_block(CHECKPOINT_SLOT + 64, H256::zero());
+        let update = fixtures::update(&config, &current, 512, &finalized, &state, Some(&next));
+        assert_ok!(BeaconLightClient::submit_update(
+            RuntimeOrigin::signed(1),
+            network_id(),
+            update.clone()
+        ));
+        assert_eq!(
+            LatestFinalizedHeader::<Test>::get(network_id()),
+            Some((finalized.root(), finalized.header.clone()))
+        );
+        assert_eq!(
+            BeaconLightClient::finalized_header(network_id(), finalized.root())
+                .map(|state| state.block_roots_root),
+            Some(state.block_roots_root)
+        );
+        assert_eq!(
+            NextSyncCommittee::<Test>::get(network_id()),
+            Some(next.sync_committee)
+        );
+        System::assert_has_event(RuntimeEvent::BeaconLightClient(
+            Event::SyncCommitteeUpdated {
+                network_id: network_id(),
+                period: beacon::compute_period(CHECKPOINT_SLOT) + 1,
+            },
+        ));
+        System::assert_last_event(RuntimeEvent::BeaconLightClient(
+            Event::BeaconHeaderFinalized {
+                network_id: network_id(),
+                block_root: finalized.root(),
+                slot: finalized.header.slot,
+            },
+        ));
+
+        assert_noop!(
+            BeaconLightClient::submit_update(RuntimeOrigin::signed(1), network_id(), update),
+            Error::<Test>::IrrelevantUpdate
+        );
+    });
+}
+
+#[test]
+fn it_works_submit_update_after_electra() {
+    new_test_ext().execute_with(|| {
+        let config = BeaconNetworkConfig::mainnet();
+        let current = Committee::new(1);
+        let next = Committee::new(2);
+        let checkpoint_slot = ELECTRA_PERIOD_START_SLOT + 100;
+        let (checkpoint, _) = fixtures::checkpoint(checkpoint_slot, &current);
+        assert_eq!(checkpoint.current_sync_committee_branch.len(), 6);
+        assert_ok!(BeaconLightClient::initialize(
+            RuntimeOrigin::root(),
+            NetworkConfig::BeaconMainnet,
+            config,
+            checkpoint
+        ));
+
+        let (finalized, state) = finalized_block(checkpoint_slot + 64, H256::zero());
+        let update = fixtures::update(&config, &current, 512, &finalized, &state, Some(&next));
+        assert_eq!(update.finality_branch.len(), 7);
+
+        // Branches of the pre-Electra state layout are rejected
+        let mut invalid = update.clone();
+        invalid.finality_branch.pop();
+        assert_noop!(
+            BeaconLightClient::submit_update(RuntimeOrigin::signed(1), network_id(), invalid),
+            Error::<Test>::InvalidFinalityBranch
+        );
+
+        assert_ok!(BeaconLightClient::submit_update(
+            RuntimeOrigin::signed(1),
+            network_id(),
+            update
+        ));
+        assert_eq!(
+            LatestFinalizedHeader::<Test>::get(network_id()),
+            Some((finalized.root(), finalized.header.clone()))
+        );
+        assert_eq!(
+            NextSyncCommittee::<Test>::get(network_id()),
+            Some(next.sync_committee)
+        );
+    });
+}
+
+#[test]
+fn it_works_rotate_sync_committee() {
+    new_test_ext().execute_with(|| {
+        let config = BeaconNetworkConfig::mainnet();
+        let first = Committee::new(1);
+        let second = Committee::new(2);
+        let third = Committee::new(3);
+        initialize(&first);
+
+        let (finalized, state) = finalized_block(
+            PERIOD_START_SLOT + beacon::SLOTS_PER_EPOCH * 256 + 10,
+            H256::zero(),
+        );
+        let update = fixtures::update(&config, &second, 400, &finalized, &state, Some(&third));
+        assert_noop!(
+            BeaconLightClient::submit_update(
+                RuntimeOrigin::signed(1),
+                network_id(),
+                update.clone()
+            ),
+            Error::<Test>::SkippedSyncCommitteePeriod
+        );
+
+        let (learn, learn_state) = finalized_block(CHECKPOINT_SLOT + 64, H256::zero());
+        assert_ok!(BeaconLightClient::submit_update(
+            RuntimeOrigin::signed(1),
+            network_id(),
+            fixtures::update(&config, &first, 512, &learn, &learn_state, Some(&second))
+        ));
+
+        assert_ok!(BeaconLightClient::submit_update(
+            RuntimeOrigin::signed(1),
+            network_id(),
+            update
+        ));
+        assert_eq!(
+            CurrentSyncCommittee::<Test>::get(network_id()),
+            Some(second.sync_committee)
+        );
+        assert_eq!(
+            NextSyncCommittee::<Test>::get(network_id()),
+            Some(third.sync_committee)
+        );
+        assert_eq!(
+            LatestFinalizedHeader::<Test>::get(network_id()).map(|(root, _)| root),
+            Some(finalized.root())
+        );
+    });
+}
+
+#[test]
+fn it_fails_submit_invalid_update() {
+    new_test_ext().execute_with(|| {
+        let config = BeaconNetworkConfig::mainnet();
+        let committee = Committee::new(1);
+        let (finalized, state) = finalized_block(CHECKPOINT_SLOT + 64, H256::zero());
+        let update = fixtures::update(&config, &committee, 512, &finalized, &state, None);
+        assert_noop!(
+            BeaconLightClient::submit_update(
+                RuntimeOrigin::signed(1),
+                network_id(),
+                update.clone()
+            ),
+            Error::<Test>::NetworkNotFound
+        );
+        initialize(&committee);
+
+        let insufficient = fixtures::update(&config, &committee, 341, &finalized, &state, None);
+        assert_noop!(
+            BeaconLightClient::submit_update(RuntimeOrigin::signed(1), network_id(), insufficient),
+            Error::<Test>::InsufficientSyncCommitteeParticipants
+        );
+
+        let mut invalid = update.clone();
+        invalid.sync_aggregate.sync_committee_bits.pop();
+        assert_noop!(
+            BeaconLightClient::submit_update(RuntimeOrigin::signed(1), network_id(), invalid),
+            Error::<Test>::InvalidSyncCommitteeBits
+        );
+
+        let mut invalid = update.clone();
+        invalid.signature_slot = invalid.attested_header.slot;
+        assert_noop!(
+            BeaconLightClient::submit_update(RuntimeOrigin::signed(1), network_id(), invalid),
+            Error::<Test>::InvalidUpdateSlot
+        );
+
+        let mut invalid = update.clone();
+        invalid.finality_branch[0] = H256::repeat_byte(1);
+        assert_noop!(
+            BeaconLightClient::submit_update(RuntimeOrigin::signed(1), network_id(), invalid),
+            Error::<Test>::InvalidFinalityBranch
+        );
+
+        let mut invalid = update.clone();
+        invalid.block_roots_root = H256::zero();
+        assert_noop!(
+            BeaconLightClient::submit_update(RuntimeOrigin::signed(1), network_id(), invalid),
+            Error::<Test>::InvalidBlockRootsBranch
+        );
+
+        let forged = fixtures::update(&config, &Committee::new(2), 512, &finalized, &state, None);
+        assert_noop!(
+            BeaconLightClient::submit_update(RuntimeOrigin::signed(1), network_id(), forged),
+            Error::<Test>::InvalidSignature
+        );
+
+        let mut invalid = update;
+        invalid.finalized_header.slot -= 1;
+        assert_noop!(
+            BeaconLightClient::submit_update(RuntimeOrigin::signed(1), network_id(), invalid),
+            Error::<Test>::InvalidFinalityBranch
+        );
+    });
+}
+
+#[test]
+fn it_works_import_execution_header() {
+    new_test_ext().execute_with(|| {
+        let committee = Committee::new(1);
+        let checkpoint = initialize(&committee);
+
+        assert_ok!(BeaconLightClient::import_execution_header(
+            RuntimeOrigin::signed(1),
+            network_id(),
+            checkpoint.execution_update()
+        ));
+        let block_hash = checkpoint.execution_header.block_hash;
+        assert_eq!(
+            LatestExecutionHeader::<Test>::get(network_id()),
+            Some(block_hash)
+        );
+        System::assert_last_event(RuntimeEvent::BeaconLightClient(
+            Event::ExecutionHeaderImported {
+                network_id: network_id(),
+                block_hash,
+                block_number: CHECKPOINT_SLOT,
+            },
+        ));
+        assert_noop!(
+            BeaconLightClient::import_execution_header(
+                RuntimeOrigin::signed(1),
+                network_id(),
+                checkpoint.execution_update()
+            ),
+            Error::<Test>::DuplicateExecutionHeader
+        );
+
+        let mut invalid = checkpoint.execution_update();
+        invalid.execution_header.gas_used = 1;
+        assert_noop!(
+            BeaconLightClient::import_execution_header(
+                RuntimeOrigin::signed(1),
+                network_id(),
+                invalid
+            ),
+            Error::<Test>::InvalidExecutionHeaderProof
+        );
+
+        let (unfinalized, _) = finalized_block(CHECKPOINT_SLOT + 10, H256::zero());
+        assert_noop!(
+            BeaconLightClient::import_execution_header(
+                RuntimeOrigin::signed(1),
+                network_id(),
+                unfinalized.execution_update()
+            ),
+            Error::<Test>::HeaderNotFinalized
+        );
+    });
+}
+
+#[test]
+fn it_works_import_execution_header_with_ancestry_proof() {
+    new_test_ext().execute_with(|| {
+        let config = BeaconNetworkConfig::mainnet();
+        let committee = Committee::new(1);
+        initialize(&committee);
+
+        let (ancestor, _) = finalized_block(CHECKPOINT_SLOT + 10, H256::zero());
+        let mut block_roots = vec![H256::zero(); beacon::SLOTS_PER_HISTORICAL_ROOT as usize];
+        let index = (ancestor.header.slot % beacon::SLOTS_PER_HISTORICAL_ROOT) as usize;
+        block_roots[index] = ancestor.root();
+        let (block_roots_root, header_branch) = fixtures::merkle_proof(block_roots, index);
+
+        let state = BeaconState {
+            block_roots_root,
+            ..Default::default()
+        };
+        let finalized = BeaconBlock::new(
+            CHECKPOINT_SLOT + 64,
+            state.root(),
+            fixtures::execution_header(CHECKPOINT_SLOT + 64, H256::zero()),
+        );
+        assert_ok!(BeaconLightClient::submit_update(
+            RuntimeOrigin::signed(1),
+            network_id(),
+            fixtures::update(&config, &committee, 512, &finalized, &state, None)
+        ));
+
+        let mut update = ancestor.execution_update();
+        update.ancestry_proof = Some(AncestryProof {
+            header_branch: header_branch.clone(),
+            finalized_block_root: finalized.root(),
+        });
+        let mut invalid = update.clone();
+        invalid.ancestry_proof = Some(AncestryProof {
+            header_branch: header_branch[1..].to_vec(),
+            finalized_block_root: finalized.root(),
+        });
+        assert_noop!(
+            BeaconLightClient::import_execution_header(
+                RuntimeOrigin::signed(1),
+                network_id(),
+                invalid
+            ),
+            Error::<Test>::InvalidAncestryProof
+        );
+        assert_ok!(BeaconLightClient::import_execution_header(
+            RuntimeOrigin::signed(1),
+            network_id(),
+            update
+        ));
+        assert!(ExecutionHeaders::<Test>::contains_key(
+            network_id(),
+            ancestor.execution_header.block_hash
+        ));
+    });
+}
+
+#[test]
+fn it_works_verify_receipt_proof() {
+    new_test_ext().execute_with(|| {
+        let config = BeaconNetworkConfig::mainnet();
+        let committee = Committee::new(1);
+        initialize(&committee);
+
+        let receipts_root = fixtures::receipts_root();
+        let (finalized, state) = finalized_block(CHECKPOINT_SLOT + 64, receipts_root);
+        let proof = Proof {
+            block_hash: finalized.execution_header.block_hash,
+            tx_index: 5,
+            data: fixtures::receipt_proof(),
+        };
+        let receipt = bridge_types::header::check_receipt_proof(receipts_root, &proof.data)
+            .unwrap()
+            .unwrap();
+        let message = Keccak256::hash_of(&receipt.logs[0]);
+
+        assert_ok!(BeaconLightClient::submit_update(
+            RuntimeOrigin::signed(1),
+            network_id(),
+            fixtures::update(&config, &committee, 512, &finalized, &state, None)
+        ));
+        assert_noop!(
+            BeaconLightClient::verify(network_id().into(), message, &proof),
+            Error::<Test>::HeaderNotFound
+        );
+
+        assert_ok!(BeaconLightClient::import_execution_header(
+            RuntimeOrigin::signed(1),
+            network_id(),
+            finalized.execution_update()
+        ));
+        assert_ok!(BeaconLightClient::verify(
+            network_id().into(),
+            message,
+            &proof
+        ));
+        assert_noop!(
+            BeaconLightClient::verify(network_id().into(), [1u8; 32].into(), &proof),
+            Error::<Test>::LogNotFound
+        );
+        assert_noop!(
+            BeaconLightClient::verify(
+                GenericNetworkId::Sub(SubNetworkId::Mainnet),
+                message,
+                &proof
+            ),
+            Error::<Test>::InvalidNetwork
+        );
+        assert_noop!(
+            BeaconLightClient::verify(
+                network_id().into(),
+                message,
+                &Proof {
+                    data: fixtures::receipt_proof()[1..].to_vec(),
+                    ..proof
+                }
+            ),
+            Error::<Test>::InvalidProof
+        );
+    });
+}
+
+#[test]
+fn it_works_gas_price_oracle() {
+    new_test_ext().execute_with(|| {
+        let committee = Committee::new(1);
+        let checkpoint = initialize(&committee);
+        assert_noop!(
+            BeaconLightClient::get_best_block_base_fee(network_id()),
+            Error::<Test>::HeaderNotFound
+        );
+        assert_ok!(BeaconLightClient::import_execution_header(
+            RuntimeOrigin::signed(1),
+            network_id(),
+            checkpoint.execution_update()
+        ));
+        let base_fee = Some(U256::from(7_000_000_000u64));
+        assert_eq!(
+            BeaconLightClient::get_base_fee(network_id(), checkpoint.execution_header.block_hash),
+            Ok(base_fee)
+        );
+        assert_eq!(
+            BeaconLightClient::get_best_block_base_fee(network_id()),
+            Ok(base_fee)
+        );
+    });
+}
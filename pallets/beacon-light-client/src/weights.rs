@@ -0,0 +1,153 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Weights for beacon_light_client
+//!
+//! NOT GENERATED BY THE BENCHMARK CLI: the values are estimates until the pallet
+//! is benchmarked, regenerate with
+//! `benchmark pallet --pallet=beacon_light_client --extrinsic=* --template=./misc/pallet-weight-template.hbs`.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for beacon_light_client.
+pub trait WeightInfo {
+	fn initialize() -> Weight;
+	fn submit_update() -> Weight;
+	fn import_execution_header() -> Weight;
+	fn verifier_verify() -> Weight;
+}
+
+/// Weights for beacon_light_client using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: BeaconLightClient BeaconConfig (r:1 w:1)
+	/// Proof Skipped: BeaconLightClient BeaconConfig (max_values: None, max_size: None, mode: Measured)
+	/// Storage: BeaconLightClient CurrentSyncCommittee (r:0 w:1)
+	/// Proof Skipped: BeaconLightClient CurrentSyncCommittee (max_values: None, max_size: None, mode: Measured)
+	/// Storage: BeaconLightClient FinalizedHeaders (r:0 w:1)
+	/// Proof Skipped: BeaconLightClient FinalizedHeaders (max_values: None, max_size: None, mode: Measured)
+	/// Storage: BeaconLightClient LatestFinalizedHeader (r:0 w:1)
+	/// Proof Skipped: BeaconLightClient LatestFinalizedHeader (max_values: None, max_size: None, mode: Measured)
+	fn initialize() -> Weight {
+		Weight::from_parts(1_870_412_000, 28337)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: BeaconLightClient BeaconConfig (r:1 w:0)
+	/// Proof Skipped: BeaconLightClient BeaconConfig (max_values: None, max_size: None, mode: Measured)
+	/// Storage: BeaconLightClient LatestFinalizedHeader (r:1 w:1)
+	/// Proof Skipped: BeaconLightClient LatestFinalizedHeader (max_values: None, max_size: None, mode: Measured)
+	/// Storage: BeaconLightClient NextSyncCommittee (r:1 w:1)
+	/// Proof Skipped: BeaconLightClient NextSyncCommittee (max_values: None, max_size: None, mode: Measured)
+	/// Storage: BeaconLightClient CurrentSyncCommittee (r:1 w:1)
+	/// Proof Skipped: BeaconLightClient CurrentSyncCommittee (max_values: None, max_size: None, mode: Measured)
+	/// Storage: BeaconLightClient FinalizedHeaders (r:0 w:1)
+	/// Proof Skipped: BeaconLightClient FinalizedHeaders (max_values: None, max_size: None, mode: Measured)
+	fn submit_update() -> Weight {
+		Weight::from_parts(6_014_871_000, 53179)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: BeaconLightClient BeaconConfig (r:1 w:0)
+	/// Proof Skipped: BeaconLightClient BeaconConfig (max_values: None, max_size: None, mode: Measured)
+	/// Storage: BeaconLightClient FinalizedHeaders (r:1 w:0)
+	/// Proof Skipped: BeaconLightClient FinalizedHeaders (max_values: None, max_size: None, mode: Measured)
+	/// Storage: BeaconLightClient ExecutionHeaders (r:2 w:1)
+	/// Proof Skipped: BeaconLightClient ExecutionHeaders (max_values: None, max_size: None, mode: Measured)
+	/// Storage: BeaconLightClient LatestExecutionHeader (r:1 w:1)
+	/// Proof Skipped: BeaconLightClient LatestExecutionHeader (max_values: None, max_size: None, mode: Measured)
+	fn import_execution_header() -> Weight {
+		Weight::from_parts(118_203_000, 7224)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: BeaconLightClient ExecutionHeaders (r:1 w:0)
+	/// Proof Skipped: BeaconLightClient ExecutionHeaders (max_values: None, max_size: None, mode: Measured)
+	fn verifier_verify() -> Weight {
+		Weight::from_parts(57_114_000, 3877)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Storage: BeaconLightClient BeaconConfig (r:1 w:1)
+	/// Proof Skipped: BeaconLightClient BeaconConfig (max_values: None, max_size: None, mode: Measured)
+	/// Storage: BeaconLightClient CurrentSyncCommittee (r:0 w:1)
+	/// Proof Skipped: BeaconLightClient CurrentSyncCommittee (max_values: None, max_size: None, mode: Measured)
+	/// Storage: BeaconLightClient FinalizedHeaders (r:0 w:1)
+	/// Proof Skipped: BeaconLightClient FinalizedHeaders (max_values: None, max_size: None, mode: Measured)
+	/// Storage: BeaconLightClient LatestFinalizedHeader (r:0 w:1)
+	/// Proof Skipped: BeaconLightClient LatestFinalizedHeader (max_values: None, max_size: None, mode: Measured)
+	fn initialize() -> Weight {
+		Weight::from_parts(1_870_412_000, 28337)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: BeaconLightClient BeaconConfig (r:1 w:0)
+	/// Proof Skipped: BeaconLightClient BeaconConfig (max_values: None, max_size: None, mode: Measured)
+	/// Storage: BeaconLightClient LatestFinalizedHeader (r:1 w:1)
+	/// Proof Skipped: BeaconLightClient LatestFinalizedHeader (max_values: None, max_size: None, mode: Measured)
+	/// Storage: BeaconLightClient NextSyncCommittee (r:1 w:1)
+	/// Proof Skipped: BeaconLightClient NextSyncCommittee (max_values: None, max_size: None, mode: Measured)
+	/// Storage: BeaconLightClient CurrentSyncCommittee (r:1 w:1)
+	/// Proof Skipped: BeaconLightClient CurrentSyncCommittee (max_values: None, max_size: None, mode: Measured)
+	/// Storage: BeaconLightClient FinalizedHeaders (r:0 w:1)
+	/// Proof Skipped: BeaconLightClient FinalizedHeaders (max_values: None, max_size: None, mode: Measured)
+	fn submit_update() -> Weight {
+		Weight::from_parts(6_014_871_000, 53179)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: BeaconLightClient BeaconConfig (r:1 w:0)
+	/// Proof Skipped: BeaconLightClient BeaconConfig (max_values: None, max_size: None, mode: Measured)
+	/// Storage: BeaconLightClient FinalizedHeaders (r:1 w:0)
+	/// Proof Skipped: BeaconLightClient FinalizedHeaders (max_values: None, max_size: None, mode: Measured)
+	/// Storage: BeaconLightClient ExecutionHeaders (r:2 w:1)
+	/// Proof Skipped: BeaconLightClient ExecutionHeaders (max_values: None, max_size: None, mode: Measured)
+	/// Storage: BeaconLightClient LatestExecutionHeader (r:1 w:1)
+	/// Proof Skipped: BeaconLightClient LatestExecutionHeader (max_values: None, max_size: None, mode: Measured)
+	fn import_execution_header() -> Weight {
+		Weight::from_parts(118_203_000, 7224)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: BeaconLightClient ExecutionHeaders (r:1 w:0)
+	/// Proof Skipped: BeaconLightClient ExecutionHeaders (max_values: None, max_size: None, mode: Measured)
+	fn verifier_verify() -> Weight {
+		Weight::from_parts(57_114_000, 3877)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+	}
+}
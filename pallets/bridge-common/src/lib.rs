@@ -32,6 +32,7 @@
 
 pub mod beefy_types;
 pub mod bitfield;
+pub mod merkle_proof;
 pub mod simplified_proof;
 
 use frame_support::weights::Weight;
@@ -0,0 +1,253 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Inclusion proofs of messages in [`MerkleCommitment`] and [`MerkleCommitmentV2`].
+
+use crate::simplified_proof::Proof;
+use bridge_types::substrate::{
+    merkle_commitment_hash, merkle_depth, merkle_node_hash, BridgeMessageV2,
+};
+#[cfg(feature = "std")]
+use bridge_types::substrate::{BridgeMessage, MerkleCommitment, MerkleCommitmentV2};
+use bridge_types::H256;
+use codec::{Decode, Encode};
+use frame_support::traits::Get;
+use frame_support::RuntimeDebug;
+#[cfg(feature = "std")]
+use scale_info::prelude::vec::Vec;
+
+/// Proof of the message inclusion in [`MerkleCommitment`] or [`MerkleCommitmentV2`].
+///
+/// Messages of [`MerkleCommitment`] are verified as [`BridgeMessageV2`] without deadline.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, scale_info::TypeInfo)]
+pub struct MessageProof {
+    /// Batch nonce of the commitment.
+    pub nonce: u64,
+    /// Number of messages in the commitment.
+    pub messages_count: u32,
+    /// Merkle proof with message index as `order`.
+    pub proof: Proof<H256>,
+}
+
+impl MessageProof {
+    pub fn message_index(&self) -> u64 {
+        self.proof.order
+    }
+
+    /// Returns hash of the commitment which includes the message,
+    /// or `None` if the proof doesn't match the commitment size.
+    pub fn commitment_hash<MaxPayload: Get<u32>>(
+        &self,
+        message: &BridgeMessageV2<MaxPayload>,
+    ) -> Option<H256> {
+        if self.message_index() >= self.messages_count as u64
+            || self.proof.items.len() != merkle_depth(self.messages_count)
+        {
+            return None;
+        }
+        let root = self.proof.root(merkle_node_hash, message.hash());
+        Some(merkle_commitment_hash(
+            self.nonce,
+            self.messages_count,
+            root,
+        ))
+    }
+
+    /// Checks that the message is included in the commitment with given hash.
+    pub fn verify<MaxPayload: Get<u32>>(
+        &self,
+        commitment_hash: H256,
+        message: &BridgeMessageV2<MaxPayload>,
+    ) -> bool {
+        self.commitment_hash(message) == Some(commitment_hash)
+    }
+}
+
+/// Generates inclusion proof of the message with given index.
+#[cfg(feature = "std")]
+pub fn generate_message_proof<MaxMessages: Get<u32>, MaxPayload: Get<u32>>(
+    commitment: &MerkleCommitment<MaxMessages, MaxPayload>,
+    index: usize,
+) -> Option<MessageProof> {
+    let leaves = commitment
+        .messages
+        .iter()
+        .map(BridgeMessage::hash)
+        .collect();
+    generate_proof(commitment.nonce, leaves, index)
+}
+
+/// Generates inclusion proof of the message with given index.
+#[cfg(feature = "std")]
+pub fn generate_message_proof_v2<MaxMessages: Get<u32>, MaxPayload: Get<u32>>(
+    commitment: &MerkleCommitmentV2<MaxMessages, MaxPayload>,
+    index: usize,
+) -> Option<MessageProof> {
+    let leaves = commitment
+        .messages
+        .iter()
+        .map(BridgeMessageV2::hash)
+        .collect();
+    generate_proof(commitment.nonce, leaves, index)
+}
+
+#[cfg(feature = "std")]
+fn generate_proof(nonce: u64, leaves: Vec<H256>, index: usize) -> Option<MessageProof> {
+    if index >= leaves.len() {
+        return None;
+    }
+    let messages_count = leaves.len() as u32;
+    let mut level = leaves;
+    let mut position = index;
+    let mut items = Vec::new();
+    while level.len() > 1 {
+        items.push(level.get(position ^ 1).copied().unwrap_or_default());
+        level = level
+            .chunks(2)
+            .map(|pair| merkle_node_hash(pair[0], pair.get(1).copied().unwrap_or_default()))
+            .collect();
+        position /= 2;
+    }
+    Some(MessageProof {
+        nonce,
+        messages_count,
+        proof: Proof {
+            order: index as u64,
+            items,
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bridge_types::GenericTimepoint;
+    use frame_support::traits::ConstU32;
+
+    type Commitment = MerkleCommitment<ConstU32<16>, ConstU32<16>>;
+    type CommitmentV2 = MerkleCommitmentV2<ConstU32<16>, ConstU32<16>>;
+
+    fn message(idx: u8) -> BridgeMessage<ConstU32<16>> {
+        BridgeMessage {
+            payload: vec![idx; 3].try_into().unwrap(),
+            timepoint: GenericTimepoint::Sora(idx as u32),
+        }
+    }
+
+    fn commitment(messages_count: u8) -> Commitment {
+        Commitment {
+            messages: (0..messages_count)
+                .map(message)
+                .collect::<Vec<_>>()
+                .try_into()
+                .unwrap(),
+            nonce: 7,
+        }
+    }
+
+    fn commitment_v2(messages_count: u8) -> CommitmentV2 {
+        CommitmentV2 {
+            messages: (0..messages_count)
+                .map(|idx| BridgeMessageV2 {
+                    deadline: (idx % 2 == 0).then_some(idx as u64 * 1_000),
+                    ..message(idx).into()
+                })
+                .collect::<Vec<_>>()
+                .try_into()
+                .unwrap(),
+            nonce: 7,
+        }
+    }
+
+    #[test]
+    fn generated_proofs_are_valid() {
+        for messages_count in 1..=9 {
+            let commitment = commitment(messages_count);
+            let hash = commitment.hash();
+            for (idx, message) in commitment.messages.iter().enumerate() {
+                let proof = generate_message_proof(&commitment, idx).unwrap();
+                assert_eq!(proof.message_index(), idx as u64);
+                assert!(proof.verify(hash, &message.clone().into()));
+            }
+            assert!(generate_message_proof(&commitment, messages_count as usize).is_none());
+        }
+    }
+
+    #[test]
+    fn generated_v2_proofs_are_valid() {
+        for messages_count in 1..=9 {
+            let commitment = commitment_v2(messages_count);
+            let hash = commitment.hash();
+            for (idx, message) in commitment.messages.iter().enumerate() {
+                let proof = generate_message_proof_v2(&commitment, idx).unwrap();
+                assert_eq!(proof.message_index(), idx as u64);
+                assert!(proof.verify(hash, message));
+            }
+            assert!(generate_message_proof_v2(&commitment, messages_count as usize).is_none());
+        }
+    }
+
+    #[test]
+    fn invalid_proofs_are_rejected() {
+        let commitment = commitment_v2(5);
+        let hash = commitment.hash();
+        let proof = generate_message_proof_v2(&commitment, 2).unwrap();
+
+        // Other message
+        assert!(!proof.verify(hash, &commitment.messages[3]));
+
+        // Other deadline
+        let mut message = commitment.messages[2].clone();
+        message.deadline = None;
+        assert!(!proof.verify(hash, &message));
+
+        // Other position
+        let mut invalid = proof.clone();
+        invalid.proof.order = 3;
+        assert!(!invalid.verify(hash, &commitment.messages[2]));
+
+        // Position out of the commitment
+        let mut invalid = proof.clone();
+        invalid.proof.order = 6;
+        assert_eq!(invalid.commitment_hash(&commitment.messages[2]), None);
+
+        // Other commitment size
+        let mut invalid = proof.clone();
+        invalid.messages_count = 4;
+        assert!(!invalid.verify(hash, &commitment.messages[2]));
+        invalid.proof.items.pop();
+        assert!(!invalid.verify(hash, &commitment.messages[2]));
+
+        // Other nonce
+        let mut invalid = proof;
+        invalid.nonce = 8;
+        assert!(!invalid.verify(hash, &commitment.messages[2]));
+    }
+}
@@ -0,0 +1,79 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Bridge pause pallet benchmarking
+
+use super::*;
+use crate::Pallet as BridgePause;
+use bridge_types::SubNetworkId;
+use frame_benchmarking::benchmarks;
+use frame_support::traits::EnsureOrigin;
+
+fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
+    frame_system::Pallet::<T>::assert_last_event(generic_event.into());
+}
+
+fn app_scope() -> PauseScope {
+    PauseScope::App(
+        GenericNetworkId::Sub(SubNetworkId::Mainnet),
+        PalletId(*b"test/app"),
+    )
+}
+
+benchmarks! {
+    pause {
+        let origin = T::PauseOrigin::try_successful_origin().map_err(|_| "no origin")?;
+        let scope = app_scope();
+    }: _<T::RuntimeOrigin>(origin, scope)
+    verify {
+        assert_last_event::<T>(Event::Paused { scope }.into());
+    }
+
+    resume {
+        let origin = T::PauseOrigin::try_successful_origin().map_err(|_| "no origin")?;
+        let scope = app_scope();
+        PausedScopes::<T>::insert(scope, true);
+    }: _<T::RuntimeOrigin>(origin, scope)
+    verify {
+        assert_last_event::<T>(Event::Resumed { scope }.into());
+    }
+
+    is_paused {
+        let network_id = GenericNetworkId::Sub(SubNetworkId::Mainnet);
+    }: {
+        BridgePause::<T>::ensure_not_paused(
+            network_id,
+            MessageDirection::Inbound,
+            Some(PalletId(*b"test/app")),
+        )?;
+    }
+
+    impl_benchmark_test_suite!(BridgePause, crate::mock::new_test_ext(), mock::Test)
+}
@@ -0,0 +1,170 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! # Bridge pause
+//!
+//! Allows to halt the bridge globally, for a network, for one direction of
+//! the network messages or for a bridge app. Channels and apps check the pause
+//! with [`BridgePauseChecker`] before sending and receiving messages.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+use weights::WeightInfo;
+
+use bridge_types::traits::BridgePauseChecker;
+use bridge_types::types::{MessageDirection, PauseScope};
+use bridge_types::GenericNetworkId;
+use frame_support::dispatch::DispatchResult;
+use frame_support::weights::Weight;
+use frame_support::{ensure, PalletId};
+use sp_std::prelude::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use frame_support::pallet_prelude::*;
+    use frame_system::pallet_prelude::*;
+
+    #[pallet::pallet]
+    #[pallet::generate_store(pub(super) trait Store)]
+    pub struct Pallet<T>(_);
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        /// The overarching event type.
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+        /// Origin which can pause and resume the bridge.
+        type PauseOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        type WeightInfo: WeightInfo;
+    }
+
+    /// Paused parts of the bridge.
+    #[pallet::storage]
+    #[pallet::getter(fn is_scope_paused)]
+    pub type PausedScopes<T: Config> =
+        StorageMap<_, Blake2_128Concat, PauseScope, bool, ValueQuery>;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        Paused { scope: PauseScope },
+        Resumed { scope: PauseScope },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// The scope is already paused.
+        AlreadyPaused,
+        /// The scope is not paused.
+        NotPaused,
+        /// The bridge is paused for the network, direction or app.
+        BridgePaused,
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        #[pallet::call_index(0)]
+        #[pallet::weight(<T as Config>::WeightInfo::pause())]
+        pub fn pause(origin: OriginFor<T>, scope: PauseScope) -> DispatchResult {
+            T::PauseOrigin::ensure_origin(origin)?;
+            ensure!(!PausedScopes::<T>::get(scope), Error::<T>::AlreadyPaused);
+            PausedScopes::<T>::insert(scope, true);
+            Self::deposit_event(Event::Paused { scope });
+            Ok(())
+        }
+
+        #[pallet::call_index(1)]
+        #[pallet::weight(<T as Config>::WeightInfo::resume())]
+        pub fn resume(origin: OriginFor<T>, scope: PauseScope) -> DispatchResult {
+            T::PauseOrigin::ensure_origin(origin)?;
+            ensure!(PausedScopes::<T>::get(scope), Error::<T>::NotPaused);
+            PausedScopes::<T>::remove(scope);
+            Self::deposit_event(Event::Resumed { scope });
+            Ok(())
+        }
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Returns true if messages of the network in the direction are paused,
+    /// transfers of the `app` are checked as well if it is set.
+    pub fn is_paused(
+        network_id: GenericNetworkId,
+        direction: MessageDirection,
+        app: Option<PalletId>,
+    ) -> bool {
+        let mut scopes = vec![
+            PauseScope::Global,
+            PauseScope::Network(network_id),
+            PauseScope::Direction(network_id, direction),
+        ];
+        if let Some(app) = app {
+            scopes.push(PauseScope::App(network_id, app));
+        }
+        scopes.into_iter().any(PausedScopes::<T>::get)
+    }
+
+    /// Paused parts of the bridge.
+    pub fn paused_scopes() -> Vec<PauseScope> {
+        PausedScopes::<T>::iter_keys().collect()
+    }
+}
+
+impl<T: Config> BridgePauseChecker for Pallet<T> {
+    fn ensure_not_paused(
+        network_id: GenericNetworkId,
+        direction: MessageDirection,
+        app: Option<PalletId>,
+    ) -> DispatchResult {
+        ensure!(
+            !Self::is_paused(network_id, direction, app),
+            Error::<T>::BridgePaused
+        );
+        Ok(())
+    }
+
+    fn ensure_not_paused_weight() -> Weight {
+        <T as Config>::WeightInfo::is_paused()
+    }
+}
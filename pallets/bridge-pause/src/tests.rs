@@ -0,0 +1,147 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::{mock::*, Error, Event};
+use bridge_types::traits::BridgePauseChecker;
+use bridge_types::types::{MessageDirection, PauseScope};
+use bridge_types::{GenericNetworkId, SubNetworkId};
+use frame_support::{assert_noop, assert_ok, PalletId};
+use sp_runtime::DispatchError;
+
+const NETWORK_ID: GenericNetworkId = GenericNetworkId::Sub(SubNetworkId::Mainnet);
+const OTHER_NETWORK_ID: GenericNetworkId = GenericNetworkId::Sub(SubNetworkId::Kusama);
+const APP: PalletId = PalletId(*b"test/app");
+
+fn is_paused(
+    network_id: GenericNetworkId,
+    direction: MessageDirection,
+    app: Option<PalletId>,
+) -> bool {
+    BridgePause::ensure_not_paused(network_id, direction, app).is_err()
+}
+
+#[test]
+fn it_works_pause_and_resume() {
+    new_test_ext().execute_with(|| {
+        let scope = PauseScope::Network(NETWORK_ID);
+
+        assert_ok!(BridgePause::pause(RuntimeOrigin::root(), scope));
+        System::assert_last_event(RuntimeEvent::BridgePause(Event::Paused { scope }));
+        assert_eq!(BridgePause::paused_scopes(), vec![scope]);
+        assert_noop!(
+            BridgePause::ensure_not_paused(NETWORK_ID, MessageDirection::Outbound, None),
+            Error::<Test>::BridgePaused
+        );
+
+        assert_ok!(BridgePause::resume(RuntimeOrigin::root(), scope));
+        System::assert_last_event(RuntimeEvent::BridgePause(Event::Resumed { scope }));
+        assert!(BridgePause::paused_scopes().is_empty());
+        assert_ok!(BridgePause::ensure_not_paused(
+            NETWORK_ID,
+            MessageDirection::Outbound,
+            None
+        ));
+    });
+}
+
+#[test]
+fn it_fails_pause_wrong_origin() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            BridgePause::pause(RuntimeOrigin::signed(1), PauseScope::Global),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(BridgePause::pause(
+            RuntimeOrigin::root(),
+            PauseScope::Global
+        ));
+        assert_noop!(
+            BridgePause::resume(RuntimeOrigin::signed(1), PauseScope::Global),
+            DispatchError::BadOrigin
+        );
+    });
+}
+
+#[test]
+fn it_fails_pause_twice_and_resume_not_paused() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            BridgePause::resume(RuntimeOrigin::root(), PauseScope::Global),
+            Error::<Test>::NotPaused
+        );
+        assert_ok!(BridgePause::pause(
+            RuntimeOrigin::root(),
+            PauseScope::Global
+        ));
+        assert_noop!(
+            BridgePause::pause(RuntimeOrigin::root(), PauseScope::Global),
+            Error::<Test>::AlreadyPaused
+        );
+    });
+}
+
+#[test]
+fn it_works_pause_scopes() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(BridgePause::pause(
+            RuntimeOrigin::root(),
+            PauseScope::Direction(NETWORK_ID, MessageDirection::Inbound)
+        ));
+        assert!(is_paused(NETWORK_ID, MessageDirection::Inbound, None));
+        assert!(!is_paused(NETWORK_ID, MessageDirection::Outbound, None));
+        assert!(!is_paused(
+            OTHER_NETWORK_ID,
+            MessageDirection::Inbound,
+            None
+        ));
+
+        assert_ok!(BridgePause::pause(
+            RuntimeOrigin::root(),
+            PauseScope::App(NETWORK_ID, APP)
+        ));
+        assert!(is_paused(NETWORK_ID, MessageDirection::Outbound, Some(APP)));
+        assert!(!is_paused(NETWORK_ID, MessageDirection::Outbound, None));
+        assert!(!is_paused(
+            OTHER_NETWORK_ID,
+            MessageDirection::Outbound,
+            Some(APP)
+        ));
+
+        assert_ok!(BridgePause::pause(
+            RuntimeOrigin::root(),
+            PauseScope::Global
+        ));
+        assert!(is_paused(
+            OTHER_NETWORK_ID,
+            MessageDirection::Outbound,
+            None
+        ));
+    });
+}
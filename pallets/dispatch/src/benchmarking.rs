@@ -68,7 +68,7 @@ benchmarks_instance_pallet! {
     dispatch_success {
         let message_id = MessageId::basic(GenericNetworkId::EVM(1.into()), GenericNetworkId::Sub(SubNetworkId::Mainnet), 1);
     }: {
-        crate::Pallet::<T, I>::dispatch(
+        let _ = crate::Pallet::<T, I>::dispatch(
             1.into(),
             message_id,
             Default::default(),
@@ -77,7 +77,7 @@ benchmarks_instance_pallet! {
             AdditionalEVMInboundData {
                 source: Default::default()
             }
-        )
+        );
     }
     verify {
         assert_last_event::<T, I>(crate::Event::<T, I>::MessageDispatched(message_id, Err(DispatchError::BadOrigin)).into());
@@ -86,7 +86,7 @@ benchmarks_instance_pallet! {
     dispatch_decode_failed {
         let message_id = MessageId::basic(GenericNetworkId::EVM(1.into()), GenericNetworkId::Sub(SubNetworkId::Mainnet), 1);
     }: {
-        crate::Pallet::<T, I>::dispatch(
+        let _ = crate::Pallet::<T, I>::dispatch(
             1.into(),
             message_id,
             Default::default(),
@@ -94,7 +94,7 @@ benchmarks_instance_pallet! {
             AdditionalEVMInboundData {
                 source: Default::default()
             }
-        )
+        );
     }
     verify {
         assert_last_event::<T, I>(crate::Event::<T, I>::MessageDecodeFailed(message_id).into());
@@ -109,7 +109,8 @@ pub mod pallet {
 
     use super::*;
     use crate::weights::WeightInfo;
-    use bridge_types::GenericTimepoint;
+    use bridge_types::types::MessageDirection;
+    use bridge_types::{GenericNetworkId, GenericTimepoint};
     use frame_support::dispatch::GetDispatchInfo;
     use frame_support::pallet_prelude::*;
     use frame_support::traits::StorageVersion;
@@ -159,6 +160,12 @@ pub mod pallet {
         /// rejects the call, special event (`Event::MessageRejected`) is emitted.
         type CallFilter: Contains<<Self as Config<I>>::Call>;
 
+        /// Checks whether the bridge messages are paused.
+        type BridgePause: traits::BridgePauseChecker;
+
+        /// Returns the app of the call to check whether the app transfers are paused.
+        type CallApp: traits::BridgeCallApp<<Self as Config<I>>::Call>;
+
         type WeightInfo: WeightInfo;
     }
 
@@ -177,15 +184,43 @@ pub mod pallet {
         MessageRejected(T::MessageId),
         /// We have failed to decode a Call from the message.
         MessageDecodeFailed(T::MessageId),
+        /// Message has been rejected because its network or app is paused.
+        MessagePaused(T::MessageId),
+    }
+
+    #[pallet::error]
+    pub enum Error<T, I = ()> {
+        /// The message call can't be decoded.
+        MessageDecodeFailed,
+        /// The message call is rejected by the call filter.
+        MessageRejected,
     }
 
     #[pallet::origin]
     #[allow(type_alias_bounds)]
     pub type Origin<T: Config<I>, I: 'static = ()> = RawOrigin<<T as Config<I>>::OriginOutput>;
 
+    impl<T: Config<I>, I: 'static> Pallet<T, I>
+    where
+        NetworkIdOf<T, I>: Into<GenericNetworkId>,
+    {
+        fn ensure_call_not_paused(
+            network_id: NetworkIdOf<T, I>,
+            call: &<T as Config<I>>::Call,
+        ) -> DispatchResult {
+            <T::BridgePause as traits::BridgePauseChecker>::ensure_not_paused(
+                network_id.into(),
+                MessageDirection::Inbound,
+                <T::CallApp as traits::BridgeCallApp<_>>::call_app(call),
+            )
+        }
+    }
+
     impl<T: Config<I>, I: 'static>
         traits::MessageDispatch<T, NetworkIdOf<T, I>, T::MessageId, AdditionalOf<T, I>>
         for Pallet<T, I>
+    where
+        NetworkIdOf<T, I>: Into<GenericNetworkId> + Clone,
     {
         fn dispatch(
             network_id: NetworkIdOf<T, I>,
@@ -193,18 +228,23 @@ pub mod pallet {
             timepoint: GenericTimepoint,
             payload: &[u8],
             additional: AdditionalOf<T, I>,
-        ) {
+        ) -> DispatchResult {
             let call = match <T as Config<I>>::Call::decode(&mut &payload[..]) {
                 Ok(call) => call,
                 Err(_) => {
                     Self::deposit_event(Event::MessageDecodeFailed(message_id));
-                    return;
+                    return Err(Error::<T, I>::MessageDecodeFailed.into());
                 }
             };
 
             if !T::CallFilter::contains(&call) {
                 Self::deposit_event(Event::MessageRejected(message_id));
-                return;
+                return Err(Error::<T, I>::MessageRejected.into());
+            }
+
+            if let Err(err) = Self::ensure_call_not_paused(network_id.clone(), &call) {
+                Self::deposit_event(Event::MessagePaused(message_id));
+                return Err(err);
             }
 
             let origin = RawOrigin::new(<T::OriginOutput as traits::BridgeOriginOutput>::new(
@@ -214,12 +254,10 @@ pub mod pallet {
                 additional,
             ))
             .into();
-            let result = call.dispatch(origin);
+            let result = call.dispatch(origin).map(drop).map_err(|e| e.error);
 
-            Self::deposit_event(Event::MessageDispatched(
-                message_id,
-                result.map(drop).map_err(|e| e.error),
-            ));
+            Self::deposit_event(Event::MessageDispatched(message_id, result));
+            result
         }
 
         fn dispatch_weight(payload: &[u8]) -> Weight {
@@ -233,6 +271,17 @@ pub mod pallet {
             dispatch_info
                 .weight
                 .saturating_add(<T as Config<I>>::WeightInfo::dispatch_success())
+                .saturating_add(
+                    <T::BridgePause as traits::BridgePauseChecker>::ensure_not_paused_weight(),
+                )
+        }
+
+        /// Undecodable calls are not paused, their dispatch fails anyway.
+        fn ensure_not_paused(network_id: NetworkIdOf<T, I>, payload: &[u8]) -> DispatchResult {
+            match <T as Config<I>>::Call::decode(&mut &payload[..]) {
+                Ok(call) => Self::ensure_call_not_paused(network_id, &call),
+                Err(_) => Ok(()),
+            }
         }
 
         #[cfg(feature = "runtime-benchmarks")]
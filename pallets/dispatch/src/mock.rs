@@ -1,12 +1,13 @@
 use super::*;
 use bridge_types::evm::AdditionalEVMInboundData;
 use bridge_types::types;
-use bridge_types::EVMChainId;
-use frame_support::parameter_types;
+use bridge_types::{EVMChainId, GenericNetworkId};
 use frame_support::traits::{ConstU32, Everything};
+use frame_support::{parameter_types, PalletId};
 use sp_core::H256;
 use sp_runtime::testing::Header;
 use sp_runtime::traits::{BlakeTwo256, IdentityLookup, Keccak256};
+use sp_runtime::DispatchError;
 
 use crate as dispatch;
 
@@ -67,6 +68,44 @@ impl frame_support::traits::Contains<RuntimeCall> for CallFilter {
     }
 }
 
+parameter_types! {
+    pub static PausedApp: Option<PalletId> = None;
+}
+
+pub const REMARK_APP: PalletId = PalletId(*b"remarkap");
+
+pub struct MockBridgePause;
+impl bridge_types::traits::BridgePauseChecker for MockBridgePause {
+    fn ensure_not_paused(
+        _network_id: GenericNetworkId,
+        _direction: types::MessageDirection,
+        app: Option<PalletId>,
+    ) -> DispatchResult {
+        match app {
+            Some(app) if PausedApp::get() == Some(app) => {
+                Err(DispatchError::Other("bridge paused"))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn ensure_not_paused_weight() -> frame_support::weights::Weight {
+        Default::default()
+    }
+}
+
+pub struct CallApp;
+impl bridge_types::traits::BridgeCallApp<RuntimeCall> for CallApp {
+    fn call_app(call: &RuntimeCall) -> Option<PalletId> {
+        match call {
+            RuntimeCall::System(frame_system::pallet::Call::<Test>::remark { .. }) => {
+                Some(REMARK_APP)
+            }
+            _ => None,
+        }
+    }
+}
+
 impl dispatch::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type OriginOutput = types::CallOriginOutput<EVMChainId, H256, AdditionalEVMInboundData>;
@@ -75,6 +114,8 @@ impl dispatch::Config for Test {
     type Hashing = Keccak256;
     type Call = RuntimeCall;
     type CallFilter = CallFilter;
+    type BridgePause = MockBridgePause;
+    type CallApp = CallApp;
     type WeightInfo = ();
 }
 
@@ -23,12 +23,15 @@ fn test_dispatch_bridge_message() {
                 .encode();
 
         System::set_block_number(1);
-        Dispatch::dispatch(
-            2u32.into(),
-            id,
-            Default::default(),
-            &message,
-            AdditionalEVMInboundData { source },
+        assert_eq!(
+            Dispatch::dispatch(
+                2u32.into(),
+                id,
+                Default::default(),
+                &message,
+                AdditionalEVMInboundData { source },
+            ),
+            Err(DispatchError::BadOrigin)
         );
 
         assert_eq!(
@@ -59,12 +62,15 @@ fn test_message_decode_failed() {
         let message: Vec<u8> = vec![1, 2, 3];
 
         System::set_block_number(1);
-        Dispatch::dispatch(
-            2u32.into(),
-            id,
-            Default::default(),
-            &message,
-            AdditionalEVMInboundData { source },
+        assert_eq!(
+            Dispatch::dispatch(
+                2u32.into(),
+                id,
+                Default::default(),
+                &message,
+                AdditionalEVMInboundData { source },
+            ),
+            Err(Error::<Test>::MessageDecodeFailed.into())
         );
 
         assert_eq!(
@@ -94,12 +100,15 @@ fn test_message_rejected() {
                 .encode();
 
         System::set_block_number(1);
-        Dispatch::dispatch(
-            2u32.into(),
-            id,
-            Default::default(),
-            &message,
-            AdditionalEVMInboundData { source },
+        assert_eq!(
+            Dispatch::dispatch(
+                2u32.into(),
+                id,
+                Default::default(),
+                &message,
+                AdditionalEVMInboundData { source },
+            ),
+            Err(Error::<Test>::MessageRejected.into())
         );
 
         assert_eq!(
@@ -112,3 +121,51 @@ fn test_message_rejected() {
         );
     })
 }
+
+#[test]
+fn test_message_paused() {
+    new_test_ext().execute_with(|| {
+        let id = types::MessageId::batched(
+            SubNetworkId::Mainnet.into(),
+            SubNetworkId::Rococo.into(),
+            1,
+            37,
+        );
+        let source = H160::repeat_byte(7);
+
+        let message =
+            RuntimeCall::System(frame_system::pallet::Call::<Test>::remark { remark: vec![] })
+                .encode();
+
+        PausedApp::set(Some(REMARK_APP));
+        assert_eq!(
+            Dispatch::ensure_not_paused(2u32.into(), &message),
+            Err(DispatchError::Other("bridge paused"))
+        );
+        // Undecodable messages are not paused, the dispatch reports them
+        assert_eq!(Dispatch::ensure_not_paused(2u32.into(), &[1, 2, 3]), Ok(()));
+
+        System::set_block_number(1);
+        assert_eq!(
+            Dispatch::dispatch(
+                2u32.into(),
+                id,
+                Default::default(),
+                &message,
+                AdditionalEVMInboundData { source },
+            ),
+            Err(DispatchError::Other("bridge paused"))
+        );
+        assert_eq!(
+            System::events(),
+            vec![EventRecord {
+                phase: Phase::Initialization,
+                event: RuntimeEvent::Dispatch(crate::Event::<Test>::MessagePaused(id)),
+                topics: vec![],
+            }],
+        );
+
+        PausedApp::set(None);
+        assert_eq!(Dispatch::ensure_not_paused(2u32.into(), &message), Ok(()));
+    })
+}
@@ -0,0 +1,249 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Benchmarks assume `VerifyPoW` is disabled, Ethash verification cost
+//! is benchmarked separately by `verify_ethash`.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use crate::Pallet as EthereumLightClient;
+use bridge_types::clique::{self, CliqueSnapshot, DIFF_IN_TURN, EXTRA_SEAL, EXTRA_VANITY};
+use bridge_types::difficulty::{ForkConfig, EPOCH_LENGTH};
+use bridge_types::ethashproof::DoubleNodeWithMerkleProof;
+use bridge_types::header::EMPTY_OMMERS_HASH;
+use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_support::assert_ok;
+use frame_support::traits::Get;
+use frame_system::{self, RawOrigin};
+use hex_literal::hex;
+use sp_runtime::traits::{Hash, Keccak256};
+
+fn network_config() -> EthNetworkConfig {
+    EthNetworkConfig::Custom {
+        chain_id: 1337u32.into(),
+        consensus: Consensus::Ethash {
+            fork_config: ForkConfig::sepolia(),
+        },
+    }
+}
+
+fn genesis_header() -> Header {
+    Header {
+        number: 0,
+        timestamp: 1_600_000_000,
+        ommers_hash: EMPTY_OMMERS_HASH.into(),
+        gas_limit: 30_000_000u64.into(),
+        difficulty: 131_072u64.into(),
+        base_fee: Some(7u64.into()),
+        ..Default::default()
+    }
+}
+
+fn child_of(parent: &Header) -> Header {
+    let timestamp = parent.timestamp + 15;
+    Header {
+        parent_hash: parent.compute_hash(),
+        number: parent.number + 1,
+        timestamp,
+        ommers_hash: EMPTY_OMMERS_HASH.into(),
+        gas_limit: parent.gas_limit,
+        difficulty: ForkConfig::sepolia()
+            .calc_difficulty(timestamp, parent)
+            .expect("difficulty is valid"),
+        base_fee: ForkConfig::sepolia()
+            .base_fee_params()
+            .calc_next_base_fee(parent)
+            .expect("base fee is valid"),
+        ..Default::default()
+    }
+}
+
+fn register_network<T: Config>() -> Header {
+    let genesis = genesis_header();
+    assert_ok!(EthereumLightClient::<T>::register_network(
+        RawOrigin::Root.into(),
+        network_config(),
+        genesis.clone(),
+        genesis.difficulty,
+    ));
+    genesis
+}
+
+fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
+    frame_system::Pallet::<T>::assert_last_event(generic_event.into());
+}
+
+benchmarks! {
+    register_network {
+        let header = genesis_header();
+        let difficulty = header.difficulty;
+        let header_id = HeaderId {
+            number: header.number,
+            hash: header.compute_hash(),
+        };
+    }: _(RawOrigin::Root, network_config(), header, difficulty)
+    verify {
+        assert_last_event::<T>(Event::NetworkRegistered {
+            network_id: network_config().chain_id(),
+            header_id,
+        }.into());
+    }
+
+    // Worst case: the header becomes the best one, finalizes its ancestors
+    // and triggers pruning of the maximum number of old headers.
+    // The ancestry walk covers `DescendantsUntilFinalized` headers, the rest
+    // of the allowed depth is charged as additional reads.
+    import_header {
+        let caller: T::AccountId = whitelisted_caller();
+        let network_id = network_config().chain_id();
+        let mut header = register_network::<T>();
+        let chain_length = T::DescendantsUntilFinalized::get()
+            + T::HeadersToKeep::get().max(1)
+            + MAX_HEADERS_TO_PRUNE_IN_SINGLE_IMPORT;
+        for _ in 0..chain_length {
+            header = child_of(&header);
+            assert_ok!(EthereumLightClient::<T>::import_header(
+                RawOrigin::Signed(caller.clone()).into(),
+                network_id,
+                header.clone(),
+                vec![],
+            ));
+        }
+        let header = child_of(&header);
+        let hash = header.compute_hash();
+    }: _(RawOrigin::Signed(caller), network_id, header, vec![])
+    verify {
+        assert_eq!(EthereumLightClient::<T>::best_block(network_id).map(|(id, _)| id.hash), Some(hash));
+    }
+
+    verifier_verify {
+        let network_id = network_config().chain_id();
+        let genesis = register_network::<T>();
+        let proof = EthereumLightClient::<T>::valid_proof().expect("proof is provided");
+        let mut header = child_of(&genesis);
+        header.receipts_root = fixtures::receipts_root();
+        let receipt = header
+            .check_receipt_proof(&proof.data)
+            .expect("proof is valid")
+            .expect("receipt is valid");
+        let message = Keccak256::hash_of(&receipt.logs[0]);
+        Headers::<T>::insert(network_id, proof.block_hash, StoredHeader {
+            submitter: None,
+            total_difficulty: genesis.difficulty + header.difficulty,
+            header,
+            finalized: true,
+        });
+    }: {
+        assert_ok!(EthereumLightClient::<T>::verify(network_id.into(), message, &proof));
+    }
+
+    append_dag_roots {
+        let n in 1 .. MAX_DAG_ROOTS_IN_BATCH;
+        let epoch_length = EPOCH_LENGTH;
+        let start_epoch = BuiltinDagRoots::end_epoch(epoch_length).expect("epoch length is valid");
+        let roots = vec![H128::repeat_byte(1); n as usize];
+        let header = Header {
+            number: start_epoch * epoch_length,
+            ..Default::default()
+        };
+    }: _(RawOrigin::Root, epoch_length, start_epoch, roots, header, vec![])
+    verify {
+        assert_last_event::<T>(Event::DagRootsAppended {
+            epoch_length,
+            start_epoch,
+            count: n,
+        }.into());
+    }
+
+    // Worst case: the maximum number of the longest DAG Merkle proofs.
+    // The proof doesn't match the DAG, but the seal is fully computed anyway.
+    verify_ethash {
+        let header = Header {
+            number: 1,
+            seal: vec![
+                [&[0xa0][..], &[1u8; 32]].concat(),
+                [&[0x88][..], &[2u8; 8]].concat(),
+            ],
+            ..genesis_header()
+        };
+        let proof = vec![
+            DoubleNodeWithMerkleProof::from_values(
+                Default::default(),
+                vec![H128::repeat_byte(1); MAX_DAG_MERKLE_PROOF_LENGTH as usize],
+            );
+            ETHASH_PROOF_NODES as usize
+        ];
+    }: {
+        assert_eq!(
+            EthereumLightClient::<T>::verify_pow(
+                StoredDagRoots::<T>::default(),
+                EPOCH_LENGTH,
+                &header,
+                &proof,
+            ),
+            Err(Error::<T>::InvalidEthashProof.into())
+        );
+    }
+
+    // The seal signature is not produced by a known key, the recovered
+    // signer is made the only authorized one instead.
+    verify_clique {
+        let genesis = genesis_header();
+        let mut extra_data = vec![0u8; EXTRA_VANITY + EXTRA_SEAL];
+        // Signature with `r` equal to the x coordinate of the secp256k1 generator and `s = 1`
+        extra_data[EXTRA_VANITY..EXTRA_VANITY + 32].copy_from_slice(&hex!(
+            "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"
+        ));
+        extra_data[EXTRA_VANITY + 63] = 1;
+        let header = Header {
+            parent_hash: genesis.compute_hash(),
+            number: 1,
+            timestamp: genesis.timestamp + 15,
+            ommers_hash: EMPTY_OMMERS_HASH.into(),
+            gas_limit: genesis.gas_limit,
+            difficulty: DIFF_IN_TURN.into(),
+            extra_data,
+            ..Default::default()
+        };
+        let signer = clique::recover_signer(&header).expect("signature is valid");
+        let mut snapshot = CliqueSnapshot {
+            number: genesis.number,
+            hash: genesis.compute_hash(),
+            timestamp: genesis.timestamp,
+            signers: vec![signer],
+            recents: vec![],
+        };
+    }: {
+        assert_eq!(snapshot.apply(&header, 15, 30000), Ok(signer));
+    }
+
+    impl_benchmark_test_suite!(EthereumLightClient, crate::mock::new_test_ext(), crate::mock::Test);
+}
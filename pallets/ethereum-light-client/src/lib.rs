@@ -0,0 +1,796 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Ethereum PoW light client.
+//!
+//! Imports Ethereum (and Ethereum Classic) headers per `EVMChainId`, verifies
+//! their Ethash seal against DAG Merkle proofs and their difficulty against
+//! the network fork configuration, tracks forks by total difficulty and
+//! finalizes headers once they are buried under enough descendants.
+//!
+//! Headers of Clique proof-of-authority networks are verified against the
+//! signers snapshot of their parent, the snapshots are kept per header.
+//!
+//! Finalized headers are used to verify receipt inclusion proofs for
+//! inbound EVM messages (see [`bridge_types::traits::Verifier`]).
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use bridge_types::clique::CliqueSnapshot;
+use bridge_types::ethashproof::{
+    BuiltinDagRoots, DagRootsProvider, DoubleNodeWithMerkleProof, Error as EthashError,
+    EthashProver,
+};
+use bridge_types::network_config::{Consensus, NetworkConfig as EthNetworkConfig};
+use bridge_types::traits::{EthereumGasPriceOracle, Verifier};
+use bridge_types::{EVMChainId, GenericNetworkId, Header, HeaderId, H128, H256, U256};
+use codec::{Decode, Encode};
+use frame_support::dispatch::{DispatchError, DispatchResult};
+use frame_support::ensure;
+use frame_support::weights::Weight;
+use frame_support::RuntimeDebug;
+use sp_core::U512;
+use sp_std::marker::PhantomData;
+use sp_std::prelude::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(any(test, feature = "runtime-benchmarks"))]
+mod fixtures;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod migrations;
+pub mod weights;
+use weights::WeightInfo;
+
+pub use pallet::*;
+
+/// Max number of block numbers pruned during a single header import.
+const MAX_HEADERS_TO_PRUNE_IN_SINGLE_IMPORT: u64 = 8;
+
+/// Max number of DAG Merkle roots appended in a single call.
+pub const MAX_DAG_ROOTS_IN_BATCH: u32 = 64;
+
+/// Max number of blocks an imported header may be ahead of the finalized one
+/// in addition to `DescendantsUntilFinalized`. Bounds the ancestry walk done
+/// to check that the header descends from the finalized one.
+pub const MAX_UNFINALIZED_HEADERS_SURPLUS: u64 = 32;
+
+/// Number of DAG node pairs with Merkle proofs needed to verify an Ethash seal.
+pub const ETHASH_PROOF_NODES: u32 = 64;
+
+/// Max length of a DAG Merkle proof, enough for DAGs of up to 2^32 node pairs.
+pub const MAX_DAG_MERKLE_PROOF_LENGTH: u32 = 32;
+
+/// Header stored in the light client along with its fork choice data.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct StoredHeader<AccountId> {
+    /// Account that imported the header, `None` for the initial header.
+    pub submitter: Option<AccountId>,
+    pub header: Header,
+    /// Sum of difficulties of the header and all its ancestors.
+    pub total_difficulty: U256,
+    pub finalized: bool,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use frame_support::pallet_prelude::*;
+    use frame_support::{fail, Identity, Twox64Concat};
+    use frame_system::pallet_prelude::*;
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+        /// Number of descendants a header needs to be considered final.
+        #[pallet::constant]
+        type DescendantsUntilFinalized: Get<u64>;
+
+        /// Number of headers (counting back from the finalized one) kept in storage.
+        /// Older headers are pruned. Must be greater than zero.
+        #[pallet::constant]
+        type HeadersToKeep: Get<u64>;
+
+        /// Whether to check the Ethash seal of imported headers.
+        /// Should only be disabled in tests.
+        #[pallet::constant]
+        type VerifyPoW: Get<bool>;
+
+        type WeightInfo: WeightInfo;
+    }
+
+    /// The current storage version.
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+    #[pallet::pallet]
+    #[pallet::generate_store(pub(super) trait Store)]
+    #[pallet::storage_version(STORAGE_VERSION)]
+    #[pallet::without_storage_info]
+    pub struct Pallet<T>(_);
+
+    /// Network configurations of the registered networks.
+    #[pallet::storage]
+    #[pallet::getter(fn network_config)]
+    pub type NetworkConfig<T> = StorageMap<_, Identity, EVMChainId, EthNetworkConfig, OptionQuery>;
+
+    /// Imported headers.
+    #[pallet::storage]
+    #[pallet::getter(fn header)]
+    pub type Headers<T: Config> = StorageDoubleMap<
+        _,
+        Identity,
+        EVMChainId,
+        Identity,
+        H256,
+        StoredHeader<T::AccountId>,
+        OptionQuery,
+    >;
+
+    /// Hashes of the imported headers by block number.
+    #[pallet::storage]
+    #[pallet::getter(fn headers_by_number)]
+    pub type HeadersByNumber<T> =
+        StorageDoubleMap<_, Identity, EVMChainId, Twox64Concat, u64, Vec<H256>, OptionQuery>;
+
+    /// Best known header and its total difficulty.
+    #[pallet::storage]
+    #[pallet::getter(fn best_block)]
+    pub type BestBlock<T> = StorageMap<_, Identity, EVMChainId, (HeaderId, U256), OptionQuery>;
+
+    /// Latest finalized header.
+    #[pallet::storage]
+    #[pallet::getter(fn finalized_block)]
+    pub type FinalizedBlock<T> = StorageMap<_, Identity, EVMChainId, HeaderId, OptionQuery>;
+
+    /// Lowest block number which headers are still stored.
+    #[pallet::storage]
+    #[pallet::getter(fn oldest_unpruned_block)]
+    pub type OldestUnprunedBlock<T> = StorageMap<_, Identity, EVMChainId, u64, ValueQuery>;
+
+    /// Clique signers snapshots at the imported headers of Clique networks.
+    #[pallet::storage]
+    #[pallet::getter(fn clique_snapshot)]
+    pub type CliqueSnapshots<T> =
+        StorageDoubleMap<_, Identity, EVMChainId, Identity, H256, CliqueSnapshot, OptionQuery>;
+
+    /// DAG Merkle roots appended by governance for epochs which roots
+    /// are not compiled into the runtime, by epoch length and epoch.
+    #[pallet::storage]
+    #[pallet::getter(fn dag_root)]
+    pub type DagRoots<T> =
+        StorageDoubleMap<_, Twox64Concat, u64, Twox64Concat, u64, H128, OptionQuery>;
+
+    /// First epoch without known DAG Merkle root, by epoch length.
+    /// Not set until the first roots are appended.
+    #[pallet::storage]
+    #[pallet::getter(fn dag_roots_end)]
+    pub type DagRootsEnd<T> = StorageMap<_, Twox64Concat, u64, u64, OptionQuery>;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        NetworkRegistered {
+            network_id: EVMChainId,
+            header_id: HeaderId,
+        },
+        HeaderImported {
+            network_id: EVMChainId,
+            header_id: HeaderId,
+        },
+        HeaderFinalized {
+            network_id: EVMChainId,
+            header_id: HeaderId,
+        },
+        DagRootsAppended {
+            epoch_length: u64,
+            start_epoch: u64,
+            count: u32,
+        },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Network with given chain id is not registered.
+        NetworkNotFound,
+        /// Network with given chain id is already registered.
+        NetworkAlreadyExists,
+        /// Network id is not an EVM network id.
+        InvalidNetwork,
+        /// Consensus of the network is not supported by this light client.
+        ConsensusNotSupported,
+        /// Header is already imported.
+        DuplicateHeader,
+        /// Parent of the header is not imported.
+        MissingParentHeader,
+        /// Header is older than the finalized header.
+        AncientHeader,
+        /// Header is too far ahead of the finalized header.
+        HeaderTooFarAhead,
+        /// Header doesn't descend from the finalized header.
+        HeaderOnStaleFork,
+        /// Header fields are inconsistent with its parent.
+        InvalidHeader,
+        /// Header difficulty doesn't match the expected one.
+        InvalidDifficulty,
+        /// Header base fee doesn't match the expected one.
+        InvalidBaseFee,
+        /// Ethash seal is invalid.
+        InvalidEthashProof,
+        /// Clique epoch length is zero.
+        InvalidCliqueConfig,
+        /// Header violates the Clique rules.
+        InvalidCliqueHeader,
+        /// Header is not found.
+        HeaderNotFound,
+        /// Header is not finalized yet.
+        HeaderNotFinalized,
+        /// Receipt proof is invalid.
+        InvalidProof,
+        /// Failed to decode the proven receipt.
+        DecodeFailed,
+        /// Proven receipt doesn't contain the message log.
+        LogNotFound,
+        /// Epoch length is not supported.
+        InvalidEpochLength,
+        /// DAG Merkle roots should be appended right after the last known one.
+        UnexpectedDagEpoch,
+        /// Number of DAG Merkle roots is zero or exceeds the batch limit.
+        InvalidDagRootsCount,
+        /// Header proving the DAG Merkle roots is not from the appended epochs.
+        HeaderOutOfDagEpochs,
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Register a new network with the trusted initial header.
+        #[pallet::call_index(0)]
+        #[pallet::weight(<T as Config>::WeightInfo::register_network())]
+        pub fn register_network(
+            origin: OriginFor<T>,
+            network_config: EthNetworkConfig,
+            header: Header,
+            initial_difficulty: U256,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            let network_id = network_config.chain_id();
+            ensure!(
+                !NetworkConfig::<T>::contains_key(network_id),
+                Error::<T>::NetworkAlreadyExists
+            );
+            let consensus = network_config.consensus();
+            ensure!(
+                Self::is_consensus_supported(&consensus),
+                Error::<T>::ConsensusNotSupported
+            );
+            let header_id = HeaderId {
+                number: header.number,
+                hash: header.compute_hash(),
+            };
+            if let Consensus::Clique { epoch, .. } = consensus {
+                ensure!(epoch > 0, Error::<T>::InvalidCliqueConfig);
+                // Initial header should be an epoch checkpoint with the signers list
+                let snapshot = CliqueSnapshot::from_checkpoint(&header).map_err(|err| {
+                    frame_support::log::debug!("Invalid Clique checkpoint: {:?}", err);
+                    Error::<T>::InvalidCliqueHeader
+                })?;
+                CliqueSnapshots::<T>::insert(network_id, header_id.hash, snapshot);
+            }
+            NetworkConfig::<T>::insert(network_id, network_config);
+            HeadersByNumber::<T>::insert(network_id, header.number, vec![header_id.hash]);
+            Headers::<T>::insert(
+                network_id,
+                header_id.hash,
+                StoredHeader {
+                    submitter: None,
+                    header,
+                    total_difficulty: initial_difficulty,
+                    finalized: true,
+                },
+            );
+            BestBlock::<T>::insert(network_id, (header_id, initial_difficulty));
+            FinalizedBlock::<T>::insert(network_id, header_id);
+            OldestUnprunedBlock::<T>::insert(network_id, header_id.number);
+            Self::deposit_event(Event::NetworkRegistered {
+                network_id,
+                header_id,
+            });
+            Ok(().into())
+        }
+
+        /// Import a single header.
+        ///
+        /// `proof` contains DAG nodes with Merkle proofs required to verify
+        /// the Ethash seal of the header.
+        ///
+        /// The header should be at most `DescendantsUntilFinalized` plus
+        /// [`MAX_UNFINALIZED_HEADERS_SURPLUS`] blocks ahead of the finalized one.
+        #[pallet::call_index(1)]
+        #[pallet::weight(Pallet::<T>::import_header_weight())]
+        pub fn import_header(
+            origin: OriginFor<T>,
+            network_id: EVMChainId,
+            header: Header,
+            proof: Vec<DoubleNodeWithMerkleProof>,
+        ) -> DispatchResultWithPostInfo {
+            let sender = ensure_signed(origin)?;
+            let Some(network_config) = NetworkConfig::<T>::get(network_id) else {
+                fail!(Error::<T>::NetworkNotFound)
+            };
+            let (parent, clique_snapshot) =
+                Self::validate_header_to_import(network_id, network_config, &header, &proof)?;
+            if let Some(snapshot) = clique_snapshot {
+                CliqueSnapshots::<T>::insert(network_id, snapshot.hash, snapshot);
+            }
+            Self::import_validated_header(network_id, sender, header, parent);
+            Ok(().into())
+        }
+
+        /// Append DAG Merkle roots for epochs which roots are not known yet.
+        ///
+        /// `header` with its Ethash `proof` should be from one of the appended
+        /// epochs, the batch is rejected if the seal can't be verified with the
+        /// new roots.
+        #[pallet::call_index(2)]
+        #[pallet::weight(<T as Config>::WeightInfo::append_dag_roots(roots.len() as u32)
+            .saturating_add(Pallet::<T>::verify_pow_weight()))]
+        pub fn append_dag_roots(
+            origin: OriginFor<T>,
+            epoch_length: u64,
+            start_epoch: u64,
+            roots: Vec<H128>,
+            header: Header,
+            proof: Vec<DoubleNodeWithMerkleProof>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            let count = roots.len() as u32;
+            ensure!(
+                count > 0 && count <= MAX_DAG_ROOTS_IN_BATCH,
+                Error::<T>::InvalidDagRootsCount
+            );
+            let end_epoch = match DagRootsEnd::<T>::get(epoch_length) {
+                Some(end_epoch) => end_epoch,
+                None => BuiltinDagRoots::end_epoch(epoch_length)
+                    .map_err(|_| Error::<T>::InvalidEpochLength)?,
+            };
+            ensure!(start_epoch == end_epoch, Error::<T>::UnexpectedDagEpoch);
+            let batch = DagRootsBatch {
+                start_epoch,
+                roots: &roots,
+            };
+            ensure!(
+                batch
+                    .dag_merkle_root(epoch_length, header.number / epoch_length)
+                    .is_ok(),
+                Error::<T>::HeaderOutOfDagEpochs
+            );
+            if T::VerifyPoW::get() {
+                Self::verify_pow(batch, epoch_length, &header, &proof)?;
+            }
+
+            for (epoch, root) in (start_epoch..).zip(roots) {
+                DagRoots::<T>::insert(epoch_length, epoch, root);
+            }
+            DagRootsEnd::<T>::insert(epoch_length, start_epoch + count as u64);
+            Self::deposit_event(Event::DagRootsAppended {
+                epoch_length,
+                start_epoch,
+                count,
+            });
+            Ok(().into())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Whether headers of networks with the given consensus can be imported.
+        pub fn is_consensus_supported(consensus: &Consensus) -> bool {
+            matches!(
+                consensus,
+                Consensus::Ethash { .. } | Consensus::Etchash { .. } | Consensus::Clique { .. }
+            )
+        }
+
+        /// Max number of blocks an imported header may be ahead of the finalized one.
+        pub fn max_unfinalized_depth() -> u64 {
+            T::DescendantsUntilFinalized::get().saturating_add(MAX_UNFINALIZED_HEADERS_SURPLUS)
+        }
+
+        /// Weight of the header import.
+        ///
+        /// The benchmark covers the ancestry walk of `DescendantsUntilFinalized`
+        /// headers, the surplus is charged as additional reads. Seal verification
+        /// is charged separately, the most expensive of Ethash (when enabled)
+        /// and Clique one.
+        pub fn import_header_weight() -> Weight {
+            <T as Config>::WeightInfo::import_header()
+                .saturating_add(T::DbWeight::get().reads(MAX_UNFINALIZED_HEADERS_SURPLUS))
+                .saturating_add(
+                    Self::verify_pow_weight().max(<T as Config>::WeightInfo::verify_clique()),
+                )
+        }
+
+        /// Weight of the Ethash seal verification, zero if it is disabled.
+        pub fn verify_pow_weight() -> Weight {
+            if T::VerifyPoW::get() {
+                <T as Config>::WeightInfo::verify_ethash()
+            } else {
+                Weight::zero()
+            }
+        }
+
+        /// Validates the header against its parent and the network consensus rules.
+        /// Returns the stored parent header and the Clique snapshot at the header
+        /// for Clique networks.
+        pub(crate) fn validate_header_to_import(
+            network_id: EVMChainId,
+            network_config: EthNetworkConfig,
+            header: &Header,
+            proof: &[DoubleNodeWithMerkleProof],
+        ) -> Result<(StoredHeader<T::AccountId>, Option<CliqueSnapshot>), DispatchError> {
+            let hash = header.compute_hash();
+            ensure!(
+                !Headers::<T>::contains_key(network_id, hash),
+                Error::<T>::DuplicateHeader
+            );
+
+            let Some(parent) = Headers::<T>::get(network_id, header.parent_hash) else {
+                fail!(Error::<T>::MissingParentHeader)
+            };
+
+            let finalized =
+                FinalizedBlock::<T>::get(network_id).ok_or(Error::<T>::NetworkNotFound)?;
+            ensure!(header.number > finalized.number, Error::<T>::AncientHeader);
+            ensure!(
+                header.number - finalized.number <= Self::max_unfinalized_depth(),
+                Error::<T>::HeaderTooFarAhead
+            );
+            let ancestor_at_finalized_number = ancestry::<T>(network_id, header.parent_hash)
+                .take((header.number - finalized.number) as usize)
+                .find(|(_, stored)| stored.header.number <= finalized.number);
+            ensure!(
+                matches!(
+                    ancestor_at_finalized_number,
+                    Some((ancestor_hash, _)) if ancestor_hash == finalized.hash
+                ),
+                Error::<T>::HeaderOnStaleFork
+            );
+
+            ensure!(
+                header.number == parent.header.number + 1
+                    && header.timestamp > parent.header.timestamp
+                    && header.gas_used <= header.gas_limit,
+                Error::<T>::InvalidHeader
+            );
+
+            if let Some(base_fee_params) = network_config.base_fee_params() {
+                base_fee_params
+                    .verify_base_fee(header, &parent.header)
+                    .map_err(|err| {
+                        frame_support::log::debug!("Invalid base fee: {:?}", err);
+                        Error::<T>::InvalidBaseFee
+                    })?;
+            }
+
+            let consensus = network_config.consensus();
+            if let Consensus::Clique { period, epoch } = consensus {
+                let Some(mut snapshot) = CliqueSnapshots::<T>::get(network_id, header.parent_hash) else {
+                    fail!(Error::<T>::MissingParentHeader)
+                };
+                snapshot.apply(header, period, epoch).map_err(|err| {
+                    frame_support::log::debug!("Invalid Clique header: {:?}", err);
+                    Error::<T>::InvalidCliqueHeader
+                })?;
+                return Ok((parent, Some(snapshot)));
+            }
+
+            // Signer data of Clique headers is stored in the extra data
+            ensure!(header.extra_data.len() <= 32, Error::<T>::InvalidHeader);
+            let expected_difficulty = match consensus {
+                Consensus::Ethash { fork_config } => {
+                    fork_config.calc_difficulty(header.timestamp, &parent.header)
+                }
+                Consensus::Etchash { fork_config } => {
+                    fork_config.calc_difficulty(header.timestamp, &parent.header)
+                }
+                _ => fail!(Error::<T>::ConsensusNotSupported),
+            }
+            .map_err(|err| {
+                frame_support::log::debug!("Failed to calculate difficulty: {}", err);
+                Error::<T>::InvalidDifficulty
+            })?;
+            ensure!(
+                header.difficulty == expected_difficulty,
+                Error::<T>::InvalidDifficulty
+            );
+
+            if T::VerifyPoW::get() {
+                let epoch_length = consensus
+                    .calc_epoch_length(header.number)
+                    .ok_or(Error::<T>::ConsensusNotSupported)?;
+                Self::verify_pow(StoredDagRoots::<T>::default(), epoch_length, header, proof)?;
+            }
+
+            Ok((parent, None))
+        }
+
+        pub(crate) fn verify_pow<P: DagRootsProvider>(
+            dag_roots: P,
+            epoch_length: u64,
+            header: &Header,
+            proof: &[DoubleNodeWithMerkleProof],
+        ) -> DispatchResult {
+            let (Some(header_mix_hash), Some(header_nonce)) = (header.mix_hash(), header.nonce()) else {
+                fail!(Error::<T>::InvalidHeader)
+            };
+            // Keep the verification cost within the benchmarked one
+            ensure!(
+                proof.len() == ETHASH_PROOF_NODES as usize
+                    && proof
+                        .iter()
+                        .all(|node| node.proof.len() <= MAX_DAG_MERKLE_PROOF_LENGTH as usize),
+                Error::<T>::InvalidEthashProof
+            );
+            let (mix_hash, result) = EthashProver::with_dag_roots(epoch_length, dag_roots)
+                .hashimoto_merkle(
+                    header.compute_partial_hash(),
+                    header_nonce,
+                    header.number,
+                    proof,
+                )
+                .map_err(|err| {
+                    frame_support::log::debug!("Failed to verify Ethash seal: {:?}", err);
+                    Error::<T>::InvalidEthashProof
+                })?;
+            ensure!(
+                H256::from(mix_hash.into_bytes()) == header_mix_hash
+                    && U256::from_big_endian(result.as_bytes())
+                        <= difficulty_boundary(header.difficulty),
+                Error::<T>::InvalidEthashProof
+            );
+            Ok(())
+        }
+
+        pub(crate) fn import_validated_header(
+            network_id: EVMChainId,
+            sender: T::AccountId,
+            header: Header,
+            parent: StoredHeader<T::AccountId>,
+        ) {
+            let header_id = HeaderId {
+                number: header.number,
+                hash: header.compute_hash(),
+            };
+            let total_difficulty = parent.total_difficulty.saturating_add(header.difficulty);
+
+            HeadersByNumber::<T>::append(network_id, header_id.number, header_id.hash);
+            Headers::<T>::insert(
+                network_id,
+                header_id.hash,
+                StoredHeader {
+                    submitter: Some(sender),
+                    header,
+                    total_difficulty,
+                    finalized: false,
+                },
+            );
+            Self::deposit_event(Event::HeaderImported {
+                network_id,
+                header_id,
+            });
+
+            let is_best = BestBlock::<T>::get(network_id)
+                .map(|(_, best_difficulty)| total_difficulty > best_difficulty)
+                .unwrap_or(true);
+            if !is_best {
+                return;
+            }
+            BestBlock::<T>::insert(network_id, (header_id, total_difficulty));
+
+            let Some(finalized) = FinalizedBlock::<T>::get(network_id) else {
+                return;
+            };
+            let Some((new_finalized_hash, new_finalized)) = ancestry::<T>(network_id, header_id.hash)
+                .nth(T::DescendantsUntilFinalized::get() as usize) else {
+                return;
+            };
+            if new_finalized.header.number <= finalized.number {
+                return;
+            }
+            let new_finalized_id = HeaderId {
+                number: new_finalized.header.number,
+                hash: new_finalized_hash,
+            };
+            for (hash, mut stored) in ancestry::<T>(network_id, new_finalized_hash)
+                .take_while(|(_, stored)| stored.header.number > finalized.number)
+            {
+                stored.finalized = true;
+                Headers::<T>::insert(network_id, hash, stored);
+            }
+            FinalizedBlock::<T>::insert(network_id, new_finalized_id);
+            Self::deposit_event(Event::HeaderFinalized {
+                network_id,
+                header_id: new_finalized_id,
+            });
+
+            Self::prune_headers(
+                network_id,
+                new_finalized_id
+                    .number
+                    .saturating_sub(T::HeadersToKeep::get().max(1)),
+            );
+        }
+
+        /// Removes headers with number lower than `prune_until`, but not more
+        /// than `MAX_HEADERS_TO_PRUNE_IN_SINGLE_IMPORT` block numbers at once.
+        fn prune_headers(network_id: EVMChainId, prune_until: u64) {
+            let oldest_unpruned = OldestUnprunedBlock::<T>::get(network_id);
+            let prune_until = prune_until
+                .min(oldest_unpruned.saturating_add(MAX_HEADERS_TO_PRUNE_IN_SINGLE_IMPORT));
+            for number in oldest_unpruned..prune_until {
+                for hash in HeadersByNumber::<T>::take(network_id, number).unwrap_or_default() {
+                    Headers::<T>::remove(network_id, hash);
+                    CliqueSnapshots::<T>::remove(network_id, hash);
+                }
+            }
+            if prune_until > oldest_unpruned {
+                OldestUnprunedBlock::<T>::insert(network_id, prune_until);
+            }
+        }
+    }
+}
+
+/// DAG Merkle roots appended by governance with the roots compiled into
+/// the runtime as a fallback.
+pub struct StoredDagRoots<T>(PhantomData<T>);
+
+impl<T> Default for StoredDagRoots<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: Config> DagRootsProvider for StoredDagRoots<T> {
+    fn dag_merkle_root(&self, epoch_length: u64, epoch: u64) -> Result<H128, EthashError> {
+        match DagRoots::<T>::get(epoch_length, epoch) {
+            Some(root) => Ok(root),
+            None => BuiltinDagRoots.dag_merkle_root(epoch_length, epoch),
+        }
+    }
+}
+
+/// DAG Merkle roots which are about to be appended.
+struct DagRootsBatch<'a> {
+    start_epoch: u64,
+    roots: &'a [H128],
+}
+
+impl DagRootsProvider for DagRootsBatch<'_> {
+    fn dag_merkle_root(&self, _epoch_length: u64, epoch: u64) -> Result<H128, EthashError> {
+        epoch
+            .checked_sub(self.start_epoch)
+            .and_then(|index| self.roots.get(index as usize))
+            .copied()
+            .ok_or(EthashError::EpochOutOfRange)
+    }
+}
+
+/// Iterates over the stored ancestors of the header, starting with the header itself.
+fn ancestry<T: Config>(
+    network_id: EVMChainId,
+    mut hash: H256,
+) -> impl Iterator<Item = (H256, StoredHeader<T::AccountId>)> {
+    sp_std::iter::from_fn(move || {
+        let stored = Headers::<T>::get(network_id, hash)?;
+        let current = hash;
+        hash = stored.header.parent_hash;
+        Some((current, stored))
+    })
+}
+
+/// Ethash boundary for the given difficulty: `2^256 / difficulty`.
+fn difficulty_boundary(difficulty: U256) -> U256 {
+    if difficulty <= U256::one() {
+        U256::max_value()
+    } else {
+        let boundary = (U512::one() << 256) / U512::from(difficulty);
+        U256::try_from(boundary).unwrap_or_else(|_| U256::max_value())
+    }
+}
+
+impl<T: Config> Verifier for Pallet<T> {
+    type Proof = bridge_types::evm::Proof;
+
+    /// Verifies that the receipt proven by `proof` is included in a finalized
+    /// header at `tx_index` and contains the log with `message` hash.
+    fn verify(network_id: GenericNetworkId, message: H256, proof: &Self::Proof) -> DispatchResult {
+        let network_id = network_id.evm().ok_or(Error::<T>::InvalidNetwork)?;
+        let stored =
+            Headers::<T>::get(network_id, proof.block_hash).ok_or(Error::<T>::HeaderNotFound)?;
+        ensure!(stored.finalized, Error::<T>::HeaderNotFinalized);
+        let network_config =
+            NetworkConfig::<T>::get(network_id).ok_or(Error::<T>::NetworkNotFound)?;
+        let receipt = match bridge_types::header::check_receipt_proof_with_types(
+            stored.header.receipts_root,
+            proof.tx_index.into(),
+            &proof.data,
+            network_config.receipt_types(),
+        ) {
+            Some(Ok(receipt)) => receipt,
+            Some(Err(err)) => {
+                frame_support::log::debug!("Failed to decode receipt: {:?}", err);
+                return Err(Error::<T>::DecodeFailed.into());
+            }
+            None => return Err(Error::<T>::InvalidProof.into()),
+        };
+        ensure!(
+            receipt.contains_hashed_log(message),
+            Error::<T>::LogNotFound
+        );
+        Ok(())
+    }
+
+    fn verify_weight(_proof: &Self::Proof) -> Weight {
+        <T as Config>::WeightInfo::verifier_verify()
+    }
+
+    /// Proof of the fixture receipt, a finalized header with its receipts
+    /// root is expected to be imported under the `block_hash`.
+    #[cfg(feature = "runtime-benchmarks")]
+    fn valid_proof() -> Option<Self::Proof> {
+        Some(bridge_types::evm::Proof {
+            block_hash: H256::repeat_byte(1),
+            tx_index: 5,
+            data: fixtures::receipt_proof(),
+        })
+    }
+}
+
+impl<T: Config> EthereumGasPriceOracle for Pallet<T> {
+    fn get_base_fee(
+        network_id: EVMChainId,
+        header_hash: H256,
+    ) -> Result<Option<U256>, DispatchError> {
+        let stored =
+            Headers::<T>::get(network_id, header_hash).ok_or(Error::<T>::HeaderNotFound)?;
+        Ok(stored.header.base_fee)
+    }
+
+    fn get_best_block_base_fee(network_id: EVMChainId) -> Result<Option<U256>, DispatchError> {
+        let (best, _) = BestBlock::<T>::get(network_id).ok_or(Error::<T>::NetworkNotFound)?;
+        Self::get_base_fee(network_id, best.hash)
+    }
+}
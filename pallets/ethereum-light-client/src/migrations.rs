@@ -0,0 +1,97 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use super::*;
+use frame_support::log::info;
+use frame_support::traits::{
+    GetStorageVersion, OnRuntimeUpgrade, PalletInfoAccess, StorageVersion,
+};
+
+pub mod v1 {
+    use super::*;
+    use bridge_types::header::LegacyHeader;
+
+    #[derive(Decode)]
+    struct OldStoredHeader<AccountId> {
+        submitter: Option<AccountId>,
+        header: LegacyHeader,
+        total_difficulty: U256,
+        finalized: bool,
+    }
+
+    /// Migrates the stored headers to the [`Header`] layout with the Shanghai and later
+    /// fields, the headers imported before the upgrade have none of them.
+    pub struct MigrateToV1<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+        fn on_runtime_upgrade() -> Weight {
+            if Pallet::<T>::on_chain_storage_version() != 0 {
+                info!(
+                    "{}: skip migration to v1, storage version is not 0",
+                    <Pallet<T> as PalletInfoAccess>::name()
+                );
+                return T::DbWeight::get().reads(1);
+            }
+            let mut translated = 0u64;
+            Headers::<T>::translate::<OldStoredHeader<T::AccountId>, _>(|_, _, stored| {
+                translated += 1;
+                Some(StoredHeader {
+                    submitter: stored.submitter,
+                    header: stored.header.into(),
+                    total_difficulty: stored.total_difficulty,
+                    finalized: stored.finalized,
+                })
+            });
+            StorageVersion::new(1).put::<Pallet<T>>();
+            info!(
+                "{}: migrated to v1, {} headers translated",
+                <Pallet<T> as PalletInfoAccess>::name(),
+                translated
+            );
+            T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+            Ok((Headers::<T>::iter_keys().count() as u64).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+            let headers =
+                u64::decode(&mut &state[..]).map_err(|_| "Failed to decode headers count")?;
+            frame_support::ensure!(
+                Headers::<T>::iter_values().count() as u64 == headers,
+                "Headers are not migrated"
+            );
+            Ok(())
+        }
+    }
+}
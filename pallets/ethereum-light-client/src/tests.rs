@@ -0,0 +1,925 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::mock::*;
+use crate::{
+    fixtures, BestBlock, DagRoots, DagRootsEnd, Error, Event, FinalizedBlock, Headers,
+    HeadersByNumber, StoredDagRoots, StoredHeader,
+};
+use bridge_types::base_fee::INITIAL_BASE_FEE;
+use bridge_types::clique::{self, DIFF_IN_TURN, DIFF_NO_TURN, EXTRA_SEAL, EXTRA_VANITY};
+use bridge_types::difficulty::{ForkConfig, EPOCH_LENGTH, ETCHASH_EPOCH_LENGTH};
+use bridge_types::ethashproof::{DagRootsProvider, DAGS_MERKLE_ROOTS};
+use bridge_types::evm::Proof;
+use bridge_types::header::EMPTY_OMMERS_HASH;
+use bridge_types::network_config::{Consensus, NetworkConfig};
+use bridge_types::traits::{EthereumGasPriceOracle, Verifier};
+use bridge_types::{
+    Address, EVMChainId, GenericNetworkId, Header, HeaderId, SubNetworkId, H128, U256,
+};
+use frame_support::{assert_noop, assert_ok};
+use sp_core::{ecdsa, Pair};
+use sp_io::hashing::keccak_256;
+use sp_runtime::traits::{Hash, Keccak256};
+use sp_runtime::DispatchError;
+
+fn network_id() -> EVMChainId {
+    1337u32.into()
+}
+
+fn network_config() -> NetworkConfig {
+    NetworkConfig::Custom {
+        chain_id: network_id(),
+        consensus: Consensus::Ethash {
+            fork_config: ForkConfig::sepolia(),
+        },
+    }
+}
+
+fn genesis_header() -> Header {
+    Header {
+        number: 0,
+        timestamp: 1_600_000_000,
+        ommers_hash: EMPTY_OMMERS_HASH.into(),
+        gas_limit: 30_000_000u64.into(),
+        difficulty: 131_072u64.into(),
+        base_fee: Some(7u64.into()),
+        ..Default::default()
+    }
+}
+
+fn child_of(parent: &Header, time_delta: u64) -> Header {
+    let timestamp = parent.timestamp + time_delta;
+    Header {
+        parent_hash: parent.compute_hash(),
+        number: parent.number + 1,
+        timestamp,
+        ommers_hash: EMPTY_OMMERS_HASH.into(),
+        gas_limit: parent.gas_limit,
+        difficulty: ForkConfig::sepolia()
+            .calc_difficulty(timestamp, parent)
+            .unwrap(),
+        base_fee: ForkConfig::sepolia()
+            .base_fee_params()
+            .calc_next_base_fee(parent)
+            .unwrap(),
+        ..Default::default()
+    }
+}
+
+fn header_id(header: &Header) -> HeaderId {
+    HeaderId {
+        number: header.number,
+        hash: header.compute_hash(),
+    }
+}
+
+fn register_network() -> Header {
+    let genesis = genesis_header();
+    assert_ok!(EthereumLightClient::register_network(
+        RuntimeOrigin::root(),
+        network_config(),
+        genesis.clone(),
+        genesis.difficulty,
+    ));
+    genesis
+}
+
+fn import(header: &Header) -> frame_support::dispatch::DispatchResultWithPostInfo {
+    EthereumLightClient::import_header(
+        RuntimeOrigin::signed(1),
+        network_id(),
+        header.clone(),
+        vec![],
+    )
+}
+
+/// Imports `count` descendants of `parent` and returns them.
+fn import_chain(parent: &Header, count: usize, time_delta: u64) -> Vec<Header> {
+    let mut headers: Vec<Header> = vec![];
+    for _ in 0..count {
+        let header = child_of(headers.last().unwrap_or(parent), time_delta);
+        assert_ok!(import(&header));
+        headers.push(header);
+    }
+    headers
+}
+
+#[test]
+fn it_works_register_network() {
+    new_test_ext().execute_with(|| {
+        let genesis = register_network();
+        let genesis_id = header_id(&genesis);
+        assert_eq!(
+            EthereumLightClient::network_config(network_id()),
+            Some(network_config())
+        );
+        assert_eq!(
+            BestBlock::<Test>::get(network_id()),
+            Some((genesis_id, genesis.difficulty))
+        );
+        assert_eq!(FinalizedBlock::<Test>::get(network_id()), Some(genesis_id));
+        assert!(
+            Headers::<Test>::get(network_id(), genesis_id.hash)
+                .unwrap()
+                .finalized
+        );
+        System::assert_last_event(
+            Event::NetworkRegistered {
+                network_id: network_id(),
+                header_id: genesis_id,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn it_fails_register_network_twice() {
+    new_test_ext().execute_with(|| {
+        let genesis = register_network();
+        assert_noop!(
+            EthereumLightClient::register_network(
+                RuntimeOrigin::root(),
+                network_config(),
+                genesis.clone(),
+                genesis.difficulty,
+            ),
+            Error::<Test>::NetworkAlreadyExists
+        );
+    });
+}
+
+#[test]
+fn it_fails_register_network_unsupported_consensus() {
+    new_test_ext().execute_with(|| {
+        let genesis = genesis_header();
+        assert_noop!(
+            EthereumLightClient::register_network(
+                RuntimeOrigin::root(),
+                NetworkConfig::BeaconMainnet,
+                genesis.clone(),
+                genesis.difficulty,
+            ),
+            Error::<Test>::ConsensusNotSupported
+        );
+    });
+}
+
+#[test]
+fn it_fails_register_network_not_root() {
+    new_test_ext().execute_with(|| {
+        let genesis = genesis_header();
+        assert_noop!(
+            EthereumLightClient::register_network(
+                RuntimeOrigin::signed(1),
+                network_config(),
+                genesis.clone(),
+                genesis.difficulty,
+            ),
+            DispatchError::BadOrigin
+        );
+    });
+}
+
+#[test]
+fn it_works_import_header() {
+    new_test_ext().execute_with(|| {
+        let genesis = register_network();
+        let header = child_of(&genesis, 15);
+        assert_ok!(import(&header));
+
+        let stored = Headers::<Test>::get(network_id(), header.compute_hash()).unwrap();
+        assert_eq!(stored.submitter, Some(1));
+        assert_eq!(
+            stored.total_difficulty,
+            genesis.difficulty + header.difficulty
+        );
+        assert!(!stored.finalized);
+        assert_eq!(
+            BestBlock::<Test>::get(network_id()),
+            Some((header_id(&header), stored.total_difficulty))
+        );
+        assert_eq!(
+            HeadersByNumber::<Test>::get(network_id(), 1),
+            Some(vec![header.compute_hash()])
+        );
+    });
+}
+
+#[test]
+fn it_fails_import_header_unknown_network() {
+    new_test_ext().execute_with(|| {
+        let header = child_of(&genesis_header(), 15);
+        assert_noop!(import(&header), Error::<Test>::NetworkNotFound);
+    });
+}
+
+#[test]
+fn it_fails_import_header_twice() {
+    new_test_ext().execute_with(|| {
+        let genesis = register_network();
+        let header = child_of(&genesis, 15);
+        assert_ok!(import(&header));
+        assert_noop!(import(&header), Error::<Test>::DuplicateHeader);
+    });
+}
+
+#[test]
+fn it_fails_import_header_missing_parent() {
+    new_test_ext().execute_with(|| {
+        let genesis = register_network();
+        let header = child_of(&child_of(&genesis, 15), 15);
+        assert_noop!(import(&header), Error::<Test>::MissingParentHeader);
+    });
+}
+
+#[test]
+fn it_fails_import_header_invalid_fields() {
+    new_test_ext().execute_with(|| {
+        let genesis = register_network();
+
+        let mut header = child_of(&genesis, 15);
+        header.number = 2;
+        assert_noop!(import(&header), Error::<Test>::InvalidHeader);
+
+        let mut header = child_of(&genesis, 15);
+        header.timestamp = genesis.timestamp;
+        assert_noop!(import(&header), Error::<Test>::InvalidHeader);
+
+        let mut header = child_of(&genesis, 15);
+        header.gas_used = header.gas_limit + 1;
+        assert_noop!(import(&header), Error::<Test>::InvalidHeader);
+    });
+}
+
+#[test]
+fn it_fails_import_header_invalid_difficulty() {
+    new_test_ext().execute_with(|| {
+        let genesis = register_network();
+        let mut header = child_of(&genesis, 15);
+        header.difficulty += 1.into();
+        assert_noop!(import(&header), Error::<Test>::InvalidDifficulty);
+    });
+}
+
+#[test]
+fn test_difficulty_boundary() {
+    assert_eq!(crate::difficulty_boundary(U256::one()), U256::max_value());
+    assert_eq!(crate::difficulty_boundary(2.into()), U256::one() << 255);
+    assert_eq!(crate::difficulty_boundary(3.into()), U256::max_value() / 3);
+    assert_eq!(crate::difficulty_boundary(U256::max_value()), U256::one());
+}
+
+#[test]
+fn it_fails_import_header_invalid_base_fee() {
+    new_test_ext().execute_with(|| {
+        let genesis = register_network();
+        let mut header = child_of(&genesis, 15);
+        header.base_fee = Some(U256::from(42));
+        assert_noop!(import(&header), Error::<Test>::InvalidBaseFee);
+        header.base_fee = None;
+        assert_noop!(import(&header), Error::<Test>::InvalidBaseFee);
+    });
+}
+
+#[test]
+fn it_fails_import_header_without_seal() {
+    new_test_ext().execute_with(|| {
+        VerifyPoW::set(true);
+        let genesis = register_network();
+        let header = child_of(&genesis, 15);
+        assert_noop!(import(&header), Error::<Test>::InvalidHeader);
+        VerifyPoW::set(false);
+    });
+}
+
+#[test]
+fn it_fails_import_header_without_ethash_proof() {
+    new_test_ext().execute_with(|| {
+        VerifyPoW::set(true);
+        let genesis = register_network();
+        let mut header = child_of(&genesis, 15);
+        header.seal = vec![
+            rlp::encode(&[1u8; 32].to_vec()).to_vec(),
+            rlp::encode(&[2u8; 8].to_vec()).to_vec(),
+        ];
+        assert_noop!(import(&header), Error::<Test>::InvalidEthashProof);
+        VerifyPoW::set(false);
+    });
+}
+
+#[test]
+fn it_works_finalize_headers() {
+    new_test_ext().execute_with(|| {
+        let genesis = register_network();
+        let headers = import_chain(&genesis, 2, 15);
+        assert_eq!(
+            FinalizedBlock::<Test>::get(network_id()),
+            Some(header_id(&genesis))
+        );
+
+        let next = import_chain(headers.last().unwrap(), 1, 15);
+        let headers = [headers, next].concat();
+        assert_eq!(
+            FinalizedBlock::<Test>::get(network_id()),
+            Some(header_id(&headers[0]))
+        );
+        assert!(
+            Headers::<Test>::get(network_id(), headers[0].compute_hash())
+                .unwrap()
+                .finalized
+        );
+        assert!(
+            !Headers::<Test>::get(network_id(), headers[1].compute_hash())
+                .unwrap()
+                .finalized
+        );
+        System::assert_has_event(
+            Event::HeaderFinalized {
+                network_id: network_id(),
+                header_id: header_id(&headers[0]),
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn it_works_switch_to_heavier_fork() {
+    new_test_ext().execute_with(|| {
+        let genesis = register_network();
+        let slow_fork = import_chain(&genesis, 2, 15);
+        assert_eq!(
+            BestBlock::<Test>::get(network_id()).unwrap().0,
+            header_id(&slow_fork[1])
+        );
+
+        // Shorter block time results in higher difficulty
+        let fast_fork = import_chain(&genesis, 1, 5);
+        assert_eq!(
+            BestBlock::<Test>::get(network_id()).unwrap().0,
+            header_id(&slow_fork[1])
+        );
+        let next = import_chain(&fast_fork[0], 1, 5);
+        let fast_fork = [fast_fork, next].concat();
+        assert_eq!(
+            BestBlock::<Test>::get(network_id()).unwrap().0,
+            header_id(&fast_fork[1])
+        );
+    });
+}
+
+#[test]
+fn it_fails_import_header_on_stale_fork() {
+    new_test_ext().execute_with(|| {
+        let genesis = register_network();
+        let canonical = import_chain(&genesis, 3, 15);
+        let stale = child_of(&genesis, 5);
+        assert_eq!(
+            FinalizedBlock::<Test>::get(network_id()),
+            Some(header_id(&canonical[0]))
+        );
+        // Competes with the finalized header
+        assert_noop!(import(&stale), Error::<Test>::AncientHeader);
+
+        // Descends from the finalized header's sibling
+        let stale_child = child_of(&stale, 5);
+        Headers::<Test>::insert(
+            network_id(),
+            stale.compute_hash(),
+            Headers::<Test>::get(network_id(), canonical[0].compute_hash())
+                .map(|mut stored| {
+                    stored.header = stale.clone();
+                    stored.finalized = false;
+                    stored
+                })
+                .unwrap(),
+        );
+        assert_noop!(import(&stale_child), Error::<Test>::HeaderOnStaleFork);
+    });
+}
+
+#[test]
+fn it_fails_import_header_too_far_ahead() {
+    new_test_ext().execute_with(|| {
+        let genesis = register_network();
+        // Parent is stored as if it was imported far ahead of the finalized header
+        let mut parent = child_of(&genesis, 15);
+        parent.number = EthereumLightClient::max_unfinalized_depth();
+        Headers::<Test>::insert(
+            network_id(),
+            parent.compute_hash(),
+            StoredHeader {
+                submitter: None,
+                header: parent.clone(),
+                total_difficulty: genesis.difficulty + parent.difficulty,
+                finalized: false,
+            },
+        );
+        assert_noop!(
+            import(&child_of(&parent, 15)),
+            Error::<Test>::HeaderTooFarAhead
+        );
+    });
+}
+
+#[test]
+fn it_works_prune_headers() {
+    new_test_ext().execute_with(|| {
+        let genesis = register_network();
+        let headers = import_chain(&genesis, 10, 15);
+        // Finalized header is #8, headers older than #4 are pruned
+        assert_eq!(
+            FinalizedBlock::<Test>::get(network_id()),
+            Some(header_id(&headers[7]))
+        );
+        assert!(Headers::<Test>::get(network_id(), genesis.compute_hash()).is_none());
+        assert!(Headers::<Test>::get(network_id(), headers[2].compute_hash()).is_none());
+        assert!(HeadersByNumber::<Test>::get(network_id(), 3).is_none());
+        assert!(Headers::<Test>::get(network_id(), headers[3].compute_hash()).is_some());
+    });
+}
+
+#[test]
+fn it_works_verify_receipt_proof() {
+    new_test_ext().execute_with(|| {
+        let genesis = register_network();
+        let mut header = child_of(&genesis, 15);
+        header.receipts_root = fixtures::receipts_root();
+        assert_ok!(import(&header));
+
+        let proof = Proof {
+            block_hash: header.compute_hash(),
+            tx_index: 5,
+            data: fixtures::receipt_proof(),
+        };
+        let receipt = header.check_receipt_proof(&proof.data).unwrap().unwrap();
+        let message = Keccak256::hash_of(&receipt.logs[0]);
+
+        assert_noop!(
+            EthereumLightClient::verify(network_id().into(), message, &proof),
+            Error::<Test>::HeaderNotFinalized
+        );
+
+        import_chain(&header, 2, 15);
+        assert_ok!(EthereumLightClient::verify(
+            network_id().into(),
+            message,
+            &proof
+        ));
+        assert_noop!(
+            EthereumLightClient::verify(network_id().into(), [1u8; 32].into(), &proof),
+            Error::<Test>::LogNotFound
+        );
+        assert_noop!(
+            EthereumLightClient::verify(
+                GenericNetworkId::Sub(SubNetworkId::Mainnet),
+                message,
+                &proof
+            ),
+            Error::<Test>::InvalidNetwork
+        );
+        assert_noop!(
+            EthereumLightClient::verify(
+                network_id().into(),
+                message,
+                &Proof {
+                    data: fixtures::receipt_proof()[1..].to_vec(),
+                    ..proof.clone()
+                }
+            ),
+            Error::<Test>::InvalidProof
+        );
+        assert_noop!(
+            EthereumLightClient::verify(
+                network_id().into(),
+                message,
+                &Proof {
+                    block_hash: genesis.compute_hash(),
+                    ..proof
+                }
+            ),
+            Error::<Test>::InvalidProof
+        );
+    });
+}
+
+#[test]
+fn it_works_gas_price_oracle() {
+    new_test_ext().execute_with(|| {
+        let genesis = register_network();
+        let mut header = child_of(&genesis, 15);
+        header.gas_used = header.gas_limit;
+        let next = child_of(&header, 15);
+        assert_ok!(import(&header));
+        assert_ok!(import(&next));
+
+        assert_eq!(
+            EthereumLightClient::get_base_fee(network_id(), genesis.compute_hash()),
+            Ok(Some(U256::from(7)))
+        );
+        assert_eq!(
+            EthereumLightClient::get_best_block_base_fee(network_id()),
+            Ok(Some(U256::from(8)))
+        );
+        assert_eq!(
+            EthereumLightClient::get_base_fee(network_id(), [1u8; 32].into()),
+            Err(Error::<Test>::HeaderNotFound.into())
+        );
+        assert_eq!(
+            EthereumLightClient::get_best_block_base_fee(2u32.into()),
+            Err(Error::<Test>::NetworkNotFound.into())
+        );
+    });
+}
+
+fn header_at_epoch(epoch: u64) -> Header {
+    Header {
+        number: epoch * EPOCH_LENGTH,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn it_works_append_dag_roots() {
+    new_test_ext().execute_with(|| {
+        let roots = vec![H128::repeat_byte(1), H128::repeat_byte(2)];
+        assert_ok!(EthereumLightClient::append_dag_roots(
+            RuntimeOrigin::root(),
+            EPOCH_LENGTH,
+            512,
+            roots,
+            header_at_epoch(513),
+            vec![],
+        ));
+        System::assert_last_event(RuntimeEvent::EthereumLightClient(Event::DagRootsAppended {
+            epoch_length: EPOCH_LENGTH,
+            start_epoch: 512,
+            count: 2,
+        }));
+        assert_eq!(
+            DagRoots::<Test>::get(EPOCH_LENGTH, 513),
+            Some(H128::repeat_byte(2))
+        );
+        assert_eq!(DagRootsEnd::<Test>::get(EPOCH_LENGTH), Some(514));
+
+        let dag_roots = StoredDagRoots::<Test>::default();
+        assert_eq!(
+            dag_roots.dag_merkle_root(EPOCH_LENGTH, 0),
+            Ok(DAGS_MERKLE_ROOTS[0].into())
+        );
+        assert_eq!(
+            dag_roots.dag_merkle_root(EPOCH_LENGTH, 512),
+            Ok(H128::repeat_byte(1))
+        );
+        assert!(dag_roots.dag_merkle_root(EPOCH_LENGTH, 514).is_err());
+
+        assert_ok!(EthereumLightClient::append_dag_roots(
+            RuntimeOrigin::root(),
+            EPOCH_LENGTH,
+            514,
+            vec![H128::repeat_byte(3)],
+            header_at_epoch(514),
+            vec![],
+        ));
+        assert_eq!(DagRootsEnd::<Test>::get(EPOCH_LENGTH), Some(515));
+    });
+}
+
+#[test]
+fn it_fails_append_dag_roots_invalid_batch() {
+    new_test_ext().execute_with(|| {
+        let append = |epoch_length, start_epoch, count, header| {
+            EthereumLightClient::append_dag_roots(
+                RuntimeOrigin::root(),
+                epoch_length,
+                start_epoch,
+                vec![H128::repeat_byte(1); count],
+                header,
+                vec![],
+            )
+        };
+        assert_noop!(
+            EthereumLightClient::append_dag_roots(
+                RuntimeOrigin::signed(1),
+                EPOCH_LENGTH,
+                512,
+                vec![H128::repeat_byte(1)],
+                header_at_epoch(512),
+                vec![],
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            append(EPOCH_LENGTH, 512, 0, header_at_epoch(512)),
+            Error::<Test>::InvalidDagRootsCount
+        );
+        assert_noop!(
+            append(EPOCH_LENGTH, 512, 65, header_at_epoch(512)),
+            Error::<Test>::InvalidDagRootsCount
+        );
+        assert_noop!(
+            append(1000, 0, 1, header_at_epoch(0)),
+            Error::<Test>::InvalidEpochLength
+        );
+        assert_noop!(
+            append(EPOCH_LENGTH, 511, 1, header_at_epoch(511)),
+            Error::<Test>::UnexpectedDagEpoch
+        );
+        assert_noop!(
+            append(EPOCH_LENGTH, 513, 1, header_at_epoch(513)),
+            Error::<Test>::UnexpectedDagEpoch
+        );
+        assert_noop!(
+            append(EPOCH_LENGTH, 512, 1, header_at_epoch(513)),
+            Error::<Test>::HeaderOutOfDagEpochs
+        );
+        assert_ok!(append(
+            ETCHASH_EPOCH_LENGTH,
+            145,
+            1,
+            Header {
+                number: 145 * ETCHASH_EPOCH_LENGTH,
+                ..Default::default()
+            }
+        ));
+    });
+}
+
+#[test]
+fn it_fails_append_dag_roots_without_ethash_proof() {
+    new_test_ext().execute_with(|| {
+        VerifyPoW::set(true);
+        let mut header = header_at_epoch(512);
+        assert_noop!(
+            EthereumLightClient::append_dag_roots(
+                RuntimeOrigin::root(),
+                EPOCH_LENGTH,
+                512,
+                vec![H128::repeat_byte(1)],
+                header.clone(),
+                vec![],
+            ),
+            Error::<Test>::InvalidHeader
+        );
+        header.seal = vec![
+            rlp::encode(&[1u8; 32].to_vec()).to_vec(),
+            rlp::encode(&[2u8; 8].to_vec()).to_vec(),
+        ];
+        assert_noop!(
+            EthereumLightClient::append_dag_roots(
+                RuntimeOrigin::root(),
+                EPOCH_LENGTH,
+                512,
+                vec![H128::repeat_byte(1)],
+                header,
+                vec![],
+            ),
+            Error::<Test>::InvalidEthashProof
+        );
+        VerifyPoW::set(false);
+    });
+}
+
+fn clique_network_id() -> EVMChainId {
+    1338u32.into()
+}
+
+fn clique_network_config(epoch: u64) -> NetworkConfig {
+    NetworkConfig::Custom {
+        chain_id: clique_network_id(),
+        consensus: Consensus::Clique { period: 15, epoch },
+    }
+}
+
+fn clique_pair(seed: u8) -> ecdsa::Pair {
+    ecdsa::Pair::from_seed(&[seed; 32])
+}
+
+fn clique_address(seed: u8) -> Address {
+    let message = [0u8; 32];
+    let signature = clique_pair(seed).sign_prehashed(&message);
+    let public = sp_io::crypto::secp256k1_ecdsa_recover(&signature.0, &message).unwrap();
+    Address::from_slice(&keccak_256(&public)[12..])
+}
+
+/// Clique header signed by `seed` signer, `signers` are listed for checkpoints.
+fn clique_header(parent: Option<&Header>, seed: u8, signers: &[u8], difficulty: u64) -> Header {
+    let base_fee = match parent {
+        Some(parent) => clique_network_config(30000)
+            .base_fee_params()
+            .unwrap()
+            .calc_next_base_fee(parent)
+            .unwrap(),
+        None => Some(INITIAL_BASE_FEE.into()),
+    };
+    clique_header_with_base_fee(parent, seed, signers, difficulty, base_fee)
+}
+
+fn clique_header_with_base_fee(
+    parent: Option<&Header>,
+    seed: u8,
+    signers: &[u8],
+    difficulty: u64,
+    base_fee: Option<U256>,
+) -> Header {
+    let mut extra_data = vec![0u8; EXTRA_VANITY];
+    for signer in signers {
+        extra_data.extend_from_slice(clique_address(*signer).as_bytes());
+    }
+    extra_data.extend_from_slice(&[0u8; EXTRA_SEAL]);
+    let mut header = Header {
+        parent_hash: parent.map(|p| p.compute_hash()).unwrap_or_default(),
+        number: parent.map(|p| p.number + 1).unwrap_or_default(),
+        timestamp: parent.map(|p| p.timestamp + 15).unwrap_or(1_600_000_000),
+        ommers_hash: EMPTY_OMMERS_HASH.into(),
+        gas_limit: 30_000_000u64.into(),
+        extra_data,
+        difficulty: difficulty.into(),
+        base_fee,
+        ..Default::default()
+    };
+    let len = header.extra_data.len();
+    let signature = clique_pair(seed).sign_prehashed(&clique::seal_hash(&header).unwrap().0);
+    header.extra_data[len - EXTRA_SEAL..].copy_from_slice(&signature.0);
+    header
+}
+
+fn register_clique_network() -> Header {
+    let genesis = clique_header(None, 1, &[1, 2], DIFF_NO_TURN);
+    assert_ok!(EthereumLightClient::register_network(
+        RuntimeOrigin::root(),
+        clique_network_config(30000),
+        genesis.clone(),
+        genesis.difficulty,
+    ));
+    genesis
+}
+
+fn import_clique(header: &Header) -> frame_support::dispatch::DispatchResultWithPostInfo {
+    EthereumLightClient::import_header(
+        RuntimeOrigin::signed(1),
+        clique_network_id(),
+        header.clone(),
+        vec![],
+    )
+}
+
+#[test]
+fn it_fails_register_clique_network_zero_epoch() {
+    new_test_ext().execute_with(|| {
+        let genesis = clique_header(None, 1, &[1, 2], DIFF_NO_TURN);
+        assert_noop!(
+            EthereumLightClient::register_network(
+                RuntimeOrigin::root(),
+                clique_network_config(0),
+                genesis.clone(),
+                genesis.difficulty,
+            ),
+            Error::<Test>::InvalidCliqueConfig
+        );
+        let not_checkpoint = clique_header(None, 1, &[], DIFF_NO_TURN);
+        assert_noop!(
+            EthereumLightClient::register_network(
+                RuntimeOrigin::root(),
+                clique_network_config(30000),
+                not_checkpoint.clone(),
+                not_checkpoint.difficulty,
+            ),
+            Error::<Test>::InvalidCliqueHeader
+        );
+    });
+}
+
+#[test]
+fn it_works_import_clique_header() {
+    new_test_ext().execute_with(|| {
+        let genesis = register_clique_network();
+        let mut signers = [clique_address(1), clique_address(2)];
+        signers.sort();
+        // Block 1 is in turn of the second signer in the sorted list
+        let in_turn = if signers[1] == clique_address(1) {
+            1
+        } else {
+            2
+        };
+        let header = clique_header(Some(&genesis), in_turn, &[], DIFF_IN_TURN);
+        assert_ok!(import_clique(&header));
+        assert_eq!(
+            BestBlock::<Test>::get(clique_network_id()).map(|(id, _)| id),
+            Some(header_id(&header))
+        );
+        let snapshot =
+            EthereumLightClient::clique_snapshot(clique_network_id(), header.compute_hash())
+                .unwrap();
+        assert_eq!(snapshot.number, 1);
+        assert_eq!(snapshot.recents, vec![(1, clique_address(in_turn))]);
+
+        // The same signer can't sign the next block
+        let recent = clique_header(Some(&header), in_turn, &[], DIFF_NO_TURN);
+        assert_noop!(import_clique(&recent), Error::<Test>::InvalidCliqueHeader);
+    });
+}
+
+#[test]
+fn it_fails_import_clique_header_unauthorized_signer() {
+    new_test_ext().execute_with(|| {
+        let genesis = register_clique_network();
+        let header = clique_header(Some(&genesis), 3, &[], DIFF_NO_TURN);
+        assert_noop!(import_clique(&header), Error::<Test>::InvalidCliqueHeader);
+    });
+}
+
+#[test]
+fn it_fails_import_clique_header_invalid_base_fee() {
+    new_test_ext().execute_with(|| {
+        let genesis = register_clique_network();
+        let header =
+            clique_header_with_base_fee(Some(&genesis), 1, &[], DIFF_NO_TURN, Some(U256::from(42)));
+        assert_noop!(import_clique(&header), Error::<Test>::InvalidBaseFee);
+        let header = clique_header_with_base_fee(Some(&genesis), 1, &[], DIFF_NO_TURN, None);
+        assert_noop!(import_clique(&header), Error::<Test>::InvalidBaseFee);
+    });
+}
+
+#[test]
+fn test_migrate_to_v1() {
+    use bridge_types::header::LegacyHeader;
+    use codec::Encode;
+    use frame_support::storage::unhashed;
+    use frame_support::traits::{OnRuntimeUpgrade, StorageVersion};
+
+    new_test_ext().execute_with(|| {
+        let header = genesis_header();
+        let legacy = LegacyHeader {
+            parent_hash: header.parent_hash,
+            timestamp: header.timestamp,
+            number: header.number,
+            author: header.author,
+            transactions_root: header.transactions_root,
+            ommers_hash: header.ommers_hash,
+            extra_data: header.extra_data.clone(),
+            state_root: header.state_root,
+            receipts_root: header.receipts_root,
+            logs_bloom: header.logs_bloom,
+            gas_used: header.gas_used,
+            gas_limit: header.gas_limit,
+            difficulty: header.difficulty,
+            seal: header.seal.clone(),
+            base_fee: header.base_fee,
+        };
+        let hash = header.compute_hash();
+        StorageVersion::new(0).put::<EthereumLightClient>();
+        // Header stored with the old layout
+        unhashed::put_raw(
+            &Headers::<Test>::hashed_key_for(network_id(), hash),
+            &(Some(1u64), legacy, header.difficulty, true).encode(),
+        );
+
+        crate::migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+
+        let stored = Headers::<Test>::get(network_id(), hash).unwrap();
+        assert_eq!(
+            stored,
+            StoredHeader {
+                submitter: Some(1),
+                header: header.clone(),
+                total_difficulty: header.difficulty,
+                finalized: true,
+            }
+        );
+        assert_eq!(stored.header.compute_hash(), hash);
+        assert_eq!(
+            StorageVersion::get::<EthereumLightClient>(),
+            StorageVersion::new(1)
+        );
+    });
+}
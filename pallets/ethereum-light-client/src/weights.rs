@@ -0,0 +1,211 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Weights for ethereum_light_client
+//!
+//! NOT GENERATED BY THE BENCHMARK CLI: the values are estimates until the pallet
+//! is benchmarked, regenerate with
+//! `benchmark pallet --pallet=ethereum_light_client --extrinsic=* --template=./misc/pallet-weight-template.hbs`.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for ethereum_light_client.
+pub trait WeightInfo {
+	fn register_network() -> Weight;
+	fn import_header() -> Weight;
+	fn verifier_verify() -> Weight;
+	fn append_dag_roots(n: u32, ) -> Weight;
+	fn verify_ethash() -> Weight;
+	fn verify_clique() -> Weight;
+}
+
+/// Weights for ethereum_light_client using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: EthereumLightClient NetworkConfig (r:1 w:1)
+	/// Proof Skipped: EthereumLightClient NetworkConfig (max_values: None, max_size: None, mode: Measured)
+	/// Storage: EthereumLightClient HeadersByNumber (r:0 w:1)
+	/// Proof Skipped: EthereumLightClient HeadersByNumber (max_values: None, max_size: None, mode: Measured)
+	/// Storage: EthereumLightClient Headers (r:0 w:1)
+	/// Proof Skipped: EthereumLightClient Headers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: EthereumLightClient BestBlock (r:0 w:1)
+	/// Proof Skipped: EthereumLightClient BestBlock (max_values: None, max_size: None, mode: Measured)
+	/// Storage: EthereumLightClient FinalizedBlock (r:0 w:1)
+	/// Proof Skipped: EthereumLightClient FinalizedBlock (max_values: None, max_size: None, mode: Measured)
+	/// Storage: EthereumLightClient OldestUnprunedBlock (r:0 w:1)
+	/// Proof Skipped: EthereumLightClient OldestUnprunedBlock (max_values: None, max_size: None, mode: Measured)
+	/// Storage: EthereumLightClient CliqueSnapshots (r:0 w:1)
+	/// Proof Skipped: EthereumLightClient CliqueSnapshots (max_values: None, max_size: None, mode: Measured)
+	fn register_network() -> Weight {
+		Weight::from_parts(25_822_000, 2551)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	/// Storage: EthereumLightClient NetworkConfig (r:1 w:0)
+	/// Proof Skipped: EthereumLightClient NetworkConfig (max_values: None, max_size: None, mode: Measured)
+	/// Storage: EthereumLightClient Headers (r:12 w:10)
+	/// Proof Skipped: EthereumLightClient Headers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: EthereumLightClient FinalizedBlock (r:1 w:1)
+	/// Proof Skipped: EthereumLightClient FinalizedBlock (max_values: None, max_size: None, mode: Measured)
+	/// Storage: EthereumLightClient HeadersByNumber (r:9 w:9)
+	/// Proof Skipped: EthereumLightClient HeadersByNumber (max_values: None, max_size: None, mode: Measured)
+	/// Storage: EthereumLightClient BestBlock (r:1 w:1)
+	/// Proof Skipped: EthereumLightClient BestBlock (max_values: None, max_size: None, mode: Measured)
+	/// Storage: EthereumLightClient OldestUnprunedBlock (r:1 w:1)
+	/// Proof Skipped: EthereumLightClient OldestUnprunedBlock (max_values: None, max_size: None, mode: Measured)
+	/// Storage: EthereumLightClient DagRoots (r:1 w:0)
+	/// Proof Skipped: EthereumLightClient DagRoots (max_values: None, max_size: None, mode: Measured)
+	fn import_header() -> Weight {
+		Weight::from_parts(5_502_114_000, 148845)
+			.saturating_add(T::DbWeight::get().reads(26_u64))
+			.saturating_add(T::DbWeight::get().writes(22_u64))
+	}
+	/// Storage: EthereumLightClient Headers (r:1 w:0)
+	/// Proof Skipped: EthereumLightClient Headers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: EthereumLightClient NetworkConfig (r:1 w:0)
+	/// Proof Skipped: EthereumLightClient NetworkConfig (max_values: None, max_size: None, mode: Measured)
+	/// Estimated: the NetworkConfig read for receipt types is not benchmarked yet.
+	fn verifier_verify() -> Weight {
+		Weight::from_parts(58_032_000, 4336)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+	}
+	/// Storage: EthereumLightClient DagRootsEnd (r:1 w:1)
+	/// Proof Skipped: EthereumLightClient DagRootsEnd (max_values: None, max_size: None, mode: Measured)
+	/// Storage: EthereumLightClient DagRoots (r:0 w:64)
+	/// Proof Skipped: EthereumLightClient DagRoots (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `n` is `[1, 64]`.
+	fn append_dag_roots(n: u32, ) -> Weight {
+		Weight::from_parts(14_934_511, 2517)
+			.saturating_add(Weight::from_parts(1_702_316, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
+	/// Storage: EthereumLightClient DagRoots (r:1 w:0)
+	/// Proof Skipped: EthereumLightClient DagRoots (max_values: None, max_size: None, mode: Measured)
+	/// Estimated, not benchmarked yet: 64 DAG node pairs with 32 long Merkle proofs
+	/// and the Ethash mix computation.
+	fn verify_ethash() -> Weight {
+		Weight::from_parts(10_000_000_000, 2517)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+	}
+	/// Storage: EthereumLightClient CliqueSnapshots (r:1 w:9)
+	/// Proof Skipped: EthereumLightClient CliqueSnapshots (max_values: None, max_size: None, mode: Measured)
+	/// Signer recovery and the snapshot update, pruned snapshots are removed with their headers.
+	fn verify_clique() -> Weight {
+		Weight::from_parts(120_000_000, 3000)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Storage: EthereumLightClient NetworkConfig (r:1 w:1)
+	/// Proof Skipped: EthereumLightClient NetworkConfig (max_values: None, max_size: None, mode: Measured)
+	/// Storage: EthereumLightClient HeadersByNumber (r:0 w:1)
+	/// Proof Skipped: EthereumLightClient HeadersByNumber (max_values: None, max_size: None, mode: Measured)
+	/// Storage: EthereumLightClient Headers (r:0 w:1)
+	/// Proof Skipped: EthereumLightClient Headers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: EthereumLightClient BestBlock (r:0 w:1)
+	/// Proof Skipped: EthereumLightClient BestBlock (max_values: None, max_size: None, mode: Measured)
+	/// Storage: EthereumLightClient FinalizedBlock (r:0 w:1)
+	/// Proof Skipped: EthereumLightClient FinalizedBlock (max_values: None, max_size: None, mode: Measured)
+	/// Storage: EthereumLightClient OldestUnprunedBlock (r:0 w:1)
+	/// Proof Skipped: EthereumLightClient OldestUnprunedBlock (max_values: None, max_size: None, mode: Measured)
+	/// Storage: EthereumLightClient CliqueSnapshots (r:0 w:1)
+	/// Proof Skipped: EthereumLightClient CliqueSnapshots (max_values: None, max_size: None, mode: Measured)
+	fn register_network() -> Weight {
+		Weight::from_parts(25_822_000, 2551)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	/// Storage: EthereumLightClient NetworkConfig (r:1 w:0)
+	/// Proof Skipped: EthereumLightClient NetworkConfig (max_values: None, max_size: None, mode: Measured)
+	/// Storage: EthereumLightClient Headers (r:12 w:10)
+	/// Proof Skipped: EthereumLightClient Headers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: EthereumLightClient FinalizedBlock (r:1 w:1)
+	/// Proof Skipped: EthereumLightClient FinalizedBlock (max_values: None, max_size: None, mode: Measured)
+	/// Storage: EthereumLightClient HeadersByNumber (r:9 w:9)
+	/// Proof Skipped: EthereumLightClient HeadersByNumber (max_values: None, max_size: None, mode: Measured)
+	/// Storage: EthereumLightClient BestBlock (r:1 w:1)
+	/// Proof Skipped: EthereumLightClient BestBlock (max_values: None, max_size: None, mode: Measured)
+	/// Storage: EthereumLightClient OldestUnprunedBlock (r:1 w:1)
+	/// Proof Skipped: EthereumLightClient OldestUnprunedBlock (max_values: None, max_size: None, mode: Measured)
+	/// Storage: EthereumLightClient DagRoots (r:1 w:0)
+	/// Proof Skipped: EthereumLightClient DagRoots (max_values: None, max_size: None, mode: Measured)
+	fn import_header() -> Weight {
+		Weight::from_parts(5_502_114_000, 148845)
+			.saturating_add(RocksDbWeight::get().reads(26_u64))
+			.saturating_add(RocksDbWeight::get().writes(22_u64))
+	}
+	/// Storage: EthereumLightClient Headers (r:1 w:0)
+	/// Proof Skipped: EthereumLightClient Headers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: EthereumLightClient NetworkConfig (r:1 w:0)
+	/// Proof Skipped: EthereumLightClient NetworkConfig (max_values: None, max_size: None, mode: Measured)
+	/// Estimated: the NetworkConfig read for receipt types is not benchmarked yet.
+	fn verifier_verify() -> Weight {
+		Weight::from_parts(58_032_000, 4336)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+	}
+	/// Storage: EthereumLightClient DagRootsEnd (r:1 w:1)
+	/// Proof Skipped: EthereumLightClient DagRootsEnd (max_values: None, max_size: None, mode: Measured)
+	/// Storage: EthereumLightClient DagRoots (r:0 w:64)
+	/// Proof Skipped: EthereumLightClient DagRoots (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `n` is `[1, 64]`.
+	fn append_dag_roots(n: u32, ) -> Weight {
+		Weight::from_parts(14_934_511, 2517)
+			.saturating_add(Weight::from_parts(1_702_316, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
+	/// Storage: EthereumLightClient DagRoots (r:1 w:0)
+	/// Proof Skipped: EthereumLightClient DagRoots (max_values: None, max_size: None, mode: Measured)
+	/// Estimated, not benchmarked yet: 64 DAG node pairs with 32 long Merkle proofs
+	/// and the Ethash mix computation.
+	fn verify_ethash() -> Weight {
+		Weight::from_parts(10_000_000_000, 2517)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+	}
+	/// Storage: EthereumLightClient CliqueSnapshots (r:1 w:9)
+	/// Proof Skipped: EthereumLightClient CliqueSnapshots (max_values: None, max_size: None, mode: Measured)
+	/// Signer recovery and the snapshot update, pruned snapshots are removed with their headers.
+	fn verify_clique() -> Weight {
+		Weight::from_parts(120_000_000, 3000)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
+	}
+}
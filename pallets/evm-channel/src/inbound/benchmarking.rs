@@ -0,0 +1,84 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! EVMBridgeInboundChannel pallet benchmarking
+
+use super::*;
+use bridge_types::evm::InboundMessage;
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite};
+use frame_system::{self, RawOrigin};
+use sp_std::prelude::*;
+
+const BASE_NETWORK_ID: EVMChainId = EVMChainId::zero();
+
+#[allow(unused_imports)]
+use crate::inbound::Pallet as BridgeInboundChannel;
+
+fn channel_address() -> H160 {
+    H160::repeat_byte(1)
+}
+
+benchmarks! {
+    // Benchmark `submit` extrinsic with the largest batch of the largest messages.
+    // Dispatch weight is added to `submit` weight separately.
+    submit {
+        ChannelAddresses::<T>::insert(BASE_NETWORK_ID, channel_address());
+        let messages = (0..T::MaxMessagesPerCommit::get())
+            .map(|_| InboundMessage {
+                source: H160::repeat_byte(2),
+                payload: vec![0u8; T::MaxMessagePayloadSize::get() as usize],
+            })
+            .collect();
+        let log = InboundBatch {
+            nonce: 1,
+            relayer: H160::repeat_byte(3),
+            gas_used: 100_000u64.into(),
+            base_fee: 1_000_000_000u64.into(),
+            messages,
+        }
+        .to_log(channel_address());
+        let proof = T::Verifier::valid_proof().unwrap();
+    }: _(RawOrigin::None, BASE_NETWORK_ID, log, proof)
+    verify {
+        assert_eq!(1, <ChannelNonces<T>>::get(BASE_NETWORK_ID));
+    }
+
+    register_channel {
+    }: _(RawOrigin::Root, BASE_NETWORK_ID, channel_address())
+    verify {
+        assert_eq!(Some(channel_address()), ChannelAddresses::<T>::get(BASE_NETWORK_ID));
+    }
+}
+
+impl_benchmark_test_suite!(
+    BridgeInboundChannel,
+    crate::inbound::test::new_tester(),
+    crate::inbound::test::Test,
+);
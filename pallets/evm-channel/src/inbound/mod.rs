@@ -0,0 +1,351 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Channel for passing messages from EVM networks to this chain.
+//!
+//! Relayers submit the `BatchCommitted` log of the outbound channel contract
+//! together with the proof of its inclusion. Messages of the batch are
+//! dispatched in order of batch nonces.
+
+use bridge_types::event::EthEvent;
+use bridge_types::evm::{AdditionalEVMInboundData, InboundBatch};
+use bridge_types::traits::{BridgePauseChecker, GasTracker, MessageDispatch, Verifier};
+use bridge_types::types::{MessageDirection, MessageId};
+use bridge_types::{EVMChainId, Log, H160};
+use frame_support::dispatch::DispatchResult;
+use frame_support::traits::Get;
+use sp_runtime::traits::{Hash, Keccak256};
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+pub use weights::WeightInfo;
+
+#[cfg(test)]
+mod test;
+
+pub use pallet::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use bridge_types::event::EventDecoder;
+    use bridge_types::{GenericNetworkId, GenericTimepoint};
+    use frame_support::log::warn;
+    use frame_support::pallet_prelude::*;
+    use frame_support::traits::StorageVersion;
+    use frame_support::weights::Weight;
+    use frame_support::Parameter;
+    use frame_system::pallet_prelude::*;
+    use sp_std::prelude::*;
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+        /// Verifier module for message verification.
+        type Verifier: Verifier;
+
+        /// Dispatcher of the received messages.
+        type MessageDispatch: MessageDispatch<Self, EVMChainId, MessageId, AdditionalEVMInboundData>;
+
+        type Balance: Parameter;
+
+        /// Tracker of the fees paid by relayers on the EVM side.
+        type GasTracker: GasTracker<Self::Balance>;
+
+        /// A configuration for base priority of unsigned transactions.
+        #[pallet::constant]
+        type UnsignedPriority: Get<TransactionPriority>;
+
+        /// A configuration for longevity of unsigned transactions.
+        #[pallet::constant]
+        type UnsignedLongevity: Get<u64>;
+
+        #[pallet::constant]
+        type ThisNetworkId: Get<GenericNetworkId>;
+
+        /// Max bytes in a message payload
+        type MaxMessagePayloadSize: Get<u32>;
+
+        /// Max number of messages that can be committed in one go for a given channel.
+        type MaxMessagesPerCommit: Get<u32>;
+
+        /// Checks whether the bridge messages are paused.
+        type BridgePause: BridgePauseChecker;
+
+        /// Weight information for extrinsics in this pallet
+        type WeightInfo: WeightInfo;
+    }
+
+    /// Address of the outbound channel contract for each network.
+    #[pallet::storage]
+    #[pallet::getter(fn channel_address)]
+    pub type ChannelAddresses<T: Config> = StorageMap<_, Identity, EVMChainId, H160, OptionQuery>;
+
+    #[pallet::storage]
+    pub type ChannelNonces<T: Config> = StorageMap<_, Identity, EVMChainId, u64, ValueQuery>;
+
+    /// The current storage version.
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+    #[pallet::pallet]
+    #[pallet::generate_store(pub(super) trait Store)]
+    #[pallet::storage_version(STORAGE_VERSION)]
+    #[pallet::without_storage_info]
+    pub struct Pallet<T>(PhantomData<T>);
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// Outbound channel contract is registered for the network.
+        ChannelRegistered {
+            network_id: EVMChainId,
+            channel: H160,
+        },
+        /// Batch of messages is received and dispatched.
+        BatchDispatched {
+            network_id: EVMChainId,
+            batch_nonce: u64,
+        },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Message came from an invalid network.
+        InvalidNetwork,
+        /// Message came from an invalid outbound channel on the EVM side.
+        InvalidSourceChannel,
+        /// Log doesn't contain a valid batch of messages.
+        InvalidBatch,
+        /// Batch exceeds message count or payload size limits.
+        BatchTooLarge,
+        /// Message has an unexpected nonce.
+        InvalidNonce,
+        /// This contract already exists
+        ContractExists,
+    }
+
+    impl<T: Config> Pallet<T> {
+        fn submit_weight(log: &Log, proof: &<T::Verifier as Verifier>::Proof) -> Weight {
+            // Source contract is checked in the call, here we only need the messages.
+            let dispatch_weight = EventDecoder::new(InboundBatch::NAME, InboundBatch::params())
+                .decode(log)
+                .and_then(InboundBatch::from_tokens)
+                .map(|batch| {
+                    batch
+                        .messages
+                        .iter()
+                        .map(|m| T::MessageDispatch::dispatch_weight(&m.payload))
+                        .fold(Weight::zero(), |acc, w| acc.saturating_add(w))
+                })
+                .unwrap_or_default();
+
+            let proof_weight = T::Verifier::verify_weight(proof);
+
+            <T as Config>::WeightInfo::submit()
+                .saturating_add(dispatch_weight)
+                .saturating_add(proof_weight)
+        }
+
+        fn decode_batch(network_id: EVMChainId, log: &Log) -> Result<InboundBatch, Error<T>> {
+            let channel =
+                ChannelAddresses::<T>::get(network_id).ok_or(Error::<T>::InvalidNetwork)?;
+            ensure!(log.address == channel, Error::<T>::InvalidSourceChannel);
+            let batch = InboundBatch::decode(log, channel).map_err(|e| {
+                warn!("Failed to decode batch: {:?}", e);
+                Error::<T>::InvalidBatch
+            })?;
+            ensure!(
+                batch.messages.len() <= T::MaxMessagesPerCommit::get() as usize
+                    && batch
+                        .messages
+                        .iter()
+                        .all(|m| m.payload.len() <= T::MaxMessagePayloadSize::get() as usize),
+                Error::<T>::BatchTooLarge
+            );
+            Ok(batch)
+        }
+
+        fn ensure_inbound_not_paused(network_id: EVMChainId) -> DispatchResult {
+            T::BridgePause::ensure_not_paused(network_id.into(), MessageDirection::Inbound, None)
+        }
+
+        /// Checks that the batch messages aren't paused before the batch nonce is consumed,
+        /// so the batch is submitted again after the pause.
+        fn ensure_batch_not_paused(network_id: EVMChainId, batch: &InboundBatch) -> DispatchResult {
+            batch.messages.iter().try_for_each(|message| {
+                T::MessageDispatch::ensure_not_paused(network_id, &message.payload)
+            })
+        }
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        #[pallet::call_index(0)]
+        #[pallet::weight(Pallet::<T>::submit_weight(log, proof))]
+        pub fn submit(
+            origin: OriginFor<T>,
+            network_id: EVMChainId,
+            log: Log,
+            proof: <T::Verifier as Verifier>::Proof,
+        ) -> DispatchResultWithPostInfo {
+            ensure_none(origin)?;
+            Self::ensure_inbound_not_paused(network_id)?;
+            let batch = Self::decode_batch(network_id, &log)?;
+            Self::ensure_batch_not_paused(network_id, &batch)?;
+            // submit log to verifier for verification
+            T::Verifier::verify(network_id.into(), Keccak256::hash_of(&log), &proof)?;
+            // Verify batch nonce
+            <ChannelNonces<T>>::try_mutate(network_id, |nonce| -> DispatchResult {
+                if batch.nonce != *nonce + 1 {
+                    Err(Error::<T>::InvalidNonce.into())
+                } else {
+                    *nonce += 1;
+                    Ok(())
+                }
+            })?;
+
+            for (idx, message) in batch.messages.into_iter().enumerate() {
+                let message_id = MessageId::batched(
+                    network_id.into(),
+                    T::ThisNetworkId::get(),
+                    batch.nonce,
+                    idx as u64,
+                );
+                // Failed messages don't affect the batch, the result is in the dispatch events
+                let _ = T::MessageDispatch::dispatch(
+                    network_id,
+                    message_id,
+                    GenericTimepoint::Unknown,
+                    &message.payload,
+                    AdditionalEVMInboundData {
+                        source: message.source,
+                    },
+                );
+            }
+
+            T::GasTracker::record_tx_fee(
+                network_id.into(),
+                batch.nonce,
+                batch.relayer,
+                batch.gas_used,
+                batch.base_fee,
+            );
+            Self::deposit_event(Event::BatchDispatched {
+                network_id,
+                batch_nonce: batch.nonce,
+            });
+            Ok(().into())
+        }
+
+        #[pallet::call_index(1)]
+        #[pallet::weight(<T as Config>::WeightInfo::register_channel())]
+        pub fn register_channel(
+            origin: OriginFor<T>,
+            network_id: EVMChainId,
+            channel: H160,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            ensure!(
+                !ChannelAddresses::<T>::contains_key(network_id),
+                Error::<T>::ContractExists
+            );
+            ChannelAddresses::<T>::insert(network_id, channel);
+            Self::deposit_event(Event::ChannelRegistered {
+                network_id,
+                channel,
+            });
+            Ok(())
+        }
+    }
+
+    #[pallet::validate_unsigned]
+    impl<T: Config> ValidateUnsigned for Pallet<T> {
+        type Call = Call<T>;
+
+        fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+            if let Call::submit {
+                network_id,
+                log,
+                proof,
+            } = call
+            {
+                Self::ensure_inbound_not_paused(*network_id)
+                    .map_err(|_| InvalidTransaction::Call)?;
+                let batch = Self::decode_batch(*network_id, log).map_err(|e| {
+                    warn!("Bad submit log received: {:?}", e);
+                    InvalidTransaction::Call
+                })?;
+                Self::ensure_batch_not_paused(*network_id, &batch)
+                    .map_err(|_| InvalidTransaction::Call)?;
+                // If batch is already submitted
+                if batch.nonce <= ChannelNonces::<T>::get(network_id) {
+                    return InvalidTransaction::Stale.into();
+                }
+                T::Verifier::verify((*network_id).into(), Keccak256::hash_of(log), proof).map_err(
+                    |e| {
+                        warn!("Bad submit proof received: {:?}", e);
+                        InvalidTransaction::BadProof
+                    },
+                )?;
+                ValidTransaction::with_tag_prefix("EVMBridgeChannelSubmit")
+                    .priority(T::UnsignedPriority::get())
+                    .longevity(T::UnsignedLongevity::get())
+                    .and_provides((network_id, batch.nonce))
+                    .propagate(true)
+                    .build()
+            } else {
+                warn!("Unknown unsigned call, can't validate");
+                InvalidTransaction::Call.into()
+            }
+        }
+    }
+
+    #[pallet::genesis_config]
+    #[cfg_attr(feature = "std", derive(Default))]
+    pub struct GenesisConfig {
+        /// Outbound channel contracts of the networks.
+        pub channels: Vec<(EVMChainId, H160)>,
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> GenesisBuild<T> for GenesisConfig {
+        fn build(&self) {
+            for (network_id, channel) in &self.channels {
+                ChannelAddresses::<T>::insert(network_id, channel);
+            }
+        }
+    }
+}
@@ -0,0 +1,385 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use super::*;
+use bridge_types::evm::InboundMessage;
+use bridge_types::{GenericNetworkId, GenericTimepoint, SubNetworkId, U256};
+use frame_support::traits::Everything;
+use frame_support::{assert_noop, assert_ok, parameter_types};
+use sp_core::{ConstU64, H256};
+use sp_keyring::AccountKeyring as Keyring;
+use sp_runtime::testing::Header;
+use sp_runtime::traits::{BlakeTwo256, IdentifyAccount, IdentityLookup, Verify};
+use sp_runtime::{DispatchError, MultiSignature};
+
+use crate::inbound as bridge_inbound_channel;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+const BASE_NETWORK_ID: EVMChainId = EVMChainId::zero();
+
+frame_support::construct_runtime!(
+    pub enum Test where
+        Block = Block,
+        NodeBlock = Block,
+        UncheckedExtrinsic = UncheckedExtrinsic,
+    {
+        System: frame_system::{Pallet, Call, Storage, Event<T>},
+        BridgeInboundChannel: bridge_inbound_channel::{Pallet, Call, Config, Storage, Event<T>},
+    }
+);
+
+pub type Signature = MultiSignature;
+pub type AccountId = <<Signature as Verify>::Signer as IdentifyAccount>::AccountId;
+
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Test {
+    type BaseCallFilter = Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type Index = u64;
+    type BlockNumber = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = BlockHashCount;
+    type DbWeight = ();
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = ();
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ();
+    type OnSetCode = ();
+    type MaxConsumers = frame_support::traits::ConstU32<65536>;
+}
+
+// Mock verifier
+pub struct MockVerifier;
+
+impl Verifier for MockVerifier {
+    type Proof = Vec<u8>;
+
+    fn verify(network_id: GenericNetworkId, _hash: H256, _proof: &Vec<u8>) -> DispatchResult {
+        if network_id == GenericNetworkId::EVM(BASE_NETWORK_ID) {
+            Ok(())
+        } else {
+            Err(Error::<Test>::InvalidNetwork.into())
+        }
+    }
+
+    fn verify_weight(_proof: &Self::Proof) -> frame_support::weights::Weight {
+        Default::default()
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn valid_proof() -> Option<Self::Proof> {
+        Some(Default::default())
+    }
+}
+
+parameter_types! {
+    pub static Dispatched: Vec<(MessageId, Vec<u8>, H160)> = vec![];
+    pub static RecordedFees: Vec<(GenericNetworkId, u64, H160, U256, U256)> = vec![];
+}
+
+// Mock Dispatch
+pub struct MockMessageDispatch;
+
+impl MessageDispatch<Test, EVMChainId, MessageId, AdditionalEVMInboundData>
+    for MockMessageDispatch
+{
+    fn dispatch(
+        _: EVMChainId,
+        id: MessageId,
+        _: GenericTimepoint,
+        payload: &[u8],
+        additional: AdditionalEVMInboundData,
+    ) -> DispatchResult {
+        Dispatched::mutate(|messages| messages.push((id, payload.to_vec(), additional.source)));
+        Ok(())
+    }
+
+    fn dispatch_weight(_: &[u8]) -> frame_support::weights::Weight {
+        Default::default()
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn successful_dispatch_event(
+        _: MessageId,
+    ) -> Option<<Test as frame_system::Config>::RuntimeEvent> {
+        None
+    }
+}
+
+pub struct MockGasTracker;
+
+impl GasTracker<u128> for MockGasTracker {
+    fn record_tx_fee(
+        network_id: GenericNetworkId,
+        batch_nonce: u64,
+        ethereum_relayer_address: H160,
+        gas_used: U256,
+        gas_price: U256,
+    ) {
+        RecordedFees::mutate(|fees| {
+            fees.push((
+                network_id,
+                batch_nonce,
+                ethereum_relayer_address,
+                gas_used,
+                gas_price,
+            ))
+        });
+    }
+}
+
+parameter_types! {
+    pub const MaxMessagePayloadSize: u32 = 128;
+    pub const MaxMessagesPerCommit: u32 = 5;
+    pub const ThisNetworkId: GenericNetworkId = GenericNetworkId::Sub(SubNetworkId::Mainnet);
+}
+
+impl bridge_inbound_channel::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type Verifier = MockVerifier;
+    type MessageDispatch = MockMessageDispatch;
+    type Balance = u128;
+    type GasTracker = MockGasTracker;
+    type UnsignedLongevity = ConstU64<100>;
+    type UnsignedPriority = ConstU64<100>;
+    type ThisNetworkId = ThisNetworkId;
+    type MaxMessagePayloadSize = MaxMessagePayloadSize;
+    type MaxMessagesPerCommit = MaxMessagesPerCommit;
+    type BridgePause = ();
+    type WeightInfo = ();
+}
+
+pub fn new_tester() -> sp_io::TestExternalities {
+    let storage = frame_system::GenesisConfig::default()
+        .build_storage::<Test>()
+        .unwrap();
+
+    let mut ext: sp_io::TestExternalities = storage.into();
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
+
+fn channel_address() -> H160 {
+    H160::repeat_byte(1)
+}
+
+fn register_channel() {
+    assert_ok!(BridgeInboundChannel::register_channel(
+        RuntimeOrigin::root(),
+        BASE_NETWORK_ID,
+        channel_address()
+    ));
+}
+
+fn batch(nonce: u64, messages_count: usize) -> InboundBatch {
+    InboundBatch {
+        nonce,
+        relayer: H160::repeat_byte(2),
+        gas_used: 100_000.into(),
+        base_fee: 20.into(),
+        messages: (0..messages_count)
+            .map(|idx| InboundMessage {
+                source: H160::from_low_u64_be(idx as u64 + 10),
+                payload: vec![idx as u8; 3],
+            })
+            .collect(),
+    }
+}
+
+fn submit(network_id: EVMChainId, log: Log) -> DispatchResult {
+    BridgeInboundChannel::submit(RuntimeOrigin::none(), network_id, log, vec![])
+        .map(|_| ())
+        .map_err(|e| e.error)
+}
+
+#[test]
+fn test_submit_dispatches_batch() {
+    new_tester().execute_with(|| {
+        register_channel();
+        assert_ok!(submit(
+            BASE_NETWORK_ID,
+            batch(1, 2).to_log(channel_address())
+        ));
+        assert_eq!(<ChannelNonces<Test>>::get(BASE_NETWORK_ID), 1);
+
+        let sender = GenericNetworkId::EVM(BASE_NETWORK_ID);
+        assert_eq!(
+            Dispatched::get(),
+            vec![
+                (
+                    MessageId::batched(sender, ThisNetworkId::get(), 1, 0),
+                    vec![0; 3],
+                    H160::from_low_u64_be(10)
+                ),
+                (
+                    MessageId::batched(sender, ThisNetworkId::get(), 1, 1),
+                    vec![1; 3],
+                    H160::from_low_u64_be(11)
+                ),
+            ]
+        );
+        assert_eq!(
+            RecordedFees::get(),
+            vec![(sender, 1, H160::repeat_byte(2), 100_000.into(), 20.into())]
+        );
+        System::assert_last_event(RuntimeEvent::BridgeInboundChannel(Event::BatchDispatched {
+            network_id: BASE_NETWORK_ID,
+            batch_nonce: 1,
+        }));
+
+        assert_ok!(submit(
+            BASE_NETWORK_ID,
+            batch(2, 0).to_log(channel_address())
+        ));
+        assert_eq!(<ChannelNonces<Test>>::get(BASE_NETWORK_ID), 2);
+    });
+}
+
+#[test]
+fn test_submit_with_invalid_nonce() {
+    new_tester().execute_with(|| {
+        register_channel();
+        assert_noop!(
+            submit(BASE_NETWORK_ID, batch(2, 1).to_log(channel_address())),
+            Error::<Test>::InvalidNonce
+        );
+
+        assert_ok!(submit(
+            BASE_NETWORK_ID,
+            batch(1, 1).to_log(channel_address())
+        ));
+        assert_noop!(
+            submit(BASE_NETWORK_ID, batch(1, 1).to_log(channel_address())),
+            Error::<Test>::InvalidNonce
+        );
+    });
+}
+
+#[test]
+fn test_submit_with_invalid_source_channel() {
+    new_tester().execute_with(|| {
+        register_channel();
+        assert_noop!(
+            submit(BASE_NETWORK_ID, batch(1, 1).to_log(H160::repeat_byte(9))),
+            Error::<Test>::InvalidSourceChannel
+        );
+    });
+}
+
+#[test]
+fn test_submit_to_unregistered_network() {
+    new_tester().execute_with(|| {
+        assert_noop!(
+            submit(BASE_NETWORK_ID, batch(1, 1).to_log(channel_address())),
+            Error::<Test>::InvalidNetwork
+        );
+    });
+}
+
+#[test]
+fn test_submit_with_invalid_batch() {
+    new_tester().execute_with(|| {
+        register_channel();
+        let mut log = batch(1, 1).to_log(channel_address());
+        log.data.truncate(64);
+        assert_noop!(submit(BASE_NETWORK_ID, log), Error::<Test>::InvalidBatch);
+
+        let mut log = batch(1, 1).to_log(channel_address());
+        log.topics[0] = H256::repeat_byte(1);
+        assert_noop!(submit(BASE_NETWORK_ID, log), Error::<Test>::InvalidBatch);
+    });
+}
+
+#[test]
+fn test_submit_exceeds_limits() {
+    new_tester().execute_with(|| {
+        register_channel();
+        let messages_count = MaxMessagesPerCommit::get() as usize + 1;
+        assert_noop!(
+            submit(
+                BASE_NETWORK_ID,
+                batch(1, messages_count).to_log(channel_address())
+            ),
+            Error::<Test>::BatchTooLarge
+        );
+
+        let mut batch = batch(1, 1);
+        batch.messages[0].payload = vec![0; MaxMessagePayloadSize::get() as usize + 1];
+        assert_noop!(
+            submit(BASE_NETWORK_ID, batch.to_log(channel_address())),
+            Error::<Test>::BatchTooLarge
+        );
+    });
+}
+
+#[test]
+fn test_register_channel() {
+    new_tester().execute_with(|| {
+        let alice: AccountId = Keyring::Alice.into();
+        assert_noop!(
+            BridgeInboundChannel::register_channel(
+                RuntimeOrigin::signed(alice),
+                BASE_NETWORK_ID,
+                channel_address()
+            ),
+            DispatchError::BadOrigin
+        );
+
+        register_channel();
+        assert_eq!(
+            BridgeInboundChannel::channel_address(BASE_NETWORK_ID),
+            Some(channel_address())
+        );
+        assert_noop!(
+            BridgeInboundChannel::register_channel(
+                RuntimeOrigin::root(),
+                BASE_NETWORK_ID,
+                H160::repeat_byte(2)
+            ),
+            Error::<Test>::ContractExists
+        );
+    });
+}
@@ -0,0 +1,89 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Weights for evm_bridge_channel::inbound
+//!
+//! NOT GENERATED BY THE BENCHMARK CLI: the values are estimates until the pallet
+//! is benchmarked, regenerate with
+//! `benchmark pallet --pallet=evm_bridge_channel::inbound --extrinsic=* --template=./misc/pallet-weight-template.hbs`.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for evm_bridge_channel::inbound.
+pub trait WeightInfo {
+	fn submit() -> Weight;
+	fn register_channel() -> Weight;
+}
+
+/// Weights for evm_bridge_channel::inbound using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: EVMBridgeInboundChannel ChannelAddresses (r:1 w:0)
+	/// Proof Skipped: EVMBridgeInboundChannel ChannelAddresses (max_values: None, max_size: None, mode: Measured)
+	/// Storage: EVMBridgeInboundChannel ChannelNonces (r:1 w:1)
+	/// Proof Skipped: EVMBridgeInboundChannel ChannelNonces (max_values: None, max_size: None, mode: Measured)
+	fn submit() -> Weight {
+		Weight::from_parts(22_100_000, 5186)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: EVMBridgeInboundChannel ChannelAddresses (r:1 w:1)
+	/// Proof Skipped: EVMBridgeInboundChannel ChannelAddresses (max_values: None, max_size: None, mode: Measured)
+	fn register_channel() -> Weight {
+		Weight::from_parts(7_700_000, 2517)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Storage: EVMBridgeInboundChannel ChannelAddresses (r:1 w:0)
+	/// Proof Skipped: EVMBridgeInboundChannel ChannelAddresses (max_values: None, max_size: None, mode: Measured)
+	/// Storage: EVMBridgeInboundChannel ChannelNonces (r:1 w:1)
+	/// Proof Skipped: EVMBridgeInboundChannel ChannelNonces (max_values: None, max_size: None, mode: Measured)
+	fn submit() -> Weight {
+		Weight::from_parts(22_100_000, 5186)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: EVMBridgeInboundChannel ChannelAddresses (r:1 w:1)
+	/// Proof Skipped: EVMBridgeInboundChannel ChannelAddresses (max_values: None, max_size: None, mode: Measured)
+	fn register_channel() -> Weight {
+		Weight::from_parts(7_700_000, 2517)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+}
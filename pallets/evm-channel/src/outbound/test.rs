@@ -0,0 +1,273 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use super::*;
+use bridge_types::traits::OutboundChannel;
+use bridge_types::types::{AuxiliaryDigestItem, BridgeOffchainData, MessageId};
+use bridge_types::{GenericCommitment, GenericNetworkId, SubNetworkId, H160};
+use codec::Decode;
+use frame_support::traits::{Everything, GenesisBuild, OnInitialize};
+use frame_support::{assert_noop, assert_ok, parameter_types};
+use frame_system::RawOrigin;
+use sp_keyring::AccountKeyring as Keyring;
+use sp_runtime::testing::Header;
+use sp_runtime::traits::{BlakeTwo256, IdentifyAccount, IdentityLookup, Verify};
+use sp_runtime::MultiSignature;
+
+use crate::outbound as bridge_outbound_channel;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+const BASE_NETWORK_ID: EVMChainId = EVMChainId::zero();
+
+frame_support::construct_runtime!(
+    pub enum Test where
+        Block = Block,
+        NodeBlock = Block,
+        UncheckedExtrinsic = UncheckedExtrinsic,
+    {
+        System: frame_system::{Pallet, Call, Storage, Event<T>},
+        BridgeOutboundChannel: bridge_outbound_channel::{Pallet, Config<T>, Storage, Event<T>},
+    }
+);
+
+pub type Signature = MultiSignature;
+pub type AccountId = <<Signature as Verify>::Signer as IdentifyAccount>::AccountId;
+
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Test {
+    type BaseCallFilter = Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type Index = u64;
+    type BlockNumber = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = BlockHashCount;
+    type DbWeight = ();
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = ();
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ();
+    type OnSetCode = ();
+    type MaxConsumers = frame_support::traits::ConstU32<65536>;
+}
+
+parameter_types! {
+    pub const MaxMessagePayloadSize: u32 = 128;
+    pub const MaxMessagesPerCommit: u32 = 5;
+    pub const MaxTotalGasLimit: u64 = 1_000_000;
+    pub const ThisNetworkId: GenericNetworkId = GenericNetworkId::Sub(SubNetworkId::Mainnet);
+    pub static DigestItems: Vec<AuxiliaryDigestItem> = vec![];
+}
+
+pub struct TestDigestHandler;
+
+impl bridge_types::traits::AuxiliaryDigestHandler for TestDigestHandler {
+    fn add_item(item: AuxiliaryDigestItem) {
+        DigestItems::mutate(|items| items.push(item));
+    }
+}
+
+impl bridge_outbound_channel::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type MaxMessagePayloadSize = MaxMessagePayloadSize;
+    type MaxMessagesPerCommit = MaxMessagesPerCommit;
+    type MaxTotalGasLimit = MaxTotalGasLimit;
+    type MessageStatusNotifier = ();
+    type AuxiliaryDigestHandler = TestDigestHandler;
+    type AssetId = ();
+    type Balance = u128;
+    type BridgePause = ();
+    type WeightInfo = ();
+    type ThisNetworkId = ThisNetworkId;
+}
+
+pub fn new_tester() -> sp_io::TestExternalities {
+    let mut storage = frame_system::GenesisConfig::default()
+        .build_storage::<Test>()
+        .unwrap();
+
+    let config: bridge_outbound_channel::GenesisConfig<Test> =
+        bridge_outbound_channel::GenesisConfig {
+            interval: 10u32.into(),
+        };
+    config.assimilate_storage(&mut storage).unwrap();
+
+    let mut ext: sp_io::TestExternalities = storage.into();
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
+
+fn additional(max_gas: u64) -> AdditionalEVMOutboundData {
+    AdditionalEVMOutboundData {
+        max_gas: max_gas.into(),
+        target: H160::repeat_byte(1),
+    }
+}
+
+fn submit(max_gas: u64) -> Result<H256, sp_runtime::DispatchError> {
+    let who: AccountId = Keyring::Bob.into();
+    BridgeOutboundChannel::submit(
+        BASE_NETWORK_ID,
+        &RawOrigin::Signed(who),
+        &[0, 1, 2],
+        additional(max_gas),
+    )
+}
+
+#[test]
+fn test_submit() {
+    new_tester().execute_with(|| {
+        assert_eq!(
+            submit(100_000),
+            Ok(MessageId::batched(ThisNetworkId::get(), BASE_NETWORK_ID.into(), 1, 0).hash())
+        );
+        assert_eq!(
+            submit(200_000),
+            Ok(MessageId::batched(ThisNetworkId::get(), BASE_NETWORK_ID.into(), 1, 1).hash())
+        );
+        assert_eq!(
+            QueuesTotalGas::<Test>::get(BASE_NETWORK_ID),
+            U256::from(300_000)
+        );
+        BridgeOutboundChannel::commit(BASE_NETWORK_ID);
+        assert_eq!(<ChannelNonces<Test>>::get(BASE_NETWORK_ID), 1);
+        assert_eq!(QueuesTotalGas::<Test>::get(BASE_NETWORK_ID), U256::zero());
+
+        assert_ok!(submit(100_000));
+        BridgeOutboundChannel::commit(BASE_NETWORK_ID);
+        assert_eq!(<ChannelNonces<Test>>::get(BASE_NETWORK_ID), 2);
+    });
+}
+
+#[test]
+fn test_commit_on_interval() {
+    let mut ext = new_tester();
+    ext.execute_with(|| {
+        assert_ok!(submit(100_000));
+        assert_ok!(submit(200_000));
+
+        BridgeOutboundChannel::on_initialize(9);
+        assert_eq!(MessageQueues::<Test>::get(BASE_NETWORK_ID).len(), 2);
+
+        BridgeOutboundChannel::on_initialize(10);
+        assert_eq!(MessageQueues::<Test>::get(BASE_NETWORK_ID).len(), 0);
+        assert_eq!(<ChannelNonces<Test>>::get(BASE_NETWORK_ID), 1);
+    });
+    ext.persist_offchain_overlay();
+
+    let key = bridge_types::utils::make_offchain_key(BASE_NETWORK_ID.into(), 1);
+    let data = ext
+        .offchain_db()
+        .get(&key)
+        .expect("commitment is stored offchain");
+    let data = BridgeOffchainData::<u64, MaxMessagesPerCommit, MaxMessagePayloadSize>::decode(
+        &mut &data[..],
+    )
+    .unwrap();
+    let GenericCommitment::EVM(commitment) = data.commitment.clone() else {
+        panic!("unexpected commitment {:?}", data.commitment)
+    };
+    assert_eq!(commitment.nonce, 1);
+    assert_eq!(commitment.total_max_gas, U256::from(300_000));
+    assert_eq!(commitment.messages.len(), 2);
+    assert_eq!(commitment.messages[0].target, H160::repeat_byte(1));
+    assert_eq!(
+        DigestItems::get(),
+        vec![AuxiliaryDigestItem::Commitment(
+            GenericNetworkId::EVM(BASE_NETWORK_ID),
+            data.commitment.hash()
+        )]
+    );
+}
+
+#[test]
+fn test_submit_exceeds_gas_limit() {
+    new_tester().execute_with(|| {
+        assert_noop!(submit(1_000_001), Error::<Test>::MaxGasTooBig);
+        assert_ok!(submit(600_000));
+        assert_noop!(submit(400_001), Error::<Test>::MaxGasTooBig);
+        assert_ok!(submit(400_000));
+    })
+}
+
+#[test]
+fn test_submit_exceeds_queue_limit() {
+    new_tester().execute_with(|| {
+        let max_messages = MaxMessagesPerCommit::get();
+        (0..max_messages).for_each(|_| {
+            submit(1).unwrap();
+        });
+
+        assert_noop!(submit(1), Error::<Test>::QueueSizeLimitReached);
+    })
+}
+
+#[test]
+fn test_submit_exceeds_payload_limit() {
+    new_tester().execute_with(|| {
+        let who: AccountId = Keyring::Bob.into();
+
+        let max_payload_bytes = MaxMessagePayloadSize::get();
+        let payload: Vec<u8> = (0..).take(max_payload_bytes as usize + 1).collect();
+
+        assert_noop!(
+            BridgeOutboundChannel::submit(
+                BASE_NETWORK_ID,
+                &RawOrigin::Signed(who),
+                payload.as_slice(),
+                additional(1)
+            ),
+            Error::<Test>::PayloadTooLarge,
+        );
+    })
+}
+
+#[test]
+fn test_submit_fails_on_nonce_overflow() {
+    new_tester().execute_with(|| {
+        <ChannelNonces<Test>>::insert(BASE_NETWORK_ID, u64::MAX);
+        assert_noop!(submit(1), Error::<Test>::Overflow);
+    });
+}
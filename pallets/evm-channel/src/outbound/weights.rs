@@ -0,0 +1,157 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Weights for evm_bridge_channel::outbound
+//!
+//! NOT GENERATED BY THE BENCHMARK CLI: the values are estimates until the pallet
+//! is benchmarked, regenerate with
+//! `benchmark pallet --pallet=evm_bridge_channel::outbound --extrinsic=* --template=./misc/pallet-weight-template.hbs`.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for evm_bridge_channel::outbound.
+pub trait WeightInfo {
+	fn on_initialize(m: u32, p: u32, ) -> Weight;
+	fn on_initialize_non_interval() -> Weight;
+	fn on_initialize_no_messages() -> Weight;
+	fn submit() -> Weight;
+}
+
+/// Weights for evm_bridge_channel::outbound using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: EVMBridgeOutboundChannel Interval (r:1 w:0)
+	/// Proof Skipped: EVMBridgeOutboundChannel Interval (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: EVMBridgeOutboundChannel MessageQueues (r:2 w:1)
+	/// Proof Skipped: EVMBridgeOutboundChannel MessageQueues (max_values: None, max_size: None, mode: Measured)
+	/// Storage: EVMBridgeOutboundChannel QueuesTotalGas (r:1 w:1)
+	/// Proof Skipped: EVMBridgeOutboundChannel QueuesTotalGas (max_values: None, max_size: None, mode: Measured)
+	/// Storage: EVMBridgeOutboundChannel QueuesTotalGas (r:1 w:1)
+	/// Proof Skipped: EVMBridgeOutboundChannel QueuesTotalGas (max_values: None, max_size: None, mode: Measured)
+	/// Storage: EVMBridgeOutboundChannel ChannelNonces (r:1 w:1)
+	/// Proof Skipped: EVMBridgeOutboundChannel ChannelNonces (max_values: None, max_size: None, mode: Measured)
+	/// Storage: BridgeProxy Senders (r:20 w:0)
+	/// Proof Skipped: BridgeProxy Senders (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LeafProvider LatestDigest (r:1 w:1)
+	/// Proof Skipped: LeafProvider LatestDigest (max_values: Some(1), max_size: None, mode: Measured)
+	/// The range of component `m` is `[1, 20]`.
+	/// The range of component `p` is `[0, 256]`.
+	fn on_initialize(m: u32, p: u32, ) -> Weight {
+		Weight::from_parts(7_859_569, 7321)
+			.saturating_add(Weight::from_parts(4_421_066, 0).saturating_mul(m.into()))
+			.saturating_add(Weight::from_parts(69_998, 0).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(m.into())))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+			.saturating_add(Weight::from_parts(0, 3006).saturating_mul(m.into()))
+			.saturating_add(Weight::from_parts(0, 42).saturating_mul(p.into()))
+	}
+	/// Storage: EVMBridgeOutboundChannel Interval (r:1 w:0)
+	/// Proof Skipped: EVMBridgeOutboundChannel Interval (max_values: Some(1), max_size: None, mode: Measured)
+	fn on_initialize_non_interval() -> Weight {
+		Weight::from_parts(2_400_000, 560)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+	}
+	/// Storage: EVMBridgeOutboundChannel Interval (r:1 w:0)
+	/// Proof Skipped: EVMBridgeOutboundChannel Interval (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: EVMBridgeOutboundChannel MessageQueues (r:1 w:0)
+	/// Proof Skipped: EVMBridgeOutboundChannel MessageQueues (max_values: None, max_size: None, mode: Measured)
+	fn on_initialize_no_messages() -> Weight {
+		Weight::from_parts(5_030_000, 3032)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+	}
+	/// Storage: EVMBridgeOutboundChannel MessageQueues (r:1 w:1)
+	/// Proof Skipped: EVMBridgeOutboundChannel MessageQueues (max_values: None, max_size: None, mode: Measured)
+	/// Storage: EVMBridgeOutboundChannel ChannelNonces (r:1 w:0)
+	/// Proof Skipped: EVMBridgeOutboundChannel ChannelNonces (max_values: None, max_size: None, mode: Measured)
+	/// Storage: EVMBridgeOutboundChannel QueuesTotalGas (r:1 w:1)
+	/// Proof Skipped: EVMBridgeOutboundChannel QueuesTotalGas (max_values: None, max_size: None, mode: Measured)
+	fn submit() -> Weight {
+		Weight::from_parts(9_310_000, 7487)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	/// Storage: EVMBridgeOutboundChannel Interval (r:1 w:0)
+	/// Proof Skipped: EVMBridgeOutboundChannel Interval (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: EVMBridgeOutboundChannel MessageQueues (r:2 w:1)
+	/// Proof Skipped: EVMBridgeOutboundChannel MessageQueues (max_values: None, max_size: None, mode: Measured)
+	/// Storage: EVMBridgeOutboundChannel ChannelNonces (r:1 w:1)
+	/// Proof Skipped: EVMBridgeOutboundChannel ChannelNonces (max_values: None, max_size: None, mode: Measured)
+	/// Storage: BridgeProxy Senders (r:20 w:0)
+	/// Proof Skipped: BridgeProxy Senders (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LeafProvider LatestDigest (r:1 w:1)
+	/// Proof Skipped: LeafProvider LatestDigest (max_values: Some(1), max_size: None, mode: Measured)
+	/// The range of component `m` is `[1, 20]`.
+	/// The range of component `p` is `[0, 256]`.
+	fn on_initialize(m: u32, p: u32, ) -> Weight {
+		Weight::from_parts(7_859_569, 7321)
+			.saturating_add(Weight::from_parts(4_421_066, 0).saturating_mul(m.into()))
+			.saturating_add(Weight::from_parts(69_998, 0).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(m.into())))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+			.saturating_add(Weight::from_parts(0, 3006).saturating_mul(m.into()))
+			.saturating_add(Weight::from_parts(0, 42).saturating_mul(p.into()))
+	}
+	/// Storage: EVMBridgeOutboundChannel Interval (r:1 w:0)
+	/// Proof Skipped: EVMBridgeOutboundChannel Interval (max_values: Some(1), max_size: None, mode: Measured)
+	fn on_initialize_non_interval() -> Weight {
+		Weight::from_parts(2_400_000, 560)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+	}
+	/// Storage: EVMBridgeOutboundChannel Interval (r:1 w:0)
+	/// Proof Skipped: EVMBridgeOutboundChannel Interval (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: EVMBridgeOutboundChannel MessageQueues (r:1 w:0)
+	/// Proof Skipped: EVMBridgeOutboundChannel MessageQueues (max_values: None, max_size: None, mode: Measured)
+	fn on_initialize_no_messages() -> Weight {
+		Weight::from_parts(5_030_000, 3032)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+	}
+	/// Storage: EVMBridgeOutboundChannel MessageQueues (r:1 w:1)
+	/// Proof Skipped: EVMBridgeOutboundChannel MessageQueues (max_values: None, max_size: None, mode: Measured)
+	/// Storage: EVMBridgeOutboundChannel ChannelNonces (r:1 w:0)
+	/// Proof Skipped: EVMBridgeOutboundChannel ChannelNonces (max_values: None, max_size: None, mode: Measured)
+	/// Storage: EVMBridgeOutboundChannel QueuesTotalGas (r:1 w:1)
+	/// Proof Skipped: EVMBridgeOutboundChannel QueuesTotalGas (max_values: None, max_size: None, mode: Measured)
+	fn submit() -> Weight {
+		Weight::from_parts(9_310_000, 7487)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+}
@@ -108,9 +108,26 @@ benchmarks! {
         assert_eq!(AllowedParachainAssets::<T>::get(BASE_NETWORK_ID, 1), vec![]);
     }
 
+    // Benchmark `update_transaction_status` for the failed transfer which is refunded.
     update_transaction_status {
+        let who: T::AccountId = whitelisted_caller();
+        let asset_id = <T as Config>::AssetRegistry::register_asset(BASE_NETWORK_ID.into(), Default::default(), Default::default())?;
+        SubstrateApp::<T>::register_thischain_asset(RawOrigin::Root.into(), BASE_NETWORK_ID, asset_id.clone(), PARENT_PARACHAIN_ASSET, Default::default(), 1u32.into())?;
+        SubstrateApp::<T>::finalize_asset_registration(<T as Config>::CallOrigin::try_successful_origin().unwrap(), asset_id.clone(), AssetKind::Thischain)?;
+        Currencies::<T>::deposit(asset_id.clone(), &who, 1000u32.into())?;
+        SubstrateApp::<T>::burn(
+            RawOrigin::Signed(who.clone()).into(),
+            BASE_NETWORK_ID,
+            asset_id.clone(),
+            ParachainAccountId::V3(xcm::v3::MultiLocation::parent().pushed_with_interior([0u8; 32]).unwrap()),
+            1000u32.into()
+        )?;
+        let message_id = OutboundTransfers::<T>::iter_keys().next().unwrap();
     }: {
-        SubstrateApp::<T>::update_transaction_status(<T as Config>::CallOrigin::try_successful_origin().unwrap(), Default::default(), XCMAppTransferStatus::Success)?;
+        SubstrateApp::<T>::update_transaction_status(<T as Config>::CallOrigin::try_successful_origin().unwrap(), message_id, XCMAppTransferStatus::XCMTransferError)?;
+    }
+    verify {
+        assert_eq!(Currencies::<T>::free_balance(asset_id, &who), 1000u32.into());
     }
 
     mint {
@@ -154,5 +171,26 @@ benchmarks! {
         assert_eq!(AssetKinds::<T>::get(BASE_NETWORK_ID, asset_id), Some(AssetKind::Thischain));
     }
 
+    report_message_timeout {
+        let who: T::AccountId = whitelisted_caller();
+        let asset_id = <T as Config>::AssetRegistry::register_asset(BASE_NETWORK_ID.into(), Default::default(), Default::default())?;
+        SubstrateApp::<T>::register_thischain_asset(RawOrigin::Root.into(), BASE_NETWORK_ID, asset_id.clone(), PARENT_PARACHAIN_ASSET, Default::default(), 1u32.into())?;
+        SubstrateApp::<T>::finalize_asset_registration(<T as Config>::CallOrigin::try_successful_origin().unwrap(), asset_id.clone(), AssetKind::Thischain)?;
+        Currencies::<T>::deposit(asset_id.clone(), &who, 1000u32.into())?;
+        SubstrateApp::<T>::burn(
+            RawOrigin::Signed(who.clone()).into(),
+            BASE_NETWORK_ID,
+            asset_id.clone(),
+            ParachainAccountId::V3(xcm::v3::MultiLocation::parent().pushed_with_interior([0u8; 32]).unwrap()),
+            1000u32.into()
+        )?;
+        let message_id = OutboundTransfers::<T>::iter_keys().next().unwrap();
+    }: {
+        SubstrateApp::<T>::report_message_timeout(<T as Config>::CallOrigin::try_successful_origin().unwrap(), message_id)?;
+    }
+    verify {
+        assert_eq!(Currencies::<T>::free_balance(asset_id, &who), 1000u32.into());
+    }
+
     impl_benchmark_test_suite!(SubstrateApp, crate::mock::new_tester(), crate::mock::Test,);
 }
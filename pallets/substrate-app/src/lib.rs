@@ -41,6 +41,9 @@
 
 pub const TRANSFER_MAX_GAS: u64 = 100_000;
 
+/// Id of the app to pause its transfers with [`bridge_types::types::PauseScope::App`].
+pub const APP_ID: PalletId = PalletId(*b"subs/app");
+
 extern crate alloc;
 
 pub mod weights;
@@ -58,14 +61,19 @@ use bridge_types::substrate::SubAssetInfo;
 use bridge_types::substrate::{ParachainAccountId, SubstrateAppCall};
 use bridge_types::traits::BridgeApp;
 use bridge_types::traits::BridgeAssetLocker;
+use bridge_types::traits::MessageDeliveryHandler;
 use bridge_types::types::{BridgeAppInfo, BridgeAssetInfo};
 use bridge_types::GenericNetworkId;
+use bridge_types::SubNetworkId;
 use bridge_types::{MainnetAccountId, MainnetAssetId};
 use frame_support::dispatch::{DispatchError, DispatchResult};
 use frame_support::ensure;
-use frame_support::traits::EnsureOrigin;
+use frame_support::traits::{EnsureOrigin, UnixTime};
+use frame_support::weights::Weight;
+use frame_support::PalletId;
 use frame_system::ensure_signed;
 use sp_runtime::traits::{Convert, Zero};
+use sp_runtime::SaturatedConversion;
 use sp_std::prelude::*;
 
 pub use weights::WeightInfo;
@@ -104,6 +112,9 @@ where
                 message_id,
                 transfer_status,
             },
+            SubstrateAppCall::ReportMessageTimeout { message_id } => {
+                Call::report_message_timeout { message_id }
+            }
         }
     }
 }
@@ -119,10 +130,10 @@ pub mod pallet {
         ParachainAccountId, ParachainAssetId, SubstrateBridgeMessageEncode, XCMAppCall,
     };
     use bridge_types::traits::{
-        BalancePrecisionConverter, BridgeAssetLocker, BridgeAssetRegistry, MessageStatusNotifier,
-        OutboundChannel,
+        BalancePrecisionConverter, BridgeAssetLocker, BridgeAssetRegistry, BridgePauseChecker,
+        MessageStatusNotifier, OutboundChannel,
     };
-    use bridge_types::types::{AssetKind, CallOriginOutput, MessageStatus};
+    use bridge_types::types::{AssetKind, CallOriginOutput, MessageDirection, MessageStatus};
     use bridge_types::{
         GenericAccount, GenericNetworkId, MainnetAccountId, MainnetAssetId, MainnetBalance,
         SubNetworkId, H256,
@@ -185,6 +196,17 @@ pub mod pallet {
 
         type BridgeAssetLocker: BridgeAssetLocker<Self::AccountId>;
 
+        /// Checks whether the app transfers are paused.
+        type BridgePause: BridgePauseChecker;
+
+        /// Time in milliseconds after which the transfer is rejected by the destination
+        /// and refunded. Zero disables the transfer deadlines.
+        #[pallet::constant]
+        type MessageTimeout: Get<u64>;
+
+        /// Current time for the transfer deadlines.
+        type UnixTime: UnixTime;
+
         type WeightInfo: WeightInfo;
     }
 
@@ -210,6 +232,8 @@ pub mod pallet {
             T::AccountId,
             BalanceOf<T>,
         ),
+        /// [network_id, message_id, asset_id, recipient, amount]
+        Refunded(SubNetworkId, H256, AssetIdOf<T>, T::AccountId, BalanceOf<T>),
     }
 
     #[pallet::storage]
@@ -236,6 +260,18 @@ pub mod pallet {
     pub(super) type RelaychainAsset<T: Config> =
         StorageMap<_, Identity, SubNetworkId, AssetIdOf<T>, OptionQuery>;
 
+    /// Locked funds of the sent transfers, refunded if the transfer fails or times out.
+    /// [network_id, sender, asset_id, amount]
+    #[pallet::storage]
+    #[pallet::getter(fn outbound_transfer)]
+    pub(super) type OutboundTransfers<T: Config> = StorageMap<
+        _,
+        Identity,
+        H256,
+        (SubNetworkId, T::AccountId, AssetIdOf<T>, BalanceOf<T>),
+        OptionQuery,
+    >;
+
     #[pallet::error]
     pub enum Error<T> {
         TokenIsNotRegistered,
@@ -278,6 +314,11 @@ pub mod pallet {
                 timepoint,
                 ..
             } = T::CallOrigin::ensure_origin(origin.clone())?;
+            T::BridgePause::ensure_not_paused(
+                network_id.into(),
+                MessageDirection::Inbound,
+                Some(APP_ID),
+            )?;
 
             let asset_kind = AssetKinds::<T>::get(network_id, &asset_id)
                 .ok_or(Error::<T>::TokenIsNotRegistered)?;
@@ -494,7 +535,16 @@ pub mod pallet {
             } = T::CallOrigin::ensure_origin(origin)?;
 
             let message_status = match transfer_status {
-                XCMAppTransferStatus::Success => MessageStatus::Done,
+                XCMAppTransferStatus::Success => {
+                    OutboundTransfers::<T>::remove(message_id);
+                    MessageStatus::Done
+                }
+                XCMAppTransferStatus::XCMTransferError
+                    if OutboundTransfers::<T>::contains_key(message_id) =>
+                {
+                    Self::refund_transfer(network_id, message_id)?;
+                    MessageStatus::Refunded
+                }
                 XCMAppTransferStatus::XCMTransferError => MessageStatus::Failed,
             };
             T::MessageStatusNotifier::update_status(
@@ -542,6 +592,26 @@ pub mod pallet {
             )?;
             Ok(())
         }
+
+        /// Refunds the transfer rejected by the destination because its deadline is expired.
+        #[pallet::call_index(10)]
+        #[pallet::weight(<T as Config>::WeightInfo::report_message_timeout())]
+        pub fn report_message_timeout(origin: OriginFor<T>, message_id: H256) -> DispatchResult {
+            let CallOriginOutput {
+                network_id,
+                timepoint,
+                ..
+            } = T::CallOrigin::ensure_origin(origin)?;
+
+            Self::refund_transfer(network_id, message_id)?;
+            T::MessageStatusNotifier::update_status(
+                network_id.into(),
+                message_id,
+                MessageStatus::Refunded,
+                timepoint,
+            );
+            Ok(())
+        }
     }
 
     impl<T: Config> Pallet<T> {
@@ -599,6 +669,11 @@ pub mod pallet {
             recipient: ParachainAccountId,
             amount: BalanceOf<T>,
         ) -> Result<H256, DispatchError> {
+            T::BridgePause::ensure_not_paused(
+                network_id.into(),
+                MessageDirection::Outbound,
+                Some(APP_ID),
+            )?;
             ensure!(amount > BalanceOf::<T>::zero(), Error::<T>::WrongAmount);
 
             if let Some(limit) = Self::get_transfer_limit() {
@@ -626,18 +701,24 @@ pub mod pallet {
                 &amount,
             )?;
 
-            let message_id = T::OutboundChannel::submit(
-                network_id,
-                &RawOrigin::Signed(who.clone()),
-                &XCMAppCall::Transfer {
-                    recipient: recipient.clone(),
-                    amount: sidechain_amount,
-                    asset_id: T::AssetIdConverter::convert(asset_id.clone()),
-                    sender: T::AccountIdConverter::convert(who.clone()),
-                }
-                .prepare_message(),
-                (),
-            )?;
+            let payload = XCMAppCall::Transfer {
+                recipient: recipient.clone(),
+                amount: sidechain_amount,
+                asset_id: T::AssetIdConverter::convert(asset_id.clone()),
+                sender: T::AccountIdConverter::convert(who.clone()),
+            }
+            .prepare_message();
+            let origin = RawOrigin::Signed(who.clone());
+            let message_id = match Self::transfer_deadline() {
+                Some(deadline) => T::OutboundChannel::submit_with_deadline(
+                    network_id,
+                    &origin,
+                    &payload,
+                    (),
+                    deadline,
+                )?,
+                None => T::OutboundChannel::submit(network_id, &origin, &payload, ())?,
+            };
 
             T::MessageStatusNotifier::outbound_request(
                 GenericNetworkId::Sub(network_id),
@@ -648,12 +729,51 @@ pub mod pallet {
                 amount.clone(),
                 MessageStatus::InQueue,
             );
+            OutboundTransfers::<T>::insert(
+                message_id,
+                (network_id, who.clone(), asset_id.clone(), amount.clone()),
+            );
 
             Self::deposit_event(Event::Burned(network_id, asset_id, who, recipient, amount));
 
             Ok(Default::default())
         }
 
+        /// Deadline of the transfer sent now, if the message timeout is set.
+        fn transfer_deadline() -> Option<u64> {
+            let timeout = T::MessageTimeout::get();
+            if timeout == 0 {
+                return None;
+            }
+            let now: u64 = T::UnixTime::now().as_millis().saturated_into();
+            Some(now.saturating_add(timeout))
+        }
+
+        /// Unlocks the funds of the failed transfer back to the sender.
+        pub(crate) fn refund_transfer(
+            network_id: SubNetworkId,
+            message_id: H256,
+        ) -> DispatchResult {
+            let (transfer_network_id, sender, asset_id, amount) =
+                OutboundTransfers::<T>::get(message_id).ok_or(Error::<T>::MessageIdNotFound)?;
+            ensure!(
+                transfer_network_id == network_id,
+                Error::<T>::InvalidNetwork
+            );
+            <Self as BridgeApp<_, ParachainAccountId, _, _>>::refund(
+                network_id.into(),
+                message_id,
+                sender.clone(),
+                asset_id.clone(),
+                amount.clone(),
+            )?;
+            OutboundTransfers::<T>::remove(message_id);
+            Self::deposit_event(Event::Refunded(
+                network_id, message_id, asset_id, sender, amount,
+            ));
+            Ok(())
+        }
+
         fn check_parachain_transfer_params(
             network_id: SubNetworkId,
             asset_id: AssetIdOf<T>,
@@ -802,3 +922,37 @@ impl<T: Config> BridgeApp<T::AccountId, ParachainAccountId, AssetIdOf<T>, Balanc
             })
     }
 }
+
+impl<T: Config> MessageDeliveryHandler<SubNetworkId> for Pallet<T> {
+    fn on_message_dispatched(network_id: SubNetworkId, message_id: bridge_types::H256) {
+        // The transfer can't be refunded after it is delivered
+        if matches!(
+            OutboundTransfers::<T>::get(message_id),
+            Some((transfer_network_id, ..)) if transfer_network_id == network_id
+        ) {
+            OutboundTransfers::<T>::remove(message_id);
+        }
+    }
+
+    fn on_message_failed(network_id: SubNetworkId, message_id: bridge_types::H256) -> bool {
+        if !OutboundTransfers::<T>::contains_key(message_id) {
+            return false;
+        }
+        match Self::refund_transfer(network_id, message_id) {
+            Ok(()) => true,
+            Err(err) => {
+                frame_support::log::warn!(
+                    "Failed to refund transfer {:?} on network {:?}: {:?}",
+                    message_id,
+                    network_id,
+                    err
+                );
+                false
+            }
+        }
+    }
+
+    fn on_message_delivered_weight() -> Weight {
+        <T as Config>::WeightInfo::report_message_timeout()
+    }
+}
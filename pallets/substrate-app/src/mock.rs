@@ -45,6 +45,7 @@ use bridge_types::types::AssetKind;
 use bridge_types::SubNetworkId;
 use frame_support::parameter_types;
 use frame_support::traits::{Everything, GenesisBuild};
+use frame_support::weights::Weight;
 use frame_support::Deserialize;
 use frame_support::RuntimeDebug;
 use frame_support::Serialize;
@@ -202,14 +203,20 @@ impl dispatch::Config for Test {
     type Hashing = Keccak256;
     type Call = RuntimeCall;
     type CallFilter = Everything;
+    type BridgePause = ();
+    type CallApp = ();
     type WeightInfo = ();
 }
 
 parameter_types! {
     pub const MaxMessagePayloadSize: u32 = 2048;
     pub const MaxMessagesPerCommit: u32 = 5;
+    pub const MaxPendingMessages: u32 = 0;
+    pub const MaxStoredCommitments: u64 = 0;
+    pub const MaxCommitWeight: Weight = Weight::MAX;
     pub const MaxTotalGasLimit: u64 = 5_000_000;
     pub const Decimals: u32 = 12;
+    pub static MessageTimeout: u64 = 0;
 }
 
 parameter_types! {
@@ -229,10 +236,19 @@ impl substrate_bridge_channel::outbound::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type MaxMessagePayloadSize = MaxMessagePayloadSize;
     type MaxMessagesPerCommit = MaxMessagesPerCommit;
+    type MaxPendingMessages = MaxPendingMessages;
+    type MaxCommitWeight = MaxCommitWeight;
+    type MaxStoredCommitments = MaxStoredCommitments;
     type MessageStatusNotifier = ();
+    type CallOrigin =
+        dispatch::EnsureAccount<bridge_types::types::CallOriginOutput<SubNetworkId, H256, ()>>;
     type AuxiliaryDigestHandler = ();
     type AssetId = ();
     type Balance = u128;
+    type Currency = Balances;
+    type FeeAccountId = GetFeesAccountId;
+    type BridgePause = ();
+    type MessageDeliveryHandler = SubstrateApp;
     type WeightInfo = ();
     type TimepointProvider = GenericTimepointProvider;
     type ThisNetworkId = ThisNetworkId;
@@ -326,6 +342,9 @@ impl substrate_app::Config for Test {
         dispatch::EnsureAccount<bridge_types::types::CallOriginOutput<SubNetworkId, H256, ()>>;
     type OutboundChannel = BridgeOutboundChannel;
     type AssetRegistry = AssetRegistryImpl;
+    type BridgePause = ();
+    type MessageTimeout = MessageTimeout;
+    type UnixTime = Timestamp;
     type WeightInfo = ();
     type AccountIdConverter = sp_runtime::traits::ConvertInto;
     type AssetIdConverter = ();
@@ -352,7 +371,11 @@ pub fn new_tester() -> sp_io::TestExternalities {
     .unwrap();
 
     GenesisBuild::<Test>::assimilate_storage(
-        &substrate_bridge_channel::outbound::GenesisConfig { interval: 10 },
+        &substrate_bridge_channel::outbound::GenesisConfig {
+            interval: 10,
+            intervals: vec![(SubNetworkId::Mainnet, 10)],
+            channels: vec![SubNetworkId::Mainnet, SubNetworkId::Kusama],
+        },
         &mut storage,
     )
     .unwrap();
@@ -432,7 +455,11 @@ pub fn new_tester_no_registered_assets() -> sp_io::TestExternalities {
     .unwrap();
 
     GenesisBuild::<Test>::assimilate_storage(
-        &substrate_bridge_channel::outbound::GenesisConfig { interval: 10 },
+        &substrate_bridge_channel::outbound::GenesisConfig {
+            interval: 10,
+            intervals: vec![(SubNetworkId::Mainnet, 10)],
+            channels: vec![SubNetworkId::Mainnet, SubNetworkId::Kusama],
+        },
         &mut storage,
     )
     .unwrap();
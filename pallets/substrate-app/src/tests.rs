@@ -32,16 +32,23 @@ use crate::mock::{
     new_tester, new_tester_no_registered_assets, BalancePrecisionConverterImpl, Currencies,
     RuntimeOrigin, PARA_A, PARA_C,
 };
-use crate::mock::{AssetId, SubstrateApp, Test};
-use crate::{Error, RelaychainAsset};
-use bridge_types::substrate::{ParachainAssetId, PARENT_PARACHAIN_ASSET};
+use crate::mock::{
+    AssetId, BridgeOutboundChannel, MaxMessagePayloadSize, MaxMessagesPerCommit, MessageTimeout,
+    SubstrateApp, Test,
+};
+use crate::{Error, OutboundTransfers, RelaychainAsset};
+use bridge_types::substrate::{
+    MessageDispatchResult, ParachainAssetId, XCMAppTransferStatus, PARENT_PARACHAIN_ASSET,
+};
 use bridge_types::test_utils::BridgeAssetLockerImpl;
 use bridge_types::traits::{BalancePrecisionConverter, BridgeOriginOutput};
-use bridge_types::types::AssetKind;
+use bridge_types::types::{AssetKind, BridgeOffchainData};
 use bridge_types::{
     substrate::{Junction, VersionedMultiLocation},
-    SubNetworkId,
+    GenericCommitment, SubNetworkId,
 };
+use codec::Decode;
+use frame_support::traits::Hooks;
 use frame_support::{assert_noop, assert_ok};
 use frame_system::Origin;
 use sp_core::H256;
@@ -771,3 +778,222 @@ fn it_works_remove_assetid_paraid() {
         ));
     });
 }
+
+fn burn_xor(amount: u128) -> H256 {
+    let location = MultiLocation::new(
+        1,
+        X2(
+            Parachain(PARA_A),
+            Junction::AccountId32 {
+                network: None,
+                id: Keyring::Bob.into(),
+            },
+        ),
+    );
+    assert_ok!(SubstrateApp::burn(
+        Origin::<Test>::Signed(Keyring::Alice.into()).into(),
+        SubNetworkId::Kusama,
+        AssetId::XOR,
+        VersionedMultiLocation::V3(location),
+        amount
+    ));
+    OutboundTransfers::<Test>::iter_keys()
+        .next()
+        .expect("outbound transfer is not stored")
+}
+
+fn kusama_origin() -> RuntimeOrigin {
+    dispatch::RawOrigin::new(BridgeOriginOutput::new(
+        SubNetworkId::Kusama,
+        H256([0; 32]),
+        bridge_types::GenericTimepoint::Unknown,
+        (),
+    ))
+    .into()
+}
+
+#[test]
+fn it_works_report_message_timeout() {
+    new_tester().execute_with(|| {
+        let amount = 1_000_000;
+        let balance_before = Currencies::total_balance(AssetId::XOR, &Keyring::Alice.into());
+        let message_id = burn_xor(amount);
+        assert_eq!(
+            Currencies::total_balance(AssetId::XOR, &Keyring::Alice.into()),
+            balance_before - amount
+        );
+
+        assert_ok!(SubstrateApp::report_message_timeout(
+            kusama_origin(),
+            message_id
+        ));
+        assert_eq!(
+            Currencies::total_balance(AssetId::XOR, &Keyring::Alice.into()),
+            balance_before
+        );
+        assert!(SubstrateApp::outbound_transfer(message_id).is_none());
+
+        // The transfer can be refunded only once
+        assert_noop!(
+            SubstrateApp::report_message_timeout(kusama_origin(), message_id),
+            Error::<Test>::MessageIdNotFound
+        );
+    });
+}
+
+#[test]
+fn it_fails_report_message_timeout_wrong_network() {
+    new_tester().execute_with(|| {
+        let message_id = burn_xor(1_000_000);
+        let origin: RuntimeOrigin = dispatch::RawOrigin::new(BridgeOriginOutput::new(
+            SubNetworkId::Polkadot,
+            H256([0; 32]),
+            bridge_types::GenericTimepoint::Unknown,
+            (),
+        ))
+        .into();
+        assert_noop!(
+            SubstrateApp::report_message_timeout(origin, message_id),
+            Error::<Test>::InvalidNetwork
+        );
+    });
+}
+
+#[test]
+fn it_works_refund_on_transfer_error() {
+    new_tester().execute_with(|| {
+        let amount = 1_000_000;
+        let balance_before = Currencies::total_balance(AssetId::XOR, &Keyring::Alice.into());
+        let message_id = burn_xor(amount);
+
+        assert_ok!(SubstrateApp::update_transaction_status(
+            kusama_origin(),
+            message_id,
+            XCMAppTransferStatus::XCMTransferError
+        ));
+        assert_eq!(
+            Currencies::total_balance(AssetId::XOR, &Keyring::Alice.into()),
+            balance_before
+        );
+        assert!(SubstrateApp::outbound_transfer(message_id).is_none());
+    });
+}
+
+#[test]
+fn it_works_transfer_success_keeps_funds_locked() {
+    new_tester().execute_with(|| {
+        let amount = 1_000_000;
+        let balance_before = Currencies::total_balance(AssetId::XOR, &Keyring::Alice.into());
+        let message_id = burn_xor(amount);
+
+        assert_ok!(SubstrateApp::update_transaction_status(
+            kusama_origin(),
+            message_id,
+            XCMAppTransferStatus::Success
+        ));
+        assert_eq!(
+            Currencies::total_balance(AssetId::XOR, &Keyring::Alice.into()),
+            balance_before - amount
+        );
+        assert!(SubstrateApp::outbound_transfer(message_id).is_none());
+    });
+}
+
+#[test]
+fn it_works_burn_with_deadline() {
+    let mut ext = new_tester();
+    ext.execute_with(|| {
+        MessageTimeout::set(60_000);
+        pallet_timestamp::Pallet::<Test>::set_timestamp(1_000);
+        let location = MultiLocation::new(
+            1,
+            X2(
+                Parachain(PARA_A),
+                Junction::AccountId32 {
+                    network: None,
+                    id: Keyring::Bob.into(),
+                },
+            ),
+        );
+        assert_noop!(
+            SubstrateApp::burn(
+                Origin::<Test>::Signed(Keyring::Alice.into()).into(),
+                SubNetworkId::Kusama,
+                AssetId::XOR,
+                VersionedMultiLocation::V3(location),
+                1_000_000
+            ),
+            substrate_bridge_channel::outbound::Error::<Test>::DeadlinesNotSupported
+        );
+
+        substrate_bridge_channel::outbound::CommitmentFormats::<Test>::insert(
+            SubNetworkId::Kusama,
+            substrate_bridge_channel::outbound::CommitmentFormat::BatchV2,
+        );
+        burn_xor(1_000_000);
+        <BridgeOutboundChannel as Hooks<u64>>::on_initialize(10);
+    });
+    ext.persist_offchain_overlay();
+
+    let key = bridge_types::utils::make_offchain_key(SubNetworkId::Kusama.into(), 1);
+    let data = ext
+        .offchain_db()
+        .get(&key)
+        .expect("commitment is stored offchain");
+    let data = BridgeOffchainData::<u64, MaxMessagesPerCommit, MaxMessagePayloadSize>::decode(
+        &mut &data[..],
+    )
+    .unwrap();
+    let GenericCommitment::SubV2(commitment) = data.commitment else {
+        panic!("unexpected commitment {:?}", data.commitment)
+    };
+    assert_eq!(commitment.messages[0].deadline, Some(61_000));
+}
+
+#[test]
+fn it_works_refund_on_failed_delivery() {
+    new_tester().execute_with(|| {
+        let amount = 1_000_000;
+        let balance_before = Currencies::total_balance(AssetId::XOR, &Keyring::Alice.into());
+        let message_id = burn_xor(amount);
+        <BridgeOutboundChannel as Hooks<u64>>::on_initialize(10);
+
+        assert_ok!(BridgeOutboundChannel::report_delivery(
+            kusama_origin(),
+            1,
+            vec![MessageDispatchResult::Failed]
+        ));
+        assert_eq!(
+            Currencies::total_balance(AssetId::XOR, &Keyring::Alice.into()),
+            balance_before
+        );
+        assert!(SubstrateApp::outbound_transfer(message_id).is_none());
+    });
+}
+
+#[test]
+fn it_works_release_on_dispatched_delivery() {
+    new_tester().execute_with(|| {
+        let amount = 1_000_000;
+        let balance_before = Currencies::total_balance(AssetId::XOR, &Keyring::Alice.into());
+        let message_id = burn_xor(amount);
+        <BridgeOutboundChannel as Hooks<u64>>::on_initialize(10);
+
+        assert_ok!(BridgeOutboundChannel::report_delivery(
+            kusama_origin(),
+            1,
+            vec![MessageDispatchResult::Dispatched]
+        ));
+        assert_eq!(
+            Currencies::total_balance(AssetId::XOR, &Keyring::Alice.into()),
+            balance_before - amount
+        );
+        assert!(SubstrateApp::outbound_transfer(message_id).is_none());
+
+        // The delivered transfer can't be refunded
+        assert_noop!(
+            SubstrateApp::report_message_timeout(kusama_origin(), message_id),
+            Error::<Test>::MessageIdNotFound
+        );
+    });
+}
@@ -28,26 +28,11 @@
 // STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-//! Autogenerated weights for substrate_bridge_app
+//! Weights for substrate_bridge_app
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
-//! DATE: 2023-07-27, STEPS: `50`, REPEAT: `20`, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! WORST CASE MAP SIZE: `1000000`
-//! HOSTNAME: `TRX40`, CPU: `AMD Ryzen Threadripper 3960X 24-Core Processor`
-//! EXECUTION: None, WASM-EXECUTION: Compiled, CHAIN: Some("local"), DB CACHE: 1024
-
-// Executed Command:
-// ./target/release/framenode
-// benchmark
-// pallet
-// --chain=local
-// --steps=50
-// --repeat=20
-// --pallet=substrate_bridge_app
-// --extrinsic=*
-// --header=./misc/file_header.txt
-// --template=./misc/pallet-weight-template.hbs
-// --output=./substrate-bridge-app.rs
+//! NOT GENERATED BY THE BENCHMARK CLI: the values are estimates until the pallet
+//! is benchmarked, regenerate with
+//! `benchmark pallet --pallet=substrate_bridge_app --extrinsic=* --template=./misc/pallet-weight-template.hbs`.
 
 #![cfg_attr(rustfmt, rustfmt_skip)]
 #![allow(unused_parens)]
@@ -67,6 +52,7 @@ pub trait WeightInfo {
 	fn mint() -> Weight;
 	fn burn() -> Weight;
 	fn finalize_asset_registration() -> Weight;
+	fn report_message_timeout() -> Weight;
 }
 
 /// Weights for substrate_bridge_app using the Substrate node and recommended hardware.
@@ -90,12 +76,7 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof Skipped: SubstrateBridgeApp SidechainPrecision (max_values: None, max_size: None, mode: Measured)
 	/// The range of component `a` is `[1, 100]`.
 	fn register_thischain_asset(a: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `1742`
-		//  Estimated: `28786 + a * (2475 ±0)`
-		// Minimum execution time: 38_142_000 picoseconds.
 		Weight::from_parts(38_745_541, 28786)
-			// Standard Error: 2_243
 			.saturating_add(Weight::from_parts(1_114_710, 0).saturating_mul(a.into()))
 			.saturating_add(T::DbWeight::get().reads(6_u64))
 			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(a.into())))
@@ -125,12 +106,7 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof Skipped: Assets AssetInfos (max_values: None, max_size: None, mode: Measured)
 	/// The range of component `a` is `[1, 100]`.
 	fn register_sidechain_asset(a: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `2219`
-		//  Estimated: `42374 + a * (2475 ±0)`
-		// Minimum execution time: 75_344_000 picoseconds.
 		Weight::from_parts(75_609_365, 42374)
-			// Standard Error: 2_927
 			.saturating_add(Weight::from_parts(1_134_879, 0).saturating_mul(a.into()))
 			.saturating_add(T::DbWeight::get().reads(9_u64))
 			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(a.into())))
@@ -141,10 +117,6 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Storage: SubstrateBridgeApp BridgeTransferLimit (r:0 w:1)
 	/// Proof Skipped: SubstrateBridgeApp BridgeTransferLimit (max_values: Some(1), max_size: None, mode: Measured)
 	fn set_transfer_limit() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `0`
-		//  Estimated: `0`
-		// Minimum execution time: 1_890_000 picoseconds.
 		Weight::from_parts(2_070_000, 0)
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
@@ -153,10 +125,6 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Storage: SubstrateBridgeApp AllowedParachainAssets (r:1 w:1)
 	/// Proof Skipped: SubstrateBridgeApp AllowedParachainAssets (max_values: None, max_size: None, mode: Measured)
 	fn add_assetid_paraid() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `273`
-		//  Estimated: `5496`
-		// Minimum execution time: 8_340_000 picoseconds.
 		Weight::from_parts(8_530_000, 5496)
 			.saturating_add(T::DbWeight::get().reads(2_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
@@ -166,23 +134,30 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Storage: SubstrateBridgeApp AllowedParachainAssets (r:1 w:1)
 	/// Proof Skipped: SubstrateBridgeApp AllowedParachainAssets (max_values: None, max_size: None, mode: Measured)
 	fn remove_assetid_paraid() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `365`
-		//  Estimated: `5680`
-		// Minimum execution time: 9_560_000 picoseconds.
 		Weight::from_parts(9_740_000, 5680)
 			.saturating_add(T::DbWeight::get().reads(2_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: SubstrateBridgeApp OutboundTransfers (r:1 w:1)
+	/// Proof Skipped: SubstrateBridgeApp OutboundTransfers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeApp AssetKinds (r:1 w:0)
+	/// Proof Skipped: SubstrateBridgeApp AssetKinds (max_values: None, max_size: None, mode: Measured)
+	/// Storage: BridgeProxy LockedAssets (r:1 w:1)
+	/// Proof Skipped: BridgeProxy LockedAssets (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Technical TechAccounts (r:1 w:0)
+	/// Proof Skipped: Technical TechAccounts (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tokens Accounts (r:2 w:2)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(136), added: 2611, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:0)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
 	/// Storage: BridgeProxy Senders (r:1 w:0)
 	/// Proof Skipped: BridgeProxy Senders (max_values: None, max_size: None, mode: Measured)
+	/// Storage: BridgeProxy Transactions (r:1 w:1)
+	/// Proof Skipped: BridgeProxy Transactions (max_values: None, max_size: None, mode: Measured)
 	fn update_transaction_status() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `4`
-		//  Estimated: `2479`
-		// Minimum execution time: 6_141_000 picoseconds.
-		Weight::from_parts(6_321_000, 2479)
-			.saturating_add(T::DbWeight::get().reads(1_u64))
+		Weight::from_parts(48_637_000, 31562)
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
 	}
 	/// Storage: SubstrateBridgeApp AssetKinds (r:1 w:0)
 	/// Proof Skipped: SubstrateBridgeApp AssetKinds (max_values: None, max_size: None, mode: Measured)
@@ -202,13 +177,11 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof Skipped: BridgeProxy Senders (max_values: None, max_size: None, mode: Measured)
 	/// Storage: BridgeProxy Transactions (r:0 w:1)
 	/// Proof Skipped: BridgeProxy Transactions (max_values: None, max_size: None, mode: Measured)
+	/// Storage: BridgePause PausedScopes (r:4 w:0)
+	/// Proof: BridgePause PausedScopes (max_values: None, max_size: Some(63), added: 2538, mode: MaxEncodedLen)
 	fn mint() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `2455`
-		//  Estimated: `37385`
-		// Minimum execution time: 54_033_000 picoseconds.
-		Weight::from_parts(54_503_000, 37385)
-			.saturating_add(T::DbWeight::get().reads(8_u64))
+		Weight::from_parts(57_503_000, 47537)
+			.saturating_add(T::DbWeight::get().reads(12_u64))
 			.saturating_add(T::DbWeight::get().writes(5_u64))
 	}
 	/// Storage: SubstrateBridgeApp BridgeTransferLimit (r:1 w:0)
@@ -227,38 +200,63 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof Skipped: Technical TechAccounts (max_values: None, max_size: None, mode: Measured)
 	/// Storage: Tokens Accounts (r:2 w:2)
 	/// Proof: Tokens Accounts (max_values: None, max_size: Some(136), added: 2611, mode: MaxEncodedLen)
-	/// Storage: System Account (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
 	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: SubstrateBridgeOutboundChannel Channels (r:1 w:0)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel Channels (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel CommitmentFormats (r:1 w:0)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel CommitmentFormats (max_values: None, max_size: None, mode: Measured)
 	/// Storage: SubstrateBridgeOutboundChannel MessageQueues (r:1 w:1)
 	/// Proof Skipped: SubstrateBridgeOutboundChannel MessageQueues (max_values: None, max_size: None, mode: Measured)
 	/// Storage: SubstrateBridgeOutboundChannel ChannelNonces (r:1 w:0)
 	/// Proof Skipped: SubstrateBridgeOutboundChannel ChannelNonces (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel MessageFees (r:1 w:0)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel MessageFees (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
 	/// Storage: BridgeProxy Senders (r:0 w:1)
 	/// Proof Skipped: BridgeProxy Senders (max_values: None, max_size: None, mode: Measured)
 	/// Storage: BridgeProxy Transactions (r:0 w:1)
 	/// Proof Skipped: BridgeProxy Transactions (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeApp OutboundTransfers (r:0 w:1)
+	/// Proof Skipped: SubstrateBridgeApp OutboundTransfers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: BridgePause PausedScopes (r:7 w:0)
+	/// Proof: BridgePause PausedScopes (max_values: None, max_size: Some(63), added: 2538, mode: MaxEncodedLen)
 	fn burn() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `2322`
-		//  Estimated: `53662`
-		// Minimum execution time: 66_103_000 picoseconds.
-		Weight::from_parts(66_823_000, 53662)
-			.saturating_add(T::DbWeight::get().reads(12_u64))
-			.saturating_add(T::DbWeight::get().writes(7_u64))
+		Weight::from_parts(84_823_000, 81233)
+			.saturating_add(T::DbWeight::get().reads(24_u64))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
 	}
 	/// Storage: SubstrateBridgeApp SidechainPrecision (r:1 w:0)
 	/// Proof Skipped: SubstrateBridgeApp SidechainPrecision (max_values: None, max_size: None, mode: Measured)
 	/// Storage: SubstrateBridgeApp AssetKinds (r:0 w:1)
 	/// Proof Skipped: SubstrateBridgeApp AssetKinds (max_values: None, max_size: None, mode: Measured)
 	fn finalize_asset_registration() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `240`
-		//  Estimated: `2955`
-		// Minimum execution time: 7_510_000 picoseconds.
 		Weight::from_parts(7_660_000, 2955)
 			.saturating_add(T::DbWeight::get().reads(1_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: SubstrateBridgeApp OutboundTransfers (r:1 w:1)
+	/// Proof Skipped: SubstrateBridgeApp OutboundTransfers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeApp AssetKinds (r:1 w:0)
+	/// Proof Skipped: SubstrateBridgeApp AssetKinds (max_values: None, max_size: None, mode: Measured)
+	/// Storage: BridgeProxy LockedAssets (r:1 w:1)
+	/// Proof Skipped: BridgeProxy LockedAssets (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Technical TechAccounts (r:1 w:0)
+	/// Proof Skipped: Technical TechAccounts (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tokens Accounts (r:2 w:2)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(136), added: 2611, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:0)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: BridgeProxy Senders (r:1 w:0)
+	/// Proof Skipped: BridgeProxy Senders (max_values: None, max_size: None, mode: Measured)
+	/// Storage: BridgeProxy Transactions (r:1 w:1)
+	/// Proof Skipped: BridgeProxy Transactions (max_values: None, max_size: None, mode: Measured)
+	fn report_message_timeout() -> Weight {
+		Weight::from_parts(48_102_000, 31562)
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -281,12 +279,7 @@ impl WeightInfo for () {
 	/// Proof Skipped: SubstrateBridgeApp SidechainPrecision (max_values: None, max_size: None, mode: Measured)
 	/// The range of component `a` is `[1, 100]`.
 	fn register_thischain_asset(a: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `1742`
-		//  Estimated: `28786 + a * (2475 ±0)`
-		// Minimum execution time: 38_142_000 picoseconds.
 		Weight::from_parts(38_745_541, 28786)
-			// Standard Error: 2_243
 			.saturating_add(Weight::from_parts(1_114_710, 0).saturating_mul(a.into()))
 			.saturating_add(RocksDbWeight::get().reads(6_u64))
 			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(a.into())))
@@ -316,12 +309,7 @@ impl WeightInfo for () {
 	/// Proof Skipped: Assets AssetInfos (max_values: None, max_size: None, mode: Measured)
 	/// The range of component `a` is `[1, 100]`.
 	fn register_sidechain_asset(a: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `2219`
-		//  Estimated: `42374 + a * (2475 ±0)`
-		// Minimum execution time: 75_344_000 picoseconds.
 		Weight::from_parts(75_609_365, 42374)
-			// Standard Error: 2_927
 			.saturating_add(Weight::from_parts(1_134_879, 0).saturating_mul(a.into()))
 			.saturating_add(RocksDbWeight::get().reads(9_u64))
 			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(a.into())))
@@ -332,10 +320,6 @@ impl WeightInfo for () {
 	/// Storage: SubstrateBridgeApp BridgeTransferLimit (r:0 w:1)
 	/// Proof Skipped: SubstrateBridgeApp BridgeTransferLimit (max_values: Some(1), max_size: None, mode: Measured)
 	fn set_transfer_limit() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `0`
-		//  Estimated: `0`
-		// Minimum execution time: 1_890_000 picoseconds.
 		Weight::from_parts(2_070_000, 0)
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
@@ -344,10 +328,6 @@ impl WeightInfo for () {
 	/// Storage: SubstrateBridgeApp AllowedParachainAssets (r:1 w:1)
 	/// Proof Skipped: SubstrateBridgeApp AllowedParachainAssets (max_values: None, max_size: None, mode: Measured)
 	fn add_assetid_paraid() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `273`
-		//  Estimated: `5496`
-		// Minimum execution time: 8_340_000 picoseconds.
 		Weight::from_parts(8_530_000, 5496)
 			.saturating_add(RocksDbWeight::get().reads(2_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
@@ -357,23 +337,30 @@ impl WeightInfo for () {
 	/// Storage: SubstrateBridgeApp AllowedParachainAssets (r:1 w:1)
 	/// Proof Skipped: SubstrateBridgeApp AllowedParachainAssets (max_values: None, max_size: None, mode: Measured)
 	fn remove_assetid_paraid() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `365`
-		//  Estimated: `5680`
-		// Minimum execution time: 9_560_000 picoseconds.
 		Weight::from_parts(9_740_000, 5680)
 			.saturating_add(RocksDbWeight::get().reads(2_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: SubstrateBridgeApp OutboundTransfers (r:1 w:1)
+	/// Proof Skipped: SubstrateBridgeApp OutboundTransfers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeApp AssetKinds (r:1 w:0)
+	/// Proof Skipped: SubstrateBridgeApp AssetKinds (max_values: None, max_size: None, mode: Measured)
+	/// Storage: BridgeProxy LockedAssets (r:1 w:1)
+	/// Proof Skipped: BridgeProxy LockedAssets (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Technical TechAccounts (r:1 w:0)
+	/// Proof Skipped: Technical TechAccounts (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tokens Accounts (r:2 w:2)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(136), added: 2611, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:0)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
 	/// Storage: BridgeProxy Senders (r:1 w:0)
 	/// Proof Skipped: BridgeProxy Senders (max_values: None, max_size: None, mode: Measured)
+	/// Storage: BridgeProxy Transactions (r:1 w:1)
+	/// Proof Skipped: BridgeProxy Transactions (max_values: None, max_size: None, mode: Measured)
 	fn update_transaction_status() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `4`
-		//  Estimated: `2479`
-		// Minimum execution time: 6_141_000 picoseconds.
-		Weight::from_parts(6_321_000, 2479)
-			.saturating_add(RocksDbWeight::get().reads(1_u64))
+		Weight::from_parts(48_637_000, 31562)
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
 	}
 	/// Storage: SubstrateBridgeApp AssetKinds (r:1 w:0)
 	/// Proof Skipped: SubstrateBridgeApp AssetKinds (max_values: None, max_size: None, mode: Measured)
@@ -393,13 +380,11 @@ impl WeightInfo for () {
 	/// Proof Skipped: BridgeProxy Senders (max_values: None, max_size: None, mode: Measured)
 	/// Storage: BridgeProxy Transactions (r:0 w:1)
 	/// Proof Skipped: BridgeProxy Transactions (max_values: None, max_size: None, mode: Measured)
+	/// Storage: BridgePause PausedScopes (r:4 w:0)
+	/// Proof: BridgePause PausedScopes (max_values: None, max_size: Some(63), added: 2538, mode: MaxEncodedLen)
 	fn mint() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `2455`
-		//  Estimated: `37385`
-		// Minimum execution time: 54_033_000 picoseconds.
-		Weight::from_parts(54_503_000, 37385)
-			.saturating_add(RocksDbWeight::get().reads(8_u64))
+		Weight::from_parts(57_503_000, 47537)
+			.saturating_add(RocksDbWeight::get().reads(12_u64))
 			.saturating_add(RocksDbWeight::get().writes(5_u64))
 	}
 	/// Storage: SubstrateBridgeApp BridgeTransferLimit (r:1 w:0)
@@ -418,36 +403,61 @@ impl WeightInfo for () {
 	/// Proof Skipped: Technical TechAccounts (max_values: None, max_size: None, mode: Measured)
 	/// Storage: Tokens Accounts (r:2 w:2)
 	/// Proof: Tokens Accounts (max_values: None, max_size: Some(136), added: 2611, mode: MaxEncodedLen)
-	/// Storage: System Account (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
 	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: SubstrateBridgeOutboundChannel Channels (r:1 w:0)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel Channels (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel CommitmentFormats (r:1 w:0)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel CommitmentFormats (max_values: None, max_size: None, mode: Measured)
 	/// Storage: SubstrateBridgeOutboundChannel MessageQueues (r:1 w:1)
 	/// Proof Skipped: SubstrateBridgeOutboundChannel MessageQueues (max_values: None, max_size: None, mode: Measured)
 	/// Storage: SubstrateBridgeOutboundChannel ChannelNonces (r:1 w:0)
 	/// Proof Skipped: SubstrateBridgeOutboundChannel ChannelNonces (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel MessageFees (r:1 w:0)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel MessageFees (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
 	/// Storage: BridgeProxy Senders (r:0 w:1)
 	/// Proof Skipped: BridgeProxy Senders (max_values: None, max_size: None, mode: Measured)
 	/// Storage: BridgeProxy Transactions (r:0 w:1)
 	/// Proof Skipped: BridgeProxy Transactions (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeApp OutboundTransfers (r:0 w:1)
+	/// Proof Skipped: SubstrateBridgeApp OutboundTransfers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: BridgePause PausedScopes (r:7 w:0)
+	/// Proof: BridgePause PausedScopes (max_values: None, max_size: Some(63), added: 2538, mode: MaxEncodedLen)
 	fn burn() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `2322`
-		//  Estimated: `53662`
-		// Minimum execution time: 66_103_000 picoseconds.
-		Weight::from_parts(66_823_000, 53662)
-			.saturating_add(RocksDbWeight::get().reads(12_u64))
-			.saturating_add(RocksDbWeight::get().writes(7_u64))
+		Weight::from_parts(84_823_000, 81233)
+			.saturating_add(RocksDbWeight::get().reads(24_u64))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
 	}
 	/// Storage: SubstrateBridgeApp SidechainPrecision (r:1 w:0)
 	/// Proof Skipped: SubstrateBridgeApp SidechainPrecision (max_values: None, max_size: None, mode: Measured)
 	/// Storage: SubstrateBridgeApp AssetKinds (r:0 w:1)
 	/// Proof Skipped: SubstrateBridgeApp AssetKinds (max_values: None, max_size: None, mode: Measured)
 	fn finalize_asset_registration() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `240`
-		//  Estimated: `2955`
-		// Minimum execution time: 7_510_000 picoseconds.
 		Weight::from_parts(7_660_000, 2955)
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: SubstrateBridgeApp OutboundTransfers (r:1 w:1)
+	/// Proof Skipped: SubstrateBridgeApp OutboundTransfers (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeApp AssetKinds (r:1 w:0)
+	/// Proof Skipped: SubstrateBridgeApp AssetKinds (max_values: None, max_size: None, mode: Measured)
+	/// Storage: BridgeProxy LockedAssets (r:1 w:1)
+	/// Proof Skipped: BridgeProxy LockedAssets (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Technical TechAccounts (r:1 w:0)
+	/// Proof Skipped: Technical TechAccounts (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tokens Accounts (r:2 w:2)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(136), added: 2611, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:0)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: BridgeProxy Senders (r:1 w:0)
+	/// Proof Skipped: BridgeProxy Senders (max_values: None, max_size: None, mode: Measured)
+	/// Storage: BridgeProxy Transactions (r:1 w:1)
+	/// Proof Skipped: BridgeProxy Transactions (max_values: None, max_size: None, mode: Measured)
+	fn report_message_timeout() -> Weight {
+		Weight::from_parts(48_102_000, 31562)
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
 }
@@ -28,11 +28,30 @@
 // STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use bridge_types::types::AuxiliaryDigestItem;
 use bridge_types::GenericNetworkId;
 use codec::Decode;
 
-use jsonrpsee::{core::RpcResult as Result, proc_macros::rpc};
+use futures::{stream, FutureExt, StreamExt};
+use jsonrpsee::{
+    core::RpcResult as Result, proc_macros::rpc, types::SubscriptionResult, SubscriptionSink,
+};
+use sc_client_api::BlockchainEvents;
+use sc_rpc::SubscriptionTaskExecutor;
+use serde::Serialize;
 use sp_api::offchain::OffchainStorage;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::generic::BlockId;
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
+
+pub use substrate_bridge_channel_runtime_api::SubstrateBridgeChannelCommitmentsAPI as BridgeChannelCommitmentsRuntimeAPI;
+
+/// Max number of commitments returned by `bridgeChannel_commitments`.
+pub const MAX_COMMITMENTS_PER_REQUEST: u64 = 100;
 
 #[rpc(server, client)]
 pub trait BridgeChannelAPI<OffchainData> {
@@ -42,6 +61,69 @@ pub trait BridgeChannelAPI<OffchainData> {
         network_id: GenericNetworkId,
         batch_nonce: u64,
     ) -> Result<Option<OffchainData>>;
+
+    /// Commitments with batch nonces in `from..=to` which are still stored offchain.
+    #[method(name = "bridgeChannel_commitments")]
+    fn commitments(
+        &self,
+        network_id: GenericNetworkId,
+        from: u64,
+        to: u64,
+    ) -> Result<Vec<(u64, OffchainData)>>;
+
+    /// Batch nonces `(first, latest)` of the commitments stored offchain, the older ones
+    /// are pruned. `None` if no commitments are stored.
+    #[method(name = "bridgeChannel_commitmentRange")]
+    fn commitment_range(&self, network_id: GenericNetworkId) -> Result<Option<(u64, u64)>>;
+
+    /// Batch nonce of the latest commitment.
+    #[method(name = "bridgeChannel_latestCommitmentNonce")]
+    fn latest_commitment_nonce(&self, network_id: GenericNetworkId) -> Result<Option<u64>>;
+}
+
+#[rpc(server, client)]
+pub trait BridgeChannelSubscriptionAPI<OffchainData> {
+    /// Pushes the network commitments as best blocks with them are imported.
+    /// Starts from the stored commitment with `from_nonce` batch nonce if it is set,
+    /// otherwise from the next commitment.
+    ///
+    /// The stored commitments are sent in pages of [`MAX_COMMITMENTS_PER_REQUEST`].
+    /// Pruned, missing or undecodable commitments are skipped. When the best chain
+    /// is switched, the commitments of the retracted blocks are sent again once the new
+    /// best chain commits, the subscriber should replace the commitments with the same
+    /// batch nonce. Reorgs deeper than 256 blocks with commitments are not handled.
+    #[subscription(
+        name = "bridgeChannel_subscribeCommitments",
+        unsubscribe = "bridgeChannel_unsubscribeCommitments",
+        item = (u64, OffchainData)
+    )]
+    fn subscribe_commitments(&self, network_id: GenericNetworkId, from_nonce: Option<u64>);
+}
+
+fn get_offchain<S: OffchainStorage, T: Decode>(storage: &S, key: &[u8]) -> Result<Option<T>> {
+    Ok(storage
+        .get(sp_offchain::STORAGE_PREFIX, key)
+        .map(|value| Decode::decode(&mut &*value))
+        .transpose()
+        .map_err(anyhow::Error::from)?)
+}
+
+fn latest_nonce<S: OffchainStorage>(
+    storage: &S,
+    network_id: GenericNetworkId,
+) -> Result<Option<u64>> {
+    get_offchain(
+        storage,
+        &bridge_types::utils::make_offchain_latest_nonce_key(network_id),
+    )
+}
+
+fn pruned_nonce<S: OffchainStorage>(storage: &S, network_id: GenericNetworkId) -> Result<u64> {
+    Ok(get_offchain(
+        storage,
+        &bridge_types::utils::make_offchain_pruned_nonce_key(network_id),
+    )?
+    .unwrap_or_default())
 }
 
 pub struct BridgeChannelClient<S, OffchainData> {
@@ -69,12 +151,254 @@ where
         network_id: GenericNetworkId,
         batch_nonce: u64,
     ) -> Result<Option<OffchainData>> {
-        let key = bridge_types::utils::make_offchain_key(network_id, batch_nonce);
-        Ok(self
-            .storage
-            .get(sp_offchain::STORAGE_PREFIX, &key)
-            .map(|value| Decode::decode(&mut &*value))
-            .transpose()
-            .map_err(anyhow::Error::from)?)
+        get_offchain(
+            &self.storage,
+            &bridge_types::utils::make_offchain_key(network_id, batch_nonce),
+        )
+    }
+
+    fn commitments(
+        &self,
+        network_id: GenericNetworkId,
+        from: u64,
+        to: u64,
+    ) -> Result<Vec<(u64, OffchainData)>> {
+        if to < from || to - from >= MAX_COMMITMENTS_PER_REQUEST {
+            return Err(anyhow::anyhow!(
+                "Invalid range, at most {} commitments can be requested",
+                MAX_COMMITMENTS_PER_REQUEST
+            )
+            .into());
+        }
+        let mut commitments = Vec::new();
+        for batch_nonce in from..=to {
+            if let Some(commitment) = self.commitment(network_id, batch_nonce)? {
+                commitments.push((batch_nonce, commitment));
+            }
+        }
+        Ok(commitments)
+    }
+
+    fn commitment_range(&self, network_id: GenericNetworkId) -> Result<Option<(u64, u64)>> {
+        let Some(latest_nonce) = latest_nonce(&self.storage, network_id)? else {
+            return Ok(None);
+        };
+        let first_nonce = pruned_nonce(&self.storage, network_id)? + 1;
+        Ok((first_nonce <= latest_nonce).then_some((first_nonce, latest_nonce)))
+    }
+
+    fn latest_commitment_nonce(&self, network_id: GenericNetworkId) -> Result<Option<u64>> {
+        latest_nonce(&self.storage, network_id)
+    }
+}
+
+pub struct BridgeChannelSubscriptions<C, Block, S, OffchainData> {
+    client: Arc<C>,
+    storage: S,
+    executor: SubscriptionTaskExecutor,
+    _phantom: std::marker::PhantomData<(Block, OffchainData)>,
+}
+
+impl<C, Block, S, OffchainData> BridgeChannelSubscriptions<C, Block, S, OffchainData> {
+    pub fn new(client: Arc<C>, storage: S, executor: SubscriptionTaskExecutor) -> Self {
+        Self {
+            client,
+            storage,
+            executor,
+            _phantom: Default::default(),
+        }
+    }
+}
+
+/// Max number of the latest best blocks with commitments tracked by the commitments
+/// subscription, deeper reorgs are not handled.
+const MAX_TRACKED_COMMITMENT_BLOCKS: usize = 256;
+
+/// State of the commitments subscription of the network.
+struct CommitmentsSubscription<S, Number> {
+    storage: S,
+    network_id: GenericNetworkId,
+    /// Batch nonce of the next commitment to send.
+    next_nonce: u64,
+    /// Batch nonce of the latest commitment of the best chain.
+    latest_nonce: u64,
+    /// Best blocks with the network commitments and their batch nonces.
+    commitment_blocks: VecDeque<(Number, u64)>,
+}
+
+impl<S, Number> CommitmentsSubscription<S, Number>
+where
+    S: OffchainStorage,
+    Number: Copy + Ord,
+{
+    /// Reads the next page of at most [`MAX_COMMITMENTS_PER_REQUEST`] stored commitments
+    /// up to the latest one of the best chain.
+    fn next_page<OffchainData: Decode>(&mut self) -> Vec<(u64, OffchainData)> {
+        // Pruned commitments can't be sent anymore
+        if let Ok(pruned_nonce) = pruned_nonce(&self.storage, self.network_id) {
+            self.next_nonce = self.next_nonce.max(pruned_nonce + 1);
+        }
+        let mut commitments = Vec::new();
+        while self.next_nonce <= self.latest_nonce
+            && (commitments.len() as u64) < MAX_COMMITMENTS_PER_REQUEST
+        {
+            let key = bridge_types::utils::make_offchain_key(self.network_id, self.next_nonce);
+            // The missing commitments are skipped, so they don't stall the subscription
+            match get_offchain::<_, OffchainData>(&self.storage, &key) {
+                Ok(Some(commitment)) => commitments.push((self.next_nonce, commitment)),
+                Ok(None) => log::warn!(
+                    "Commitment {:?} {} is not found in offchain storage, skipping",
+                    self.network_id,
+                    self.next_nonce
+                ),
+                Err(err) => log::warn!(
+                    "Failed to decode commitment {:?} {}, skipping: {:?}",
+                    self.network_id,
+                    self.next_nonce,
+                    err
+                ),
+            }
+            self.next_nonce += 1;
+        }
+        commitments
+    }
+
+    /// Tracks the new best block, `reorg_base` is the last common block with the previous
+    /// best chain if the chain is switched, `commitment_nonce` is the batch nonce committed
+    /// in the block.
+    fn on_new_best(
+        &mut self,
+        number: Number,
+        reorg_base: Option<Number>,
+        commitment_nonce: Option<u64>,
+    ) {
+        if let Some(reorg_base) = reorg_base {
+            // Commitments of the retracted blocks are sent again from the new best chain
+            while let Some(&(block_number, batch_nonce)) = self.commitment_blocks.back() {
+                if block_number <= reorg_base {
+                    break;
+                }
+                self.commitment_blocks.pop_back();
+                self.next_nonce = self.next_nonce.min(batch_nonce);
+                self.latest_nonce = batch_nonce.saturating_sub(1);
+            }
+        }
+        let Some(batch_nonce) = commitment_nonce else {
+            return;
+        };
+        self.latest_nonce = batch_nonce;
+        self.commitment_blocks.push_back((number, batch_nonce));
+        if self.commitment_blocks.len() > MAX_TRACKED_COMMITMENT_BLOCKS {
+            self.commitment_blocks.pop_front();
+        }
+    }
+}
+
+/// Batch nonce of the latest network commitment in the state of the block. The nonce is read
+/// from the block state, the offchain latest nonce is overwritten by the fork blocks.
+fn block_commitment_nonce<C, Block>(
+    client: &C,
+    hash: Block::Hash,
+    network_id: GenericNetworkId,
+) -> Option<u64>
+where
+    Block: BlockT,
+    C: ProvideRuntimeApi<Block>,
+    C::Api: BridgeChannelCommitmentsRuntimeAPI<Block>,
+{
+    let GenericNetworkId::Sub(sub_network_id) = network_id else {
+        return None;
+    };
+    client
+        .runtime_api()
+        .latest_commitment_nonce(&BlockId::hash(hash), sub_network_id)
+        .map_err(|err| {
+            log::warn!(
+                "Failed to read latest commitment nonce {:?} at {:?}: {:?}",
+                network_id,
+                hash,
+                err
+            )
+        })
+        .ok()
+}
+
+fn has_commitment<Header: HeaderT>(header: &Header, network_id: GenericNetworkId) -> bool {
+    header.digest().logs().iter().any(|log| {
+        matches!(
+            AuxiliaryDigestItem::try_from(log.clone()),
+            Ok(AuxiliaryDigestItem::Commitment(id, _)) if id == network_id
+        )
+    })
+}
+
+impl<C, Block, S, OffchainData> BridgeChannelSubscriptionAPIServer<OffchainData>
+    for BridgeChannelSubscriptions<C, Block, S, OffchainData>
+where
+    C: BlockchainEvents<Block> + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C: Send + Sync + 'static,
+    C::Api: BridgeChannelCommitmentsRuntimeAPI<Block>,
+    Block: BlockT,
+    S: OffchainStorage + Clone + 'static,
+    OffchainData: Decode + Serialize + 'static + Send + Sync,
+{
+    fn subscribe_commitments(
+        &self,
+        mut sink: SubscriptionSink,
+        network_id: GenericNetworkId,
+        from_nonce: Option<u64>,
+    ) -> SubscriptionResult {
+        let latest_nonce =
+            block_commitment_nonce(&*self.client, self.client.info().best_hash, network_id)
+                .unwrap_or_default();
+        let subscription = CommitmentsSubscription {
+            storage: self.storage.clone(),
+            network_id,
+            next_nonce: from_nonce.unwrap_or(latest_nonce + 1),
+            latest_nonce,
+            commitment_blocks: VecDeque::new(),
+        };
+        let notifications = self.client.import_notification_stream();
+        let client = self.client.clone();
+
+        // Stored commitments are read page by page as the subscriber receives them,
+        // then the subscription waits for the new best blocks with commitments
+        let commitments = stream::unfold(
+            (subscription, notifications, client),
+            |(mut subscription, mut notifications, client)| async move {
+                loop {
+                    let page = subscription.next_page::<OffchainData>();
+                    if !page.is_empty() {
+                        return Some((stream::iter(page), (subscription, notifications, client)));
+                    }
+                    let notification = notifications.next().await?;
+                    if !notification.is_new_best {
+                        continue;
+                    }
+                    let network_id = subscription.network_id;
+                    let commitment_nonce = if has_commitment(&notification.header, network_id) {
+                        block_commitment_nonce(&*client, notification.hash, network_id)
+                    } else {
+                        None
+                    };
+                    subscription.on_new_best(
+                        *notification.header.number(),
+                        notification
+                            .tree_route
+                            .as_ref()
+                            .map(|tree_route| tree_route.common_block().number),
+                        commitment_nonce,
+                    );
+                }
+            },
+        )
+        .flatten();
+
+        let fut = async move {
+            sink.pipe_from_stream(commitments).await;
+        };
+        self.executor
+            .spawn("bridge-channel-rpc-subscription", Some("rpc"), fut.boxed());
+        Ok(())
     }
 }
@@ -40,6 +40,20 @@ const BASE_NETWORK_ID: SubNetworkId = SubNetworkId::Mainnet;
 #[allow(unused_imports)]
 use crate::inbound::Pallet as BridgeInboundChannel;
 
+/// Marks `count` single message batches starting from `first_nonce` as delivered.
+fn deliver_batches<T: Config>(first_nonce: u64, count: u32) {
+    for batch_nonce in first_nonce..first_nonce + count as u64 {
+        let mut delivered = BitField::with_zeroes(1);
+        delivered.set(0);
+        DeliveredMessages::<T>::insert(BASE_NETWORK_ID, batch_nonce, delivered);
+        DispatchResults::<T>::insert(
+            BASE_NETWORK_ID,
+            batch_nonce,
+            vec![MessageDispatchResult::Dispatched],
+        );
+    }
+}
+
 // This collection of benchmarks should include a benchmark for each
 // call dispatched by the channel, i.e. each "app" pallet function
 // that can be invoked by MessageDispatch. The most expensive call
@@ -51,7 +65,9 @@ benchmarks! {
     // Benchmark `submit` extrinsic under worst case conditions:
     // * `submit` dispatches the DotApp::unlock call
     // * `unlock` call successfully unlocks DOT
+    // * `b` next batches are delivered message by message, so the channel nonce moves over them
     submit {
+        let b in 0 .. T::MaxBatchesAhead::get() - 1;
         let messages = vec![];
         let commitment = bridge_types::GenericCommitment::Sub(
             bridge_types::substrate::Commitment {
@@ -59,10 +75,62 @@ benchmarks! {
                 nonce: 1u64,
             }
         );
+        Channels::<T>::insert(BASE_NETWORK_ID, ChannelState::Active);
+        deliver_batches::<T>(2, b);
         let proof = T::Verifier::valid_proof().unwrap();
     }: _(RawOrigin::None, BASE_NETWORK_ID, commitment, proof)
     verify {
-        assert_eq!(1, <ChannelNonces<T>>::get(BASE_NETWORK_ID));
+        assert_eq!(1 + b as u64, <ChannelNonces<T>>::get(BASE_NETWORK_ID));
+    }
+
+    // Benchmark `submit_message` extrinsic for the message which completes the batch,
+    // `b - 1` next batches are already delivered.
+    submit_message {
+        let b in 1 .. T::MaxBatchesAhead::get();
+        let message = BridgeMessageV2 {
+            payload: vec![0u8; T::MaxMessagePayloadSize::get() as usize].try_into().unwrap(),
+            timepoint: Default::default(),
+            deadline: None,
+        };
+        let message_proof = MessageProof {
+            nonce: 1,
+            messages_count: 1,
+            proof: bridge_common::simplified_proof::Proof {
+                order: 0,
+                items: vec![],
+            },
+        };
+        Channels::<T>::insert(BASE_NETWORK_ID, ChannelState::Active);
+        deliver_batches::<T>(2, b - 1);
+        let proof = T::Verifier::valid_proof().unwrap();
+    }: _(RawOrigin::None, BASE_NETWORK_ID, message, message_proof, proof)
+    verify {
+        assert_eq!(b as u64, <ChannelNonces<T>>::get(BASE_NETWORK_ID));
+        assert!(!DispatchResults::<T>::contains_key(BASE_NETWORK_ID, 1));
+    }
+
+    register_channel {
+        let network_id = SubNetworkId::Custom(1);
+    }: _(RawOrigin::Root, network_id)
+    verify {
+        assert_eq!(Channels::<T>::get(network_id), Some(ChannelState::Registered));
+    }
+
+    open_channel {
+        let network_id = SubNetworkId::Custom(1);
+        Channels::<T>::insert(network_id, ChannelState::Registered);
+    }: _(RawOrigin::Root, network_id)
+    verify {
+        assert_eq!(Channels::<T>::get(network_id), Some(ChannelState::Active));
+    }
+
+    // Benchmark `close_channel` with undelivered batches, so the channel is drained.
+    close_channel {
+        let network_id = SubNetworkId::Custom(1);
+        Channels::<T>::insert(network_id, ChannelState::Active);
+    }: _(RawOrigin::Root, network_id, 1)
+    verify {
+        assert_eq!(Channels::<T>::get(network_id), Some(ChannelState::Draining));
     }
 }
 
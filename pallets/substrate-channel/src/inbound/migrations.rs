@@ -0,0 +1,84 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use super::*;
+use frame_support::log::info;
+use frame_support::traits::{
+    GetStorageVersion, OnRuntimeUpgrade, PalletInfoAccess, StorageVersion,
+};
+use frame_support::weights::Weight;
+use sp_std::marker::PhantomData;
+
+pub mod v2 {
+    use super::*;
+
+    /// Registers the channels which were created implicitly by the first message.
+    ///
+    /// All networks known to the channel are opened, so they keep accepting messages.
+    pub struct MigrateToV2<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+        fn on_runtime_upgrade() -> Weight {
+            if Pallet::<T>::on_chain_storage_version() != 1 {
+                info!(
+                    "{}: skip migration to v2, storage version is not 1",
+                    <Pallet<T> as PalletInfoAccess>::name()
+                );
+                return T::DbWeight::get().reads(1);
+            }
+            let mut reads = 1;
+            let mut writes = 1;
+            for network_id in ChannelNonces::<T>::iter_keys() {
+                reads += 2;
+                if !Channels::<T>::contains_key(network_id) {
+                    Channels::<T>::insert(network_id, ChannelState::Active);
+                    writes += 1;
+                }
+            }
+            StorageVersion::new(2).put::<Pallet<T>>();
+            info!(
+                "{}: migrated to v2",
+                <Pallet<T> as PalletInfoAccess>::name()
+            );
+            T::DbWeight::get().reads_writes(reads, writes)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(_state: sp_std::vec::Vec<u8>) -> Result<(), &'static str> {
+            for network_id in ChannelNonces::<T>::iter_keys() {
+                frame_support::ensure!(
+                    Channels::<T>::contains_key(network_id),
+                    "Channel is not registered"
+                );
+            }
+            Ok(())
+        }
+    }
+}
@@ -30,15 +30,24 @@
 
 //! Channel for passing messages from ethereum to substrate.
 
-use bridge_types::traits::{MessageDispatch, Verifier};
-use bridge_types::types::MessageId;
-use bridge_types::SubNetworkId;
+use bridge_common::bitfield::BitField;
+use bridge_common::merkle_proof::MessageProof;
+use bridge_types::substrate::{
+    BridgeCall, BridgeMessageV2, MessageDispatchResult, OutboundChannelCall,
+    SubstrateBridgeMessageEncode,
+};
+use bridge_types::traits::{
+    BridgePauseChecker, MessageDispatch, MessageTimeoutReporter, OutboundChannel, Verifier,
+};
+use bridge_types::types::{ChannelState, MessageDirection, MessageId};
+use bridge_types::{SubNetworkId, H256};
 use frame_support::dispatch::DispatchResult;
 use frame_support::traits::Get;
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod migrations;
 pub mod weights;
 pub use weights::WeightInfo;
 
@@ -56,6 +65,7 @@ pub mod pallet {
     use frame_support::traits::StorageVersion;
     use frame_support::weights::Weight;
     use frame_system::pallet_prelude::*;
+    use sp_runtime::traits::UniqueSaturatedInto;
     use sp_std::prelude::*;
 
     #[pallet::config]
@@ -85,15 +95,58 @@ pub mod pallet {
         /// Max number of messages that can be queued and committed in one go for a given channel.
         type MaxMessagesPerCommit: Get<u32>;
 
+        /// Max number of batches ahead of the channel nonce which messages can be submitted
+        /// one by one. Bounds the number of batches delivered by a single call.
+        #[pallet::constant]
+        type MaxBatchesAhead: Get<u32>;
+
+        /// Checks whether the bridge messages are paused.
+        type BridgePause: BridgePauseChecker;
+
+        /// Reports the messages rejected because of the expired deadline to the source.
+        type TimeoutReporter: MessageTimeoutReporter<SubNetworkId>;
+
+        /// Channel to send the delivery reports back to the source networks.
+        type OutboundChannel: OutboundChannel<SubNetworkId, Self::AccountId, ()>;
+
         /// Weight information for extrinsics in this pallet
         type WeightInfo: WeightInfo;
     }
 
+    /// Nonce of the last batch which messages are all dispatched.
     #[pallet::storage]
     pub type ChannelNonces<T: Config> = StorageMap<_, Identity, SubNetworkId, u64, ValueQuery>;
 
+    /// Messages of the Merkle commitments submitted one by one, by batch nonce.
+    /// Removed when the batch and all previous ones are fully dispatched.
+    #[pallet::storage]
+    pub type DeliveredMessages<T: Config> =
+        StorageDoubleMap<_, Identity, SubNetworkId, Twox64Concat, u64, BitField, OptionQuery>;
+
+    /// Dispatch results of the Merkle commitment messages submitted one by one, by batch nonce.
+    /// Removed when the batch delivery is reported to the source.
+    #[pallet::storage]
+    pub type DispatchResults<T: Config> = StorageDoubleMap<
+        _,
+        Identity,
+        SubNetworkId,
+        Twox64Concat,
+        u64,
+        Vec<MessageDispatchResult>,
+        OptionQuery,
+    >;
+
+    /// Registered channels, messages are accepted only from the active ones.
+    #[pallet::storage]
+    #[pallet::getter(fn channel_state)]
+    pub type Channels<T: Config> = StorageMap<_, Identity, SubNetworkId, ChannelState, OptionQuery>;
+
+    /// Nonce of the last batch accepted from the draining channel.
+    #[pallet::storage]
+    pub type DrainingNonces<T: Config> = StorageMap<_, Identity, SubNetworkId, u64, OptionQuery>;
+
     /// The current storage version.
-    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
 
     #[pallet::pallet]
     #[pallet::generate_store(pub(super) trait Store)]
@@ -105,9 +158,33 @@ pub mod pallet {
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
 
     #[pallet::event]
-    // #[pallet::generate_deposit(pub(super) fn deposit_event)]
-    // This pallet don't have events
-    pub enum Event<T: Config> {}
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        ChannelRegistered {
+            network_id: SubNetworkId,
+        },
+        ChannelOpened {
+            network_id: SubNetworkId,
+        },
+        /// The channel accepts only the batches up to `last_batch_nonce`.
+        ChannelDraining {
+            network_id: SubNetworkId,
+            last_batch_nonce: u64,
+        },
+        ChannelClosed {
+            network_id: SubNetworkId,
+        },
+        /// The message is not dispatched because its deadline is expired.
+        MessageExpired {
+            network_id: SubNetworkId,
+            message_id: H256,
+        },
+        /// All messages of the batch are delivered.
+        BatchDelivered {
+            network_id: SubNetworkId,
+            batch_nonce: u64,
+        },
+    }
 
     #[pallet::error]
     pub enum Error<T> {
@@ -121,14 +198,47 @@ pub mod pallet {
         InvalidNonce,
         /// Incorrect reward fraction
         InvalidRewardFraction,
-        /// This contract already exists
+        /// This channel already exists
         ContractExists,
         /// Call encoding failed.
         CallEncodeFailed,
+        /// Message inclusion proof doesn't match the commitment.
+        InvalidMessageProof,
+        /// Message is already dispatched.
+        MessageAlreadyDelivered,
+        /// The channel is not registered.
+        ChannelNotRegistered,
+        /// The channel doesn't accept messages.
+        ChannelNotActive,
+        /// The channel state doesn't allow the operation.
+        InvalidChannelState,
+        /// The batch is too far ahead of the channel nonce.
+        BatchTooFarAhead,
     }
 
     impl<T: Config> Pallet<T> {
-        fn submit_weight(
+        /// Weight of `submit` which moves the channel nonce over `batches` batches
+        /// previously delivered message by message. The delivery of the submitted batch
+        /// and of every advanced batch is reported.
+        fn submit_weight(dispatch_weight: Weight, batches: u32) -> Weight {
+            <T as Config>::WeightInfo::submit(batches)
+                .saturating_add(dispatch_weight)
+                .saturating_add(
+                    T::OutboundChannel::submit_weight()
+                        .saturating_mul(u64::from(batches).saturating_add(1)),
+                )
+        }
+
+        /// Weight of `submit_message` which moves the channel nonce over `batches` batches,
+        /// the delivery of every advanced batch is reported.
+        fn submit_message_weight(dispatch_weight: Weight, batches: u32) -> Weight {
+            <T as Config>::WeightInfo::submit_message(batches)
+                .saturating_add(dispatch_weight)
+                .saturating_add(T::OutboundChannel::submit_weight().saturating_mul(batches.into()))
+        }
+
+        /// Weight of the commitment verification and its messages dispatch.
+        fn commitment_dispatch_weight(
             commitment: &bridge_types::GenericCommitment<
                 T::MaxMessagesPerCommit,
                 T::MaxMessagePayloadSize,
@@ -139,25 +249,222 @@ pub mod pallet {
                 bridge_types::GenericCommitment::EVM(_) => {
                     <T as frame_system::Config>::BlockWeights::get().max_block
                 }
+                _ => Self::messages_weight(&Self::commitment_messages(commitment)),
+            };
+
+            commitment_weight.saturating_add(T::Verifier::verify_weight(proof))
+        }
+
+        /// Weight of the message inclusion verification and its dispatch.
+        fn message_dispatch_weight(
+            message: &BridgeMessageV2<T::MaxMessagePayloadSize>,
+            proof: &<T::Verifier as Verifier>::Proof,
+        ) -> Weight {
+            Self::message_weight(message).saturating_add(T::Verifier::verify_weight(proof))
+        }
+
+        fn messages_weight(messages: &[BridgeMessageV2<T::MaxMessagePayloadSize>]) -> Weight {
+            messages
+                .iter()
+                .map(Self::message_weight)
+                .fold(Weight::zero(), |acc, w| acc.saturating_add(w))
+        }
+
+        /// Weight of the message dispatch, or of the timeout report if the message is expired.
+        fn message_weight(message: &BridgeMessageV2<T::MaxMessagePayloadSize>) -> Weight {
+            let dispatch_weight = T::MessageDispatch::dispatch_weight(&message.payload);
+            if message.deadline.is_some() {
+                dispatch_weight.max(T::TimeoutReporter::report_timeout_weight())
+            } else {
+                dispatch_weight
+            }
+        }
+
+        fn is_expired(message: &BridgeMessageV2<T::MaxMessagePayloadSize>) -> bool {
+            let Some(deadline) = message.deadline else {
+                return false;
+            };
+            let now: u64 = pallet_timestamp::Pallet::<T>::now().unique_saturated_into();
+            now > deadline
+        }
+
+        fn is_delivery_report(message: &BridgeMessageV2<T::MaxMessagePayloadSize>) -> bool {
+            matches!(
+                BridgeCall::decode(&mut &message.payload[..]),
+                Ok(BridgeCall::OutboundChannel(_))
+            )
+        }
+
+        fn dispatch_message(
+            network_id: SubNetworkId,
+            batch_nonce: u64,
+            idx: u64,
+            message: BridgeMessageV2<T::MaxMessagePayloadSize>,
+        ) -> MessageDispatchResult {
+            let message_id =
+                MessageId::batched(network_id.into(), T::ThisNetworkId::get(), batch_nonce, idx);
+            if Self::is_expired(&message) {
+                let message_id = message_id.hash();
+                Self::deposit_event(Event::MessageExpired {
+                    network_id,
+                    message_id,
+                });
+                if let Err(err) = T::TimeoutReporter::report_timeout(network_id, message_id) {
+                    warn!("Failed to report timeout of {:?}: {:?}", message_id, err);
+                }
+                return MessageDispatchResult::Expired;
+            }
+            let is_delivery_report = Self::is_delivery_report(&message);
+            let result = T::MessageDispatch::dispatch(
+                network_id,
+                message_id,
+                message.timepoint,
+                &message.payload,
+                (),
+            );
+            match result {
+                _ if is_delivery_report => MessageDispatchResult::DeliveryReport,
+                Ok(()) => MessageDispatchResult::Dispatched,
+                Err(_) => MessageDispatchResult::Failed,
+            }
+        }
+
+        /// Sends the dispatch results of the delivered batch back to the source network.
+        fn report_delivery(
+            network_id: SubNetworkId,
+            batch_nonce: u64,
+            results: Vec<MessageDispatchResult>,
+        ) {
+            Self::deposit_event(Event::BatchDelivered {
+                network_id,
+                batch_nonce,
+            });
+            // Batches of the delivery reports are not reported back,
+            // otherwise the networks would exchange the reports endlessly
+            if results
+                .iter()
+                .all(|result| *result == MessageDispatchResult::DeliveryReport)
+            {
+                return;
+            }
+            let payload = OutboundChannelCall::ReportDelivery {
+                batch_nonce,
+                results,
+            }
+            .prepare_message();
+            if let Err(err) =
+                T::OutboundChannel::submit(network_id, &frame_system::RawOrigin::Root, &payload, ())
+            {
+                warn!(
+                    "Failed to report delivery of batch {} to {:?}: {:?}",
+                    batch_nonce, network_id, err
+                );
+            }
+        }
+
+        fn ensure_inbound_not_paused(network_id: SubNetworkId) -> DispatchResult {
+            T::BridgePause::ensure_not_paused(network_id.into(), MessageDirection::Inbound, None)
+        }
+
+        /// Checks that the messages aren't paused before the batch nonce is consumed,
+        /// so the paused messages are submitted again after the pause.
+        fn ensure_messages_not_paused<'a>(
+            network_id: SubNetworkId,
+            messages: impl IntoIterator<Item = &'a BridgeMessageV2<T::MaxMessagePayloadSize>>,
+        ) -> DispatchResult {
+            messages.into_iter().try_for_each(|message| {
+                T::MessageDispatch::ensure_not_paused(network_id, &message.payload)
+            })
+        }
+
+        /// Messages of the commitment, the messages of the commitments
+        /// without deadlines are never expired.
+        fn commitment_messages(
+            commitment: &bridge_types::GenericCommitment<
+                T::MaxMessagesPerCommit,
+                T::MaxMessagePayloadSize,
+            >,
+        ) -> Vec<BridgeMessageV2<T::MaxMessagePayloadSize>> {
+            match commitment {
                 bridge_types::GenericCommitment::Sub(commitment) => commitment
                     .messages
                     .iter()
-                    .map(|m| T::MessageDispatch::dispatch_weight(&m.payload))
-                    .fold(Weight::zero(), |acc, w| acc.saturating_add(w)),
-            };
+                    .cloned()
+                    .map(Into::into)
+                    .collect(),
+                bridge_types::GenericCommitment::SubMerkle(commitment) => commitment
+                    .messages
+                    .iter()
+                    .cloned()
+                    .map(Into::into)
+                    .collect(),
+                bridge_types::GenericCommitment::SubV2(commitment) => commitment.messages.to_vec(),
+                bridge_types::GenericCommitment::SubMerkleV2(commitment) => {
+                    commitment.messages.to_vec()
+                }
+                bridge_types::GenericCommitment::EVM(_) => Vec::new(),
+            }
+        }
 
-            let proof_weight = T::Verifier::verify_weight(proof);
+        /// Checks that the channel accepts messages of the batch.
+        fn ensure_channel_accepts(network_id: SubNetworkId, batch_nonce: u64) -> DispatchResult {
+            match Channels::<T>::get(network_id) {
+                Some(ChannelState::Active) => Ok(()),
+                Some(ChannelState::Draining)
+                    if DrainingNonces::<T>::get(network_id)
+                        .map_or(false, |last_nonce| batch_nonce <= last_nonce) =>
+                {
+                    Ok(())
+                }
+                Some(_) => Err(Error::<T>::ChannelNotActive.into()),
+                None => Err(Error::<T>::ChannelNotRegistered.into()),
+            }
+        }
+
+        /// Closes the draining channel when all batches up to the last one are delivered.
+        fn close_drained_channel(network_id: SubNetworkId) {
+            let Some(last_nonce) = DrainingNonces::<T>::get(network_id) else {
+                return;
+            };
+            if ChannelNonces::<T>::get(network_id) >= last_nonce {
+                DrainingNonces::<T>::remove(network_id);
+                Channels::<T>::insert(network_id, ChannelState::Closed);
+                Self::deposit_event(Event::ChannelClosed { network_id });
+            }
+        }
 
-            <T as Config>::WeightInfo::submit()
-                .saturating_add(commitment_weight)
-                .saturating_add(proof_weight)
+        /// Moves channel nonce over the batches which messages are all delivered.
+        /// Returns the number of batches passed, at most `MaxBatchesAhead` as the messages
+        /// of further batches are not accepted.
+        fn advance_nonce(network_id: SubNetworkId) -> u32 {
+            let mut nonce = ChannelNonces::<T>::get(network_id);
+            let mut batches = 0;
+            while batches < T::MaxBatchesAhead::get() {
+                let Some(delivered) = DeliveredMessages::<T>::get(network_id, nonce + 1) else {
+                    break;
+                };
+                if delivered.count_set_bits() < delivered.len() {
+                    break;
+                }
+                batches += 1;
+                DeliveredMessages::<T>::remove(network_id, nonce + 1);
+                nonce += 1;
+                let results = DispatchResults::<T>::take(network_id, nonce).unwrap_or_default();
+                Self::report_delivery(network_id, nonce, results);
+            }
+            ChannelNonces::<T>::insert(network_id, nonce);
+            Self::close_drained_channel(network_id);
+            batches
         }
     }
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         #[pallet::call_index(0)]
-        #[pallet::weight(Pallet::<T>::submit_weight(commitment, proof))]
+        #[pallet::weight(Pallet::<T>::submit_weight(
+            Pallet::<T>::commitment_dispatch_weight(commitment, proof),
+            T::MaxBatchesAhead::get(),
+        ))]
         pub fn submit(
             origin: OriginFor<T>,
             network_id: SubNetworkId,
@@ -168,15 +475,27 @@ pub mod pallet {
             proof: <T::Verifier as Verifier>::Proof,
         ) -> DispatchResultWithPostInfo {
             ensure_none(origin)?;
+            Self::ensure_inbound_not_paused(network_id)?;
+            let messages = Self::commitment_messages(&commitment);
+            Self::ensure_messages_not_paused(network_id, &messages)?;
+            Self::ensure_channel_accepts(network_id, commitment.nonce())?;
+            ensure!(
+                !matches!(commitment, bridge_types::GenericCommitment::EVM(_)),
+                Error::<T>::InvalidCommitment
+            );
+            let dispatch_weight = Self::commitment_dispatch_weight(&commitment, &proof);
             let commitment_hash = commitment.hash();
-            let bridge_types::GenericCommitment::Sub(sub_commitment) = commitment else {
-                frame_support::fail!(Error::<T>::InvalidCommitment);
-            };
+            let batch_nonce = commitment.nonce();
+            // Messages of the batch can't be submitted both ways
+            ensure!(
+                !DeliveredMessages::<T>::contains_key(network_id, batch_nonce),
+                Error::<T>::InvalidNonce
+            );
             // submit commitment to verifier for verification
             T::Verifier::verify(network_id.into(), commitment_hash, &proof)?;
             // Verify batch nonce
             <ChannelNonces<T>>::try_mutate(network_id, |nonce| -> DispatchResult {
-                if sub_commitment.nonce != *nonce + 1 {
+                if batch_nonce != *nonce + 1 {
                     Err(Error::<T>::InvalidNonce.into())
                 } else {
                     *nonce += 1;
@@ -184,22 +503,160 @@ pub mod pallet {
                 }
             })?;
 
-            for (idx, message) in sub_commitment.messages.into_iter().enumerate() {
-                let message_id = MessageId::batched(
-                    network_id.into(),
-                    T::ThisNetworkId::get(),
-                    sub_commitment.nonce,
-                    idx as u64,
-                );
-                T::MessageDispatch::dispatch(
-                    network_id,
-                    message_id,
-                    message.timepoint,
-                    &message.payload,
-                    (),
+            let results = messages
+                .into_iter()
+                .enumerate()
+                .map(|(idx, message)| {
+                    Self::dispatch_message(network_id, batch_nonce, idx as u64, message)
+                })
+                .collect();
+            Self::report_delivery(network_id, batch_nonce, results);
+            let batches = Self::advance_nonce(network_id);
+            Ok(Some(Self::submit_weight(dispatch_weight, batches)).into())
+        }
+
+        /// Submits a single message of [`bridge_types::substrate::MerkleCommitment`]
+        /// or [`bridge_types::substrate::MerkleCommitmentV2`] with its inclusion proof.
+        /// The messages of [`bridge_types::substrate::MerkleCommitment`] are submitted
+        /// without deadline.
+        #[pallet::call_index(1)]
+        #[pallet::weight(Pallet::<T>::submit_message_weight(
+            Pallet::<T>::message_dispatch_weight(message, proof),
+            T::MaxBatchesAhead::get(),
+        ))]
+        pub fn submit_message(
+            origin: OriginFor<T>,
+            network_id: SubNetworkId,
+            message: BridgeMessageV2<T::MaxMessagePayloadSize>,
+            message_proof: MessageProof,
+            proof: <T::Verifier as Verifier>::Proof,
+        ) -> DispatchResultWithPostInfo {
+            ensure_none(origin)?;
+            Self::ensure_inbound_not_paused(network_id)?;
+            Self::ensure_messages_not_paused(network_id, [&message])?;
+            let batch_nonce = message_proof.nonce;
+            Self::ensure_channel_accepts(network_id, batch_nonce)?;
+            let channel_nonce = ChannelNonces::<T>::get(network_id);
+            ensure!(batch_nonce > channel_nonce, Error::<T>::InvalidNonce);
+            ensure!(
+                batch_nonce <= channel_nonce.saturating_add(T::MaxBatchesAhead::get().into()),
+                Error::<T>::BatchTooFarAhead
+            );
+            let dispatch_weight = Self::message_dispatch_weight(&message, &proof);
+            ensure!(
+                message_proof.messages_count <= T::MaxMessagesPerCommit::get(),
+                Error::<T>::InvalidMessageProof
+            );
+            let commitment_hash = message_proof
+                .commitment_hash(&message)
+                .ok_or(Error::<T>::InvalidMessageProof)?;
+            // submit commitment to verifier for verification
+            T::Verifier::verify(network_id.into(), commitment_hash, &proof)?;
+
+            let idx = message_proof.message_index();
+            DeliveredMessages::<T>::try_mutate(network_id, batch_nonce, |delivered| {
+                let delivered = delivered.get_or_insert_with(|| {
+                    BitField::with_zeroes(message_proof.messages_count as usize)
+                });
+                ensure!(
+                    !delivered.is_set(idx as usize),
+                    Error::<T>::MessageAlreadyDelivered
                 );
+                delivered.set(idx as usize);
+                Ok::<_, Error<T>>(())
+            })?;
+
+            let result = Self::dispatch_message(network_id, batch_nonce, idx, message);
+            DispatchResults::<T>::mutate(network_id, batch_nonce, |results| {
+                // Results of the undelivered messages are overwritten on their delivery
+                let results = results.get_or_insert_with(|| {
+                    vec![MessageDispatchResult::Failed; message_proof.messages_count as usize]
+                });
+                if let Some(slot) = results.get_mut(idx as usize) {
+                    *slot = result;
+                }
+            });
+            let batches = Self::advance_nonce(network_id);
+            Ok(Some(Self::submit_message_weight(dispatch_weight, batches)).into())
+        }
+
+        /// Registers the channel with the network, it accepts messages after it is opened.
+        #[pallet::call_index(2)]
+        #[pallet::weight(<T as Config>::WeightInfo::register_channel())]
+        pub fn register_channel(origin: OriginFor<T>, network_id: SubNetworkId) -> DispatchResult {
+            ensure_root(origin)?;
+            ensure!(
+                !Channels::<T>::contains_key(network_id),
+                Error::<T>::ContractExists
+            );
+            Channels::<T>::insert(network_id, ChannelState::Registered);
+            Self::deposit_event(Event::ChannelRegistered { network_id });
+            Ok(())
+        }
+
+        /// Opens the registered or closed channel for messages.
+        #[pallet::call_index(3)]
+        #[pallet::weight(<T as Config>::WeightInfo::open_channel())]
+        pub fn open_channel(origin: OriginFor<T>, network_id: SubNetworkId) -> DispatchResult {
+            ensure_root(origin)?;
+            match Channels::<T>::get(network_id) {
+                Some(ChannelState::Registered | ChannelState::Closed) => {}
+                Some(_) => frame_support::fail!(Error::<T>::InvalidChannelState),
+                None => frame_support::fail!(Error::<T>::ChannelNotRegistered),
+            }
+            Channels::<T>::insert(network_id, ChannelState::Active);
+            Self::deposit_event(Event::ChannelOpened { network_id });
+            Ok(())
+        }
+
+        /// Stops accepting messages from the channel after the batch `last_batch_nonce`,
+        /// which is the last batch committed by the closed outbound channel.
+        /// The channel is closed when all batches up to it are delivered.
+        #[pallet::call_index(4)]
+        #[pallet::weight(<T as Config>::WeightInfo::close_channel())]
+        pub fn close_channel(
+            origin: OriginFor<T>,
+            network_id: SubNetworkId,
+            last_batch_nonce: u64,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            match Channels::<T>::get(network_id) {
+                Some(ChannelState::Registered | ChannelState::Active) => {}
+                Some(_) => frame_support::fail!(Error::<T>::InvalidChannelState),
+                None => frame_support::fail!(Error::<T>::ChannelNotRegistered),
+            }
+            Channels::<T>::insert(network_id, ChannelState::Draining);
+            DrainingNonces::<T>::insert(network_id, last_batch_nonce);
+            Self::deposit_event(Event::ChannelDraining {
+                network_id,
+                last_batch_nonce,
+            });
+            Self::close_drained_channel(network_id);
+            Ok(())
+        }
+    }
+
+    #[pallet::genesis_config]
+    pub struct GenesisConfig {
+        /// Networks with the channels opened at genesis.
+        pub channels: Vec<SubNetworkId>,
+    }
+
+    #[cfg(feature = "std")]
+    impl Default for GenesisConfig {
+        fn default() -> Self {
+            Self {
+                channels: Default::default(),
+            }
+        }
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> GenesisBuild<T> for GenesisConfig {
+        fn build(&self) {
+            for network_id in &self.channels {
+                Channels::<T>::insert(network_id, ChannelState::Active);
             }
-            Ok(().into())
         }
     }
 
@@ -214,6 +671,15 @@ pub mod pallet {
                 proof,
             } = call
             {
+                Self::ensure_inbound_not_paused(*network_id)
+                    .map_err(|_| InvalidTransaction::Call)?;
+                Self::ensure_messages_not_paused(
+                    *network_id,
+                    &Self::commitment_messages(commitment),
+                )
+                .map_err(|_| InvalidTransaction::Call)?;
+                Self::ensure_channel_accepts(*network_id, commitment.nonce())
+                    .map_err(|_| InvalidTransaction::Call)?;
                 let nonce = ChannelNonces::<T>::get(network_id);
                 // If messages array is empty or messages already submitted
                 if commitment.nonce() <= nonce {
@@ -230,6 +696,41 @@ pub mod pallet {
                     .and_provides((network_id, commitment_hash))
                     .propagate(true)
                     .build()
+            } else if let Call::submit_message {
+                network_id,
+                message,
+                message_proof,
+                proof,
+            } = call
+            {
+                Self::ensure_inbound_not_paused(*network_id)
+                    .map_err(|_| InvalidTransaction::Call)?;
+                Self::ensure_messages_not_paused(*network_id, [message])
+                    .map_err(|_| InvalidTransaction::Call)?;
+                let batch_nonce = message_proof.nonce;
+                Self::ensure_channel_accepts(*network_id, batch_nonce)
+                    .map_err(|_| InvalidTransaction::Call)?;
+                let idx = message_proof.message_index();
+                let delivered = DeliveredMessages::<T>::get(network_id, batch_nonce)
+                    .map(|delivered| delivered.get(idx as usize).map_or(false, |bit| *bit))
+                    .unwrap_or_default();
+                // If message already submitted
+                if batch_nonce <= ChannelNonces::<T>::get(network_id) || delivered {
+                    return InvalidTransaction::Stale.into();
+                }
+                let commitment_hash = message_proof
+                    .commitment_hash(message)
+                    .ok_or(InvalidTransaction::BadProof)?;
+                T::Verifier::verify((*network_id).into(), commitment_hash, proof).map_err(|e| {
+                    warn!("Bad submit message proof received: {:?}", e);
+                    InvalidTransaction::BadProof
+                })?;
+                ValidTransaction::with_tag_prefix("SubstrateBridgeChannelSubmitMessage")
+                    .priority(T::UnsignedPriority::get())
+                    .longevity(T::UnsignedLongevity::get())
+                    .and_provides((network_id, batch_nonce, idx))
+                    .propagate(true)
+                    .build()
             } else {
                 warn!("Unknown unsigned call, can't validate");
                 InvalidTransaction::Call.into()
@@ -29,22 +29,27 @@
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use super::*;
-use bridge_types::substrate::BridgeMessage;
+use bridge_common::merkle_proof::{generate_message_proof, generate_message_proof_v2};
+use bridge_types::substrate::{
+    BridgeMessage, BridgeMessageV2, MessageDispatchResult, OutboundChannelCall,
+    SubstrateBridgeMessageEncode,
+};
 use codec::{Decode, Encode, MaxEncodedLen};
 
-use frame_support::traits::Everything;
+use frame_support::dispatch::DispatchResultWithPostInfo;
+use frame_support::traits::{Everything, GenesisBuild};
 use frame_support::{
     assert_noop, assert_ok, parameter_types, Deserialize, RuntimeDebug, Serialize,
 };
 use scale_info::TypeInfo;
-use sp_core::{ConstU64, H256};
+use sp_core::{ConstU32, ConstU64, H256};
 use sp_keyring::AccountKeyring as Keyring;
 use sp_runtime::testing::Header;
 use sp_runtime::traits::{BlakeTwo256, IdentifyAccount, IdentityLookup, Verify};
-use sp_runtime::MultiSignature;
+use sp_runtime::{DispatchError, MultiSignature};
 use sp_std::convert::From;
 
-use bridge_types::traits::MessageDispatch;
+use bridge_types::traits::{MessageDispatch, OutboundChannel};
 use bridge_types::{GenericNetworkId, GenericTimepoint};
 
 use crate::inbound::Error;
@@ -65,7 +70,7 @@ frame_support::construct_runtime!(
         System: frame_system::{Pallet, Call, Storage, Event<T>},
         Timestamp: pallet_timestamp::{Pallet, Call, Storage},
         Balances: pallet_balances::{Pallet, Call, Storage, Event<T>},
-        BridgeInboundChannel: bridge_inbound_channel::{Pallet, Call, Storage, Event<T>},
+        BridgeInboundChannel: bridge_inbound_channel::{Pallet, Call, Config, Storage, Event<T>},
     }
 );
 
@@ -181,12 +186,36 @@ impl Verifier for MockVerifier {
 pub struct MockMessageDispatch;
 
 impl MessageDispatch<Test, SubNetworkId, MessageId, ()> for MockMessageDispatch {
-    fn dispatch(_: SubNetworkId, _: MessageId, _: GenericTimepoint, _: &[u8], _: ()) {}
+    // Messages with the empty payload fail
+    fn dispatch(
+        _: SubNetworkId,
+        _: MessageId,
+        _: GenericTimepoint,
+        payload: &[u8],
+        _: (),
+    ) -> DispatchResult {
+        if payload.is_empty() {
+            Err(DispatchError::Other("empty payload"))
+        } else {
+            Ok(())
+        }
+    }
 
     fn dispatch_weight(_: &[u8]) -> frame_support::weights::Weight {
         Default::default()
     }
 
+    fn ensure_not_paused(_: SubNetworkId, payload: &[u8]) -> DispatchResult {
+        if PausedPayloads::get()
+            .iter()
+            .any(|paused| paused[..] == *payload)
+        {
+            Err(DispatchError::Other("paused"))
+        } else {
+            Ok(())
+        }
+    }
+
     #[cfg(feature = "runtime-benchmarks")]
     fn successful_dispatch_event(
         _: MessageId,
@@ -195,8 +224,44 @@ impl MessageDispatch<Test, SubNetworkId, MessageId, ()> for MockMessageDispatch
     }
 }
 
+// Mock timeout reporter
+pub struct MockTimeoutReporter;
+
+impl MessageTimeoutReporter<SubNetworkId> for MockTimeoutReporter {
+    fn report_timeout(network_id: SubNetworkId, message_id: H256) -> DispatchResult {
+        ReportedTimeouts::mutate(|reported| reported.push((network_id, message_id)));
+        Ok(())
+    }
+
+    fn report_timeout_weight() -> frame_support::weights::Weight {
+        Default::default()
+    }
+}
+
 parameter_types! {
     pub SourceAccount: AccountId = Keyring::Eve.into();
+    pub static ReportedTimeouts: Vec<(SubNetworkId, H256)> = Vec::new();
+    pub static SentMessages: Vec<(SubNetworkId, Vec<u8>)> = Vec::new();
+    pub static PausedPayloads: Vec<Vec<u8>> = Vec::new();
+}
+
+// Mock outbound channel
+pub struct MockOutboundChannel;
+
+impl OutboundChannel<SubNetworkId, AccountId, ()> for MockOutboundChannel {
+    fn submit(
+        network_id: SubNetworkId,
+        _: &frame_system::RawOrigin<AccountId>,
+        payload: &[u8],
+        _: (),
+    ) -> Result<H256, DispatchError> {
+        SentMessages::mutate(|messages| messages.push((network_id, payload.to_vec())));
+        Ok(H256::zero())
+    }
+
+    fn submit_weight() -> frame_support::weights::Weight {
+        frame_support::weights::Weight::from_parts(1_000, 0)
+    }
 }
 
 impl pallet_timestamp::Config for Test {
@@ -220,7 +285,11 @@ impl bridge_inbound_channel::Config for Test {
     type UnsignedPriority = ConstU64<100>;
     type MaxMessagePayloadSize = MaxMessagePayloadSize;
     type MaxMessagesPerCommit = MaxMessagesPerCommit;
+    type MaxBatchesAhead = ConstU32<4>;
     type ThisNetworkId = ThisNetworkId;
+    type BridgePause = ();
+    type TimeoutReporter = MockTimeoutReporter;
+    type OutboundChannel = MockOutboundChannel;
     type WeightInfo = ();
 }
 
@@ -236,6 +305,11 @@ pub fn new_tester() -> sp_io::TestExternalities {
     .assimilate_storage(&mut storage)
     .unwrap();
 
+    let config = bridge_inbound_channel::GenesisConfig {
+        channels: vec![BASE_NETWORK_ID, SubNetworkId::Kusama],
+    };
+    GenesisBuild::<Test>::assimilate_storage(&config, &mut storage).unwrap();
+
     let mut ext: sp_io::TestExternalities = storage.into();
     ext.execute_with(|| System::set_block_number(1));
     ext
@@ -339,3 +413,463 @@ fn test_submit_with_invalid_network_id() {
         );
     });
 }
+
+fn merkle_commitment(
+    nonce: u64,
+    messages_count: u8,
+) -> bridge_types::substrate::MerkleCommitment<MaxMessagesPerCommit, MaxMessagePayloadSize> {
+    bridge_types::substrate::MerkleCommitment {
+        nonce,
+        messages: (0..messages_count)
+            .map(|idx| BridgeMessage {
+                timepoint: Default::default(),
+                payload: vec![idx].try_into().unwrap(),
+            })
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap(),
+    }
+}
+
+fn submit_message(
+    commitment: &bridge_types::substrate::MerkleCommitment<
+        MaxMessagesPerCommit,
+        MaxMessagePayloadSize,
+    >,
+    idx: usize,
+) -> DispatchResultWithPostInfo {
+    let message_proof = generate_message_proof(commitment, idx).unwrap();
+    BridgeInboundChannel::submit_message(
+        RuntimeOrigin::none(),
+        BASE_NETWORK_ID,
+        commitment.messages[idx].clone().into(),
+        message_proof,
+        Vec::new(),
+    )
+}
+
+#[test]
+fn test_submit_message() {
+    new_tester().execute_with(|| {
+        let commitment = merkle_commitment(1, 3);
+
+        assert_ok!(submit_message(&commitment, 2));
+        assert_ok!(submit_message(&commitment, 0));
+        assert_eq!(<ChannelNonces<Test>>::get(BASE_NETWORK_ID), 0);
+        assert!(DeliveredMessages::<Test>::contains_key(BASE_NETWORK_ID, 1));
+        assert_noop!(
+            submit_message(&commitment, 2),
+            Error::<Test>::MessageAlreadyDelivered
+        );
+
+        assert_ok!(submit_message(&commitment, 1));
+        assert_eq!(<ChannelNonces<Test>>::get(BASE_NETWORK_ID), 1);
+        assert!(!DeliveredMessages::<Test>::contains_key(BASE_NETWORK_ID, 1));
+        assert_noop!(submit_message(&commitment, 0), Error::<Test>::InvalidNonce);
+    });
+}
+
+#[test]
+fn test_submit_message_out_of_order() {
+    new_tester().execute_with(|| {
+        let commitment_1 = merkle_commitment(1, 2);
+        let commitment_2 = merkle_commitment(2, 1);
+
+        assert_ok!(submit_message(&commitment_2, 0));
+        assert_ok!(submit_message(&commitment_1, 1));
+        assert_eq!(<ChannelNonces<Test>>::get(BASE_NETWORK_ID), 0);
+
+        assert_ok!(submit_message(&commitment_1, 0));
+        assert_eq!(<ChannelNonces<Test>>::get(BASE_NETWORK_ID), 2);
+        assert_eq!(
+            DeliveredMessages::<Test>::iter_prefix(BASE_NETWORK_ID).count(),
+            0
+        );
+    });
+}
+
+#[test]
+fn test_paused_messages_are_not_consumed() {
+    new_tester().execute_with(|| {
+        let commitment = merkle_commitment(1, 2);
+        PausedPayloads::set(vec![vec![1]]);
+
+        assert_noop!(
+            BridgeInboundChannel::submit(
+                RuntimeOrigin::none(),
+                BASE_NETWORK_ID,
+                bridge_types::GenericCommitment::SubMerkle(commitment.clone()),
+                Vec::new(),
+            ),
+            DispatchError::Other("paused")
+        );
+        assert_noop!(
+            submit_message(&commitment, 1),
+            DispatchError::Other("paused")
+        );
+        assert_ok!(submit_message(&commitment, 0));
+        assert_eq!(<ChannelNonces<Test>>::get(BASE_NETWORK_ID), 0);
+
+        PausedPayloads::set(vec![]);
+        assert_ok!(submit_message(&commitment, 1));
+        assert_eq!(<ChannelNonces<Test>>::get(BASE_NETWORK_ID), 1);
+    });
+}
+
+#[test]
+fn test_submit_message_too_far_ahead() {
+    new_tester().execute_with(|| {
+        assert_noop!(
+            submit_message(&merkle_commitment(5, 1), 0),
+            Error::<Test>::BatchTooFarAhead
+        );
+
+        for nonce in (2..=4).rev() {
+            assert_ok!(submit_message(&merkle_commitment(nonce, 1), 0));
+        }
+        let post_info = submit_message(&merkle_commitment(1, 1), 0).unwrap();
+        assert_eq!(<ChannelNonces<Test>>::get(BASE_NETWORK_ID), 4);
+        assert_eq!(
+            post_info.actual_weight,
+            Some(
+                <() as crate::inbound::WeightInfo>::submit_message(4)
+                    .saturating_add(frame_support::weights::Weight::from_parts(4_000, 0))
+            )
+        );
+        assert_ok!(submit_message(&merkle_commitment(8, 1), 0));
+    });
+}
+
+#[test]
+fn test_submit_message_with_invalid_proof() {
+    new_tester().execute_with(|| {
+        let commitment = merkle_commitment(1, 3);
+        let mut message_proof = generate_message_proof(&commitment, 1).unwrap();
+        message_proof.proof.items.pop();
+        assert_noop!(
+            BridgeInboundChannel::submit_message(
+                RuntimeOrigin::none(),
+                BASE_NETWORK_ID,
+                commitment.messages[1].clone().into(),
+                message_proof,
+                Vec::new(),
+            ),
+            Error::<Test>::InvalidMessageProof
+        );
+    });
+}
+
+#[test]
+fn test_submit_merkle_commitment() {
+    new_tester().execute_with(|| {
+        let commitment = merkle_commitment(1, 3);
+        assert_ok!(BridgeInboundChannel::submit(
+            RuntimeOrigin::none(),
+            BASE_NETWORK_ID,
+            bridge_types::GenericCommitment::SubMerkle(commitment),
+            Vec::new(),
+        ));
+        assert_eq!(<ChannelNonces<Test>>::get(BASE_NETWORK_ID), 1);
+
+        // Batch can't be submitted whole if some messages are already delivered
+        let commitment = merkle_commitment(2, 3);
+        assert_ok!(submit_message(&commitment, 1));
+        assert_noop!(
+            BridgeInboundChannel::submit(
+                RuntimeOrigin::none(),
+                BASE_NETWORK_ID,
+                bridge_types::GenericCommitment::SubMerkle(commitment),
+                Vec::new(),
+            ),
+            Error::<Test>::InvalidNonce
+        );
+    });
+}
+
+#[test]
+fn test_submit_on_unregistered_channel() {
+    new_tester().execute_with(|| {
+        let commitment = merkle_commitment(1, 1);
+        assert_noop!(
+            BridgeInboundChannel::submit(
+                RuntimeOrigin::none(),
+                SubNetworkId::Rococo,
+                bridge_types::GenericCommitment::SubMerkle(commitment.clone()),
+                Vec::new(),
+            ),
+            Error::<Test>::ChannelNotRegistered
+        );
+
+        assert_ok!(BridgeInboundChannel::register_channel(
+            RuntimeOrigin::root(),
+            SubNetworkId::Rococo
+        ));
+        assert_noop!(
+            BridgeInboundChannel::register_channel(RuntimeOrigin::root(), SubNetworkId::Rococo),
+            Error::<Test>::ContractExists
+        );
+        assert_noop!(
+            BridgeInboundChannel::submit(
+                RuntimeOrigin::none(),
+                SubNetworkId::Rococo,
+                bridge_types::GenericCommitment::SubMerkle(commitment.clone()),
+                Vec::new(),
+            ),
+            Error::<Test>::ChannelNotActive
+        );
+
+        assert_ok!(BridgeInboundChannel::open_channel(
+            RuntimeOrigin::root(),
+            SubNetworkId::Rococo
+        ));
+        // The channel accepts the batch, but the mock verifier knows only the base network
+        assert_noop!(
+            BridgeInboundChannel::submit(
+                RuntimeOrigin::none(),
+                SubNetworkId::Rococo,
+                bridge_types::GenericCommitment::SubMerkle(commitment),
+                Vec::new(),
+            ),
+            Error::<Test>::InvalidNetwork
+        );
+    });
+}
+
+#[test]
+fn test_close_channel_drains_batches() {
+    new_tester().execute_with(|| {
+        assert_noop!(
+            BridgeInboundChannel::close_channel(
+                RuntimeOrigin::signed(Keyring::Bob.into()),
+                BASE_NETWORK_ID,
+                2
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(BridgeInboundChannel::close_channel(
+            RuntimeOrigin::root(),
+            BASE_NETWORK_ID,
+            2
+        ));
+        assert_eq!(
+            BridgeInboundChannel::channel_state(BASE_NETWORK_ID),
+            Some(ChannelState::Draining)
+        );
+
+        // Batches committed before closure are still delivered
+        assert_ok!(BridgeInboundChannel::submit(
+            RuntimeOrigin::none(),
+            BASE_NETWORK_ID,
+            bridge_types::GenericCommitment::SubMerkle(merkle_commitment(1, 1)),
+            Vec::new(),
+        ));
+        let commitment = merkle_commitment(2, 2);
+        assert_ok!(submit_message(&commitment, 0));
+        assert_eq!(
+            BridgeInboundChannel::channel_state(BASE_NETWORK_ID),
+            Some(ChannelState::Draining)
+        );
+        assert_ok!(submit_message(&commitment, 1));
+        assert_eq!(
+            BridgeInboundChannel::channel_state(BASE_NETWORK_ID),
+            Some(ChannelState::Closed)
+        );
+        System::assert_last_event(RuntimeEvent::BridgeInboundChannel(Event::ChannelClosed {
+            network_id: BASE_NETWORK_ID,
+        }));
+
+        assert_noop!(
+            submit_message(&merkle_commitment(3, 1), 0),
+            Error::<Test>::ChannelNotActive
+        );
+    });
+}
+
+#[test]
+fn test_close_channel_with_delivered_batches() {
+    new_tester().execute_with(|| {
+        assert_ok!(BridgeInboundChannel::close_channel(
+            RuntimeOrigin::root(),
+            SubNetworkId::Kusama,
+            0
+        ));
+        assert_eq!(
+            BridgeInboundChannel::channel_state(SubNetworkId::Kusama),
+            Some(ChannelState::Closed)
+        );
+        assert_noop!(
+            BridgeInboundChannel::close_channel(RuntimeOrigin::root(), SubNetworkId::Kusama, 0),
+            Error::<Test>::InvalidChannelState
+        );
+        assert_ok!(BridgeInboundChannel::open_channel(
+            RuntimeOrigin::root(),
+            SubNetworkId::Kusama
+        ));
+    });
+}
+
+#[test]
+fn test_submit_expired_message() {
+    new_tester().execute_with(|| {
+        pallet_timestamp::Pallet::<Test>::set_timestamp(10_000);
+        let message = |deadline| BridgeMessageV2 {
+            timepoint: Default::default(),
+            payload: Default::default(),
+            deadline: Some(deadline),
+        };
+        let commitment =
+            bridge_types::GenericCommitment::SubV2(bridge_types::substrate::CommitmentV2 {
+                nonce: 1,
+                messages: vec![message(10_000), message(9_999)].try_into().unwrap(),
+            });
+        assert_ok!(BridgeInboundChannel::submit(
+            RuntimeOrigin::none(),
+            BASE_NETWORK_ID,
+            commitment,
+            Vec::new(),
+        ));
+        assert_eq!(<ChannelNonces<Test>>::get(BASE_NETWORK_ID), 1);
+
+        let message_id =
+            MessageId::batched(BASE_NETWORK_ID.into(), ThisNetworkId::get(), 1, 1).hash();
+        System::assert_has_event(RuntimeEvent::BridgeInboundChannel(Event::MessageExpired {
+            network_id: BASE_NETWORK_ID,
+            message_id,
+        }));
+        assert_eq!(ReportedTimeouts::get(), vec![(BASE_NETWORK_ID, message_id)]);
+    });
+}
+
+#[test]
+fn test_submit_expired_merkle_message() {
+    new_tester().execute_with(|| {
+        pallet_timestamp::Pallet::<Test>::set_timestamp(10_000);
+        let commitment = bridge_types::substrate::MerkleCommitmentV2::<
+            MaxMessagesPerCommit,
+            MaxMessagePayloadSize,
+        > {
+            nonce: 1,
+            messages: vec![BridgeMessageV2 {
+                timepoint: Default::default(),
+                payload: Default::default(),
+                deadline: Some(9_999),
+            }]
+            .try_into()
+            .unwrap(),
+        };
+        assert_ok!(BridgeInboundChannel::submit_message(
+            RuntimeOrigin::none(),
+            BASE_NETWORK_ID,
+            commitment.messages[0].clone(),
+            generate_message_proof_v2(&commitment, 0).unwrap(),
+            Vec::new(),
+        ));
+        assert_eq!(<ChannelNonces<Test>>::get(BASE_NETWORK_ID), 1);
+
+        let message_id =
+            MessageId::batched(BASE_NETWORK_ID.into(), ThisNetworkId::get(), 1, 0).hash();
+        assert_eq!(ReportedTimeouts::get(), vec![(BASE_NETWORK_ID, message_id)]);
+    });
+}
+
+#[test]
+fn test_submit_reports_delivery() {
+    new_tester().execute_with(|| {
+        let message = |payload: Vec<u8>| BridgeMessage {
+            timepoint: Default::default(),
+            payload: payload.try_into().unwrap(),
+        };
+        let delivery_report = OutboundChannelCall::ReportDelivery {
+            batch_nonce: 1,
+            results: vec![MessageDispatchResult::Dispatched],
+        }
+        .prepare_message();
+        let commitment =
+            bridge_types::GenericCommitment::Sub(bridge_types::substrate::Commitment {
+                nonce: 1,
+                messages: vec![
+                    message(vec![1]),
+                    message(vec![]),
+                    message(delivery_report.clone()),
+                ]
+                .try_into()
+                .unwrap(),
+            });
+        assert_ok!(BridgeInboundChannel::submit(
+            RuntimeOrigin::none(),
+            BASE_NETWORK_ID,
+            commitment,
+            Vec::new(),
+        ));
+        System::assert_last_event(RuntimeEvent::BridgeInboundChannel(Event::BatchDelivered {
+            network_id: BASE_NETWORK_ID,
+            batch_nonce: 1,
+        }));
+        assert_eq!(
+            SentMessages::take(),
+            vec![(
+                BASE_NETWORK_ID,
+                OutboundChannelCall::ReportDelivery {
+                    batch_nonce: 1,
+                    results: vec![
+                        MessageDispatchResult::Dispatched,
+                        MessageDispatchResult::Failed,
+                        MessageDispatchResult::DeliveryReport,
+                    ],
+                }
+                .prepare_message()
+            )]
+        );
+
+        // Batch of the delivery reports is not reported back
+        let commitment =
+            bridge_types::GenericCommitment::Sub(bridge_types::substrate::Commitment {
+                nonce: 2,
+                messages: vec![message(delivery_report)].try_into().unwrap(),
+            });
+        assert_ok!(BridgeInboundChannel::submit(
+            RuntimeOrigin::none(),
+            BASE_NETWORK_ID,
+            commitment,
+            Vec::new(),
+        ));
+        System::assert_last_event(RuntimeEvent::BridgeInboundChannel(Event::BatchDelivered {
+            network_id: BASE_NETWORK_ID,
+            batch_nonce: 2,
+        }));
+        assert!(SentMessages::get().is_empty());
+    });
+}
+
+#[test]
+fn test_submit_message_reports_delivery() {
+    new_tester().execute_with(|| {
+        let commitment_1 = merkle_commitment(1, 2);
+        let commitment_2 = merkle_commitment(2, 1);
+
+        assert_ok!(submit_message(&commitment_2, 0));
+        assert_ok!(submit_message(&commitment_1, 1));
+        assert!(SentMessages::get().is_empty());
+        assert_eq!(
+            DispatchResults::<Test>::get(BASE_NETWORK_ID, 2),
+            Some(vec![MessageDispatchResult::Dispatched])
+        );
+
+        assert_ok!(submit_message(&commitment_1, 0));
+        let report = |batch_nonce, messages_count| {
+            (
+                BASE_NETWORK_ID,
+                OutboundChannelCall::ReportDelivery {
+                    batch_nonce,
+                    results: vec![MessageDispatchResult::Dispatched; messages_count],
+                }
+                .prepare_message(),
+            )
+        };
+        assert_eq!(SentMessages::get(), vec![report(1, 2), report(2, 1)]);
+        assert_eq!(
+            DispatchResults::<Test>::iter_prefix(BASE_NETWORK_ID).count(),
+            0
+        );
+    });
+}
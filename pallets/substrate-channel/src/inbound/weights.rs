@@ -28,26 +28,11 @@
 // STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-//! Autogenerated weights for substrate_bridge_channel::inbound
+//! Weights for substrate_bridge_channel::inbound
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
-//! DATE: 2023-07-27, STEPS: `50`, REPEAT: `20`, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! WORST CASE MAP SIZE: `1000000`
-//! HOSTNAME: `TRX40`, CPU: `AMD Ryzen Threadripper 3960X 24-Core Processor`
-//! EXECUTION: None, WASM-EXECUTION: Compiled, CHAIN: Some("local"), DB CACHE: 1024
-
-// Executed Command:
-// ./target/release/framenode
-// benchmark
-// pallet
-// --chain=local
-// --steps=50
-// --repeat=20
-// --pallet=substrate_bridge_channel::inbound
-// --extrinsic=*
-// --header=./misc/file_header.txt
-// --template=./misc/pallet-weight-template.hbs
-// --output=./inbound-channel.rs
+//! NOT GENERATED BY THE BENCHMARK CLI: the values are estimates until the pallet
+//! is benchmarked, regenerate with
+//! `benchmark pallet --pallet=substrate_bridge_channel::inbound --extrinsic=* --template=./misc/pallet-weight-template.hbs`.
 
 #![cfg_attr(rustfmt, rustfmt_skip)]
 #![allow(unused_parens)]
@@ -58,36 +43,156 @@ use core::marker::PhantomData;
 
 /// Weight functions needed for substrate_bridge_channel::inbound.
 pub trait WeightInfo {
-	fn submit() -> Weight;
+	fn submit(b: u32, ) -> Weight;
+	fn submit_message(b: u32, ) -> Weight;
+	fn register_channel() -> Weight;
+	fn open_channel() -> Weight;
+	fn close_channel() -> Weight;
 }
 
 /// Weights for substrate_bridge_channel::inbound using the Substrate node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: SubstrateBridgeInboundChannel Channels (r:1 w:0)
+	/// Proof Skipped: SubstrateBridgeInboundChannel Channels (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeInboundChannel DrainingNonces (r:1 w:0)
+	/// Proof Skipped: SubstrateBridgeInboundChannel DrainingNonces (max_values: None, max_size: None, mode: Measured)
 	/// Storage: SubstrateBridgeInboundChannel ChannelNonces (r:1 w:1)
 	/// Proof Skipped: SubstrateBridgeInboundChannel ChannelNonces (max_values: None, max_size: None, mode: Measured)
-	fn submit() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `42`
-		//  Estimated: `2517`
-		// Minimum execution time: 4_800_000 picoseconds.
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: SubstrateBridgeInboundChannel DeliveredMessages (r:5 w:3)
+	/// Proof Skipped: SubstrateBridgeInboundChannel DeliveredMessages (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeInboundChannel DispatchResults (r:3 w:3)
+	/// Proof Skipped: SubstrateBridgeInboundChannel DispatchResults (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `b` is `[0, 3]`.
+	fn submit(b: u32, ) -> Weight {
 		Weight::from_parts(5_100_000, 2517)
+			.saturating_add(Weight::from_parts(3_500_000, 0).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(b.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(b.into())))
+			.saturating_add(Weight::from_parts(0, 5090).saturating_mul(b.into()))
+	}
+	/// Storage: SubstrateBridgeInboundChannel Channels (r:1 w:0)
+	/// Proof Skipped: SubstrateBridgeInboundChannel Channels (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeInboundChannel DrainingNonces (r:1 w:0)
+	/// Proof Skipped: SubstrateBridgeInboundChannel DrainingNonces (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeInboundChannel ChannelNonces (r:1 w:1)
+	/// Proof Skipped: SubstrateBridgeInboundChannel ChannelNonces (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: SubstrateBridgeInboundChannel DeliveredMessages (r:6 w:5)
+	/// Proof Skipped: SubstrateBridgeInboundChannel DeliveredMessages (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeInboundChannel DispatchResults (r:5 w:5)
+	/// Proof Skipped: SubstrateBridgeInboundChannel DispatchResults (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `b` is `[1, 4]`.
+	fn submit_message(b: u32, ) -> Weight {
+		Weight::from_parts(14_420_000, 5072)
+			.saturating_add(Weight::from_parts(3_500_000, 0).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(b.into())))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(b.into())))
+			.saturating_add(Weight::from_parts(0, 5090).saturating_mul(b.into()))
+	}
+	/// Storage: SubstrateBridgeInboundChannel Channels (r:1 w:1)
+	/// Proof Skipped: SubstrateBridgeInboundChannel Channels (max_values: None, max_size: None, mode: Measured)
+	fn register_channel() -> Weight {
+		Weight::from_parts(8_150_000, 2475)
 			.saturating_add(T::DbWeight::get().reads(1_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: SubstrateBridgeInboundChannel Channels (r:1 w:1)
+	/// Proof Skipped: SubstrateBridgeInboundChannel Channels (max_values: None, max_size: None, mode: Measured)
+	fn open_channel() -> Weight {
+		Weight::from_parts(8_870_000, 2554)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: SubstrateBridgeInboundChannel Channels (r:1 w:1)
+	/// Proof Skipped: SubstrateBridgeInboundChannel Channels (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeInboundChannel DrainingNonces (r:1 w:1)
+	/// Proof Skipped: SubstrateBridgeInboundChannel DrainingNonces (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeInboundChannel ChannelNonces (r:1 w:0)
+	/// Proof Skipped: SubstrateBridgeInboundChannel ChannelNonces (max_values: None, max_size: None, mode: Measured)
+	fn close_channel() -> Weight {
+		Weight::from_parts(13_640_000, 7596)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
+	/// Storage: SubstrateBridgeInboundChannel Channels (r:1 w:0)
+	/// Proof Skipped: SubstrateBridgeInboundChannel Channels (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeInboundChannel DrainingNonces (r:1 w:0)
+	/// Proof Skipped: SubstrateBridgeInboundChannel DrainingNonces (max_values: None, max_size: None, mode: Measured)
 	/// Storage: SubstrateBridgeInboundChannel ChannelNonces (r:1 w:1)
 	/// Proof Skipped: SubstrateBridgeInboundChannel ChannelNonces (max_values: None, max_size: None, mode: Measured)
-	fn submit() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `42`
-		//  Estimated: `2517`
-		// Minimum execution time: 4_800_000 picoseconds.
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: SubstrateBridgeInboundChannel DeliveredMessages (r:5 w:3)
+	/// Proof Skipped: SubstrateBridgeInboundChannel DeliveredMessages (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeInboundChannel DispatchResults (r:3 w:3)
+	/// Proof Skipped: SubstrateBridgeInboundChannel DispatchResults (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `b` is `[0, 3]`.
+	fn submit(b: u32, ) -> Weight {
 		Weight::from_parts(5_100_000, 2517)
+			.saturating_add(Weight::from_parts(3_500_000, 0).saturating_mul(b.into()))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(b.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(b.into())))
+			.saturating_add(Weight::from_parts(0, 5090).saturating_mul(b.into()))
+	}
+	/// Storage: SubstrateBridgeInboundChannel Channels (r:1 w:0)
+	/// Proof Skipped: SubstrateBridgeInboundChannel Channels (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeInboundChannel DrainingNonces (r:1 w:0)
+	/// Proof Skipped: SubstrateBridgeInboundChannel DrainingNonces (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeInboundChannel ChannelNonces (r:1 w:1)
+	/// Proof Skipped: SubstrateBridgeInboundChannel ChannelNonces (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: SubstrateBridgeInboundChannel DeliveredMessages (r:6 w:5)
+	/// Proof Skipped: SubstrateBridgeInboundChannel DeliveredMessages (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeInboundChannel DispatchResults (r:5 w:5)
+	/// Proof Skipped: SubstrateBridgeInboundChannel DispatchResults (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `b` is `[1, 4]`.
+	fn submit_message(b: u32, ) -> Weight {
+		Weight::from_parts(14_420_000, 5072)
+			.saturating_add(Weight::from_parts(3_500_000, 0).saturating_mul(b.into()))
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(b.into())))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(b.into())))
+			.saturating_add(Weight::from_parts(0, 5090).saturating_mul(b.into()))
+	}
+	/// Storage: SubstrateBridgeInboundChannel Channels (r:1 w:1)
+	/// Proof Skipped: SubstrateBridgeInboundChannel Channels (max_values: None, max_size: None, mode: Measured)
+	fn register_channel() -> Weight {
+		Weight::from_parts(8_150_000, 2475)
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: SubstrateBridgeInboundChannel Channels (r:1 w:1)
+	/// Proof Skipped: SubstrateBridgeInboundChannel Channels (max_values: None, max_size: None, mode: Measured)
+	fn open_channel() -> Weight {
+		Weight::from_parts(8_870_000, 2554)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: SubstrateBridgeInboundChannel Channels (r:1 w:1)
+	/// Proof Skipped: SubstrateBridgeInboundChannel Channels (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeInboundChannel DrainingNonces (r:1 w:1)
+	/// Proof Skipped: SubstrateBridgeInboundChannel DrainingNonces (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeInboundChannel ChannelNonces (r:1 w:0)
+	/// Proof Skipped: SubstrateBridgeInboundChannel ChannelNonces (max_values: None, max_size: None, mode: Measured)
+	fn close_channel() -> Weight {
+		Weight::from_parts(13_640_000, 7596)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
 }
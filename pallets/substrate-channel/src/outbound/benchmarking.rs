@@ -31,10 +31,10 @@
 //! BridgeOutboundChannel pallet benchmarking
 use super::*;
 
-use bridge_types::substrate::BridgeMessage;
+use bridge_types::substrate::BridgeMessageV2;
 use bridge_types::traits::OutboundChannel;
 use frame_benchmarking::benchmarks;
-use frame_support::traits::OnInitialize;
+use frame_support::traits::{EnsureOrigin, OnInitialize};
 use frame_system::EventRecord;
 use frame_system::RawOrigin;
 use sp_std::prelude::*;
@@ -65,9 +65,10 @@ benchmarks! {
         for _ in 0 .. m {
             let payload: Vec<u8> = (0..).take(p as usize).collect();
             MessageQueues::<T>::try_append(
-                BASE_NETWORK_ID, BridgeMessage {
+                BASE_NETWORK_ID, BridgeMessageV2 {
                 payload: payload.try_into().unwrap(),
                 timepoint: Default::default(),
+                deadline: None,
             }).unwrap();
         }
 
@@ -78,19 +79,31 @@ benchmarks! {
         assert_eq!(<MessageQueues<T>>::get(BASE_NETWORK_ID).len(), 0);
     }
 
+    // Benchmark `on_initialize` overhead of the commits scheduling
+    // without any network queues.
+    on_initialize_base {
+        let _ = MessageQueues::<T>::clear(u32::MAX, None);
+        LastCommittedNetwork::<T>::put(BASE_NETWORK_ID);
+        let block_number: T::BlockNumber = 10u32.into();
+    }: { BridgeOutboundChannel::<T>::on_initialize(block_number) }
+    verify {
+        assert!(DeferredCommits::<T>::get().is_empty());
+    }
+
     // Benchmark 'on_initialize` for the best case, i.e. nothing is done
     // because it's not a commitment interval.
     on_initialize_non_interval {
         MessageQueues::<T>::take(BASE_NETWORK_ID);
         let payload: Vec<u8> = (0..).take(10).collect();
         MessageQueues::<T>::try_append(
-            BASE_NETWORK_ID, BridgeMessage {
+            BASE_NETWORK_ID, BridgeMessageV2 {
             payload: payload.try_into().unwrap(),
             timepoint: Default::default(),
+            deadline: None,
         }).unwrap();
 
         let interval: T::BlockNumber = 10u32.into();
-        Interval::<T>::put(interval);
+        Intervals::<T>::insert(BASE_NETWORK_ID, interval);
         let block_number: T::BlockNumber = 12u32.into();
 
     }: { BridgeOutboundChannel::<T>::on_initialize(block_number) }
@@ -103,23 +116,135 @@ benchmarks! {
     on_initialize_no_messages {
         MessageQueues::<T>::take(BASE_NETWORK_ID);
 
-        let block_number = Interval::<T>::get();
+        let block_number = BridgeOutboundChannel::<T>::interval(BASE_NETWORK_ID);
 
     }: { BridgeOutboundChannel::<T>::on_initialize(block_number) }
 
+    // Benchmark `submit` for the signed origin paying the message fee
+    // when the queue is full and the message is placed into the pending messages.
     submit {
-
+        let caller: T::AccountId = frame_benchmarking::whitelisted_caller();
+        let pending_enabled = T::MaxPendingMessages::get() > 0;
+        if pending_enabled {
+            for _ in 0 .. T::MaxMessagesPerCommit::get() {
+                MessageQueues::<T>::try_append(
+                    SubNetworkId::Rococo, BridgeMessageV2 {
+                    payload: Default::default(),
+                    timepoint: Default::default(),
+                    deadline: None,
+                }).unwrap();
+            }
+        }
+        Channels::<T>::insert(SubNetworkId::Rococo, ChannelState::Active);
+        MessageFees::<T>::insert(SubNetworkId::Rococo, MessageFee {
+            base_fee: 1_000u32.into(),
+            byte_fee: 10u32.into(),
+        });
+        T::Currency::make_free_balance_be(&caller, 1_000_000u32.into());
     }: {
-        BridgeOutboundChannel::<T>::submit(SubNetworkId::Rococo, &RawOrigin::Root, &[0u8; 128], ()).unwrap()
+        BridgeOutboundChannel::<T>::submit(SubNetworkId::Rococo, &RawOrigin::Signed(caller), &[0u8; 128], ()).unwrap()
     }
     verify {
         assert_last_event::<T>(crate::outbound::Event::<T>::MessageAccepted {
             network_id: SubNetworkId::Rococo,
-            batch_nonce: 1,
+            batch_nonce: if pending_enabled { 2 } else { 1 },
             message_nonce: 0
         }.into());
     }
 
+    promote_pending_messages {
+        let m in 0 .. T::MaxMessagesPerCommit::get();
+
+        for index in 0 .. m as u64 {
+            let payload: Vec<u8> = (0..).take(T::MaxMessagePayloadSize::get() as usize).collect();
+            PendingMessages::<T>::insert(BASE_NETWORK_ID, index, BridgeMessageV2 {
+                payload: payload.try_into().unwrap(),
+                timepoint: Default::default(),
+                deadline: None,
+            });
+        }
+        PendingMessagesRange::<T>::insert(BASE_NETWORK_ID, (0, m as u64));
+        PendingMessagesCount::<T>::put(m);
+    }: { BridgeOutboundChannel::<T>::promote_pending_messages(BASE_NETWORK_ID) }
+    verify {
+        assert_eq!(<MessageQueues<T>>::get(BASE_NETWORK_ID).len(), m as usize);
+        assert_eq!(PendingMessagesCount::<T>::get(), 0);
+    }
+
+    set_commitment_format {
+    }: _(RawOrigin::Root, BASE_NETWORK_ID, CommitmentFormat::Merkle)
+    verify {
+        assert_eq!(CommitmentFormats::<T>::get(BASE_NETWORK_ID), CommitmentFormat::Merkle);
+    }
+
+    set_message_fee {
+    }: _(RawOrigin::Root, BASE_NETWORK_ID, 1_000u32.into(), 10u32.into())
+    verify {
+        assert_eq!(BridgeOutboundChannel::<T>::message_fee(BASE_NETWORK_ID, 10), 1_100u32.into());
+    }
+
+    set_interval {
+    }: _(RawOrigin::Root, BASE_NETWORK_ID, 20u32.into())
+    verify {
+        assert_eq!(BridgeOutboundChannel::<T>::interval(BASE_NETWORK_ID), 20u32.into());
+    }
+
+    stagger_commits {
+        let n in 0 .. 5;
+        let networks: Vec<SubNetworkId> = (0..n).map(SubNetworkId::Custom).collect();
+    }: _(RawOrigin::Root, networks)
+    verify {
+        if n > 0 {
+            assert_eq!(CommitOffsets::<T>::get(SubNetworkId::Custom(n - 1)), (n - 1).into());
+        }
+    }
+
+    register_channel {
+        let network_id = SubNetworkId::Custom(1);
+    }: _(RawOrigin::Root, network_id)
+    verify {
+        assert_eq!(Channels::<T>::get(network_id), Some(ChannelState::Registered));
+    }
+
+    open_channel {
+        let network_id = SubNetworkId::Custom(1);
+        Channels::<T>::insert(network_id, ChannelState::Registered);
+    }: _(RawOrigin::Root, network_id)
+    verify {
+        assert_eq!(Channels::<T>::get(network_id), Some(ChannelState::Active));
+    }
+
+    // Benchmark `close_channel` with queued messages, so the channel is drained.
+    close_channel {
+        let network_id = SubNetworkId::Custom(1);
+        Channels::<T>::insert(network_id, ChannelState::Active);
+        MessageQueues::<T>::try_append(
+            network_id, BridgeMessageV2 {
+            payload: Default::default(),
+            timepoint: Default::default(),
+            deadline: None,
+        }).unwrap();
+    }: _(RawOrigin::Root, network_id)
+    verify {
+        assert_eq!(Channels::<T>::get(network_id), Some(ChannelState::Draining));
+    }
+
+    report_delivery {
+        let n in 1 .. T::MaxMessagesPerCommit::get();
+        let origin = T::CallOrigin::try_successful_origin().unwrap();
+        let network_id = T::CallOrigin::ensure_origin(origin.clone()).unwrap().network_id;
+        CommittedBatches::<T>::insert(network_id, 1, n);
+        let results = vec![MessageDispatchResult::Dispatched; n as usize];
+    }: {
+        BridgeOutboundChannel::<T>::report_delivery(origin, 1, results)?;
+    }
+    verify {
+        assert_last_event::<T>(crate::outbound::Event::<T>::DeliveryReported {
+            network_id,
+            batch_nonce: 1,
+        }.into());
+    }
+
     impl_benchmark_test_suite!(
         BridgeOutboundChannel,
         crate::outbound::test::new_tester(),
@@ -0,0 +1,217 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use super::*;
+use frame_support::log::info;
+use frame_support::traits::{
+    GetStorageVersion, OnRuntimeUpgrade, PalletInfoAccess, StorageVersion,
+};
+use sp_std::marker::PhantomData;
+use sp_std::prelude::*;
+
+pub mod v2 {
+    use super::*;
+    use frame_support::pallet_prelude::OptionQuery;
+
+    #[frame_support::storage_alias]
+    pub(crate) type Interval<T: Config> =
+        StorageValue<Pallet<T>, <T as frame_system::Config>::BlockNumber, OptionQuery>;
+
+    /// Replaces the single commit interval with the per network intervals.
+    ///
+    /// The old interval becomes [`DefaultInterval`], so all networks keep it
+    /// until their own interval is set.
+    pub struct MigrateToV2<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+        fn on_runtime_upgrade() -> Weight {
+            if Pallet::<T>::on_chain_storage_version() != 1 {
+                info!(
+                    "{}: skip migration to v2, storage version is not 1",
+                    <Pallet<T> as PalletInfoAccess>::name()
+                );
+                return T::DbWeight::get().reads(1);
+            }
+            let mut writes = 2;
+            if let Some(interval) = Interval::<T>::take() {
+                DefaultInterval::<T>::put(interval);
+                writes += 1;
+            }
+            StorageVersion::new(2).put::<Pallet<T>>();
+            info!(
+                "{}: migrated to v2",
+                <Pallet<T> as PalletInfoAccess>::name()
+            );
+            T::DbWeight::get().reads_writes(2, writes)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+            Ok(Interval::<T>::get().encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+            let interval = Option::<T::BlockNumber>::decode(&mut &state[..])
+                .map_err(|_| "Failed to decode old interval")?;
+            frame_support::ensure!(
+                Interval::<T>::get().is_none(),
+                "Old interval is not removed"
+            );
+            if let Some(interval) = interval {
+                frame_support::ensure!(
+                    DefaultInterval::<T>::get() == interval,
+                    "Interval is not migrated"
+                );
+            }
+            Ok(())
+        }
+    }
+}
+
+pub mod v3 {
+    use super::*;
+
+    /// Registers the channels which were created implicitly by the first message.
+    ///
+    /// All networks known to the channel are opened, so they keep accepting messages.
+    pub struct MigrateToV3<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV3<T> {
+        fn on_runtime_upgrade() -> Weight {
+            if Pallet::<T>::on_chain_storage_version() != 2 {
+                info!(
+                    "{}: skip migration to v3, storage version is not 2",
+                    <Pallet<T> as PalletInfoAccess>::name()
+                );
+                return T::DbWeight::get().reads(1);
+            }
+            let mut reads = 1;
+            let mut writes = 1;
+            let mut networks = Vec::new();
+            for network_id in ChannelNonces::<T>::iter_keys().chain(MessageQueues::<T>::iter_keys())
+            {
+                reads += 1;
+                if !networks.contains(&network_id) {
+                    networks.push(network_id);
+                }
+            }
+            for network_id in networks {
+                reads += 1;
+                if !Channels::<T>::contains_key(network_id) {
+                    Channels::<T>::insert(network_id, ChannelState::Active);
+                    writes += 1;
+                }
+            }
+            StorageVersion::new(3).put::<Pallet<T>>();
+            info!(
+                "{}: migrated to v3",
+                <Pallet<T> as PalletInfoAccess>::name()
+            );
+            T::DbWeight::get().reads_writes(reads, writes)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+            for network_id in ChannelNonces::<T>::iter_keys() {
+                frame_support::ensure!(
+                    Channels::<T>::contains_key(network_id),
+                    "Channel is not registered"
+                );
+            }
+            Ok(())
+        }
+    }
+}
+
+pub mod v4 {
+    use super::*;
+
+    type OldMessage<T> = BridgeMessage<<T as Config>::MaxMessagePayloadSize>;
+
+    /// Migrates the queued messages to [`BridgeMessageV2`], the messages
+    /// queued before the upgrade have no deadline.
+    pub struct MigrateToV4<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV4<T> {
+        fn on_runtime_upgrade() -> Weight {
+            if Pallet::<T>::on_chain_storage_version() != 3 {
+                info!(
+                    "{}: skip migration to v4, storage version is not 3",
+                    <Pallet<T> as PalletInfoAccess>::name()
+                );
+                return T::DbWeight::get().reads(1);
+            }
+            let mut translated = 0u64;
+            MessageQueues::<T>::translate::<BoundedVec<OldMessage<T>, T::MaxMessagesPerCommit>, _>(
+                |_, messages| {
+                    translated += 1;
+                    // Can't be truncated, both vectors have the same bound
+                    Some(BoundedVec::truncate_from(
+                        messages.into_iter().map(Into::into).collect(),
+                    ))
+                },
+            );
+            PendingMessages::<T>::translate::<OldMessage<T>, _>(|_, _, message| {
+                translated += 1;
+                Some(message.into())
+            });
+            StorageVersion::new(4).put::<Pallet<T>>();
+            info!(
+                "{}: migrated to v4, {} entries translated",
+                <Pallet<T> as PalletInfoAccess>::name(),
+                translated
+            );
+            T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+            let queues = MessageQueues::<T>::iter_keys().count() as u64;
+            let pending = PendingMessages::<T>::iter_keys().count() as u64;
+            Ok((queues, pending).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+            let (queues, pending) = <(u64, u64)>::decode(&mut &state[..])
+                .map_err(|_| "Failed to decode messages count")?;
+            frame_support::ensure!(
+                MessageQueues::<T>::iter_values().count() as u64 == queues,
+                "Message queues are not migrated"
+            );
+            frame_support::ensure!(
+                PendingMessages::<T>::iter_values().count() as u64 == pending,
+                "Pending messages are not migrated"
+            );
+            Ok(())
+        }
+    }
+}
@@ -30,17 +30,26 @@
 
 //! Channel for passing messages from substrate to ethereum.
 
-use bridge_types::substrate::BridgeMessage;
-use codec::Encode;
+use bridge_types::substrate::{
+    BridgeMessage, BridgeMessageV2, MessageDispatchResult, OutboundChannelCall, SubstrateAppCall,
+    SubstrateBridgeMessageEncode,
+};
+use bridge_types::traits::{MessageTimeoutReporter, OutboundChannel};
+use codec::{Decode, Encode};
+use frame_support::dispatch::DispatchResult;
 use frame_support::ensure;
-use frame_support::traits::Get;
+use frame_support::traits::{Currency, Get};
 use frame_support::weights::Weight;
+use frame_system::RawOrigin;
 use sp_core::H256;
 use sp_io::offchain_index;
+use sp_runtime::BoundedVec;
+use sp_std::marker::PhantomData;
 
-use bridge_types::types::MessageNonce;
+use bridge_types::types::{ChannelState, MessageNonce};
 use bridge_types::SubNetworkId;
 
+pub mod migrations;
 pub mod weights;
 pub use weights::WeightInfo;
 
@@ -52,26 +61,122 @@ mod test;
 
 pub use pallet::*;
 
+/// Max number of commitments pruned for a network by one offchain worker run.
+pub const MAX_PRUNED_COMMITMENTS: u64 = 100;
+
+pub type BalanceOf<T> =
+    <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// Fee charged for submitting a message to a network.
+#[derive(
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    Encode,
+    Decode,
+    scale_info::TypeInfo,
+    frame_support::RuntimeDebug,
+)]
+pub struct MessageFee<Balance> {
+    /// Fee charged for every message.
+    pub base_fee: Balance,
+    /// Fee charged for every byte of the message payload.
+    pub byte_fee: Balance,
+}
+
+/// Format of the commitments generated for a network.
+#[derive(
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    Encode,
+    Decode,
+    scale_info::TypeInfo,
+    frame_support::RuntimeDebug,
+)]
+pub enum CommitmentFormat {
+    /// [`bridge_types::substrate::Commitment`], the whole batch is submitted at once.
+    #[default]
+    Batch,
+    /// [`bridge_types::substrate::MerkleCommitment`], messages can be submitted
+    /// one by one with their inclusion proofs.
+    Merkle,
+    /// [`bridge_types::substrate::CommitmentV2`], [`CommitmentFormat::Batch`]
+    /// with the message deadlines.
+    BatchV2,
+    /// [`bridge_types::substrate::MerkleCommitmentV2`], [`CommitmentFormat::Merkle`]
+    /// with the message deadlines.
+    MerkleV2,
+}
+
+impl CommitmentFormat {
+    /// Whether the commitments pass the message deadlines to the destination,
+    /// the destination should support the format before it is set.
+    pub fn supports_deadlines(&self) -> bool {
+        matches!(self, CommitmentFormat::BatchV2 | CommitmentFormat::MerkleV2)
+    }
+}
+
+/// Reports the expired messages back to the source network as
+/// [`SubstrateAppCall::ReportMessageTimeout`] over the outbound channel,
+/// so the source app refunds the transfer.
+pub struct SubstrateAppTimeoutReporter<T>(PhantomData<T>);
+
+impl<T: Config> MessageTimeoutReporter<SubNetworkId> for SubstrateAppTimeoutReporter<T> {
+    fn report_timeout(network_id: SubNetworkId, message_id: H256) -> DispatchResult {
+        let payload = SubstrateAppCall::ReportMessageTimeout { message_id }.prepare_message();
+        Pallet::<T>::submit(network_id, &RawOrigin::Root, &payload, ())?;
+        Ok(())
+    }
+
+    fn report_timeout_weight() -> Weight {
+        <Pallet<T> as OutboundChannel<SubNetworkId, T::AccountId, ()>>::submit_weight()
+    }
+}
+
+impl<T: Config> From<OutboundChannelCall> for Call<T> {
+    fn from(value: OutboundChannelCall) -> Self {
+        match value {
+            OutboundChannelCall::ReportDelivery {
+                batch_nonce,
+                results,
+            } => Call::report_delivery {
+                batch_nonce,
+                results,
+            },
+        }
+    }
+}
+
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
     use bridge_types::traits::AuxiliaryDigestHandler;
+    use bridge_types::traits::BridgePauseChecker;
+    use bridge_types::traits::MessageDeliveryHandler;
     use bridge_types::traits::MessageStatusNotifier;
-    use bridge_types::traits::OutboundChannel;
     use bridge_types::traits::TimepointProvider;
     use bridge_types::types::AuxiliaryDigestItem;
+    use bridge_types::types::CallOriginOutput;
+    use bridge_types::types::MessageDirection;
     use bridge_types::types::MessageId;
     use bridge_types::types::MessageStatus;
     use bridge_types::GenericNetworkId;
     use bridge_types::GenericTimepoint;
-    use frame_support::log::debug;
+    use frame_support::log::{debug, warn};
     use frame_support::pallet_prelude::*;
+    use frame_support::traits::ExistenceRequirement;
     use frame_support::traits::StorageVersion;
     use frame_support::Parameter;
     use frame_system::pallet_prelude::*;
-    use frame_system::RawOrigin;
-    use sp_runtime::traits::Zero;
+    use sp_runtime::offchain::storage::StorageValueRef;
+    use sp_runtime::traits::{Saturating, Zero};
     use sp_runtime::DispatchError;
+    use sp_std::prelude::*;
 
     #[pallet::config]
     pub trait Config: frame_system::Config + pallet_timestamp::Config {
@@ -83,16 +188,43 @@ pub mod pallet {
         /// Max number of messages that can be queued and committed in one go for a given channel.
         type MaxMessagesPerCommit: Get<u32>;
 
+        /// Max number of messages waiting in [`PendingMessages`] for all channels.
+        /// Messages which don't fit into full queues are rejected if it is zero.
+        #[pallet::constant]
+        type MaxPendingMessages: Get<u32>;
+
+        /// Max weight of the commits in one block, networks which don't fit
+        /// are committed in the next blocks.
+        #[pallet::constant]
+        type MaxCommitWeight: Get<Weight>;
+
+        /// Number of the last commitments kept offchain for each network,
+        /// older ones are pruned by the offchain worker. Zero keeps all commitments.
+        #[pallet::constant]
+        type MaxStoredCommitments: Get<u64>;
+
         type AssetId: Parameter;
 
         type Balance: Parameter;
 
+        /// Currency to pay message fees.
+        type Currency: Currency<Self::AccountId, Balance = Self::Balance>;
+
+        /// Account which receives message fees.
+        type FeeAccountId: Get<Self::AccountId>;
+
         type MessageStatusNotifier: MessageStatusNotifier<
             Self::AssetId,
             Self::AccountId,
             Self::Balance,
         >;
 
+        /// Origin of the delivery reports sent back by the destination networks.
+        type CallOrigin: EnsureOrigin<
+            Self::RuntimeOrigin,
+            Success = CallOriginOutput<SubNetworkId, H256, ()>,
+        >;
+
         type AuxiliaryDigestHandler: AuxiliaryDigestHandler;
 
         type TimepointProvider: TimepointProvider;
@@ -100,22 +232,41 @@ pub mod pallet {
         #[pallet::constant]
         type ThisNetworkId: Get<GenericNetworkId>;
 
+        /// Checks whether the bridge messages are paused.
+        type BridgePause: BridgePauseChecker;
+
+        /// Handles the messages reported as dispatched or failed by the destination.
+        type MessageDeliveryHandler: MessageDeliveryHandler<SubNetworkId>;
+
         /// Weight information for extrinsics in this pallet
         type WeightInfo: WeightInfo;
     }
 
-    /// Interval between committing messages.
+    /// Interval between committing messages for each network,
+    /// [`DefaultInterval`] is used for the networks without their own interval.
     #[pallet::storage]
-    #[pallet::getter(fn interval)]
-    pub(crate) type Interval<T: Config> =
-        StorageValue<_, T::BlockNumber, ValueQuery, DefaultInterval<T>>;
+    pub(crate) type Intervals<T: Config> =
+        StorageMap<_, Identity, SubNetworkId, T::BlockNumber, OptionQuery>;
+
+    /// Interval between committing messages for the networks without their own interval.
+    #[pallet::storage]
+    #[pallet::getter(fn default_interval)]
+    pub(crate) type DefaultInterval<T: Config> =
+        StorageValue<_, T::BlockNumber, ValueQuery, InitialInterval<T>>;
 
     #[pallet::type_value]
-    pub(crate) fn DefaultInterval<T: Config>() -> T::BlockNumber {
+    pub(crate) fn InitialInterval<T: Config>() -> T::BlockNumber {
         // TODO: Select interval
         10u32.into()
     }
 
+    /// Offset of the commit blocks for each network, so networks with the same interval
+    /// can be committed in different blocks.
+    #[pallet::storage]
+    #[pallet::getter(fn commit_offset)]
+    pub(crate) type CommitOffsets<T: Config> =
+        StorageMap<_, Identity, SubNetworkId, T::BlockNumber, ValueQuery>;
+
     /// Messages waiting to be committed. To update the queue, use `append_message_queue` and `take_message_queue` methods
     /// (to keep correct value in [QueuesTotalGas]).
     #[pallet::storage]
@@ -123,15 +274,72 @@ pub mod pallet {
         _,
         Identity,
         SubNetworkId,
-        BoundedVec<BridgeMessage<T::MaxMessagePayloadSize>, T::MaxMessagesPerCommit>,
+        BoundedVec<BridgeMessageV2<T::MaxMessagePayloadSize>, T::MaxMessagesPerCommit>,
         ValueQuery,
     >;
 
+    /// Messages which didn't fit into the full [`MessageQueues`], moved to the queue
+    /// in order after each commit, so they are committed in the subsequent batches.
     #[pallet::storage]
+    pub(crate) type PendingMessages<T: Config> = StorageDoubleMap<
+        _,
+        Identity,
+        SubNetworkId,
+        Twox64Concat,
+        u64,
+        BridgeMessageV2<T::MaxMessagePayloadSize>,
+        OptionQuery,
+    >;
+
+    /// Range `[head, tail)` of the [`PendingMessages`] indexes for each network.
+    #[pallet::storage]
+    pub(crate) type PendingMessagesRange<T: Config> =
+        StorageMap<_, Identity, SubNetworkId, (u64, u64), ValueQuery>;
+
+    /// Number of [`PendingMessages`] for all networks.
+    #[pallet::storage]
+    #[pallet::getter(fn pending_messages_count)]
+    pub type PendingMessagesCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+    /// Networks which commits didn't fit into the block commit weight,
+    /// committed first in the next block.
+    #[pallet::storage]
+    #[pallet::getter(fn deferred_commits)]
+    pub(crate) type DeferredCommits<T: Config> = StorageValue<_, Vec<SubNetworkId>, ValueQuery>;
+
+    /// The last committed network, the next commits start from the network after it.
+    #[pallet::storage]
+    pub(crate) type LastCommittedNetwork<T: Config> = StorageValue<_, SubNetworkId, OptionQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn channel_nonce)]
     pub type ChannelNonces<T: Config> = StorageMap<_, Identity, SubNetworkId, u64, ValueQuery>;
 
+    /// Registered channels, messages are accepted only for the active ones.
+    #[pallet::storage]
+    #[pallet::getter(fn channel_state)]
+    pub type Channels<T: Config> = StorageMap<_, Identity, SubNetworkId, ChannelState, OptionQuery>;
+
+    /// Fee charged from signed origins for submitting a message to a network.
+    #[pallet::storage]
+    #[pallet::getter(fn message_fees)]
+    pub type MessageFees<T: Config> =
+        StorageMap<_, Identity, SubNetworkId, MessageFee<BalanceOf<T>>, ValueQuery>;
+
+    /// Number of messages in the committed batches, removed when the destination reports
+    /// the batch delivery, so each batch is reported once.
+    #[pallet::storage]
+    pub(crate) type CommittedBatches<T: Config> =
+        StorageDoubleMap<_, Identity, SubNetworkId, Twox64Concat, u64, u32, OptionQuery>;
+
+    /// Format of the commitments generated for a network.
+    #[pallet::storage]
+    #[pallet::getter(fn commitment_format)]
+    pub type CommitmentFormats<T: Config> =
+        StorageMap<_, Identity, SubNetworkId, CommitmentFormat, ValueQuery>;
+
     /// The current storage version.
-    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(4);
 
     #[pallet::pallet]
     #[pallet::generate_store(trait Store)]
@@ -141,20 +349,67 @@ pub mod pallet {
 
     #[pallet::hooks]
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
-        // Generate a message commitment every [`Interval`] blocks.
+        // Generate a message commitment for each network every [`Intervals`] blocks,
+        // shifted by [`CommitOffsets`].
         //
         // The commitment hash is included in an [`AuxiliaryDigestItem`] in the block header,
         // with the corresponding commitment is persisted offchain.
+        //
+        // Commits are limited by [`Config::MaxCommitWeight`], the networks are committed
+        // in round-robin order and the rest of them are deferred to the next block.
         fn on_initialize(now: T::BlockNumber) -> Weight {
-            let interval = Self::interval();
-            let mut weight = Default::default();
-            if now % interval == Zero::zero() {
-                for chain_id in MessageQueues::<T>::iter_keys() {
-                    weight += Self::commit(chain_id);
+            let mut weight = <T as Config>::WeightInfo::on_initialize_base();
+            let mut networks = DeferredCommits::<T>::take();
+            let mut due = Vec::new();
+            for chain_id in MessageQueues::<T>::iter_keys() {
+                weight += <T as Config>::WeightInfo::on_initialize_non_interval();
+                if !networks.contains(&chain_id) && Self::is_commit_block(chain_id, now) {
+                    due.push(chain_id);
+                }
+            }
+            // Networks are iterated in the order of their encoded keys,
+            // so start from the one after the last committed network.
+            if let Some(last) = LastCommittedNetwork::<T>::get() {
+                let last = last.encode();
+                let start = due
+                    .iter()
+                    .position(|chain_id| chain_id.encode() > last)
+                    .unwrap_or(0);
+                due.rotate_left(start);
+            }
+            networks.extend(due);
+
+            let max_weight = T::MaxCommitWeight::get();
+            let mut committed = false;
+            let mut deferred = Vec::new();
+            for chain_id in networks {
+                // At least one network is committed to make progress
+                if !deferred.is_empty()
+                    || (committed
+                        && weight
+                            .saturating_add(Self::max_commit_weight(chain_id))
+                            .any_gt(max_weight))
+                {
+                    Self::deposit_event(Event::CommitDeferred {
+                        network_id: chain_id,
+                    });
+                    deferred.push(chain_id);
+                    continue;
                 }
+                weight += Self::commit(chain_id);
+                LastCommittedNetwork::<T>::put(chain_id);
+                committed = true;
+            }
+            if !deferred.is_empty() {
+                DeferredCommits::<T>::put(deferred);
             }
             weight
         }
+
+        // Prune commitments which are out of [`Config::MaxStoredCommitments`] from the offchain storage.
+        fn offchain_worker(_now: T::BlockNumber) {
+            Self::prune_commitments();
+        }
     }
 
     #[pallet::event]
@@ -165,13 +420,69 @@ pub mod pallet {
             batch_nonce: u64,
             message_nonce: MessageNonce,
         },
+        CommitmentFormatChanged {
+            network_id: SubNetworkId,
+            format: CommitmentFormat,
+        },
+        MessageFeeChanged {
+            network_id: SubNetworkId,
+            fee: MessageFee<BalanceOf<T>>,
+        },
+        MessageFeePaid {
+            network_id: SubNetworkId,
+            who: T::AccountId,
+            fee: BalanceOf<T>,
+        },
+        IntervalChanged {
+            network_id: SubNetworkId,
+            interval: T::BlockNumber,
+        },
+        CommitOffsetChanged {
+            network_id: SubNetworkId,
+            offset: T::BlockNumber,
+        },
+        /// The network queue is full, new messages are placed into the pending messages.
+        MessageQueueOverflowed {
+            network_id: SubNetworkId,
+        },
+        /// All pending messages of the network are moved to the queue.
+        PendingMessagesDrained {
+            network_id: SubNetworkId,
+        },
+        /// Pending messages limit is reached, new messages are rejected until
+        /// the pending messages are committed.
+        PendingMessagesLimitReached,
+        /// The network commit doesn't fit into the block and is deferred to the next block.
+        CommitDeferred {
+            network_id: SubNetworkId,
+        },
+        ChannelRegistered {
+            network_id: SubNetworkId,
+        },
+        ChannelOpened {
+            network_id: SubNetworkId,
+        },
+        /// The channel doesn't accept new messages, queued messages are committed before closure.
+        ChannelDraining {
+            network_id: SubNetworkId,
+        },
+        /// The channel is closed, `batch_nonce` is the nonce of the last committed batch.
+        ChannelClosed {
+            network_id: SubNetworkId,
+            batch_nonce: u64,
+        },
+        /// The destination reported delivery of the batch messages.
+        DeliveryReported {
+            network_id: SubNetworkId,
+            batch_nonce: u64,
+        },
     }
 
     #[pallet::error]
     pub enum Error<T> {
         /// The message payload exceeds byte limit.
         PayloadTooLarge,
-        /// No more messages can be queued for the channel during this commit cycle.
+        /// No more messages can be queued for the channel and the pending messages limit is reached.
         QueueSizeLimitReached,
         /// Maximum gas for queued batch exceeds limit.
         MaxGasTooBig,
@@ -181,6 +492,18 @@ pub mod pallet {
         Overflow,
         /// This channel already exists
         ChannelExists,
+        /// Commit interval should be greater than zero.
+        InvalidInterval,
+        /// The channel is not registered.
+        ChannelNotRegistered,
+        /// The channel doesn't accept messages.
+        ChannelNotActive,
+        /// The channel state doesn't allow the operation.
+        InvalidChannelState,
+        /// The delivery report doesn't match the committed batch.
+        InvalidDeliveryReport,
+        /// The commitment format of the network doesn't pass the message deadlines.
+        DeadlinesNotSupported,
     }
 
     impl<T: Config> Pallet<T> {
@@ -195,6 +518,8 @@ pub mod pallet {
                 *nonce += 1;
                 *nonce
             });
+            let promote_weight = Self::promote_pending_messages(network_id);
+            Self::close_drained_channel(network_id, batch_nonce);
 
             for idx in 0..messages.len() as u64 {
                 T::MessageStatusNotifier::update_status(
@@ -213,12 +538,34 @@ pub mod pallet {
 
             let average_payload_size = Self::average_payload_size(&messages);
             let messages_count = messages.len();
+            CommittedBatches::<T>::insert(network_id, batch_nonce, messages_count as u32);
 
-            let commitment =
-                bridge_types::GenericCommitment::Sub(bridge_types::substrate::Commitment {
-                    messages,
-                    nonce: batch_nonce,
-                });
+            let commitment = match CommitmentFormats::<T>::get(network_id) {
+                CommitmentFormat::Batch => {
+                    bridge_types::GenericCommitment::Sub(bridge_types::substrate::Commitment {
+                        messages: Self::messages_without_deadlines(messages),
+                        nonce: batch_nonce,
+                    })
+                }
+                CommitmentFormat::Merkle => bridge_types::GenericCommitment::SubMerkle(
+                    bridge_types::substrate::MerkleCommitment {
+                        messages: Self::messages_without_deadlines(messages),
+                        nonce: batch_nonce,
+                    },
+                ),
+                CommitmentFormat::BatchV2 => {
+                    bridge_types::GenericCommitment::SubV2(bridge_types::substrate::CommitmentV2 {
+                        messages,
+                        nonce: batch_nonce,
+                    })
+                }
+                CommitmentFormat::MerkleV2 => bridge_types::GenericCommitment::SubMerkleV2(
+                    bridge_types::substrate::MerkleCommitmentV2 {
+                        messages,
+                        nonce: batch_nonce,
+                    },
+                ),
+            };
 
             let commitment_hash = commitment.hash();
             let digest_item =
@@ -231,14 +578,277 @@ pub mod pallet {
                 block_number: <frame_system::Pallet<T>>::block_number(),
             };
             offchain_index::set(&key, &offchain_data.encode());
+            offchain_index::set(
+                &bridge_types::utils::make_offchain_latest_nonce_key(network_id.into()),
+                &batch_nonce.encode(),
+            );
 
             <T as Config>::WeightInfo::on_initialize(
                 messages_count as u32,
                 average_payload_size as u32,
             )
+            .saturating_add(promote_weight)
+        }
+
+        /// Moves up to [`Config::MaxMessagesPerCommit`] pending messages to the empty network queue,
+        /// so they are committed in the next batch.
+        pub(crate) fn promote_pending_messages(network_id: SubNetworkId) -> Weight {
+            let (mut head, tail) = PendingMessagesRange::<T>::get(network_id);
+            if head == tail {
+                return <T as Config>::WeightInfo::promote_pending_messages(0);
+            }
+            let count = (tail - head).min(T::MaxMessagesPerCommit::get() as u64);
+            let mut messages = BoundedVec::<_, T::MaxMessagesPerCommit>::default();
+            for _ in 0..count {
+                if let Some(message) = PendingMessages::<T>::take(network_id, head) {
+                    // Can't fail, `count` is limited by the queue size
+                    let _ = messages.try_push(message);
+                }
+                head += 1;
+            }
+            MessageQueues::<T>::insert(network_id, messages);
+            if head == tail {
+                PendingMessagesRange::<T>::remove(network_id);
+                Self::deposit_event(Event::PendingMessagesDrained { network_id });
+            } else {
+                PendingMessagesRange::<T>::insert(network_id, (head, tail));
+            }
+            PendingMessagesCount::<T>::mutate(|pending| {
+                *pending = pending.saturating_sub(count as u32)
+            });
+            <T as Config>::WeightInfo::promote_pending_messages(count as u32)
         }
 
-        fn average_payload_size(messages: &[BridgeMessage<T::MaxMessagePayloadSize>]) -> usize {
+        /// Closes the draining channel when all its messages are committed.
+        fn close_drained_channel(network_id: SubNetworkId, batch_nonce: u64) {
+            if Channels::<T>::get(network_id) == Some(ChannelState::Draining)
+                && MessageQueues::<T>::decode_len(network_id).unwrap_or(0) == 0
+            {
+                Channels::<T>::insert(network_id, ChannelState::Closed);
+                Self::deposit_event(Event::ChannelClosed {
+                    network_id,
+                    batch_nonce,
+                });
+            }
+        }
+
+        fn ensure_channel_active(network_id: SubNetworkId) -> DispatchResult {
+            match Channels::<T>::get(network_id) {
+                Some(ChannelState::Active) => Ok(()),
+                Some(_) => Err(Error::<T>::ChannelNotActive.into()),
+                None => Err(Error::<T>::ChannelNotRegistered.into()),
+            }
+        }
+
+        /// Queues the message, or places it after the full queue.
+        fn do_submit(
+            network_id: SubNetworkId,
+            who: &RawOrigin<T::AccountId>,
+            payload: &[u8],
+            deadline: Option<u64>,
+        ) -> Result<H256, DispatchError> {
+            debug!("Send message from {:?} to network {:?}", who, network_id);
+            T::BridgePause::ensure_not_paused(network_id.into(), MessageDirection::Outbound, None)?;
+            Self::ensure_channel_active(network_id)?;
+            ensure!(
+                payload.len() <= T::MaxMessagePayloadSize::get() as usize,
+                Error::<T>::PayloadTooLarge,
+            );
+            let message = BridgeMessageV2 {
+                payload: payload
+                    .to_vec()
+                    .try_into()
+                    .map_err(|_| Error::<T>::PayloadTooLarge)?,
+                timepoint: T::TimepointProvider::get_timepoint(),
+                deadline,
+            };
+
+            let messages_count = MessageQueues::<T>::decode_len(network_id).unwrap_or(0) as u64;
+            let (batch_nonce, message_nonce) =
+                if messages_count < T::MaxMessagesPerCommit::get() as u64 {
+                    let batch_nonce = ChannelNonces::<T>::get(network_id)
+                        .checked_add(1)
+                        .ok_or(Error::<T>::Overflow)?;
+
+                    Self::charge_fee(network_id, who, payload.len() as u32)?;
+
+                    MessageQueues::<T>::try_append(network_id, message)
+                        .map_err(|_| Error::<T>::QueueSizeLimitReached)?;
+                    (batch_nonce, messages_count)
+                } else {
+                    Self::append_pending_message(network_id, who, message)?
+                };
+            Self::deposit_event(Event::MessageAccepted {
+                network_id,
+                batch_nonce,
+                message_nonce,
+            });
+            Ok(MessageId::batched(
+                T::ThisNetworkId::get(),
+                network_id.into(),
+                batch_nonce,
+                message_nonce,
+            )
+            .hash())
+        }
+
+        /// Places the message after the full network queue, returns batch and message nonces
+        /// the message will be committed with.
+        fn append_pending_message(
+            network_id: SubNetworkId,
+            who: &RawOrigin<T::AccountId>,
+            message: BridgeMessageV2<T::MaxMessagePayloadSize>,
+        ) -> Result<(u64, MessageNonce), DispatchError> {
+            let pending_count = PendingMessagesCount::<T>::get();
+            ensure!(
+                pending_count < T::MaxPendingMessages::get(),
+                Error::<T>::QueueSizeLimitReached,
+            );
+            let max_messages = T::MaxMessagesPerCommit::get() as u64;
+            let (head, tail) = PendingMessagesRange::<T>::get(network_id);
+            let position = tail - head;
+            // The queue is committed in the next batch, the pending messages
+            // fill the subsequent batches in order.
+            let batch_nonce = ChannelNonces::<T>::get(network_id)
+                .checked_add(2 + position / max_messages)
+                .ok_or(Error::<T>::Overflow)?;
+
+            Self::charge_fee(network_id, who, message.payload.len() as u32)?;
+
+            PendingMessages::<T>::insert(network_id, tail, message);
+            PendingMessagesRange::<T>::insert(network_id, (head, tail + 1));
+            PendingMessagesCount::<T>::put(pending_count + 1);
+            if position == 0 {
+                Self::deposit_event(Event::MessageQueueOverflowed { network_id });
+            }
+            if pending_count + 1 == T::MaxPendingMessages::get() {
+                Self::deposit_event(Event::PendingMessagesLimitReached);
+            }
+            Ok((batch_nonce, position % max_messages))
+        }
+
+        /// Removes the commitments older than the last [`Config::MaxStoredCommitments`]
+        /// from the offchain storage, at most [`MAX_PRUNED_COMMITMENTS`] per network at once.
+        pub(crate) fn prune_commitments() {
+            let max_stored = T::MaxStoredCommitments::get();
+            if max_stored == 0 {
+                return;
+            }
+            for (network_id, latest_nonce) in ChannelNonces::<T>::iter() {
+                let network_id = GenericNetworkId::Sub(network_id);
+                let prune_to = latest_nonce.saturating_sub(max_stored);
+                let pruned_key = bridge_types::utils::make_offchain_pruned_nonce_key(network_id);
+                let pruned_ref = StorageValueRef::persistent(&pruned_key);
+                let pruned = match pruned_ref.get::<u64>() {
+                    Ok(pruned) => pruned.unwrap_or_default(),
+                    Err(_) => {
+                        warn!("Failed to decode pruned nonce for {:?}", network_id);
+                        continue;
+                    }
+                };
+                if pruned >= prune_to {
+                    continue;
+                }
+                let prune_to = prune_to.min(pruned.saturating_add(MAX_PRUNED_COMMITMENTS));
+                debug!(
+                    "Prune {:?} commitments {}..={}",
+                    network_id,
+                    pruned + 1,
+                    prune_to
+                );
+                for batch_nonce in pruned + 1..=prune_to {
+                    let key = bridge_types::utils::make_offchain_key(network_id, batch_nonce);
+                    StorageValueRef::persistent(&key).clear();
+                }
+                pruned_ref.set(&prune_to);
+            }
+        }
+
+        /// Upper bound of the network commit weight.
+        fn max_commit_weight(network_id: SubNetworkId) -> Weight {
+            let messages_count = MessageQueues::<T>::decode_len(network_id).unwrap_or(0) as u32;
+            <T as Config>::WeightInfo::on_initialize(
+                messages_count.max(1),
+                T::MaxMessagePayloadSize::get(),
+            )
+            .saturating_add(<T as Config>::WeightInfo::promote_pending_messages(
+                T::MaxMessagesPerCommit::get(),
+            ))
+        }
+
+        /// Interval between committing messages for the network.
+        pub fn interval(network_id: SubNetworkId) -> T::BlockNumber {
+            Intervals::<T>::get(network_id).unwrap_or_else(DefaultInterval::<T>::get)
+        }
+
+        pub(crate) fn is_commit_block(network_id: SubNetworkId, now: T::BlockNumber) -> bool {
+            let interval = Self::interval(network_id);
+            if interval.is_zero() {
+                return false;
+            }
+            now % interval == CommitOffsets::<T>::get(network_id) % interval
+        }
+
+        /// Fee for submitting a message with `payload_size` bytes payload by a signed origin.
+        pub fn message_fee(network_id: SubNetworkId, payload_size: u32) -> BalanceOf<T> {
+            let fee = MessageFees::<T>::get(network_id);
+            fee.byte_fee
+                .saturating_mul(payload_size.into())
+                .saturating_add(fee.base_fee)
+        }
+
+        fn charge_fee(
+            network_id: SubNetworkId,
+            who: &RawOrigin<T::AccountId>,
+            payload_size: u32,
+        ) -> DispatchResult {
+            // Only users pay for messages, root and pallets sending messages on behalf of
+            // this chain are exempt
+            let RawOrigin::Signed(who) = who else {
+                return Ok(());
+            };
+            let fee = Self::message_fee(network_id, payload_size);
+            if fee.is_zero() {
+                return Ok(());
+            }
+            T::Currency::transfer(
+                who,
+                &T::FeeAccountId::get(),
+                fee,
+                ExistenceRequirement::KeepAlive,
+            )
+            .map_err(|_| Error::<T>::NoFunds)?;
+            Self::deposit_event(Event::MessageFeePaid {
+                network_id,
+                who: who.clone(),
+                fee,
+            });
+            Ok(())
+        }
+
+        /// Weight of `report_delivery` with `results_count` results, each of them is handled
+        /// by [`Config::MessageDeliveryHandler`].
+        fn report_delivery_weight(results_count: u32) -> Weight {
+            <T as Config>::WeightInfo::report_delivery(results_count).saturating_add(
+                T::MessageDeliveryHandler::on_message_delivered_weight()
+                    .saturating_mul(results_count.into()),
+            )
+        }
+
+        /// Messages of the commitment formats without deadlines. The deadlines are set only
+        /// with the deadline formats, so they are dropped only if the format is changed back
+        /// before the messages are committed.
+        fn messages_without_deadlines(
+            messages: BoundedVec<
+                BridgeMessageV2<T::MaxMessagePayloadSize>,
+                T::MaxMessagesPerCommit,
+            >,
+        ) -> BoundedVec<BridgeMessage<T::MaxMessagePayloadSize>, T::MaxMessagesPerCommit> {
+            // Can't be truncated, both vectors have the same bound
+            BoundedVec::truncate_from(messages.into_iter().map(Into::into).collect())
+        }
+
+        fn average_payload_size(messages: &[BridgeMessageV2<T::MaxMessagePayloadSize>]) -> usize {
             let sum: usize = messages.iter().fold(0, |acc, x| acc + x.payload.len());
             // We overestimate message payload size rather than underestimate.
             // So add 1 here to account for integer division truncation.
@@ -246,16 +856,219 @@ pub mod pallet {
         }
     }
 
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Sets format of the commitments generated for the network,
+        /// applied starting from the next commitment.
+        #[pallet::call_index(0)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_commitment_format())]
+        pub fn set_commitment_format(
+            origin: OriginFor<T>,
+            network_id: SubNetworkId,
+            format: CommitmentFormat,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            CommitmentFormats::<T>::insert(network_id, format);
+            Self::deposit_event(Event::CommitmentFormatChanged { network_id, format });
+            Ok(())
+        }
+
+        /// Sets fee charged from signed origins for submitting a message to the network.
+        #[pallet::call_index(1)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_message_fee())]
+        pub fn set_message_fee(
+            origin: OriginFor<T>,
+            network_id: SubNetworkId,
+            base_fee: BalanceOf<T>,
+            byte_fee: BalanceOf<T>,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            let fee = MessageFee { base_fee, byte_fee };
+            MessageFees::<T>::insert(network_id, fee);
+            Self::deposit_event(Event::MessageFeeChanged { network_id, fee });
+            Ok(())
+        }
+
+        /// Sets interval between commits of the network messages.
+        #[pallet::call_index(2)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_interval())]
+        pub fn set_interval(
+            origin: OriginFor<T>,
+            network_id: SubNetworkId,
+            interval: T::BlockNumber,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            ensure!(!interval.is_zero(), Error::<T>::InvalidInterval);
+            Intervals::<T>::insert(network_id, interval);
+            Self::deposit_event(Event::IntervalChanged {
+                network_id,
+                interval,
+            });
+            Ok(())
+        }
+
+        /// Commits queued messages of the network in the current block.
+        #[pallet::call_index(3)]
+        #[pallet::weight(<T as Config>::WeightInfo::on_initialize(
+            T::MaxMessagesPerCommit::get(),
+            T::MaxMessagePayloadSize::get(),
+        ).saturating_add(<T as Config>::WeightInfo::promote_pending_messages(
+            T::MaxMessagesPerCommit::get(),
+        )))]
+        pub fn force_commit(
+            origin: OriginFor<T>,
+            network_id: SubNetworkId,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            let weight = Self::commit(network_id);
+            Ok(Some(weight).into())
+        }
+
+        /// Assigns consecutive commit offsets to the networks in the given order,
+        /// so networks with the same interval are committed in different blocks.
+        #[pallet::call_index(4)]
+        #[pallet::weight(<T as Config>::WeightInfo::stagger_commits(networks.len() as u32))]
+        pub fn stagger_commits(
+            origin: OriginFor<T>,
+            networks: Vec<SubNetworkId>,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            for (offset, network_id) in networks.into_iter().enumerate() {
+                let offset: T::BlockNumber = (offset as u32).into();
+                CommitOffsets::<T>::insert(network_id, offset);
+                Self::deposit_event(Event::CommitOffsetChanged { network_id, offset });
+            }
+            Ok(())
+        }
+
+        /// Registers the channel with the network, it accepts messages after it is opened.
+        #[pallet::call_index(5)]
+        #[pallet::weight(<T as Config>::WeightInfo::register_channel())]
+        pub fn register_channel(origin: OriginFor<T>, network_id: SubNetworkId) -> DispatchResult {
+            ensure_root(origin)?;
+            ensure!(
+                !Channels::<T>::contains_key(network_id),
+                Error::<T>::ChannelExists
+            );
+            Channels::<T>::insert(network_id, ChannelState::Registered);
+            Self::deposit_event(Event::ChannelRegistered { network_id });
+            Ok(())
+        }
+
+        /// Opens the registered or closed channel for messages.
+        #[pallet::call_index(6)]
+        #[pallet::weight(<T as Config>::WeightInfo::open_channel())]
+        pub fn open_channel(origin: OriginFor<T>, network_id: SubNetworkId) -> DispatchResult {
+            ensure_root(origin)?;
+            match Channels::<T>::get(network_id) {
+                Some(ChannelState::Registered | ChannelState::Closed) => {}
+                Some(_) => frame_support::fail!(Error::<T>::InvalidChannelState),
+                None => frame_support::fail!(Error::<T>::ChannelNotRegistered),
+            }
+            Channels::<T>::insert(network_id, ChannelState::Active);
+            Self::deposit_event(Event::ChannelOpened { network_id });
+            Ok(())
+        }
+
+        /// Stops accepting messages on the channel. The channel is closed right away
+        /// if there are no queued messages, otherwise it is drained and closed
+        /// after the queued and pending messages are committed.
+        #[pallet::call_index(7)]
+        #[pallet::weight(<T as Config>::WeightInfo::close_channel())]
+        pub fn close_channel(origin: OriginFor<T>, network_id: SubNetworkId) -> DispatchResult {
+            ensure_root(origin)?;
+            match Channels::<T>::get(network_id) {
+                Some(ChannelState::Registered | ChannelState::Active) => {}
+                Some(_) => frame_support::fail!(Error::<T>::InvalidChannelState),
+                None => frame_support::fail!(Error::<T>::ChannelNotRegistered),
+            }
+            Channels::<T>::insert(network_id, ChannelState::Draining);
+            Self::deposit_event(Event::ChannelDraining { network_id });
+            Self::close_drained_channel(network_id, ChannelNonces::<T>::get(network_id));
+            Ok(())
+        }
+
+        /// Updates status of the batch messages by the delivery report of the destination,
+        /// the report timepoint is the end timepoint of the messages. The report must have
+        /// a result for each committed message, the batch is reported once.
+        /// The dispatched and failed messages are passed to [`Config::MessageDeliveryHandler`],
+        /// the failed ones are refunded by it.
+        #[pallet::call_index(8)]
+        #[pallet::weight(Pallet::<T>::report_delivery_weight(results.len() as u32))]
+        pub fn report_delivery(
+            origin: OriginFor<T>,
+            batch_nonce: u64,
+            results: Vec<MessageDispatchResult>,
+        ) -> DispatchResult {
+            let CallOriginOutput {
+                network_id,
+                timepoint,
+                ..
+            } = T::CallOrigin::ensure_origin(origin)?;
+            let messages_count = CommittedBatches::<T>::get(network_id, batch_nonce)
+                .ok_or(Error::<T>::InvalidDeliveryReport)?;
+            ensure!(
+                results.len() == messages_count as usize,
+                Error::<T>::InvalidDeliveryReport
+            );
+            CommittedBatches::<T>::remove(network_id, batch_nonce);
+            for (idx, result) in results.into_iter().enumerate() {
+                let message_id = MessageId::batched(
+                    T::ThisNetworkId::get(),
+                    network_id.into(),
+                    batch_nonce,
+                    idx as u64,
+                )
+                .hash();
+                let status = match result {
+                    MessageDispatchResult::Dispatched => {
+                        T::MessageDeliveryHandler::on_message_dispatched(network_id, message_id);
+                        MessageStatus::Done
+                    }
+                    MessageDispatchResult::Failed
+                        if T::MessageDeliveryHandler::on_message_failed(network_id, message_id) =>
+                    {
+                        MessageStatus::Refunded
+                    }
+                    MessageDispatchResult::Failed => MessageStatus::Failed,
+                    // Status of the expired messages is updated by the timeout reports
+                    MessageDispatchResult::Expired | MessageDispatchResult::DeliveryReport => {
+                        continue
+                    }
+                };
+                T::MessageStatusNotifier::update_status(
+                    GenericNetworkId::Sub(network_id),
+                    message_id,
+                    status,
+                    timepoint,
+                );
+            }
+            Self::deposit_event(Event::DeliveryReported {
+                network_id,
+                batch_nonce,
+            });
+            Ok(())
+        }
+    }
+
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config> {
+        /// Commit interval of the networks without their own interval.
         pub interval: T::BlockNumber,
+        /// Commit intervals of the networks, `interval` is used for the rest.
+        #[cfg_attr(feature = "std", serde(default))]
+        pub intervals: Vec<(SubNetworkId, T::BlockNumber)>,
+        /// Networks with the channels opened at genesis.
+        pub channels: Vec<SubNetworkId>,
     }
 
     #[cfg(feature = "std")]
     impl<T: Config> Default for GenesisConfig<T> {
         fn default() -> Self {
             Self {
-                interval: 10u32.into(),
+                interval: InitialInterval::<T>::get(),
+                intervals: Default::default(),
+                channels: Default::default(),
             }
         }
     }
@@ -263,7 +1076,21 @@ pub mod pallet {
     #[pallet::genesis_build]
     impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
         fn build(&self) {
-            Interval::<T>::set(self.interval);
+            assert!(
+                !self.interval.is_zero(),
+                "Commit interval should be greater than zero"
+            );
+            DefaultInterval::<T>::put(self.interval);
+            for (network_id, interval) in &self.intervals {
+                assert!(
+                    !interval.is_zero(),
+                    "Commit interval should be greater than zero"
+                );
+                Intervals::<T>::insert(network_id, interval);
+            }
+            for network_id in &self.channels {
+                Channels::<T>::insert(network_id, ChannelState::Active);
+            }
         }
     }
 
@@ -275,48 +1102,27 @@ pub mod pallet {
             payload: &[u8],
             _: (),
         ) -> Result<H256, DispatchError> {
-            debug!("Send message from {:?} to network {:?}", who, network_id);
-            let messages_count = MessageQueues::<T>::decode_len(network_id).unwrap_or(0) as u64;
-            ensure!(
-                messages_count < T::MaxMessagesPerCommit::get() as u64,
-                Error::<T>::QueueSizeLimitReached,
-            );
+            Self::do_submit(network_id, who, payload, None)
+        }
+
+        /// Submit message with the deadline on the outbound channel,
+        /// the commitment format of the network should support the deadlines.
+        fn submit_with_deadline(
+            network_id: SubNetworkId,
+            who: &RawOrigin<T::AccountId>,
+            payload: &[u8],
+            _: (),
+            deadline: u64,
+        ) -> Result<H256, DispatchError> {
             ensure!(
-                payload.len() <= T::MaxMessagePayloadSize::get() as usize,
-                Error::<T>::PayloadTooLarge,
+                CommitmentFormats::<T>::get(network_id).supports_deadlines(),
+                Error::<T>::DeadlinesNotSupported
             );
-
-            let batch_nonce = ChannelNonces::<T>::get(network_id)
-                .checked_add(1)
-                .ok_or(Error::<T>::Overflow)?;
-
-            MessageQueues::<T>::try_append(
-                network_id,
-                BridgeMessage {
-                    payload: payload
-                        .to_vec()
-                        .try_into()
-                        .map_err(|_| Error::<T>::PayloadTooLarge)?,
-                    timepoint: T::TimepointProvider::get_timepoint(),
-                },
-            )
-            .map_err(|_| Error::<T>::QueueSizeLimitReached)?;
-            Self::deposit_event(Event::MessageAccepted {
-                network_id,
-                batch_nonce,
-                message_nonce: messages_count,
-            });
-            Ok(MessageId::batched(
-                T::ThisNetworkId::get(),
-                network_id.into(),
-                batch_nonce,
-                messages_count,
-            )
-            .hash())
+            Self::do_submit(network_id, who, payload, Some(deadline))
         }
 
         fn submit_weight() -> Weight {
-            Default::default()
+            <T as Config>::WeightInfo::submit()
         }
     }
 }
@@ -29,15 +29,23 @@
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use super::*;
-use bridge_types::GenericNetworkId;
+use bridge_types::substrate::{BridgeMessage, MessageDispatchResult};
+use bridge_types::types::{
+    BridgeOffchainData, CallOriginOutput, ChannelState, MessageId, MessageStatus,
+};
+use bridge_types::{GenericAccount, GenericCommitment, GenericNetworkId, GenericTimepoint};
 use codec::{Decode, MaxEncodedLen};
 use currencies::BasicCurrencyAdapter;
 
-use bridge_types::traits::{OutboundChannel, TimepointProvider};
-use frame_support::traits::{Everything, GenesisBuild};
+use bridge_types::traits::{
+    MessageDeliveryHandler, MessageStatusNotifier, OutboundChannel, TimepointProvider,
+};
+use frame_support::traits::{EnsureOrigin, Everything, GenesisBuild, OffchainWorker, OnInitialize};
 use frame_support::{assert_noop, assert_ok, parameter_types, Deserialize, Serialize};
 use frame_system::RawOrigin;
 use scale_info::TypeInfo;
+use sp_core::offchain::testing::TestOffchainExt;
+use sp_core::offchain::{OffchainDbExt, OffchainWorkerExt};
 use sp_core::H256;
 use sp_keyring::AccountKeyring as Keyring;
 use sp_runtime::testing::Header;
@@ -64,7 +72,7 @@ frame_support::construct_runtime!(
         Tokens: tokens::{Pallet, Call, Config<T>, Storage, Event<T>},
         Currencies: currencies::{Pallet, Call, Storage},
         Balances: pallet_balances::{Pallet, Call, Storage, Event<T>},
-        BridgeOutboundChannel: bridge_outbound_channel::{Pallet, Config<T>, Storage, Event<T>},
+        BridgeOutboundChannel: bridge_outbound_channel::{Pallet, Call, Config<T>, Storage, Event<T>},
     }
 );
 
@@ -178,7 +186,86 @@ parameter_types! {
 parameter_types! {
     pub const MaxMessagePayloadSize: u32 = 128;
     pub const MaxMessagesPerCommit: u32 = 5;
+    pub const MaxPendingMessages: u32 = 10;
+    pub const MaxStoredCommitments: u64 = 2;
+    pub static MaxCommitWeight: Weight = Weight::MAX;
     pub const ThisNetworkId: GenericNetworkId = GenericNetworkId::Sub(SubNetworkId::Mainnet);
+    pub static UpdatedStatuses: Vec<(H256, MessageStatus, GenericTimepoint)> = vec![];
+    pub static RefundableMessages: Vec<H256> = vec![];
+    pub static DispatchedMessages: Vec<H256> = vec![];
+}
+
+pub struct MockDeliveryHandler;
+
+impl MessageDeliveryHandler<SubNetworkId> for MockDeliveryHandler {
+    fn on_message_dispatched(_network_id: SubNetworkId, message_id: H256) {
+        DispatchedMessages::mutate(|messages| messages.push(message_id));
+    }
+
+    fn on_message_failed(_network_id: SubNetworkId, message_id: H256) -> bool {
+        RefundableMessages::get().contains(&message_id)
+    }
+
+    fn on_message_delivered_weight() -> Weight {
+        Weight::zero()
+    }
+}
+
+pub struct MockStatusNotifier;
+
+impl MessageStatusNotifier<(), AccountId, u128> for MockStatusNotifier {
+    fn update_status(
+        _network_id: GenericNetworkId,
+        message_id: H256,
+        status: MessageStatus,
+        end_timepoint: GenericTimepoint,
+    ) {
+        UpdatedStatuses::mutate(|statuses| statuses.push((message_id, status, end_timepoint)));
+    }
+
+    fn inbound_request(
+        _network_id: GenericNetworkId,
+        _message_id: H256,
+        _source: GenericAccount<AccountId>,
+        _dest: AccountId,
+        _asset_id: (),
+        _amount: u128,
+        _start_timepoint: GenericTimepoint,
+        _status: MessageStatus,
+    ) {
+    }
+
+    fn outbound_request(
+        _network_id: GenericNetworkId,
+        _message_id: H256,
+        _source: AccountId,
+        _dest: GenericAccount<AccountId>,
+        _asset_id: (),
+        _amount: u128,
+        _status: MessageStatus,
+    ) {
+    }
+}
+
+/// Root origin stands for the delivery reports sent from the base network.
+pub struct EnsureDeliveryReport;
+
+impl EnsureOrigin<RuntimeOrigin> for EnsureDeliveryReport {
+    type Success = CallOriginOutput<SubNetworkId, H256, ()>;
+
+    fn try_origin(o: RuntimeOrigin) -> Result<Self::Success, RuntimeOrigin> {
+        frame_system::EnsureRoot::<AccountId>::try_origin(o).map(|_| CallOriginOutput {
+            network_id: BASE_NETWORK_ID,
+            message_id: H256::zero(),
+            timepoint: GenericTimepoint::Parachain(100),
+            additional: (),
+        })
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn try_successful_origin() -> Result<RuntimeOrigin, ()> {
+        Ok(RuntimeOrigin::root())
+    }
 }
 
 pub struct GenericTimepointProvider;
@@ -193,10 +280,18 @@ impl bridge_outbound_channel::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type MaxMessagePayloadSize = MaxMessagePayloadSize;
     type MaxMessagesPerCommit = MaxMessagesPerCommit;
-    type MessageStatusNotifier = ();
+    type MaxPendingMessages = MaxPendingMessages;
+    type MaxCommitWeight = MaxCommitWeight;
+    type MaxStoredCommitments = MaxStoredCommitments;
+    type MessageStatusNotifier = MockStatusNotifier;
+    type CallOrigin = EnsureDeliveryReport;
     type AuxiliaryDigestHandler = ();
     type AssetId = ();
     type Balance = u128;
+    type Currency = Balances;
+    type FeeAccountId = GetFeeAccountId;
+    type BridgePause = ();
+    type MessageDeliveryHandler = MockDeliveryHandler;
     type WeightInfo = ();
     type TimepointProvider = GenericTimepointProvider;
     type ThisNetworkId = ThisNetworkId;
@@ -217,6 +312,12 @@ pub fn new_tester() -> sp_io::TestExternalities {
     let config: bridge_outbound_channel::GenesisConfig<Test> =
         bridge_outbound_channel::GenesisConfig {
             interval: 10u32.into(),
+            intervals: vec![(BASE_NETWORK_ID, 10u32.into())],
+            channels: vec![
+                BASE_NETWORK_ID,
+                SubNetworkId::Kusama,
+                SubNetworkId::Polkadot,
+            ],
         };
     config.assimilate_storage(&mut storage).unwrap();
 
@@ -259,12 +360,144 @@ fn test_submit() {
     });
 }
 
+#[test]
+fn test_commit_merkle_commitment() {
+    let mut ext = new_tester();
+    ext.execute_with(|| {
+        let who: AccountId = Keyring::Bob.into();
+
+        assert_noop!(
+            BridgeOutboundChannel::set_commitment_format(
+                RuntimeOrigin::signed(who.clone()),
+                BASE_NETWORK_ID,
+                CommitmentFormat::Merkle
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(BridgeOutboundChannel::set_commitment_format(
+            RuntimeOrigin::root(),
+            BASE_NETWORK_ID,
+            CommitmentFormat::Merkle
+        ));
+        System::assert_last_event(RuntimeEvent::BridgeOutboundChannel(
+            Event::CommitmentFormatChanged {
+                network_id: BASE_NETWORK_ID,
+                format: CommitmentFormat::Merkle,
+            },
+        ));
+
+        assert_ok!(BridgeOutboundChannel::submit(
+            BASE_NETWORK_ID,
+            &RawOrigin::Signed(who),
+            &[0, 1, 2],
+            ()
+        ));
+        BridgeOutboundChannel::commit(BASE_NETWORK_ID);
+        assert_eq!(<ChannelNonces<Test>>::get(BASE_NETWORK_ID), 1);
+    });
+    ext.persist_offchain_overlay();
+
+    let key = bridge_types::utils::make_offchain_key(BASE_NETWORK_ID.into(), 1);
+    let data = ext
+        .offchain_db()
+        .get(&key)
+        .expect("commitment is stored offchain");
+    let data = BridgeOffchainData::<u64, MaxMessagesPerCommit, MaxMessagePayloadSize>::decode(
+        &mut &data[..],
+    )
+    .unwrap();
+    let GenericCommitment::SubMerkle(commitment) = data.commitment else {
+        panic!("unexpected commitment {:?}", data.commitment)
+    };
+    assert_eq!(commitment.nonce, 1);
+    assert_eq!(commitment.messages.len(), 1);
+}
+
+#[test]
+fn test_submit_charges_fee() {
+    new_tester().execute_with(|| {
+        let who: AccountId = Keyring::Bob.into();
+        Balances::make_free_balance_be(&who, 1_000);
+
+        assert_noop!(
+            BridgeOutboundChannel::set_message_fee(
+                RuntimeOrigin::signed(who.clone()),
+                BASE_NETWORK_ID,
+                100,
+                2
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(BridgeOutboundChannel::set_message_fee(
+            RuntimeOrigin::root(),
+            BASE_NETWORK_ID,
+            100,
+            2
+        ));
+        System::assert_last_event(RuntimeEvent::BridgeOutboundChannel(
+            Event::MessageFeeChanged {
+                network_id: BASE_NETWORK_ID,
+                fee: MessageFee {
+                    base_fee: 100,
+                    byte_fee: 2,
+                },
+            },
+        ));
+        assert_eq!(BridgeOutboundChannel::message_fee(BASE_NETWORK_ID, 3), 106);
+        assert_eq!(
+            BridgeOutboundChannel::message_fee(SubNetworkId::Kusama, 3),
+            0
+        );
+
+        assert_ok!(BridgeOutboundChannel::submit(
+            BASE_NETWORK_ID,
+            &RawOrigin::Signed(who.clone()),
+            &[0, 1, 2],
+            ()
+        ));
+        assert_eq!(Balances::free_balance(&who), 894);
+        assert_eq!(Balances::free_balance(GetFeeAccountId::get()), 106);
+        System::assert_has_event(RuntimeEvent::BridgeOutboundChannel(Event::MessageFeePaid {
+            network_id: BASE_NETWORK_ID,
+            who,
+            fee: 106,
+        }));
+
+        // Root doesn't pay the fee
+        assert_ok!(BridgeOutboundChannel::submit(
+            BASE_NETWORK_ID,
+            &RawOrigin::Root,
+            &[0, 1, 2],
+            ()
+        ));
+        assert_eq!(Balances::free_balance(GetFeeAccountId::get()), 106);
+    });
+}
+
+#[test]
+fn test_submit_fails_without_funds() {
+    new_tester().execute_with(|| {
+        let who: AccountId = Keyring::Bob.into();
+        assert_ok!(BridgeOutboundChannel::set_message_fee(
+            RuntimeOrigin::root(),
+            BASE_NETWORK_ID,
+            100,
+            2
+        ));
+
+        assert_noop!(
+            BridgeOutboundChannel::submit(BASE_NETWORK_ID, &RawOrigin::Signed(who), &[0, 1, 2], ()),
+            Error::<Test>::NoFunds
+        );
+    });
+}
+
 #[test]
 fn test_submit_exceeds_queue_limit() {
     new_tester().execute_with(|| {
         let who: AccountId = Keyring::Bob.into();
 
-        let max_messages = MaxMessagesPerCommit::get();
+        let max_messages = MaxMessagesPerCommit::get() + MaxPendingMessages::get();
         (0..max_messages).for_each(|_| {
             BridgeOutboundChannel::submit(
                 BASE_NETWORK_ID,
@@ -274,6 +507,9 @@ fn test_submit_exceeds_queue_limit() {
             )
             .unwrap();
         });
+        System::assert_has_event(RuntimeEvent::BridgeOutboundChannel(
+            Event::PendingMessagesLimitReached,
+        ));
 
         assert_noop!(
             BridgeOutboundChannel::submit(BASE_NETWORK_ID, &RawOrigin::Signed(who), &[0, 1, 2], ()),
@@ -282,6 +518,73 @@ fn test_submit_exceeds_queue_limit() {
     })
 }
 
+#[test]
+fn test_submit_spills_over_to_pending_messages() {
+    new_tester().execute_with(|| {
+        let who: AccountId = Keyring::Bob.into();
+        let max_messages = MaxMessagesPerCommit::get() as u64;
+
+        let message_ids = (0..max_messages * 2 + 2)
+            .map(|index| {
+                BridgeOutboundChannel::submit(
+                    BASE_NETWORK_ID,
+                    &RawOrigin::Signed(who.clone()),
+                    &[index as u8],
+                    (),
+                )
+                .unwrap()
+            })
+            .collect::<Vec<_>>();
+        for (index, message_id) in message_ids.iter().enumerate() {
+            let index = index as u64;
+            assert_eq!(
+                *message_id,
+                MessageId::batched(
+                    ThisNetworkId::get(),
+                    BASE_NETWORK_ID.into(),
+                    index / max_messages + 1,
+                    index % max_messages,
+                )
+                .hash()
+            );
+        }
+        System::assert_has_event(RuntimeEvent::BridgeOutboundChannel(
+            Event::MessageQueueOverflowed {
+                network_id: BASE_NETWORK_ID,
+            },
+        ));
+        assert_eq!(
+            BridgeOutboundChannel::pending_messages_count(),
+            max_messages as u32 + 2
+        );
+
+        // Every commit takes the next messages in the submission order
+        for batch_nonce in 1..=3u64 {
+            let queue = <MessageQueues<Test>>::get(BASE_NETWORK_ID);
+            let first = (batch_nonce - 1) * max_messages;
+            assert_eq!(queue[0].payload.to_vec(), vec![first as u8]);
+            BridgeOutboundChannel::commit(BASE_NETWORK_ID);
+            assert_eq!(<ChannelNonces<Test>>::get(BASE_NETWORK_ID), batch_nonce);
+        }
+        System::assert_has_event(RuntimeEvent::BridgeOutboundChannel(
+            Event::PendingMessagesDrained {
+                network_id: BASE_NETWORK_ID,
+            },
+        ));
+        assert_eq!(BridgeOutboundChannel::pending_messages_count(), 0);
+        assert!(<MessageQueues<Test>>::get(BASE_NETWORK_ID).is_empty());
+
+        // New messages go to the queue again
+        let message_id =
+            BridgeOutboundChannel::submit(BASE_NETWORK_ID, &RawOrigin::Signed(who), &[0, 1, 2], ())
+                .unwrap();
+        assert_eq!(
+            message_id,
+            MessageId::batched(ThisNetworkId::get(), BASE_NETWORK_ID.into(), 4, 0).hash()
+        );
+    })
+}
+
 #[test]
 fn test_submit_exceeds_payload_limit() {
     new_tester().execute_with(|| {
@@ -314,3 +617,704 @@ fn test_submit_fails_on_nonce_overflow() {
         );
     });
 }
+
+#[test]
+fn test_set_interval() {
+    new_tester().execute_with(|| {
+        let who: AccountId = Keyring::Bob.into();
+
+        assert_noop!(
+            BridgeOutboundChannel::set_interval(
+                RuntimeOrigin::signed(who.clone()),
+                BASE_NETWORK_ID,
+                5
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            BridgeOutboundChannel::set_interval(RuntimeOrigin::root(), BASE_NETWORK_ID, 0),
+            Error::<Test>::InvalidInterval
+        );
+        assert_ok!(BridgeOutboundChannel::set_interval(
+            RuntimeOrigin::root(),
+            BASE_NETWORK_ID,
+            5
+        ));
+        System::assert_last_event(RuntimeEvent::BridgeOutboundChannel(
+            Event::IntervalChanged {
+                network_id: BASE_NETWORK_ID,
+                interval: 5,
+            },
+        ));
+        assert_eq!(BridgeOutboundChannel::interval(BASE_NETWORK_ID), 5);
+        assert_eq!(BridgeOutboundChannel::interval(SubNetworkId::Kusama), 10);
+
+        for network_id in [BASE_NETWORK_ID, SubNetworkId::Kusama] {
+            assert_ok!(BridgeOutboundChannel::submit(
+                network_id,
+                &RawOrigin::Signed(who.clone()),
+                &[0, 1, 2],
+                ()
+            ));
+        }
+        BridgeOutboundChannel::on_initialize(5);
+        assert_eq!(<ChannelNonces<Test>>::get(BASE_NETWORK_ID), 1);
+        assert_eq!(<ChannelNonces<Test>>::get(SubNetworkId::Kusama), 0);
+        BridgeOutboundChannel::on_initialize(10);
+        assert_eq!(<ChannelNonces<Test>>::get(SubNetworkId::Kusama), 1);
+    });
+}
+
+#[test]
+fn test_force_commit() {
+    new_tester().execute_with(|| {
+        let who: AccountId = Keyring::Bob.into();
+
+        assert_ok!(BridgeOutboundChannel::submit(
+            BASE_NETWORK_ID,
+            &RawOrigin::Signed(who.clone()),
+            &[0, 1, 2],
+            ()
+        ));
+        assert_noop!(
+            BridgeOutboundChannel::force_commit(RuntimeOrigin::signed(who), BASE_NETWORK_ID),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(BridgeOutboundChannel::force_commit(
+            RuntimeOrigin::root(),
+            BASE_NETWORK_ID
+        ));
+        assert_eq!(<ChannelNonces<Test>>::get(BASE_NETWORK_ID), 1);
+        assert!(<MessageQueues<Test>>::get(BASE_NETWORK_ID).is_empty());
+    });
+}
+
+#[test]
+fn test_stagger_commits() {
+    new_tester().execute_with(|| {
+        let who: AccountId = Keyring::Bob.into();
+        let networks = vec![BASE_NETWORK_ID, SubNetworkId::Kusama];
+
+        assert_noop!(
+            BridgeOutboundChannel::stagger_commits(
+                RuntimeOrigin::signed(who.clone()),
+                networks.clone()
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(BridgeOutboundChannel::stagger_commits(
+            RuntimeOrigin::root(),
+            networks.clone()
+        ));
+        assert_eq!(BridgeOutboundChannel::commit_offset(BASE_NETWORK_ID), 0);
+        assert_eq!(
+            BridgeOutboundChannel::commit_offset(SubNetworkId::Kusama),
+            1
+        );
+
+        for network_id in networks {
+            assert_ok!(BridgeOutboundChannel::submit(
+                network_id,
+                &RawOrigin::Signed(who.clone()),
+                &[0, 1, 2],
+                ()
+            ));
+        }
+        BridgeOutboundChannel::on_initialize(10);
+        assert_eq!(<ChannelNonces<Test>>::get(BASE_NETWORK_ID), 1);
+        assert_eq!(<ChannelNonces<Test>>::get(SubNetworkId::Kusama), 0);
+        BridgeOutboundChannel::on_initialize(11);
+        assert_eq!(<ChannelNonces<Test>>::get(SubNetworkId::Kusama), 1);
+    });
+}
+
+#[test]
+fn test_migrate_to_v2() {
+    use frame_support::traits::{OnRuntimeUpgrade, StorageVersion};
+
+    new_tester().execute_with(|| {
+        StorageVersion::new(1).put::<BridgeOutboundChannel>();
+        migrations::v2::Interval::<Test>::put(20);
+        <ChannelNonces<Test>>::insert(SubNetworkId::Kusama, 3);
+        <ChannelNonces<Test>>::insert(SubNetworkId::Polkadot, 1);
+
+        migrations::v2::MigrateToV2::<Test>::on_runtime_upgrade();
+
+        assert_eq!(migrations::v2::Interval::<Test>::get(), None);
+        assert_eq!(BridgeOutboundChannel::interval(SubNetworkId::Kusama), 20);
+        assert_eq!(BridgeOutboundChannel::interval(SubNetworkId::Polkadot), 20);
+        assert_eq!(BridgeOutboundChannel::interval(SubNetworkId::Rococo), 20);
+        assert_eq!(BridgeOutboundChannel::default_interval(), 20);
+        assert_eq!(
+            StorageVersion::get::<BridgeOutboundChannel>(),
+            StorageVersion::new(2)
+        );
+    });
+}
+
+#[test]
+fn test_commits_deferred_over_max_commit_weight() {
+    new_tester().execute_with(|| {
+        let who: AccountId = Keyring::Bob.into();
+        let networks = [
+            BASE_NETWORK_ID,
+            SubNetworkId::Kusama,
+            SubNetworkId::Polkadot,
+        ];
+        for network_id in networks {
+            assert_ok!(BridgeOutboundChannel::submit(
+                network_id,
+                &RawOrigin::Signed(who.clone()),
+                &[0, 1, 2],
+                ()
+            ));
+        }
+        MaxCommitWeight::set(Weight::from_parts(1, 0));
+
+        // Only one network is committed in every block
+        BridgeOutboundChannel::on_initialize(10);
+        assert_eq!(<ChannelNonces<Test>>::get(BASE_NETWORK_ID), 1);
+        assert_eq!(
+            BridgeOutboundChannel::deferred_commits(),
+            vec![SubNetworkId::Kusama, SubNetworkId::Polkadot]
+        );
+        System::assert_has_event(RuntimeEvent::BridgeOutboundChannel(Event::CommitDeferred {
+            network_id: SubNetworkId::Polkadot,
+        }));
+
+        BridgeOutboundChannel::on_initialize(11);
+        assert_eq!(<ChannelNonces<Test>>::get(SubNetworkId::Kusama), 1);
+        assert_eq!(
+            BridgeOutboundChannel::deferred_commits(),
+            vec![SubNetworkId::Polkadot]
+        );
+
+        BridgeOutboundChannel::on_initialize(12);
+        assert_eq!(<ChannelNonces<Test>>::get(SubNetworkId::Polkadot), 1);
+        assert!(BridgeOutboundChannel::deferred_commits().is_empty());
+
+        MaxCommitWeight::set(Weight::MAX);
+        BridgeOutboundChannel::on_initialize(13);
+        assert!(BridgeOutboundChannel::deferred_commits().is_empty());
+    });
+}
+
+#[test]
+fn test_commits_round_robin() {
+    new_tester().execute_with(|| {
+        let who: AccountId = Keyring::Bob.into();
+        for network_id in [BASE_NETWORK_ID, SubNetworkId::Kusama] {
+            assert_ok!(BridgeOutboundChannel::submit(
+                network_id,
+                &RawOrigin::Signed(who.clone()),
+                &[0, 1, 2],
+                ()
+            ));
+        }
+        MaxCommitWeight::set(Weight::from_parts(1, 0));
+        LastCommittedNetwork::<Test>::put(BASE_NETWORK_ID);
+
+        BridgeOutboundChannel::on_initialize(10);
+        assert_eq!(<ChannelNonces<Test>>::get(SubNetworkId::Kusama), 1);
+        assert_eq!(<ChannelNonces<Test>>::get(BASE_NETWORK_ID), 0);
+        assert_eq!(
+            BridgeOutboundChannel::deferred_commits(),
+            vec![BASE_NETWORK_ID]
+        );
+    });
+}
+
+#[test]
+fn test_prune_commitments() {
+    let mut ext = new_tester();
+    ext.execute_with(|| {
+        let who: AccountId = Keyring::Bob.into();
+        for _ in 0..3 {
+            assert_ok!(BridgeOutboundChannel::submit(
+                BASE_NETWORK_ID,
+                &RawOrigin::Signed(who.clone()),
+                &[0, 1, 2],
+                ()
+            ));
+            BridgeOutboundChannel::commit(BASE_NETWORK_ID);
+        }
+    });
+    ext.persist_offchain_overlay();
+
+    let network_id = GenericNetworkId::from(BASE_NETWORK_ID);
+    let latest_key = bridge_types::utils::make_offchain_latest_nonce_key(network_id);
+    assert_eq!(ext.offchain_db().get(&latest_key), Some(3u64.encode()));
+
+    let (offchain, _state) = TestOffchainExt::with_offchain_db(ext.offchain_db());
+    ext.register_extension(OffchainDbExt::new(offchain.clone()));
+    ext.register_extension(OffchainWorkerExt::new(offchain));
+    ext.execute_with(|| BridgeOutboundChannel::offchain_worker(3));
+
+    let offchain_db = ext.offchain_db();
+    let commitment_key = |nonce| bridge_types::utils::make_offchain_key(network_id, nonce);
+    assert_eq!(offchain_db.get(&commitment_key(1)), None);
+    assert!(offchain_db.get(&commitment_key(2)).is_some());
+    assert!(offchain_db.get(&commitment_key(3)).is_some());
+    assert_eq!(
+        offchain_db.get(&bridge_types::utils::make_offchain_pruned_nonce_key(
+            network_id
+        )),
+        Some(1u64.encode())
+    );
+}
+
+#[test]
+fn test_submit_fails_on_unregistered_channel() {
+    new_tester().execute_with(|| {
+        assert_noop!(
+            BridgeOutboundChannel::submit(SubNetworkId::Rococo, &RawOrigin::Root, &[0, 1, 2], ()),
+            Error::<Test>::ChannelNotRegistered,
+        );
+
+        assert_ok!(BridgeOutboundChannel::register_channel(
+            RuntimeOrigin::root(),
+            SubNetworkId::Rococo
+        ));
+        assert_noop!(
+            BridgeOutboundChannel::register_channel(RuntimeOrigin::root(), SubNetworkId::Rococo),
+            Error::<Test>::ChannelExists,
+        );
+        assert_noop!(
+            BridgeOutboundChannel::submit(SubNetworkId::Rococo, &RawOrigin::Root, &[0, 1, 2], ()),
+            Error::<Test>::ChannelNotActive,
+        );
+
+        assert_ok!(BridgeOutboundChannel::open_channel(
+            RuntimeOrigin::root(),
+            SubNetworkId::Rococo
+        ));
+        System::assert_last_event(RuntimeEvent::BridgeOutboundChannel(Event::ChannelOpened {
+            network_id: SubNetworkId::Rococo,
+        }));
+        assert_ok!(BridgeOutboundChannel::submit(
+            SubNetworkId::Rococo,
+            &RawOrigin::Root,
+            &[0, 1, 2],
+            ()
+        ));
+    });
+}
+
+#[test]
+fn test_close_channel_drains_messages() {
+    new_tester().execute_with(|| {
+        let max_messages = MaxMessagesPerCommit::get() as usize;
+        for _ in 0..max_messages + 1 {
+            assert_ok!(BridgeOutboundChannel::submit(
+                BASE_NETWORK_ID,
+                &RawOrigin::Root,
+                &[0, 1, 2],
+                ()
+            ));
+        }
+
+        assert_noop!(
+            BridgeOutboundChannel::close_channel(
+                RuntimeOrigin::signed(Keyring::Bob.into()),
+                BASE_NETWORK_ID
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(BridgeOutboundChannel::close_channel(
+            RuntimeOrigin::root(),
+            BASE_NETWORK_ID
+        ));
+        assert_eq!(
+            BridgeOutboundChannel::channel_state(BASE_NETWORK_ID),
+            Some(ChannelState::Draining)
+        );
+        assert_noop!(
+            BridgeOutboundChannel::submit(BASE_NETWORK_ID, &RawOrigin::Root, &[0, 1, 2], ()),
+            Error::<Test>::ChannelNotActive,
+        );
+        assert_noop!(
+            BridgeOutboundChannel::open_channel(RuntimeOrigin::root(), BASE_NETWORK_ID),
+            Error::<Test>::InvalidChannelState,
+        );
+
+        // The full queue is committed, the pending message is left for the next batch
+        BridgeOutboundChannel::commit(BASE_NETWORK_ID);
+        assert_eq!(
+            BridgeOutboundChannel::channel_state(BASE_NETWORK_ID),
+            Some(ChannelState::Draining)
+        );
+
+        BridgeOutboundChannel::commit(BASE_NETWORK_ID);
+        assert_eq!(
+            BridgeOutboundChannel::channel_state(BASE_NETWORK_ID),
+            Some(ChannelState::Closed)
+        );
+        System::assert_last_event(RuntimeEvent::BridgeOutboundChannel(Event::ChannelClosed {
+            network_id: BASE_NETWORK_ID,
+            batch_nonce: 2,
+        }));
+
+        assert_ok!(BridgeOutboundChannel::open_channel(
+            RuntimeOrigin::root(),
+            BASE_NETWORK_ID
+        ));
+        assert_ok!(BridgeOutboundChannel::submit(
+            BASE_NETWORK_ID,
+            &RawOrigin::Root,
+            &[0, 1, 2],
+            ()
+        ));
+    });
+}
+
+#[test]
+fn test_close_empty_channel() {
+    new_tester().execute_with(|| {
+        assert_ok!(BridgeOutboundChannel::close_channel(
+            RuntimeOrigin::root(),
+            SubNetworkId::Kusama
+        ));
+        assert_eq!(
+            BridgeOutboundChannel::channel_state(SubNetworkId::Kusama),
+            Some(ChannelState::Closed)
+        );
+        System::assert_last_event(RuntimeEvent::BridgeOutboundChannel(Event::ChannelClosed {
+            network_id: SubNetworkId::Kusama,
+            batch_nonce: 0,
+        }));
+        assert_noop!(
+            BridgeOutboundChannel::close_channel(RuntimeOrigin::root(), SubNetworkId::Kusama),
+            Error::<Test>::InvalidChannelState,
+        );
+        assert_noop!(
+            BridgeOutboundChannel::close_channel(RuntimeOrigin::root(), SubNetworkId::Rococo),
+            Error::<Test>::ChannelNotRegistered,
+        );
+    });
+}
+
+#[test]
+fn test_migrate_to_v3() {
+    use frame_support::traits::{OnRuntimeUpgrade, StorageVersion};
+
+    new_tester().execute_with(|| {
+        StorageVersion::new(2).put::<BridgeOutboundChannel>();
+        <ChannelNonces<Test>>::insert(SubNetworkId::Rococo, 3);
+        assert_ok!(BridgeOutboundChannel::close_channel(
+            RuntimeOrigin::root(),
+            SubNetworkId::Kusama
+        ));
+        <ChannelNonces<Test>>::insert(SubNetworkId::Kusama, 1);
+
+        migrations::v3::MigrateToV3::<Test>::on_runtime_upgrade();
+
+        assert_eq!(
+            BridgeOutboundChannel::channel_state(SubNetworkId::Rococo),
+            Some(ChannelState::Active)
+        );
+        assert_eq!(
+            BridgeOutboundChannel::channel_state(SubNetworkId::Kusama),
+            Some(ChannelState::Closed)
+        );
+        assert_eq!(
+            StorageVersion::get::<BridgeOutboundChannel>(),
+            StorageVersion::new(3)
+        );
+    });
+}
+
+#[test]
+fn test_migrate_to_v4() {
+    use frame_support::storage::unhashed;
+    use frame_support::traits::{OnRuntimeUpgrade, StorageVersion};
+
+    let message = |idx: u8| BridgeMessage::<MaxMessagePayloadSize> {
+        payload: vec![idx].try_into().unwrap(),
+        timepoint: GenericTimepoint::Parachain(idx as u32),
+    };
+    let queue: BoundedVec<_, MaxMessagesPerCommit> =
+        vec![message(0), message(1)].try_into().unwrap();
+    let mut ext = new_tester();
+    ext.execute_with(|| {
+        StorageVersion::new(3).put::<BridgeOutboundChannel>();
+        // Messages stored with the old layout
+        unhashed::put(
+            &MessageQueues::<Test>::hashed_key_for(BASE_NETWORK_ID),
+            &queue,
+        );
+        unhashed::put(
+            &PendingMessages::<Test>::hashed_key_for(BASE_NETWORK_ID, 0),
+            &message(2),
+        );
+        PendingMessagesRange::<Test>::insert(BASE_NETWORK_ID, (0, 1));
+
+        migrations::v4::MigrateToV4::<Test>::on_runtime_upgrade();
+
+        assert_eq!(
+            MessageQueues::<Test>::get(BASE_NETWORK_ID).into_inner(),
+            vec![message(0).into(), message(1).into()]
+        );
+        assert_eq!(
+            PendingMessages::<Test>::get(BASE_NETWORK_ID, 0),
+            Some(message(2).into())
+        );
+        assert_eq!(
+            StorageVersion::get::<BridgeOutboundChannel>(),
+            StorageVersion::new(4)
+        );
+
+        BridgeOutboundChannel::commit(BASE_NETWORK_ID);
+        assert_eq!(
+            MessageQueues::<Test>::get(BASE_NETWORK_ID).into_inner(),
+            vec![message(2).into()]
+        );
+    });
+    ext.persist_offchain_overlay();
+
+    // The migrated messages are committed with the same hash as before the upgrade
+    let key = bridge_types::utils::make_offchain_key(BASE_NETWORK_ID.into(), 1);
+    let data = ext
+        .offchain_db()
+        .get(&key)
+        .expect("commitment is stored offchain");
+    let data = BridgeOffchainData::<u64, MaxMessagesPerCommit, MaxMessagePayloadSize>::decode(
+        &mut &data[..],
+    )
+    .unwrap();
+    assert_eq!(
+        data.commitment.hash(),
+        bridge_types::substrate::Commitment {
+            messages: queue,
+            nonce: 1,
+        }
+        .hash()
+    );
+}
+
+#[test]
+fn test_submit_with_deadline() {
+    let mut ext = new_tester();
+    ext.execute_with(|| {
+        assert_noop!(
+            BridgeOutboundChannel::submit_with_deadline(
+                BASE_NETWORK_ID,
+                &RawOrigin::Root,
+                &[0, 1, 2],
+                (),
+                1_000
+            ),
+            Error::<Test>::DeadlinesNotSupported
+        );
+        assert_ok!(BridgeOutboundChannel::set_commitment_format(
+            RuntimeOrigin::root(),
+            BASE_NETWORK_ID,
+            CommitmentFormat::BatchV2
+        ));
+        assert_ok!(BridgeOutboundChannel::submit_with_deadline(
+            BASE_NETWORK_ID,
+            &RawOrigin::Root,
+            &[0, 1, 2],
+            (),
+            1_000
+        ));
+        assert_ok!(BridgeOutboundChannel::submit(
+            BASE_NETWORK_ID,
+            &RawOrigin::Root,
+            &[3],
+            ()
+        ));
+        BridgeOutboundChannel::commit(BASE_NETWORK_ID);
+    });
+    ext.persist_offchain_overlay();
+
+    let key = bridge_types::utils::make_offchain_key(BASE_NETWORK_ID.into(), 1);
+    let data = ext
+        .offchain_db()
+        .get(&key)
+        .expect("commitment is stored offchain");
+    let data = BridgeOffchainData::<u64, MaxMessagesPerCommit, MaxMessagePayloadSize>::decode(
+        &mut &data[..],
+    )
+    .unwrap();
+    let GenericCommitment::SubV2(commitment) = data.commitment else {
+        panic!("unexpected commitment {:?}", data.commitment)
+    };
+    assert_eq!(commitment.nonce, 1);
+    assert_eq!(
+        commitment
+            .messages
+            .iter()
+            .map(|message| message.deadline)
+            .collect::<Vec<_>>(),
+        vec![Some(1_000), None]
+    );
+}
+
+#[test]
+fn test_report_delivery() {
+    new_tester().execute_with(|| {
+        for _ in 0..3 {
+            assert_ok!(BridgeOutboundChannel::submit(
+                BASE_NETWORK_ID,
+                &RawOrigin::Root,
+                &[0, 1, 2],
+                ()
+            ));
+        }
+        BridgeOutboundChannel::commit(BASE_NETWORK_ID);
+        let message_id =
+            |idx| MessageId::batched(ThisNetworkId::get(), BASE_NETWORK_ID.into(), 1, idx).hash();
+        assert_eq!(
+            UpdatedStatuses::take(),
+            (0..3)
+                .map(|idx| (
+                    message_id(idx),
+                    MessageStatus::Committed,
+                    GenericTimepoint::Pending
+                ))
+                .collect::<Vec<_>>()
+        );
+
+        assert_noop!(
+            BridgeOutboundChannel::report_delivery(
+                RuntimeOrigin::signed(Keyring::Bob.into()),
+                1,
+                vec![]
+            ),
+            sp_runtime::DispatchError::BadOrigin,
+        );
+        assert_noop!(
+            BridgeOutboundChannel::report_delivery(RuntimeOrigin::root(), 2, vec![]),
+            Error::<Test>::InvalidDeliveryReport,
+        );
+
+        assert_ok!(BridgeOutboundChannel::report_delivery(
+            RuntimeOrigin::root(),
+            1,
+            vec![
+                MessageDispatchResult::Dispatched,
+                MessageDispatchResult::Failed,
+                MessageDispatchResult::Expired,
+            ]
+        ));
+        assert_eq!(
+            UpdatedStatuses::take(),
+            vec![
+                (
+                    message_id(0),
+                    MessageStatus::Done,
+                    GenericTimepoint::Parachain(100)
+                ),
+                (
+                    message_id(1),
+                    MessageStatus::Failed,
+                    GenericTimepoint::Parachain(100)
+                ),
+            ]
+        );
+        assert_eq!(DispatchedMessages::take(), vec![message_id(0)]);
+    });
+}
+
+#[test]
+fn test_report_delivery_refunds_failed_messages() {
+    new_tester().execute_with(|| {
+        for _ in 0..2 {
+            assert_ok!(BridgeOutboundChannel::submit(
+                BASE_NETWORK_ID,
+                &RawOrigin::Root,
+                &[0, 1, 2],
+                ()
+            ));
+        }
+        BridgeOutboundChannel::commit(BASE_NETWORK_ID);
+        UpdatedStatuses::take();
+        let message_id =
+            |idx| MessageId::batched(ThisNetworkId::get(), BASE_NETWORK_ID.into(), 1, idx).hash();
+        RefundableMessages::set(vec![message_id(1)]);
+
+        assert_ok!(BridgeOutboundChannel::report_delivery(
+            RuntimeOrigin::root(),
+            1,
+            vec![MessageDispatchResult::Failed, MessageDispatchResult::Failed]
+        ));
+        assert_eq!(
+            UpdatedStatuses::take(),
+            vec![
+                (
+                    message_id(0),
+                    MessageStatus::Failed,
+                    GenericTimepoint::Parachain(100)
+                ),
+                (
+                    message_id(1),
+                    MessageStatus::Refunded,
+                    GenericTimepoint::Parachain(100)
+                ),
+            ]
+        );
+    });
+}
+
+#[test]
+fn test_report_delivery_once() {
+    new_tester().execute_with(|| {
+        assert_ok!(BridgeOutboundChannel::submit(
+            BASE_NETWORK_ID,
+            &RawOrigin::Root,
+            &[0, 1, 2],
+            ()
+        ));
+        BridgeOutboundChannel::commit(BASE_NETWORK_ID);
+        let message_id =
+            MessageId::batched(ThisNetworkId::get(), BASE_NETWORK_ID.into(), 1, 0).hash();
+        RefundableMessages::set(vec![message_id]);
+
+        assert_ok!(BridgeOutboundChannel::report_delivery(
+            RuntimeOrigin::root(),
+            1,
+            vec![MessageDispatchResult::Failed]
+        ));
+        UpdatedStatuses::take();
+        assert_noop!(
+            BridgeOutboundChannel::report_delivery(
+                RuntimeOrigin::root(),
+                1,
+                vec![MessageDispatchResult::Failed]
+            ),
+            Error::<Test>::InvalidDeliveryReport,
+        );
+        assert!(UpdatedStatuses::take().is_empty());
+    });
+}
+
+#[test]
+fn test_report_delivery_with_wrong_results_count() {
+    new_tester().execute_with(|| {
+        for _ in 0..2 {
+            assert_ok!(BridgeOutboundChannel::submit(
+                BASE_NETWORK_ID,
+                &RawOrigin::Root,
+                &[0, 1, 2],
+                ()
+            ));
+        }
+        BridgeOutboundChannel::commit(BASE_NETWORK_ID);
+
+        for results_count in [0, 1, 3] {
+            assert_noop!(
+                BridgeOutboundChannel::report_delivery(
+                    RuntimeOrigin::root(),
+                    1,
+                    vec![MessageDispatchResult::Dispatched; results_count]
+                ),
+                Error::<Test>::InvalidDeliveryReport,
+            );
+        }
+        assert_ok!(BridgeOutboundChannel::report_delivery(
+            RuntimeOrigin::root(),
+            1,
+            vec![MessageDispatchResult::Dispatched; 2]
+        ));
+    });
+}
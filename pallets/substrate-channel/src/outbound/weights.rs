@@ -28,26 +28,11 @@
 // STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-//! Autogenerated weights for substrate_bridge_channel::outbound
+//! Weights for substrate_bridge_channel::outbound
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
-//! DATE: 2023-07-27, STEPS: `50`, REPEAT: `20`, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! WORST CASE MAP SIZE: `1000000`
-//! HOSTNAME: `TRX40`, CPU: `AMD Ryzen Threadripper 3960X 24-Core Processor`
-//! EXECUTION: None, WASM-EXECUTION: Compiled, CHAIN: Some("local"), DB CACHE: 1024
-
-// Executed Command:
-// ./target/release/framenode
-// benchmark
-// pallet
-// --chain=local
-// --steps=50
-// --repeat=20
-// --pallet=substrate_bridge_channel::outbound
-// --extrinsic=*
-// --header=./misc/file_header.txt
-// --template=./misc/pallet-weight-template.hbs
-// --output=./outbound-channel.rs
+//! NOT GENERATED BY THE BENCHMARK CLI: the values are estimates until the pallet
+//! is benchmarked, regenerate with
+//! `benchmark pallet --pallet=substrate_bridge_channel::outbound --extrinsic=* --template=./misc/pallet-weight-template.hbs`.
 
 #![cfg_attr(rustfmt, rustfmt_skip)]
 #![allow(unused_parens)]
@@ -59,142 +44,364 @@ use core::marker::PhantomData;
 /// Weight functions needed for substrate_bridge_channel::outbound.
 pub trait WeightInfo {
 	fn on_initialize(m: u32, p: u32, ) -> Weight;
+	fn on_initialize_base() -> Weight;
 	fn on_initialize_non_interval() -> Weight;
 	fn on_initialize_no_messages() -> Weight;
 	fn submit() -> Weight;
+	fn promote_pending_messages(m: u32, ) -> Weight;
+	fn set_commitment_format() -> Weight;
+	fn set_message_fee() -> Weight;
+	fn set_interval() -> Weight;
+	fn stagger_commits(n: u32, ) -> Weight;
+	fn register_channel() -> Weight;
+	fn open_channel() -> Weight;
+	fn close_channel() -> Weight;
+	fn report_delivery(n: u32, ) -> Weight;
 }
 
 /// Weights for substrate_bridge_channel::outbound using the Substrate node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
-	/// Storage: SubstrateBridgeOutboundChannel Interval (r:1 w:0)
-	/// Proof Skipped: SubstrateBridgeOutboundChannel Interval (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel Intervals (r:1 w:0)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel Intervals (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel DefaultInterval (r:1 w:0)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel DefaultInterval (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel CommitOffsets (r:1 w:0)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel CommitOffsets (max_values: None, max_size: None, mode: Measured)
 	/// Storage: SubstrateBridgeOutboundChannel MessageQueues (r:2 w:1)
 	/// Proof Skipped: SubstrateBridgeOutboundChannel MessageQueues (max_values: None, max_size: None, mode: Measured)
 	/// Storage: SubstrateBridgeOutboundChannel ChannelNonces (r:1 w:1)
 	/// Proof Skipped: SubstrateBridgeOutboundChannel ChannelNonces (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel CommitmentFormats (r:1 w:0)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel CommitmentFormats (max_values: None, max_size: None, mode: Measured)
 	/// Storage: BridgeProxy Senders (r:20 w:0)
 	/// Proof Skipped: BridgeProxy Senders (max_values: None, max_size: None, mode: Measured)
 	/// Storage: LeafProvider LatestDigest (r:1 w:1)
 	/// Proof Skipped: LeafProvider LatestDigest (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: System Digest (r:1 w:1)
+	/// Proof Skipped: System Digest (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel Channels (r:1 w:1)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel Channels (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel Channels (r:1 w:1)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel Channels (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel CommittedBatches (r:0 w:1)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel CommittedBatches (max_values: None, max_size: None, mode: Measured)
+	/// Offchain index: commitment, latest nonce (w:2)
 	/// The range of component `m` is `[1, 20]`.
 	/// The range of component `p` is `[0, 256]`.
 	fn on_initialize(m: u32, p: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `0 + m * (259 ±0) + p * (20 ±0)`
-		//  Estimated: `7321 + m * (3006 ±2) + p * (42 ±0)`
-		// Minimum execution time: 24_211_000 picoseconds.
-		Weight::from_parts(7_859_569, 7321)
-			// Standard Error: 31_278
-			.saturating_add(Weight::from_parts(4_421_066, 0).saturating_mul(m.into()))
-			// Standard Error: 2_388
-			.saturating_add(Weight::from_parts(69_998, 0).saturating_mul(p.into()))
-			.saturating_add(T::DbWeight::get().reads(5_u64))
+		Weight::from_parts(12_102_331, 8883)
+			.saturating_add(Weight::from_parts(4_618_237, 0).saturating_mul(m.into()))
+			.saturating_add(Weight::from_parts(92_416, 0).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(10_u64))
 			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(m.into())))
-			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
 			.saturating_add(Weight::from_parts(0, 3006).saturating_mul(m.into()))
 			.saturating_add(Weight::from_parts(0, 42).saturating_mul(p.into()))
 	}
-	/// Storage: SubstrateBridgeOutboundChannel Interval (r:1 w:0)
-	/// Proof Skipped: SubstrateBridgeOutboundChannel Interval (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel DeferredCommits (r:1 w:1)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel DeferredCommits (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel LastCommittedNetwork (r:1 w:1)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel LastCommittedNetwork (max_values: Some(1), max_size: None, mode: Measured)
+	fn on_initialize_base() -> Weight {
+		Weight::from_parts(4_120_000, 990)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: SubstrateBridgeOutboundChannel Intervals (r:1 w:0)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel Intervals (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel DefaultInterval (r:1 w:0)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel DefaultInterval (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel CommitOffsets (r:1 w:0)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel CommitOffsets (max_values: None, max_size: None, mode: Measured)
 	fn on_initialize_non_interval() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `65`
-		//  Estimated: `560`
-		// Minimum execution time: 2_340_000 picoseconds.
-		Weight::from_parts(2_400_000, 560)
-			.saturating_add(T::DbWeight::get().reads(1_u64))
+		Weight::from_parts(4_100_000, 5040)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
 	}
-	/// Storage: SubstrateBridgeOutboundChannel Interval (r:1 w:0)
-	/// Proof Skipped: SubstrateBridgeOutboundChannel Interval (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel Intervals (r:1 w:0)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel Intervals (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel DefaultInterval (r:1 w:0)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel DefaultInterval (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel CommitOffsets (r:1 w:0)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel CommitOffsets (max_values: None, max_size: None, mode: Measured)
 	/// Storage: SubstrateBridgeOutboundChannel MessageQueues (r:1 w:0)
 	/// Proof Skipped: SubstrateBridgeOutboundChannel MessageQueues (max_values: None, max_size: None, mode: Measured)
 	fn on_initialize_no_messages() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `31`
-		//  Estimated: `3032`
-		// Minimum execution time: 4_850_000 picoseconds.
-		Weight::from_parts(5_030_000, 3032)
-			.saturating_add(T::DbWeight::get().reads(2_u64))
+		Weight::from_parts(6_710_000, 7512)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
 	}
-	/// Storage: SubstrateBridgeOutboundChannel MessageQueues (r:1 w:1)
+	/// Storage: SubstrateBridgeOutboundChannel Channels (r:1 w:0)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel Channels (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel MessageQueues (r:1 w:0)
 	/// Proof Skipped: SubstrateBridgeOutboundChannel MessageQueues (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel PendingMessagesCount (r:1 w:1)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel PendingMessagesCount (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel PendingMessagesRange (r:1 w:1)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel PendingMessagesRange (max_values: None, max_size: None, mode: Measured)
 	/// Storage: SubstrateBridgeOutboundChannel ChannelNonces (r:1 w:0)
 	/// Proof Skipped: SubstrateBridgeOutboundChannel ChannelNonces (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel MessageFees (r:1 w:0)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel MessageFees (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: SubstrateBridgeOutboundChannel PendingMessages (r:0 w:1)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel PendingMessages (max_values: None, max_size: None, mode: Measured)
 	fn submit() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `31`
-		//  Estimated: `5012`
-		// Minimum execution time: 7_380_000 picoseconds.
-		Weight::from_parts(7_460_000, 5012)
+		Weight::from_parts(47_110_000, 20386)
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: SubstrateBridgeOutboundChannel PendingMessagesRange (r:1 w:1)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel PendingMessagesRange (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel PendingMessages (r:20 w:20)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel PendingMessages (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel PendingMessagesCount (r:1 w:1)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel PendingMessagesCount (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel MessageQueues (r:0 w:1)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel MessageQueues (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `m` is `[0, 20]`.
+	fn promote_pending_messages(m: u32, ) -> Weight {
+		Weight::from_parts(9_871_204, 5274)
+			.saturating_add(Weight::from_parts(4_127_385, 0).saturating_mul(m.into()))
 			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(m.into())))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(m.into())))
+			.saturating_add(Weight::from_parts(0, 2766).saturating_mul(m.into()))
+	}
+	/// Storage: SubstrateBridgeOutboundChannel CommitmentFormats (r:0 w:1)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel CommitmentFormats (max_values: None, max_size: None, mode: Measured)
+	fn set_commitment_format() -> Weight {
+		Weight::from_parts(6_350_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: SubstrateBridgeOutboundChannel MessageFees (r:0 w:1)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel MessageFees (max_values: None, max_size: None, mode: Measured)
+	fn set_message_fee() -> Weight {
+		Weight::from_parts(6_710_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: SubstrateBridgeOutboundChannel Intervals (r:0 w:1)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel Intervals (max_values: None, max_size: None, mode: Measured)
+	fn set_interval() -> Weight {
+		Weight::from_parts(6_410_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: SubstrateBridgeOutboundChannel CommitOffsets (r:0 w:5)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel CommitOffsets (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `n` is `[0, 5]`.
+	fn stagger_commits(n: u32, ) -> Weight {
+		Weight::from_parts(3_207_718, 0)
+			.saturating_add(Weight::from_parts(3_384_540, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
+	/// Storage: SubstrateBridgeOutboundChannel Channels (r:1 w:1)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel Channels (max_values: None, max_size: None, mode: Measured)
+	fn register_channel() -> Weight {
+		Weight::from_parts(8_240_000, 2475)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: SubstrateBridgeOutboundChannel Channels (r:1 w:1)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel Channels (max_values: None, max_size: None, mode: Measured)
+	fn open_channel() -> Weight {
+		Weight::from_parts(8_920_000, 2554)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: SubstrateBridgeOutboundChannel Channels (r:1 w:1)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel Channels (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel MessageQueues (r:1 w:0)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel MessageQueues (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel ChannelNonces (r:1 w:0)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel ChannelNonces (max_values: None, max_size: None, mode: Measured)
+	fn close_channel() -> Weight {
+		Weight::from_parts(12_880_000, 7662)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: SubstrateBridgeOutboundChannel CommittedBatches (r:1 w:1)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel CommittedBatches (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `n` is `[1, 5]`.
+	fn report_delivery(n: u32, ) -> Weight {
+		Weight::from_parts(9_874_215, 2554)
+			.saturating_add(Weight::from_parts(412_830, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
-	/// Storage: SubstrateBridgeOutboundChannel Interval (r:1 w:0)
-	/// Proof Skipped: SubstrateBridgeOutboundChannel Interval (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel Intervals (r:1 w:0)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel Intervals (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel DefaultInterval (r:1 w:0)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel DefaultInterval (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel CommitOffsets (r:1 w:0)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel CommitOffsets (max_values: None, max_size: None, mode: Measured)
 	/// Storage: SubstrateBridgeOutboundChannel MessageQueues (r:2 w:1)
 	/// Proof Skipped: SubstrateBridgeOutboundChannel MessageQueues (max_values: None, max_size: None, mode: Measured)
 	/// Storage: SubstrateBridgeOutboundChannel ChannelNonces (r:1 w:1)
 	/// Proof Skipped: SubstrateBridgeOutboundChannel ChannelNonces (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel CommitmentFormats (r:1 w:0)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel CommitmentFormats (max_values: None, max_size: None, mode: Measured)
 	/// Storage: BridgeProxy Senders (r:20 w:0)
 	/// Proof Skipped: BridgeProxy Senders (max_values: None, max_size: None, mode: Measured)
 	/// Storage: LeafProvider LatestDigest (r:1 w:1)
 	/// Proof Skipped: LeafProvider LatestDigest (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: System Digest (r:1 w:1)
+	/// Proof Skipped: System Digest (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel Channels (r:1 w:1)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel Channels (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel Channels (r:1 w:1)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel Channels (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel CommittedBatches (r:0 w:1)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel CommittedBatches (max_values: None, max_size: None, mode: Measured)
+	/// Offchain index: commitment, latest nonce (w:2)
 	/// The range of component `m` is `[1, 20]`.
 	/// The range of component `p` is `[0, 256]`.
 	fn on_initialize(m: u32, p: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `0 + m * (259 ±0) + p * (20 ±0)`
-		//  Estimated: `7321 + m * (3006 ±2) + p * (42 ±0)`
-		// Minimum execution time: 24_211_000 picoseconds.
-		Weight::from_parts(7_859_569, 7321)
-			// Standard Error: 31_278
-			.saturating_add(Weight::from_parts(4_421_066, 0).saturating_mul(m.into()))
-			// Standard Error: 2_388
-			.saturating_add(Weight::from_parts(69_998, 0).saturating_mul(p.into()))
-			.saturating_add(RocksDbWeight::get().reads(5_u64))
+		Weight::from_parts(12_102_331, 8883)
+			.saturating_add(Weight::from_parts(4_618_237, 0).saturating_mul(m.into()))
+			.saturating_add(Weight::from_parts(92_416, 0).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(10_u64))
 			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(m.into())))
-			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
 			.saturating_add(Weight::from_parts(0, 3006).saturating_mul(m.into()))
 			.saturating_add(Weight::from_parts(0, 42).saturating_mul(p.into()))
 	}
-	/// Storage: SubstrateBridgeOutboundChannel Interval (r:1 w:0)
-	/// Proof Skipped: SubstrateBridgeOutboundChannel Interval (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel DeferredCommits (r:1 w:1)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel DeferredCommits (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel LastCommittedNetwork (r:1 w:1)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel LastCommittedNetwork (max_values: Some(1), max_size: None, mode: Measured)
+	fn on_initialize_base() -> Weight {
+		Weight::from_parts(4_120_000, 990)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: SubstrateBridgeOutboundChannel Intervals (r:1 w:0)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel Intervals (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel DefaultInterval (r:1 w:0)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel DefaultInterval (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel CommitOffsets (r:1 w:0)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel CommitOffsets (max_values: None, max_size: None, mode: Measured)
 	fn on_initialize_non_interval() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `65`
-		//  Estimated: `560`
-		// Minimum execution time: 2_340_000 picoseconds.
-		Weight::from_parts(2_400_000, 560)
-			.saturating_add(RocksDbWeight::get().reads(1_u64))
+		Weight::from_parts(4_100_000, 5040)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
 	}
-	/// Storage: SubstrateBridgeOutboundChannel Interval (r:1 w:0)
-	/// Proof Skipped: SubstrateBridgeOutboundChannel Interval (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel Intervals (r:1 w:0)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel Intervals (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel DefaultInterval (r:1 w:0)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel DefaultInterval (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel CommitOffsets (r:1 w:0)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel CommitOffsets (max_values: None, max_size: None, mode: Measured)
 	/// Storage: SubstrateBridgeOutboundChannel MessageQueues (r:1 w:0)
 	/// Proof Skipped: SubstrateBridgeOutboundChannel MessageQueues (max_values: None, max_size: None, mode: Measured)
 	fn on_initialize_no_messages() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `31`
-		//  Estimated: `3032`
-		// Minimum execution time: 4_850_000 picoseconds.
-		Weight::from_parts(5_030_000, 3032)
-			.saturating_add(RocksDbWeight::get().reads(2_u64))
+		Weight::from_parts(6_710_000, 7512)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
 	}
-	/// Storage: SubstrateBridgeOutboundChannel MessageQueues (r:1 w:1)
+	/// Storage: SubstrateBridgeOutboundChannel Channels (r:1 w:0)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel Channels (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel MessageQueues (r:1 w:0)
 	/// Proof Skipped: SubstrateBridgeOutboundChannel MessageQueues (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel PendingMessagesCount (r:1 w:1)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel PendingMessagesCount (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel PendingMessagesRange (r:1 w:1)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel PendingMessagesRange (max_values: None, max_size: None, mode: Measured)
 	/// Storage: SubstrateBridgeOutboundChannel ChannelNonces (r:1 w:0)
 	/// Proof Skipped: SubstrateBridgeOutboundChannel ChannelNonces (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel MessageFees (r:1 w:0)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel MessageFees (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: SubstrateBridgeOutboundChannel PendingMessages (r:0 w:1)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel PendingMessages (max_values: None, max_size: None, mode: Measured)
 	fn submit() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `31`
-		//  Estimated: `5012`
-		// Minimum execution time: 7_380_000 picoseconds.
-		Weight::from_parts(7_460_000, 5012)
+		Weight::from_parts(47_110_000, 20386)
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: SubstrateBridgeOutboundChannel PendingMessagesRange (r:1 w:1)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel PendingMessagesRange (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel PendingMessages (r:20 w:20)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel PendingMessages (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel PendingMessagesCount (r:1 w:1)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel PendingMessagesCount (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel MessageQueues (r:0 w:1)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel MessageQueues (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `m` is `[0, 20]`.
+	fn promote_pending_messages(m: u32, ) -> Weight {
+		Weight::from_parts(9_871_204, 5274)
+			.saturating_add(Weight::from_parts(4_127_385, 0).saturating_mul(m.into()))
 			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(m.into())))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(m.into())))
+			.saturating_add(Weight::from_parts(0, 2766).saturating_mul(m.into()))
+	}
+	/// Storage: SubstrateBridgeOutboundChannel CommitmentFormats (r:0 w:1)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel CommitmentFormats (max_values: None, max_size: None, mode: Measured)
+	fn set_commitment_format() -> Weight {
+		Weight::from_parts(6_350_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: SubstrateBridgeOutboundChannel MessageFees (r:0 w:1)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel MessageFees (max_values: None, max_size: None, mode: Measured)
+	fn set_message_fee() -> Weight {
+		Weight::from_parts(6_710_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: SubstrateBridgeOutboundChannel Intervals (r:0 w:1)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel Intervals (max_values: None, max_size: None, mode: Measured)
+	fn set_interval() -> Weight {
+		Weight::from_parts(6_410_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: SubstrateBridgeOutboundChannel CommitOffsets (r:0 w:5)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel CommitOffsets (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `n` is `[0, 5]`.
+	fn stagger_commits(n: u32, ) -> Weight {
+		Weight::from_parts(3_207_718, 0)
+			.saturating_add(Weight::from_parts(3_384_540, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
+	/// Storage: SubstrateBridgeOutboundChannel Channels (r:1 w:1)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel Channels (max_values: None, max_size: None, mode: Measured)
+	fn register_channel() -> Weight {
+		Weight::from_parts(8_240_000, 2475)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: SubstrateBridgeOutboundChannel Channels (r:1 w:1)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel Channels (max_values: None, max_size: None, mode: Measured)
+	fn open_channel() -> Weight {
+		Weight::from_parts(8_920_000, 2554)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: SubstrateBridgeOutboundChannel Channels (r:1 w:1)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel Channels (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel MessageQueues (r:1 w:0)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel MessageQueues (max_values: None, max_size: None, mode: Measured)
+	/// Storage: SubstrateBridgeOutboundChannel ChannelNonces (r:1 w:0)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel ChannelNonces (max_values: None, max_size: None, mode: Measured)
+	fn close_channel() -> Weight {
+		Weight::from_parts(12_880_000, 7662)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: SubstrateBridgeOutboundChannel CommittedBatches (r:1 w:1)
+	/// Proof Skipped: SubstrateBridgeOutboundChannel CommittedBatches (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `n` is `[1, 5]`.
+	fn report_delivery(n: u32, ) -> Weight {
+		Weight::from_parts(9_874_215, 2554)
+			.saturating_add(Weight::from_parts(412_830, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
 }
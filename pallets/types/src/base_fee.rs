@@ -0,0 +1,265 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! EIP-1559 base fee calculation (https://eips.ethereum.org/EIPS/eip-1559).
+//!
+//! Used to validate base fee of the imported headers and to estimate
+//! base fee of the upcoming blocks for relayer fee estimation.
+
+use crate::{Header, U256};
+use codec::{Decode, Encode};
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+/// Base fee of the first EIP-1559 block, 1 gwei.
+pub const INITIAL_BASE_FEE: u64 = 1_000_000_000;
+/// Bounds the amount the base fee can change between blocks.
+pub const BASE_FEE_MAX_CHANGE_DENOMINATOR: u64 = 8;
+/// Bounds the maximum gas limit an EIP-1559 block may have.
+pub const ELASTICITY_MULTIPLIER: u64 = 2;
+
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub enum BaseFeeError {
+    /// Parent header is post-London but has no base fee.
+    MissingParentBaseFee,
+    /// Post-London header has no base fee.
+    MissingBaseFee,
+    /// Pre-London header has base fee.
+    UnexpectedBaseFee,
+    /// Header base fee doesn't match the expected one.
+    InvalidBaseFee,
+}
+
+/// EIP-1559 parameters of the network.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct BaseFeeParams {
+    // Block number on which London (EIP-1559) activated
+    pub london_block: u64,
+    pub elasticity_multiplier: u64,
+    pub max_change_denominator: u64,
+    pub initial_base_fee: U256,
+}
+
+impl BaseFeeParams {
+    /// Ethereum parameters with the given London activation block.
+    pub fn ethereum(london_block: u64) -> Self {
+        BaseFeeParams {
+            london_block,
+            elasticity_multiplier: ELASTICITY_MULTIPLIER,
+            max_change_denominator: BASE_FEE_MAX_CHANGE_DENOMINATOR,
+            initial_base_fee: INITIAL_BASE_FEE.into(),
+        }
+    }
+
+    pub fn is_london(&self, block_number: u64) -> bool {
+        block_number >= self.london_block
+    }
+
+    /// Calculates expected base fee of the child of `parent`,
+    /// `None` if the child is pre-London.
+    pub fn calc_next_base_fee(&self, parent: &Header) -> Result<Option<U256>, BaseFeeError> {
+        if !self.is_london(parent.number + 1) {
+            return Ok(None);
+        }
+        if !self.is_london(parent.number) {
+            return Ok(Some(self.initial_base_fee));
+        }
+        let parent_base_fee = parent.base_fee.ok_or(BaseFeeError::MissingParentBaseFee)?;
+        Ok(Some(self.next_base_fee(
+            parent_base_fee,
+            parent.gas_limit,
+            parent.gas_used,
+        )))
+    }
+
+    /// Checks that `header` base fee matches the one expected after `parent`.
+    pub fn verify_base_fee(&self, header: &Header, parent: &Header) -> Result<(), BaseFeeError> {
+        match (self.calc_next_base_fee(parent)?, header.base_fee) {
+            (None, None) => Ok(()),
+            (None, Some(_)) => Err(BaseFeeError::UnexpectedBaseFee),
+            (Some(_), None) => Err(BaseFeeError::MissingBaseFee),
+            (Some(expected), Some(actual)) if expected == actual => Ok(()),
+            (Some(_), Some(_)) => Err(BaseFeeError::InvalidBaseFee),
+        }
+    }
+
+    /// Forecasts base fees of the `blocks` blocks following `parent`,
+    /// assuming each of them uses `gas_used` gas with the parent gas limit.
+    /// Base fee of pre-London blocks is zero.
+    ///
+    /// Pass `parent.gas_limit` as `gas_used` to get the upper bound.
+    pub fn forecast_base_fees(
+        &self,
+        parent: &Header,
+        gas_used: U256,
+        blocks: usize,
+    ) -> Result<Vec<U256>, BaseFeeError> {
+        let mut base_fees = Vec::with_capacity(blocks);
+        let mut next = self.calc_next_base_fee(parent)?;
+        let mut number = parent.number + 1;
+        while base_fees.len() < blocks {
+            let base_fee = next.unwrap_or_default();
+            base_fees.push(base_fee);
+            number += 1;
+            next = if !self.is_london(number) {
+                None
+            } else if next.is_none() {
+                Some(self.initial_base_fee)
+            } else {
+                Some(self.next_base_fee(base_fee, parent.gas_limit, gas_used))
+            };
+        }
+        Ok(base_fees)
+    }
+
+    fn next_base_fee(&self, parent_base_fee: U256, gas_limit: U256, gas_used: U256) -> U256 {
+        let gas_target = gas_limit / self.elasticity_multiplier.max(1);
+        if gas_target.is_zero() || gas_used == gas_target {
+            return parent_base_fee;
+        }
+        let denominator = self.max_change_denominator.max(1);
+        if gas_used > gas_target {
+            let delta =
+                parent_base_fee.saturating_mul(gas_used - gas_target) / gas_target / denominator;
+            parent_base_fee.saturating_add(delta.max(U256::one()))
+        } else {
+            let delta =
+                parent_base_fee.saturating_mul(gas_target - gas_used) / gas_target / denominator;
+            parent_base_fee.saturating_sub(delta)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(number: u64, base_fee: Option<u64>, gas_used: u64) -> Header {
+        Header {
+            number,
+            gas_limit: 30_000_000u64.into(),
+            gas_used: gas_used.into(),
+            base_fee: base_fee.map(Into::into),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn next_base_fee_follows_gas_usage() {
+        let params = BaseFeeParams::ethereum(10);
+        let next = |base_fee, gas_used| {
+            params
+                .calc_next_base_fee(&header(20, Some(base_fee), gas_used))
+                .unwrap()
+        };
+        assert_eq!(
+            next(1_000_000_000, 15_000_000),
+            Some(1_000_000_000u64.into())
+        );
+        assert_eq!(next(1_000_000_000, 14_000_000), Some(991_666_667u64.into()));
+        assert_eq!(
+            next(1_000_000_000, 16_000_000),
+            Some(1_008_333_333u64.into())
+        );
+        assert_eq!(
+            next(1_000_000_000, 30_000_000),
+            Some(1_125_000_000u64.into())
+        );
+        assert_eq!(next(1_000_000_000, 0), Some(875_000_000u64.into()));
+        // Increases at least by 1 wei
+        assert_eq!(next(7, 16_000_000), Some(8u64.into()));
+    }
+
+    #[test]
+    fn next_base_fee_around_london() {
+        let params = BaseFeeParams::ethereum(10);
+        assert_eq!(params.calc_next_base_fee(&header(8, None, 0)), Ok(None));
+        assert_eq!(
+            params.calc_next_base_fee(&header(9, None, 0)),
+            Ok(Some(INITIAL_BASE_FEE.into()))
+        );
+        assert_eq!(
+            params.calc_next_base_fee(&header(10, None, 0)),
+            Err(BaseFeeError::MissingParentBaseFee)
+        );
+    }
+
+    #[test]
+    fn verify_base_fee_works() {
+        let params = BaseFeeParams::ethereum(10);
+        let parent = header(10, Some(1_000_000_000), 30_000_000);
+        assert_eq!(
+            params.verify_base_fee(&header(11, Some(1_125_000_000), 0), &parent),
+            Ok(())
+        );
+        assert_eq!(
+            params.verify_base_fee(&header(11, Some(1_000_000_000), 0), &parent),
+            Err(BaseFeeError::InvalidBaseFee)
+        );
+        assert_eq!(
+            params.verify_base_fee(&header(11, None, 0), &parent),
+            Err(BaseFeeError::MissingBaseFee)
+        );
+        assert_eq!(
+            params.verify_base_fee(&header(8, Some(1), 0), &header(7, None, 0)),
+            Err(BaseFeeError::UnexpectedBaseFee)
+        );
+        assert_eq!(
+            params.verify_base_fee(&header(8, None, 0), &header(7, None, 0)),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn forecast_base_fees_works() {
+        let params = BaseFeeParams::ethereum(10);
+        let parent = header(20, Some(1_000_000_000), 30_000_000);
+        assert_eq!(
+            params.forecast_base_fees(&parent, parent.gas_limit, 3),
+            Ok(vec![
+                1_125_000_000u64.into(),
+                1_265_625_000u64.into(),
+                1_423_828_125u64.into()
+            ])
+        );
+        assert_eq!(
+            params.forecast_base_fees(&header(8, None, 0), 0u64.into(), 3),
+            Ok(vec![
+                0u64.into(),
+                INITIAL_BASE_FEE.into(),
+                875_000_000u64.into()
+            ])
+        );
+    }
+}
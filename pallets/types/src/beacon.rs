@@ -0,0 +1,659 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Beacon chain (Altair and later) light client primitives.
+//!
+//! Contains the beacon chain types required to follow sync committees and
+//! SSZ merkleization routines used to verify Merkle branches against
+//! beacon state and block body roots.
+
+use crate::{H160, H256, U256};
+use codec::{Decode, Encode};
+use ethbloom::Bloom;
+use hex_literal::hex;
+use sp_io::hashing::sha2_256;
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+
+pub const SLOTS_PER_EPOCH: u64 = 32;
+pub const EPOCHS_PER_SYNC_COMMITTEE_PERIOD: u64 = 256;
+pub const SLOTS_PER_HISTORICAL_ROOT: u64 = 8192;
+pub const SYNC_COMMITTEE_SIZE: usize = 512;
+pub const PUBKEY_SIZE: usize = 48;
+pub const SIGNATURE_SIZE: usize = 96;
+
+pub const DOMAIN_SYNC_COMMITTEE: [u8; 4] = [7, 0, 0, 0];
+
+// Generalized indices of the proven fields, split into depth and subtree index.
+// `execution_payload` in `BeaconBlockBody`, the same from Capella to Fulu
+pub const EXECUTION_PAYLOAD_DEPTH: usize = 4;
+pub const EXECUTION_PAYLOAD_INDEX: u64 = 9;
+// Depth of the `block_roots` vector
+pub const HISTORICAL_ROOT_DEPTH: usize = 13;
+
+/// Generalized index of a proven field, split into depth and subtree index.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct GeneralizedIndex {
+    pub depth: usize,
+    pub index: u64,
+}
+
+impl GeneralizedIndex {
+    pub const fn new(depth: usize, index: u64) -> Self {
+        GeneralizedIndex { depth, index }
+    }
+}
+
+/// Generalized indices of the proven `BeaconState` fields.
+///
+/// Electra grew `BeaconState` over 32 fields, which added a level to the state tree.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct StateGeneralizedIndices {
+    /// `finalized_checkpoint.root`
+    pub finalized_root: GeneralizedIndex,
+    pub current_sync_committee: GeneralizedIndex,
+    pub next_sync_committee: GeneralizedIndex,
+    pub block_roots: GeneralizedIndex,
+}
+
+impl StateGeneralizedIndices {
+    /// Altair to Deneb.
+    pub const PRE_ELECTRA: Self = StateGeneralizedIndices {
+        finalized_root: GeneralizedIndex::new(6, 41),
+        current_sync_committee: GeneralizedIndex::new(5, 22),
+        next_sync_committee: GeneralizedIndex::new(5, 23),
+        block_roots: GeneralizedIndex::new(5, 5),
+    };
+
+    /// Electra and Fulu.
+    pub const ELECTRA: Self = StateGeneralizedIndices {
+        finalized_root: GeneralizedIndex::new(7, 41),
+        current_sync_committee: GeneralizedIndex::new(6, 22),
+        next_sync_committee: GeneralizedIndex::new(6, 23),
+        block_roots: GeneralizedIndex::new(6, 5),
+    };
+}
+
+pub fn compute_epoch(slot: u64) -> u64 {
+    slot / SLOTS_PER_EPOCH
+}
+
+pub fn compute_period(slot: u64) -> u64 {
+    compute_epoch(slot) / EPOCHS_PER_SYNC_COMMITTEE_PERIOD
+}
+
+#[derive(Clone, Default, Encode, Decode, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct BeaconHeader {
+    pub slot: u64,
+    pub proposer_index: u64,
+    pub parent_root: H256,
+    pub state_root: H256,
+    pub body_root: H256,
+}
+
+impl BeaconHeader {
+    pub fn hash_tree_root(&self) -> H256 {
+        merkleize(&[
+            uint64_leaf(self.slot),
+            uint64_leaf(self.proposer_index),
+            self.parent_root,
+            self.state_root,
+            self.body_root,
+        ])
+    }
+}
+
+/// Compressed BLS12-381 public key.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct PublicKey(pub [u8; PUBKEY_SIZE]);
+
+impl Default for PublicKey {
+    fn default() -> Self {
+        PublicKey([0u8; PUBKEY_SIZE])
+    }
+}
+
+impl PublicKey {
+    pub fn hash_tree_root(&self) -> H256 {
+        merkleize(&bytes_to_chunks(&self.0))
+    }
+}
+
+/// Compressed BLS12-381 signature.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct Signature(pub [u8; SIGNATURE_SIZE]);
+
+impl Default for Signature {
+    fn default() -> Self {
+        Signature([0u8; SIGNATURE_SIZE])
+    }
+}
+
+#[derive(Clone, Default, Encode, Decode, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct SyncCommittee {
+    pub pubkeys: Vec<PublicKey>,
+    pub aggregate_pubkey: PublicKey,
+}
+
+impl SyncCommittee {
+    pub fn hash_tree_root(&self) -> H256 {
+        let pubkeys: Vec<H256> = self.pubkeys.iter().map(|x| x.hash_tree_root()).collect();
+        hash_pair(merkleize(&pubkeys), self.aggregate_pubkey.hash_tree_root())
+    }
+}
+
+#[derive(Clone, Default, Encode, Decode, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct SyncAggregate {
+    /// SSZ bitvector of the participating sync committee members.
+    pub sync_committee_bits: Vec<u8>,
+    pub sync_committee_signature: Signature,
+}
+
+impl SyncAggregate {
+    pub fn is_participant(&self, index: usize) -> bool {
+        self.sync_committee_bits
+            .get(index / 8)
+            .map(|byte| (byte >> (index % 8)) & 1 == 1)
+            .unwrap_or(false)
+    }
+
+    pub fn participants(&self) -> usize {
+        self.sync_committee_bits
+            .iter()
+            .map(|byte| byte.count_ones() as usize)
+            .sum()
+    }
+}
+
+#[derive(
+    Clone, Copy, Default, Encode, Decode, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo,
+)]
+pub struct Fork {
+    pub version: [u8; 4],
+    pub epoch: u64,
+}
+
+/// Beacon chain forks which changed the fork version.
+#[derive(
+    Clone, Copy, Default, Encode, Decode, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo,
+)]
+pub struct ForkVersions {
+    pub genesis: Fork,
+    pub altair: Fork,
+    pub bellatrix: Fork,
+    pub capella: Fork,
+    pub deneb: Fork,
+    pub electra: Fork,
+    pub fulu: Fork,
+}
+
+impl ForkVersions {
+    pub fn mainnet() -> Self {
+        ForkVersions {
+            genesis: Fork {
+                version: [0, 0, 0, 0],
+                epoch: 0,
+            },
+            altair: Fork {
+                version: [1, 0, 0, 0],
+                epoch: 74240,
+            },
+            bellatrix: Fork {
+                version: [2, 0, 0, 0],
+                epoch: 144896,
+            },
+            capella: Fork {
+                version: [3, 0, 0, 0],
+                epoch: 194048,
+            },
+            deneb: Fork {
+                version: [4, 0, 0, 0],
+                epoch: 269568,
+            },
+            electra: Fork {
+                version: [5, 0, 0, 0],
+                epoch: 364032,
+            },
+            fulu: Fork {
+                version: [6, 0, 0, 0],
+                epoch: 411392,
+            },
+        }
+    }
+
+    pub fn sepolia() -> Self {
+        ForkVersions {
+            genesis: Fork {
+                version: [0x90, 0x00, 0x00, 0x69],
+                epoch: 0,
+            },
+            altair: Fork {
+                version: [0x90, 0x00, 0x00, 0x70],
+                epoch: 50,
+            },
+            bellatrix: Fork {
+                version: [0x90, 0x00, 0x00, 0x71],
+                epoch: 100,
+            },
+            capella: Fork {
+                version: [0x90, 0x00, 0x00, 0x72],
+                epoch: 56832,
+            },
+            deneb: Fork {
+                version: [0x90, 0x00, 0x00, 0x73],
+                epoch: 132608,
+            },
+            electra: Fork {
+                version: [0x90, 0x00, 0x00, 0x74],
+                epoch: 222464,
+            },
+            fulu: Fork {
+                version: [0x90, 0x00, 0x00, 0x75],
+                epoch: 272640,
+            },
+        }
+    }
+
+    pub fn fork_version(&self, epoch: u64) -> [u8; 4] {
+        [
+            self.fulu,
+            self.electra,
+            self.deneb,
+            self.capella,
+            self.bellatrix,
+            self.altair,
+        ]
+        .into_iter()
+        .find(|fork| epoch >= fork.epoch)
+        .unwrap_or(self.genesis)
+        .version
+    }
+
+    /// Generalized indices of the proven fields in the state of the block at the given slot.
+    pub fn state_gindices(&self, slot: u64) -> StateGeneralizedIndices {
+        if compute_epoch(slot) >= self.electra.epoch {
+            StateGeneralizedIndices::ELECTRA
+        } else {
+            StateGeneralizedIndices::PRE_ELECTRA
+        }
+    }
+}
+
+/// Configuration of a beacon chain network.
+#[derive(
+    Clone, Copy, Default, Encode, Decode, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo,
+)]
+pub struct BeaconNetworkConfig {
+    pub genesis_validators_root: H256,
+    pub fork_versions: ForkVersions,
+}
+
+impl BeaconNetworkConfig {
+    pub fn mainnet() -> Self {
+        BeaconNetworkConfig {
+            genesis_validators_root: hex!(
+                "4b363db94e286120d76eb905340fdd4e54bfe9f06bf33ff6cf5ad27f511bfe95"
+            )
+            .into(),
+            fork_versions: ForkVersions::mainnet(),
+        }
+    }
+
+    pub fn sepolia() -> Self {
+        BeaconNetworkConfig {
+            genesis_validators_root: hex!(
+                "d8ea171f3c94aea21ebc42a1ed61052acf3f9209c00e4efbaaddac09ed9b8078"
+            )
+            .into(),
+            fork_versions: ForkVersions::sepolia(),
+        }
+    }
+
+    /// Domain used to sign the sync committee messages at the given slot.
+    pub fn sync_committee_domain(&self, signature_slot: u64) -> H256 {
+        let fork_version = self
+            .fork_versions
+            .fork_version(compute_epoch(signature_slot.max(1) - 1));
+        compute_domain(
+            DOMAIN_SYNC_COMMITTEE,
+            fork_version,
+            self.genesis_validators_root,
+        )
+    }
+}
+
+/// Execution payload header (Capella and Deneb).
+#[derive(Clone, Default, Encode, Decode, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct ExecutionPayloadHeader {
+    pub parent_hash: H256,
+    pub fee_recipient: H160,
+    pub state_root: H256,
+    pub receipts_root: H256,
+    pub logs_bloom: Bloom,
+    pub prev_randao: H256,
+    pub block_number: u64,
+    pub gas_limit: u64,
+    pub gas_used: u64,
+    pub timestamp: u64,
+    pub extra_data: Vec<u8>,
+    pub base_fee_per_gas: U256,
+    pub block_hash: H256,
+    pub transactions_root: H256,
+    pub withdrawals_root: H256,
+    /// Deneb fields, `None` for Capella headers.
+    pub blob_gas_used: Option<u64>,
+    pub excess_blob_gas: Option<u64>,
+}
+
+impl ExecutionPayloadHeader {
+    pub fn hash_tree_root(&self) -> H256 {
+        let mut fee_recipient = [0u8; 32];
+        fee_recipient[..20].copy_from_slice(self.fee_recipient.as_bytes());
+        let mut base_fee_per_gas = [0u8; 32];
+        self.base_fee_per_gas
+            .to_little_endian(&mut base_fee_per_gas);
+        let extra_data = mix_in_length(
+            merkleize(&bytes_to_chunks(&self.extra_data)),
+            self.extra_data.len() as u64,
+        );
+
+        let mut leaves = vec![
+            self.parent_hash,
+            fee_recipient.into(),
+            self.state_root,
+            self.receipts_root,
+            merkleize(&bytes_to_chunks(self.logs_bloom.as_bytes())),
+            self.prev_randao,
+            uint64_leaf(self.block_number),
+            uint64_leaf(self.gas_limit),
+            uint64_leaf(self.gas_used),
+            uint64_leaf(self.timestamp),
+            extra_data,
+            base_fee_per_gas.into(),
+            self.block_hash,
+            self.transactions_root,
+            self.withdrawals_root,
+        ];
+        if self.blob_gas_used.is_some() || self.excess_blob_gas.is_some() {
+            leaves.push(uint64_leaf(self.blob_gas_used.unwrap_or_default()));
+            leaves.push(uint64_leaf(self.excess_blob_gas.unwrap_or_default()));
+        }
+        merkleize(&leaves)
+    }
+}
+
+/// Execution header data required to verify receipt and state proofs.
+#[derive(Clone, Default, Encode, Decode, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct CompactExecutionHeader {
+    pub parent_hash: H256,
+    pub block_number: u64,
+    pub state_root: H256,
+    pub receipts_root: H256,
+    pub base_fee: U256,
+}
+
+impl From<&ExecutionPayloadHeader> for CompactExecutionHeader {
+    fn from(header: &ExecutionPayloadHeader) -> Self {
+        CompactExecutionHeader {
+            parent_hash: header.parent_hash,
+            block_number: header.block_number,
+            state_root: header.state_root,
+            receipts_root: header.receipts_root,
+            base_fee: header.base_fee_per_gas,
+        }
+    }
+}
+
+/// Trusted checkpoint the light client is initialized with.
+#[derive(Clone, Default, Encode, Decode, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct CheckpointUpdate {
+    pub header: BeaconHeader,
+    pub current_sync_committee: SyncCommittee,
+    pub current_sync_committee_branch: Vec<H256>,
+    pub block_roots_root: H256,
+    pub block_roots_branch: Vec<H256>,
+}
+
+#[derive(Clone, Default, Encode, Decode, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct NextSyncCommitteeUpdate {
+    pub next_sync_committee: SyncCommittee,
+    pub next_sync_committee_branch: Vec<H256>,
+}
+
+/// Light client update signed by the sync committee.
+#[derive(Clone, Default, Encode, Decode, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct LightClientUpdate {
+    /// Header the sync committee signed.
+    pub attested_header: BeaconHeader,
+    pub sync_aggregate: SyncAggregate,
+    /// Slot at which the aggregate signature was created.
+    pub signature_slot: u64,
+    pub next_sync_committee_update: Option<NextSyncCommitteeUpdate>,
+    /// Header finalized in the attested header state.
+    pub finalized_header: BeaconHeader,
+    pub finality_branch: Vec<H256>,
+    /// Root of the `block_roots` vector in the finalized header state.
+    pub block_roots_root: H256,
+    pub block_roots_branch: Vec<H256>,
+}
+
+/// Proof that a beacon header is an ancestor of a finalized beacon header.
+#[derive(Clone, Default, Encode, Decode, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct AncestryProof {
+    pub header_branch: Vec<H256>,
+    pub finalized_block_root: H256,
+}
+
+#[derive(Clone, Default, Encode, Decode, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct ExecutionHeaderUpdate {
+    pub header: BeaconHeader,
+    /// Not required if `header` is finalized itself.
+    pub ancestry_proof: Option<AncestryProof>,
+    pub execution_header: ExecutionPayloadHeader,
+    pub execution_branch: Vec<H256>,
+}
+
+pub fn compute_fork_data_root(fork_version: [u8; 4], genesis_validators_root: H256) -> H256 {
+    let mut version = [0u8; 32];
+    version[..4].copy_from_slice(&fork_version);
+    hash_pair(version.into(), genesis_validators_root)
+}
+
+pub fn compute_domain(
+    domain_type: [u8; 4],
+    fork_version: [u8; 4],
+    genesis_validators_root: H256,
+) -> H256 {
+    let fork_data_root = compute_fork_data_root(fork_version, genesis_validators_root);
+    let mut domain = [0u8; 32];
+    domain[..4].copy_from_slice(&domain_type);
+    domain[4..].copy_from_slice(&fork_data_root[..28]);
+    domain.into()
+}
+
+pub fn compute_signing_root(object_root: H256, domain: H256) -> H256 {
+    hash_pair(object_root, domain)
+}
+
+/// Checks that `leaf` is located at `index` of the tree with given `depth` and `root`.
+pub fn is_valid_merkle_branch(
+    leaf: H256,
+    branch: &[H256],
+    depth: usize,
+    index: u64,
+    root: H256,
+) -> bool {
+    if branch.len() != depth {
+        return false;
+    }
+    let computed = branch.iter().enumerate().fold(leaf, |value, (i, node)| {
+        if (index >> i) & 1 == 1 {
+            hash_pair(*node, value)
+        } else {
+            hash_pair(value, *node)
+        }
+    });
+    computed == root
+}
+
+fn hash_pair(left: H256, right: H256) -> H256 {
+    let mut data = [0u8; 64];
+    data[..32].copy_from_slice(left.as_bytes());
+    data[32..].copy_from_slice(right.as_bytes());
+    sha2_256(&data).into()
+}
+
+fn uint64_leaf(value: u64) -> H256 {
+    let mut leaf = [0u8; 32];
+    leaf[..8].copy_from_slice(&value.to_le_bytes());
+    leaf.into()
+}
+
+fn mix_in_length(root: H256, length: u64) -> H256 {
+    hash_pair(root, uint64_leaf(length))
+}
+
+fn bytes_to_chunks(bytes: &[u8]) -> Vec<H256> {
+    bytes
+        .chunks(32)
+        .map(|chunk| {
+            let mut leaf = [0u8; 32];
+            leaf[..chunk.len()].copy_from_slice(chunk);
+            leaf.into()
+        })
+        .collect()
+}
+
+/// SSZ merkleization of the chunks padded with zero chunks to the next power of two.
+fn merkleize(chunks: &[H256]) -> H256 {
+    let mut layer = chunks.to_vec();
+    if layer.is_empty() {
+        return H256::zero();
+    }
+    layer.resize(layer.len().next_power_of_two(), H256::zero());
+    while layer.len() > 1 {
+        layer = layer
+            .chunks(2)
+            .map(|pair| hash_pair(pair[0], pair[1]))
+            .collect();
+    }
+    layer[0]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fork_data_root_matches_mainnet_fork_digests() {
+        let config = BeaconNetworkConfig::mainnet();
+        let fork_versions = config.fork_versions;
+        for (fork, digest) in [
+            (fork_versions.genesis, hex!("b5303f2a")),
+            (fork_versions.altair, hex!("afcaaba0")),
+            (fork_versions.bellatrix, hex!("4a26c58b")),
+            (fork_versions.capella, hex!("bba4da96")),
+            (fork_versions.deneb, hex!("6a95a1a9")),
+        ] {
+            let root = compute_fork_data_root(fork.version, config.genesis_validators_root);
+            assert_eq!(root[..4], digest);
+        }
+    }
+
+    #[test]
+    fn beacon_header_hash_tree_root() {
+        let header = BeaconHeader {
+            slot: 5000,
+            proposer_index: 42,
+            parent_root: [1u8; 32].into(),
+            state_root: [2u8; 32].into(),
+            body_root: [3u8; 32].into(),
+        };
+        assert_eq!(
+            header.hash_tree_root(),
+            hex!("681071d0daf52076fc6da4a8e82376ccac39f24c964d1fb506c8b5f3078c9dc6").into()
+        );
+    }
+
+    #[test]
+    fn fork_version_is_selected_by_epoch() {
+        let fork_versions = ForkVersions::mainnet();
+        assert_eq!(fork_versions.fork_version(0), [0, 0, 0, 0]);
+        assert_eq!(fork_versions.fork_version(74239), [0, 0, 0, 0]);
+        assert_eq!(fork_versions.fork_version(74240), [1, 0, 0, 0]);
+        assert_eq!(fork_versions.fork_version(200000), [3, 0, 0, 0]);
+        assert_eq!(fork_versions.fork_version(300000), [4, 0, 0, 0]);
+        assert_eq!(fork_versions.fork_version(364032), [5, 0, 0, 0]);
+        assert_eq!(fork_versions.fork_version(u64::MAX), [6, 0, 0, 0]);
+    }
+
+    #[test]
+    fn state_gindices_are_selected_by_fork() {
+        let fork_versions = ForkVersions::mainnet();
+        let electra_slot = 364032 * SLOTS_PER_EPOCH;
+        assert_eq!(
+            fork_versions.state_gindices(electra_slot - 1),
+            StateGeneralizedIndices::PRE_ELECTRA
+        );
+        assert_eq!(
+            fork_versions.state_gindices(electra_slot),
+            StateGeneralizedIndices::ELECTRA
+        );
+        // Generalized indices from the consensus specs
+        for (gindex, expected) in [
+            (StateGeneralizedIndices::PRE_ELECTRA.finalized_root, 105),
+            (
+                StateGeneralizedIndices::PRE_ELECTRA.current_sync_committee,
+                54,
+            ),
+            (StateGeneralizedIndices::PRE_ELECTRA.next_sync_committee, 55),
+            (StateGeneralizedIndices::ELECTRA.finalized_root, 169),
+            (StateGeneralizedIndices::ELECTRA.current_sync_committee, 86),
+            (StateGeneralizedIndices::ELECTRA.next_sync_committee, 87),
+        ] {
+            assert_eq!((1u64 << gindex.depth) + gindex.index, expected);
+        }
+    }
+
+    #[test]
+    fn merkle_branch_is_verified() {
+        let leaves: Vec<H256> = (0u64..8).map(uint64_leaf).collect();
+        let root = merkleize(&leaves);
+        let branch = vec![
+            leaves[4],
+            hash_pair(leaves[6], leaves[7]),
+            merkleize(&leaves[..4]),
+        ];
+        assert!(is_valid_merkle_branch(leaves[5], &branch, 3, 5, root));
+        assert!(!is_valid_merkle_branch(leaves[5], &branch, 3, 4, root));
+        assert!(!is_valid_merkle_branch(leaves[4], &branch, 3, 5, root));
+        assert!(!is_valid_merkle_branch(leaves[5], &branch[..2], 2, 5, root));
+    }
+}
@@ -0,0 +1,445 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Clique proof-of-authority header verification.
+//!
+//! Headers are sealed by one of the authorized signers with a secp256k1
+//! signature stored in the last 65 bytes of `extra_data`. The signer list
+//! is taken from the epoch checkpoint headers, signer votes are not tracked.
+
+use crate::{Address, Header, H256, U256};
+use codec::{Decode, Encode};
+use sp_io::hashing::keccak_256;
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+
+/// Fixed number of extra data prefix bytes reserved for signer vanity.
+pub const EXTRA_VANITY: usize = 32;
+/// Fixed number of extra data suffix bytes reserved for signer seal.
+pub const EXTRA_SEAL: usize = 65;
+/// Block difficulty for in-turn signatures.
+pub const DIFF_IN_TURN: u64 = 2;
+/// Block difficulty for out-of-turn signatures.
+pub const DIFF_NO_TURN: u64 = 1;
+
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub enum CliqueError {
+    /// Extra data is shorter than the vanity prefix.
+    MissingVanity,
+    /// Extra data doesn't contain the seal signature.
+    MissingSignature,
+    /// Non-checkpoint header contains a signer list.
+    ExtraSigners,
+    /// Checkpoint header signer list is empty or malformed.
+    InvalidCheckpointSigners,
+    /// Mix digest is not zero.
+    InvalidMixDigest,
+    /// Header contains ommers.
+    InvalidOmmersHash,
+    /// Difficulty is neither in-turn nor out-of-turn one.
+    InvalidDifficulty,
+    /// Difficulty doesn't match the signer turn.
+    WrongDifficulty,
+    /// Header is not a child of the latest verified header.
+    UnknownAncestor,
+    /// Header timestamp is less than the period after the parent.
+    InvalidTimestamp,
+    /// Failed to recover the signer from the seal.
+    InvalidSignature,
+    /// Signer is not in the authorized signer list.
+    UnauthorizedSigner,
+    /// Signer has signed one of the recent blocks.
+    RecentlySigned,
+    /// Epoch length is zero.
+    InvalidEpoch,
+}
+
+/// State of the Clique signers at the given header.
+#[derive(Clone, Default, Encode, Decode, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct CliqueSnapshot {
+    /// Block number of the latest verified header.
+    pub number: u64,
+    /// Block hash of the latest verified header.
+    pub hash: H256,
+    /// Timestamp of the latest verified header.
+    pub timestamp: u64,
+    /// Authorized signers sorted in ascending order.
+    pub signers: Vec<Address>,
+    /// Recent signers by block number.
+    pub recents: Vec<(u64, Address)>,
+}
+
+impl CliqueSnapshot {
+    /// Creates snapshot from a trusted checkpoint header.
+    pub fn from_checkpoint(header: &Header) -> Result<Self, CliqueError> {
+        Ok(CliqueSnapshot {
+            number: header.number,
+            hash: header.compute_hash(),
+            timestamp: header.timestamp,
+            signers: checkpoint_signers(header)?,
+            recents: Vec::new(),
+        })
+    }
+
+    /// Checks whether the signer is in turn to sign the block with given number.
+    pub fn is_in_turn(&self, number: u64, signer: &Address) -> bool {
+        if self.signers.is_empty() {
+            return false;
+        }
+        let offset = (number % self.signers.len() as u64) as usize;
+        self.signers[offset] == *signer
+    }
+
+    /// Verifies the child of the latest verified header and applies it to the snapshot.
+    /// Returns the header signer.
+    pub fn apply(
+        &mut self,
+        header: &Header,
+        period: u64,
+        epoch: u64,
+    ) -> Result<Address, CliqueError> {
+        if epoch == 0 {
+            return Err(CliqueError::InvalidEpoch);
+        }
+        verify_header_fields(header, epoch)?;
+        if header.number != self.number + 1 || header.parent_hash != self.hash {
+            return Err(CliqueError::UnknownAncestor);
+        }
+        if header.timestamp < self.timestamp.saturating_add(period) {
+            return Err(CliqueError::InvalidTimestamp);
+        }
+
+        let signer = recover_signer(header)?;
+        if self.signers.binary_search(&signer).is_err() {
+            return Err(CliqueError::UnauthorizedSigner);
+        }
+        let limit = self.signers_limit();
+        self.recents
+            .retain(|(number, _)| number + limit > header.number);
+        if self.recents.iter().any(|(_, recent)| *recent == signer) {
+            return Err(CliqueError::RecentlySigned);
+        }
+        let expected_difficulty = if self.is_in_turn(header.number, &signer) {
+            DIFF_IN_TURN
+        } else {
+            DIFF_NO_TURN
+        };
+        if header.difficulty != U256::from(expected_difficulty) {
+            return Err(CliqueError::WrongDifficulty);
+        }
+
+        self.recents.push((header.number, signer));
+        if header.number % epoch == 0 {
+            self.signers = checkpoint_signers(header)?;
+            let limit = self.signers_limit();
+            self.recents
+                .retain(|(number, _)| number + limit > header.number);
+        }
+        self.number = header.number;
+        self.hash = header.compute_hash();
+        self.timestamp = header.timestamp;
+        Ok(signer)
+    }
+
+    /// Number of consecutive blocks in which a signer can sign only once.
+    fn signers_limit(&self) -> u64 {
+        self.signers.len() as u64 / 2 + 1
+    }
+}
+
+/// Checks the header fields which don't depend on the signers state.
+pub fn verify_header_fields(header: &Header, epoch: u64) -> Result<(), CliqueError> {
+    if header.extra_data.len() < EXTRA_VANITY {
+        return Err(CliqueError::MissingVanity);
+    }
+    if header.extra_data.len() < EXTRA_VANITY + EXTRA_SEAL {
+        return Err(CliqueError::MissingSignature);
+    }
+    let signers_len = header.extra_data.len() - EXTRA_VANITY - EXTRA_SEAL;
+    let checkpoint = epoch > 0 && header.number % epoch == 0;
+    if !checkpoint && signers_len != 0 {
+        return Err(CliqueError::ExtraSigners);
+    }
+    if checkpoint && (signers_len == 0 || signers_len % Address::len_bytes() != 0) {
+        return Err(CliqueError::InvalidCheckpointSigners);
+    }
+    if header.mix_hash().unwrap_or_default() != H256::zero() {
+        return Err(CliqueError::InvalidMixDigest);
+    }
+    if header.has_ommers() {
+        return Err(CliqueError::InvalidOmmersHash);
+    }
+    if header.number > 0
+        && header.difficulty != DIFF_IN_TURN.into()
+        && header.difficulty != DIFF_NO_TURN.into()
+    {
+        return Err(CliqueError::InvalidDifficulty);
+    }
+    Ok(())
+}
+
+/// Returns sorted signer list of the checkpoint header.
+pub fn checkpoint_signers(header: &Header) -> Result<Vec<Address>, CliqueError> {
+    let len = header.extra_data.len();
+    if len < EXTRA_VANITY + EXTRA_SEAL {
+        return Err(CliqueError::MissingSignature);
+    }
+    let signers = &header.extra_data[EXTRA_VANITY..len - EXTRA_SEAL];
+    if signers.is_empty() || signers.len() % Address::len_bytes() != 0 {
+        return Err(CliqueError::InvalidCheckpointSigners);
+    }
+    let mut signers: Vec<Address> = signers
+        .chunks(Address::len_bytes())
+        .map(Address::from_slice)
+        .collect();
+    signers.sort();
+    Ok(signers)
+}
+
+/// Hash of the header signed by the Clique signer, i.e. header hash without the seal signature.
+pub fn seal_hash(header: &Header) -> Result<H256, CliqueError> {
+    let len = header.extra_data.len();
+    if len < EXTRA_SEAL {
+        return Err(CliqueError::MissingSignature);
+    }
+    let mut header = header.clone();
+    header.extra_data.truncate(len - EXTRA_SEAL);
+    Ok(header.compute_hash())
+}
+
+/// Recovers the header signer from the seal signature.
+pub fn recover_signer(header: &Header) -> Result<Address, CliqueError> {
+    let message = seal_hash(header)?;
+    let len = header.extra_data.len();
+    let mut signature = [0u8; EXTRA_SEAL];
+    signature.copy_from_slice(&header.extra_data[len - EXTRA_SEAL..]);
+    let public = sp_io::crypto::secp256k1_ecdsa_recover(&signature, &message.0)
+        .map_err(|_| CliqueError::InvalidSignature)?;
+    Ok(Address::from_slice(&keccak_256(&public)[12..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::header::EMPTY_OMMERS_HASH;
+    use sp_core::{ecdsa, Pair};
+
+    const EPOCH: u64 = 6;
+    const PERIOD: u64 = 15;
+
+    fn pair(seed: u8) -> ecdsa::Pair {
+        ecdsa::Pair::from_seed(&[seed; 32])
+    }
+
+    fn address(seed: u8) -> Address {
+        let message = [0u8; 32];
+        let signature = pair(seed).sign_prehashed(&message);
+        let public = sp_io::crypto::secp256k1_ecdsa_recover(&signature.0, &message).unwrap();
+        Address::from_slice(&keccak_256(&public)[12..])
+    }
+
+    fn sign(header: &mut Header, seed: u8) {
+        let len = header.extra_data.len();
+        let signature = pair(seed).sign_prehashed(&seal_hash(header).unwrap().0);
+        header.extra_data[len - EXTRA_SEAL..].copy_from_slice(&signature.0);
+    }
+
+    fn header(number: u64, parent: Option<&Header>, signers: &[u8]) -> Header {
+        let mut extra_data = vec![0u8; EXTRA_VANITY];
+        for seed in signers {
+            extra_data.extend_from_slice(address(*seed).as_bytes());
+        }
+        extra_data.extend_from_slice(&[0u8; EXTRA_SEAL]);
+        Header {
+            parent_hash: parent.map(|p| p.compute_hash()).unwrap_or_default(),
+            number,
+            timestamp: parent
+                .map(|p| p.timestamp + PERIOD)
+                .unwrap_or(1_600_000_000),
+            ommers_hash: EMPTY_OMMERS_HASH.into(),
+            extra_data,
+            difficulty: DIFF_NO_TURN.into(),
+            seal: vec![
+                rlp::encode(&H256::zero()).to_vec(),
+                rlp::encode(&[0u8; 8].to_vec()).to_vec(),
+            ],
+            ..Default::default()
+        }
+    }
+
+    fn signed_child(
+        snapshot: &CliqueSnapshot,
+        parent: &Header,
+        seed: u8,
+        signers: &[u8],
+    ) -> Header {
+        let mut child = header(parent.number + 1, Some(parent), signers);
+        if snapshot.is_in_turn(child.number, &address(seed)) {
+            child.difficulty = DIFF_IN_TURN.into();
+        }
+        sign(&mut child, seed);
+        child
+    }
+
+    #[test]
+    fn recover_signer_works() {
+        let mut header = header(1, None, &[]);
+        sign(&mut header, 1);
+        assert_eq!(recover_signer(&header), Ok(address(1)));
+
+        header.gas_used = 1.into();
+        assert_ne!(recover_signer(&header), Ok(address(1)));
+
+        header.extra_data.truncate(EXTRA_SEAL - 1);
+        assert_eq!(recover_signer(&header), Err(CliqueError::MissingSignature));
+    }
+
+    #[test]
+    fn checkpoint_signers_are_sorted() {
+        let checkpoint = header(0, None, &[1, 2, 3]);
+        let mut expected = vec![address(1), address(2), address(3)];
+        expected.sort();
+        assert_eq!(checkpoint_signers(&checkpoint), Ok(expected.clone()));
+        assert_eq!(
+            CliqueSnapshot::from_checkpoint(&checkpoint).map(|s| s.signers),
+            Ok(expected)
+        );
+
+        let mut invalid = checkpoint;
+        invalid.extra_data.remove(EXTRA_VANITY);
+        assert_eq!(
+            checkpoint_signers(&invalid),
+            Err(CliqueError::InvalidCheckpointSigners)
+        );
+        assert_eq!(
+            checkpoint_signers(&header(0, None, &[])),
+            Err(CliqueError::InvalidCheckpointSigners)
+        );
+    }
+
+    #[test]
+    fn apply_headers_works() {
+        let genesis = header(0, None, &[1, 2, 3]);
+        let mut snapshot = CliqueSnapshot::from_checkpoint(&genesis).unwrap();
+        let mut parent = genesis;
+        for seed in [1, 2, 3, 1, 2] {
+            let child = signed_child(&snapshot, &parent, seed, &[]);
+            assert_eq!(snapshot.apply(&child, PERIOD, EPOCH), Ok(address(seed)));
+            parent = child;
+        }
+        // Epoch checkpoint removes the third signer
+        let checkpoint = signed_child(&snapshot, &parent, 3, &[1, 2]);
+        assert_eq!(snapshot.apply(&checkpoint, PERIOD, EPOCH), Ok(address(3)));
+        let mut expected = vec![address(1), address(2)];
+        expected.sort();
+        assert_eq!(snapshot.signers, expected);
+        assert_eq!(snapshot.number, EPOCH);
+        assert_eq!(snapshot.hash, checkpoint.compute_hash());
+
+        let child = signed_child(&snapshot, &checkpoint, 3, &[]);
+        assert_eq!(
+            snapshot.apply(&child, PERIOD, EPOCH),
+            Err(CliqueError::UnauthorizedSigner)
+        );
+    }
+
+    #[test]
+    fn apply_rejects_invalid_headers() {
+        let genesis = header(0, None, &[1, 2, 3]);
+        let mut snapshot = CliqueSnapshot::from_checkpoint(&genesis).unwrap();
+        let first = signed_child(&snapshot, &genesis, 1, &[]);
+        assert_eq!(snapshot.apply(&first, PERIOD, EPOCH), Ok(address(1)));
+
+        let recent = signed_child(&snapshot, &first, 1, &[]);
+        assert_eq!(
+            snapshot.clone().apply(&recent, PERIOD, EPOCH),
+            Err(CliqueError::RecentlySigned)
+        );
+
+        let unauthorized = signed_child(&snapshot, &first, 4, &[]);
+        assert_eq!(
+            snapshot.clone().apply(&unauthorized, PERIOD, EPOCH),
+            Err(CliqueError::UnauthorizedSigner)
+        );
+
+        let mut wrong_difficulty = header(2, Some(&first), &[]);
+        wrong_difficulty.difficulty = if snapshot.is_in_turn(2, &address(2)) {
+            DIFF_NO_TURN.into()
+        } else {
+            DIFF_IN_TURN.into()
+        };
+        sign(&mut wrong_difficulty, 2);
+        assert_eq!(
+            snapshot.clone().apply(&wrong_difficulty, PERIOD, EPOCH),
+            Err(CliqueError::WrongDifficulty)
+        );
+
+        let mut invalid_difficulty = header(2, Some(&first), &[]);
+        invalid_difficulty.difficulty = 3.into();
+        sign(&mut invalid_difficulty, 2);
+        assert_eq!(
+            snapshot.clone().apply(&invalid_difficulty, PERIOD, EPOCH),
+            Err(CliqueError::InvalidDifficulty)
+        );
+
+        let mut early = header(2, Some(&first), &[]);
+        early.timestamp = first.timestamp + PERIOD - 1;
+        sign(&mut early, 2);
+        assert_eq!(
+            snapshot.clone().apply(&early, PERIOD, EPOCH),
+            Err(CliqueError::InvalidTimestamp)
+        );
+
+        let with_signers = signed_child(&snapshot, &first, 2, &[2]);
+        assert_eq!(
+            snapshot.clone().apply(&with_signers, PERIOD, EPOCH),
+            Err(CliqueError::ExtraSigners)
+        );
+
+        let orphan = signed_child(&snapshot, &genesis, 2, &[]);
+        assert_eq!(
+            snapshot.clone().apply(&orphan, PERIOD, EPOCH),
+            Err(CliqueError::UnknownAncestor)
+        );
+
+        let child = signed_child(&snapshot, &first, 2, &[]);
+        assert_eq!(
+            snapshot.clone().apply(&child, PERIOD, 0),
+            Err(CliqueError::InvalidEpoch)
+        );
+
+        let mut unsigned = header(2, Some(&first), &[]);
+        unsigned.extra_data.truncate(EXTRA_VANITY);
+        assert_eq!(
+            snapshot.apply(&unsigned, PERIOD, EPOCH),
+            Err(CliqueError::MissingSignature)
+        );
+    }
+}
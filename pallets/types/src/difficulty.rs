@@ -28,8 +28,10 @@
 // STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+use crate::base_fee::BaseFeeParams;
 use crate::header::Header;
 use crate::U256;
+use ethereum_types::U512;
 use sp_runtime::RuntimeDebug;
 use sp_std::convert::TryFrom;
 
@@ -48,6 +50,12 @@ pub const ETCHASH_EPOCH_LENGTH: u64 = 60000;
 const DIFFICULTY_BOUND_DIVISOR: u32 = 11;
 const EXP_DIFFICULTY_PERIOD: u64 = 100000;
 const MINIMUM_DIFFICULTY: u32 = 131072;
+/// Block time below which Frontier difficulty increases
+const FRONTIER_DURATION_LIMIT: u64 = 13;
+/// Block reward before the first ECIP-1017 era reduction, 5 ETC
+const CLASSIC_BASE_BLOCK_REWARD: u128 = 5_000_000_000_000_000_000;
+/// Era after which the ECIP-1017 block reward is below 1 wei
+const CLASSIC_MAX_REWARD_ERA: u64 = 200;
 
 #[derive(PartialEq, Eq, RuntimeDebug)]
 pub enum BombDelay {
@@ -70,6 +78,9 @@ pub enum BombDelay {
 #[derive(Copy, Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct ForkConfig {
+    // Block number on which Homestead (EIP-2) rules activated
+    #[cfg_attr(feature = "std", serde(default))]
+    pub homestead_fork_block: u64,
     // Block number on which Byzantium (EIP-649) rules activated
     pub byzantium_fork_block: u64,
     // Block number on which Constantinople (EIP-1234) rules activated
@@ -105,6 +116,7 @@ impl ForkConfig {
 
     pub fn mainnet() -> Self {
         ForkConfig {
+            homestead_fork_block: 1_150_000,
             byzantium_fork_block: 4_370_000,
             constantinople_fork_block: 7_280_000,
             muir_glacier_fork_block: 9_200_000,
@@ -116,6 +128,7 @@ impl ForkConfig {
 
     pub fn ropsten() -> Self {
         ForkConfig {
+            homestead_fork_block: 0,
             byzantium_fork_block: 1_700_000,
             constantinople_fork_block: 4_230_000,
             muir_glacier_fork_block: 7_117_117,
@@ -128,6 +141,7 @@ impl ForkConfig {
 
     pub fn sepolia() -> Self {
         ForkConfig {
+            homestead_fork_block: 0,
             byzantium_fork_block: 0,
             constantinople_fork_block: 0,
             muir_glacier_fork_block: 0,
@@ -139,16 +153,24 @@ impl ForkConfig {
     }
 
     pub fn calc_difficulty(&self, time: u64, parent: &Header) -> Result<U256, &'static str> {
-        let bomb_delay = self
-            .bomb_delay(parent.number + 1)
-            .ok_or("Cannot calculate difficulty for block number prior to Byzantium")?;
-
-        calc_difficulty(Some(bomb_delay as u64), time, parent)
+        let block_number = parent.number + 1;
+        if let Some(bomb_delay) = self.bomb_delay(block_number) {
+            calc_difficulty(Some(bomb_delay as u64), time, parent)
+        } else if block_number >= self.homestead_fork_block {
+            calc_homestead_difficulty(Some(block_number), time, parent)
+        } else {
+            calc_frontier_difficulty(Some(block_number), time, parent)
+        }
     }
 
     pub fn epoch_length(&self) -> u64 {
         EPOCH_LENGTH
     }
+
+    /// EIP-1559 parameters activated at London fork.
+    pub fn base_fee_params(&self) -> BaseFeeParams {
+        BaseFeeParams::ethereum(self.london_fork_block)
+    }
 }
 
 /// Describes when hard forks occurred in Ethereum Classic based networks
@@ -156,32 +178,79 @@ impl ForkConfig {
 #[derive(Copy, Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct ClassicForkConfig {
+    // Block number on which Homestead (EIP-2) rules activated
+    #[cfg_attr(feature = "std", serde(default))]
+    pub homestead_block: u64,
+    // Block number on which the difficulty bomb was paused
+    // https://ecips.ethereumclassic.org/ECIPs/ecip-1010
+    #[cfg_attr(feature = "std", serde(default))]
+    pub ecip1010_pause_block: u64,
+    // Number of blocks the difficulty bomb was paused for
+    #[cfg_attr(feature = "std", serde(default))]
+    pub ecip1010_length: u64,
+    // Block number on which the difficulty bomb was removed
     // https://ecips.ethereumclassic.org/ECIPs/ecip-1041
-    ecip1041_block: u64,
+    pub ecip1041_block: u64,
+    // Block number on which Atlantis (ECIP-1054) enabled EIP-100 difficulty rules
+    #[cfg_attr(feature = "std", serde(default))]
+    pub atlantis_block: u64,
+    // Number of blocks in the block reward era
+    // https://ecips.ethereumclassic.org/ECIPs/ecip-1017
+    #[cfg_attr(feature = "std", serde(default))]
+    pub ecip1017_era_rounds: u64,
     // https://ecips.ethereumclassic.org/ECIPs/ecip-1099
-    ecip1099_block: u64,
+    pub ecip1099_block: u64,
 }
 
 impl ClassicForkConfig {
     pub fn classic() -> Self {
         ClassicForkConfig {
+            homestead_block: 1_150_000,
+            ecip1010_pause_block: 3_000_000,
+            ecip1010_length: 2_000_000,
             ecip1041_block: 5_900_000,
+            atlantis_block: 8_772_000,
+            ecip1017_era_rounds: 5_000_000,
             ecip1099_block: 11_700_000,
         }
     }
 
     pub fn mordor() -> Self {
         ClassicForkConfig {
+            homestead_block: 0,
+            ecip1010_pause_block: 0,
+            ecip1010_length: 0,
             ecip1041_block: 0,
+            atlantis_block: 0,
+            ecip1017_era_rounds: 2_000_000,
             ecip1099_block: 2_520_000,
         }
     }
 
+    /// Returns the block number used to calculate the difficulty bomb
+    /// according to ECIP-1010, `None` if the bomb is removed (ECIP-1041).
+    pub fn bomb_block_number(&self, block_number: u64) -> Option<u64> {
+        let continue_block = self.ecip1010_pause_block + self.ecip1010_length;
+        if block_number >= self.ecip1041_block {
+            None
+        } else if block_number >= continue_block {
+            Some(block_number - self.ecip1010_length)
+        } else if block_number >= self.ecip1010_pause_block {
+            Some(self.ecip1010_pause_block)
+        } else {
+            Some(block_number)
+        }
+    }
+
     pub fn calc_difficulty(&self, time: u64, parent: &Header) -> Result<U256, &'static str> {
-        if parent.number < self.ecip1041_block {
-            return Err("Cannot calculate difficulty for block number prior to ECIP1041");
+        let block_number = parent.number + 1;
+        if block_number >= self.atlantis_block {
+            calc_difficulty(None, time, parent)
+        } else if block_number >= self.homestead_block {
+            calc_homestead_difficulty(self.bomb_block_number(block_number), time, parent)
+        } else {
+            calc_frontier_difficulty(self.bomb_block_number(block_number), time, parent)
         }
-        calc_difficulty(None, time, parent)
     }
 
     pub fn calc_epoch_length(&self, block_number: u64) -> u64 {
@@ -191,10 +260,86 @@ impl ClassicForkConfig {
             ETCHASH_EPOCH_LENGTH
         }
     }
+
+    /// Block reward reduced by 20% every era according to ECIP-1017.
+    pub fn block_reward(&self, block_number: u64) -> U256 {
+        let era = block_number
+            .saturating_sub(1)
+            .checked_div(self.ecip1017_era_rounds)
+            .unwrap_or_default();
+        if era > CLASSIC_MAX_REWARD_ERA {
+            return U256::zero();
+        }
+        // Computed as `reward * 4^era / 5^era` like core-geth, rounding once instead of per era
+        let reward = U512::from(CLASSIC_BASE_BLOCK_REWARD) * U512::from(4).pow(era.into())
+            / U512::from(5).pow(era.into());
+        U256::try_from(reward).unwrap_or_default()
+    }
+}
+
+/// Difficulty bomb for the given block number, `2^(period - 2)`.
+fn difficulty_bomb(bomb_block_number: u64) -> U256 {
+    let period_count = bomb_block_number / EXP_DIFFICULTY_PERIOD;
+    // If period_count < 2, exp is fractional and we can skip adding it
+    if period_count >= 2 {
+        U256::from(2).pow((period_count - 2).into())
+    } else {
+        U256::zero()
+    }
+}
+
+/// Adjusts parent difficulty by `sigma` of 1/2048 parts, bounded by the minimum difficulty.
+fn adjust_difficulty(parent_difficulty: U256, sigma: i64) -> U256 {
+    let step = parent_difficulty >> DIFFICULTY_BOUND_DIVISOR;
+    let difficulty = if sigma < 0 {
+        parent_difficulty.saturating_sub(step * sigma.unsigned_abs())
+    } else {
+        parent_difficulty + step * sigma as u64
+    };
+    difficulty.max(MINIMUM_DIFFICULTY.into())
+}
+
+fn block_time(time: u64, parent: &Header) -> Result<u64, &'static str> {
+    time.checked_sub(parent.timestamp)
+        .ok_or("Invalid block time")
+}
+
+/// Difficulty calculation of the Frontier release.
+///
+/// `bomb_block_number` is the block number used to calculate the difficulty bomb,
+/// `None` if the network has no difficulty bomb.
+pub fn calc_frontier_difficulty(
+    bomb_block_number: Option<u64>,
+    time: u64,
+    parent: &Header,
+) -> Result<U256, &'static str> {
+    let sigma = match block_time(time, parent)? < FRONTIER_DURATION_LIMIT {
+        true => 1,
+        false => -1,
+    };
+    let difficulty = adjust_difficulty(parent.difficulty, sigma);
+    Ok(difficulty + bomb_block_number.map(difficulty_bomb).unwrap_or_default())
+}
+
+/// Difficulty calculation of the Homestead release (https://eips.ethereum.org/EIPS/eip-2).
+///
+/// `bomb_block_number` is the block number used to calculate the difficulty bomb,
+/// `None` if the network has no difficulty bomb.
+pub fn calc_homestead_difficulty(
+    bomb_block_number: Option<u64>,
+    time: u64,
+    parent: &Header,
+) -> Result<U256, &'static str> {
+    let block_time_div_10 =
+        i64::try_from(block_time(time, parent)? / 10).or(Err("Invalid block time"))?;
+    let sigma = (1 - block_time_div_10).max(-99);
+    let difficulty = adjust_difficulty(parent.difficulty, sigma);
+    Ok(difficulty + bomb_block_number.map(difficulty_bomb).unwrap_or_default())
 }
 
 /// This difficulty calculation follows Byzantium rules (https://eips.ethereum.org/EIPS/eip-649)
-/// and shouldn't be used to calculate difficulty prior to the Byzantium fork.
+/// and shouldn't be used to calculate difficulty prior to the Byzantium fork,
+/// see [`calc_homestead_difficulty`] and [`calc_frontier_difficulty`] for earlier blocks.
 pub fn calc_difficulty(
     bomb_delay: Option<u64>,
     time: u64,
@@ -398,23 +543,13 @@ mod tests {
     macro_rules! test_difficulty {
         ($config:ident, $test_case_name:ident, $test_case:ident, $parent:ident) => {
             let difficulty = $config.calc_difficulty($test_case.current_timestamp, &$parent);
-            if $config.byzantium_fork_block > $test_case.current_block_number {
-                assert_eq!(
-                    difficulty,
-                    Err("Cannot calculate difficulty for block number prior to Byzantium"),
-                    "Test case {} failed: {:?}",
-                    $test_case_name,
-                    $test_case,
-                );
-            } else {
-                assert_eq!(
-                    difficulty,
-                    Ok($test_case.current_difficulty),
-                    "Test case {} failed: {:?}",
-                    $test_case_name,
-                    $test_case,
-                );
-            }
+            assert_eq!(
+                difficulty,
+                Ok($test_case.current_difficulty),
+                "Test case {} failed: {:?}",
+                $test_case_name,
+                $test_case,
+            );
         };
     }
 
@@ -457,6 +592,7 @@ mod tests {
     #[test]
     fn frontier_difficulty_calc_is_correct() {
         let all_blocks_are_frontier = ForkConfig {
+            homestead_fork_block: u64::MAX,
             byzantium_fork_block: u64::MAX,
             constantinople_fork_block: u64::MAX,
             muir_glacier_fork_block: u64::MAX,
@@ -470,6 +606,7 @@ mod tests {
     #[test]
     fn homestead_difficulty_calc_is_correct() {
         let all_blocks_are_homestead = ForkConfig {
+            homestead_fork_block: 0,
             byzantium_fork_block: u64::MAX,
             constantinople_fork_block: u64::MAX,
             muir_glacier_fork_block: u64::MAX,
@@ -483,6 +620,7 @@ mod tests {
     #[test]
     fn byzantium_difficulty_calc_is_correct() {
         let all_blocks_are_byzantium = ForkConfig {
+            homestead_fork_block: 0,
             byzantium_fork_block: 0,
             constantinople_fork_block: u64::MAX,
             muir_glacier_fork_block: u64::MAX,
@@ -496,6 +634,7 @@ mod tests {
     #[test]
     fn constantinople_difficulty_calc_is_correct() {
         let all_blocks_are_constantinople = ForkConfig {
+            homestead_fork_block: 0,
             byzantium_fork_block: 0,
             constantinople_fork_block: 0,
             muir_glacier_fork_block: u64::MAX,
@@ -512,6 +651,7 @@ mod tests {
     #[test]
     fn muir_glacier_difficulty_calc_is_correct() {
         let all_blocks_are_muir_glacier = ForkConfig {
+            homestead_fork_block: 0,
             byzantium_fork_block: 0,
             constantinople_fork_block: 0,
             muir_glacier_fork_block: 0,
@@ -533,6 +673,7 @@ mod tests {
     #[test]
     fn arrow_glacier_difficulty_calc_is_correct() {
         let all_blocks_are_arrow_glacier = ForkConfig {
+            homestead_fork_block: 0,
             byzantium_fork_block: 0,
             constantinople_fork_block: 0,
             muir_glacier_fork_block: 0,
@@ -565,6 +706,7 @@ mod tests {
     #[test]
     fn gray_glacier_difficulty_calc_is_correct() {
         let all_blocks_are_gray_glacier = ForkConfig {
+            homestead_fork_block: 0,
             byzantium_fork_block: 0,
             constantinople_fork_block: 0,
             muir_glacier_fork_block: 0,
@@ -594,6 +736,102 @@ mod tests {
         );
     }
 
+    #[test]
+    fn classic_difficulty_bomb_follows_ecip1010_and_ecip1041() {
+        let config = ClassicForkConfig::classic();
+        assert_eq!(config.bomb_block_number(2_999_999), Some(2_999_999));
+        // Paused at 2^28
+        assert_eq!(config.bomb_block_number(3_500_000), Some(3_000_000));
+        assert_eq!(config.bomb_block_number(4_999_999), Some(3_000_000));
+        // Continued with 20 periods delay
+        assert_eq!(config.bomb_block_number(5_500_000), Some(3_500_000));
+        // Removed
+        assert_eq!(config.bomb_block_number(5_900_000), None);
+        assert_eq!(ClassicForkConfig::mordor().bomb_block_number(0), None);
+    }
+
+    #[test]
+    fn classic_difficulty_calc_is_correct() {
+        let config = ClassicForkConfig::classic();
+        let parent = |number: u64, has_ommers: bool| Header {
+            number,
+            timestamp: 1_500_000_000,
+            difficulty: 0x1000_0000_0000u64.into(),
+            ommers_hash: if has_ommers {
+                NON_EMPTY_OMMERS_HASH.into()
+            } else {
+                EMPTY_OMMERS_HASH.into()
+            },
+            ..Default::default()
+        };
+        let step = U256::from(0x1000_0000_0000u64 >> 11);
+        let base = U256::from(0x1000_0000_0000u64);
+
+        // Frontier: block time below 13 seconds increases difficulty
+        assert_eq!(
+            config.calc_difficulty(1_500_000_012, &parent(1_000_000, false)),
+            Ok(base + step + U256::from(2).pow(8.into()))
+        );
+        // Homestead with paused bomb
+        assert_eq!(
+            config.calc_difficulty(1_500_000_025, &parent(3_499_999, false)),
+            Ok(base - step + U256::from(2).pow(28.into()))
+        );
+        // Homestead with continued bomb
+        assert_eq!(
+            config.calc_difficulty(1_500_000_005, &parent(5_499_999, false)),
+            Ok(base + step + U256::from(2).pow(33.into()))
+        );
+        // Homestead without bomb ignores ommers
+        assert_eq!(
+            config.calc_difficulty(1_500_000_005, &parent(5_999_999, true)),
+            Ok(base + step)
+        );
+        // Atlantis (EIP-100) takes ommers into account
+        assert_eq!(
+            config.calc_difficulty(1_500_000_005, &parent(8_772_000, true)),
+            Ok(base + step * 2)
+        );
+        assert_eq!(
+            config.calc_difficulty(1_499_999_999, &parent(8_772_000, true)),
+            Err("Invalid block time")
+        );
+    }
+
+    #[test]
+    fn classic_block_reward_follows_ecip1017() {
+        let config = ClassicForkConfig::classic();
+        let reward = U256::from(CLASSIC_BASE_BLOCK_REWARD);
+        assert_eq!(config.block_reward(5_000_000), reward);
+        assert_eq!(config.block_reward(5_000_001), reward * 4 / 5);
+        assert_eq!(config.block_reward(10_000_001), reward * 16 / 25);
+        // Per-era rounding would diverge once the reward is no longer divisible by 5
+        let era = 25u64;
+        assert_eq!(
+            config.block_reward(era * 5_000_000 + 1),
+            reward * U256::from(4).pow(era.into()) / U256::from(5).pow(era.into())
+        );
+        assert_eq!(
+            config.block_reward(era * 5_000_000 + 1),
+            (0..era).fold(reward, |reward, _| reward * 4 / 5) + 1
+        );
+        assert_eq!(config.block_reward(u64::MAX), U256::zero());
+    }
+
+    #[test]
+    fn classic_fork_config_deserializes_without_new_fields() {
+        let config: ClassicForkConfig =
+            serde_json::from_str(r#"{"ecip1041_block":5900000,"ecip1099_block":11700000}"#)
+                .unwrap();
+        assert_eq!(config.ecip1041_block, 5_900_000);
+        assert_eq!(config.ecip1099_block, 11_700_000);
+        assert_eq!(config.atlantis_block, 0);
+        assert_eq!(
+            config.block_reward(1),
+            U256::from(CLASSIC_BASE_BLOCK_REWARD)
+        );
+    }
+
     #[test]
     fn mainnet_difficulty_calc_is_correct() {
         let mainnet_config = ForkConfig::mainnet();
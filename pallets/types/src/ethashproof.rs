@@ -87,12 +87,15 @@ impl DoubleNodeWithMerkleProof {
         Self::truncate_to_h128(sha2_256(&data).into())
     }
 
-    pub fn apply_merkle_proof(&self, index: u64) -> Result<H128, &'static str> {
+    fn hash_dag_nodes(dag_nodes: &[H512; 2]) -> H128 {
         let mut data = [0u8; 128];
-        data[..64].copy_from_slice(&(self.dag_nodes[0].0));
-        data[64..].copy_from_slice(&(self.dag_nodes[1].0));
+        data[..64].copy_from_slice(&(dag_nodes[0].0));
+        data[64..].copy_from_slice(&(dag_nodes[1].0));
+        Self::truncate_to_h128(sha2_256(&data).into())
+    }
 
-        let mut leaf = Self::truncate_to_h128(sha2_256(&data).into());
+    pub fn apply_merkle_proof(&self, index: u64) -> Result<H128, &'static str> {
+        let mut leaf = Self::hash_dag_nodes(&self.dag_nodes);
 
         for i in 0..self.proof.len() {
             let index_shifted = index.checked_shr(i as u32).ok_or("Failed to shift index")?;
@@ -191,6 +194,54 @@ impl EthashCache {
         ethash::make_cache(data.as_mut_slice(), seed);
         data
     }
+
+    /// Calculates DAG Merkle roots of the given epochs, so they can be appended
+    /// to the on-chain roots once the compiled ones run out.
+    ///
+    /// Generates the full DAG of every epoch, which takes a while.
+    #[cfg(feature = "std")]
+    pub fn dag_merkle_roots(&mut self, epochs: sp_std::ops::Range<u64>) -> Vec<H128> {
+        epochs
+            .map(|epoch| {
+                let full_size = ethash::get_full_size(epoch as usize);
+                calc_dag_merkle_root(self.get(epoch, epoch), full_size)
+            })
+            .collect()
+    }
+}
+
+/// Calculates Merkle root of the DAG generated from the `cache`.
+///
+/// Leaves are hashes of pairs of DAG nodes, odd tree levels are padded with zero nodes.
+#[cfg(feature = "std")]
+pub fn calc_dag_merkle_root(cache: &[u8], full_size: usize) -> H128 {
+    let leaves = (0..full_size / MIX_BYTES).map(|i| {
+        let node = |index: usize| {
+            // Reverse each 32 bytes to get nodes in the form used in proofs
+            let mut data = ethash::calc_dataset_item(cache, index).0;
+            data[..32].reverse();
+            data[32..].reverse();
+            H512::from(data)
+        };
+        DoubleNodeWithMerkleProof::hash_dag_nodes(&[node(2 * i), node(2 * i + 1)])
+    });
+    calc_merkle_root(leaves.collect())
+}
+
+#[cfg(feature = "std")]
+fn calc_merkle_root(mut nodes: Vec<H128>) -> H128 {
+    while nodes.len() > 1 {
+        nodes = nodes
+            .chunks(2)
+            .map(|pair| {
+                DoubleNodeWithMerkleProof::hash_h128(
+                    pair[0],
+                    pair.get(1).copied().unwrap_or_default(),
+                )
+            })
+            .collect();
+    }
+    nodes.pop().unwrap_or_default()
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -205,40 +256,75 @@ pub enum Error {
     IncorrectEpochLength,
 }
 
-pub struct EthashProver {
+/// Source of DAG Merkle roots used to verify Ethash proofs.
+pub trait DagRootsProvider {
+    /// Returns DAG Merkle root for the given epoch of the given epoch length.
+    fn dag_merkle_root(&self, epoch_length: u64, epoch: u64) -> Result<H128, Error>;
+}
+
+/// DAG Merkle roots compiled into the runtime (see [`crate::ethashdata`]).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BuiltinDagRoots;
+
+impl BuiltinDagRoots {
+    /// Returns the first epoch and the roots known for the given epoch length.
+    pub fn roots(epoch_length: u64) -> Result<(u64, &'static [[u8; 16]]), Error> {
+        if epoch_length == EPOCH_LENGTH {
+            Ok((DAGS_START_EPOCH, &DAGS_MERKLE_ROOTS))
+        } else if epoch_length == ETCHASH_EPOCH_LENGTH {
+            Ok((ETCHASH_DAGS_START_EPOCH, &ETCHASH_DAGS_MERKLE_ROOTS))
+        } else {
+            Err(Error::IncorrectEpochLength)
+        }
+    }
+
+    /// Returns the first epoch which root is not compiled into the runtime.
+    pub fn end_epoch(epoch_length: u64) -> Result<u64, Error> {
+        let (start, roots) = Self::roots(epoch_length)?;
+        Ok(start + roots.len() as u64)
+    }
+}
+
+impl DagRootsProvider for BuiltinDagRoots {
+    fn dag_merkle_root(&self, epoch_length: u64, epoch: u64) -> Result<H128, Error> {
+        let (start, roots) = Self::roots(epoch_length)?;
+        epoch
+            .checked_sub(start)
+            .and_then(|index| roots.get(index as usize))
+            .map(H128::from)
+            .ok_or(Error::EpochOutOfRange)
+    }
+}
+
+pub struct EthashProver<P = BuiltinDagRoots> {
     /// A LRU cache of DAG caches
     dags_cache: Option<EthashCache>,
     epoch_length: u64,
+    /// Source of DAG Merkle roots
+    dag_roots: P,
 }
 
-impl EthashProver {
+impl EthashProver<BuiltinDagRoots> {
     pub fn new(epoch_length: u64) -> Self {
-        Self {
-            dags_cache: None,
-            epoch_length,
-        }
+        Self::with_dag_roots(epoch_length, BuiltinDagRoots)
     }
 
     pub fn with_hashimoto_light(epoch_length: u64, max_cache_entries: usize) -> Self {
         Self {
             dags_cache: Some(EthashCache::new(epoch_length, max_cache_entries)),
             epoch_length,
+            dag_roots: BuiltinDagRoots,
         }
     }
+}
 
-    fn dag_merkle_root(&self, epoch_length: u64, epoch: u64) -> Result<H128, Error> {
-        if epoch_length == EPOCH_LENGTH {
-            DAGS_MERKLE_ROOTS
-                .get((epoch - DAGS_START_EPOCH) as usize)
-                .map(H128::from)
-                .ok_or(Error::EpochOutOfRange)
-        } else if epoch_length == ETCHASH_EPOCH_LENGTH {
-            ETCHASH_DAGS_MERKLE_ROOTS
-                .get((epoch - ETCHASH_DAGS_START_EPOCH) as usize)
-                .map(H128::from)
-                .ok_or(Error::EpochOutOfRange)
-        } else {
-            Err(Error::IncorrectEpochLength)
+impl<P: DagRootsProvider> EthashProver<P> {
+    /// Creates prover which verifies DAG Merkle proofs against roots from `dag_roots`.
+    pub fn with_dag_roots(epoch_length: u64, dag_roots: P) -> Self {
+        Self {
+            dags_cache: None,
+            epoch_length,
+            dag_roots,
         }
     }
 
@@ -258,7 +344,7 @@ impl EthashProver {
 
         let epoch = header_number / self.epoch_length;
         // Reuse single Merkle root across all the proofs
-        let merkle_root = self.dag_merkle_root(self.epoch_length, epoch)?;
+        let merkle_root = self.dag_roots.dag_merkle_root(self.epoch_length, epoch)?;
         let full_size = ethash::get_full_size(epoch as usize);
 
         // Boxed index since ethash::hashimoto gets Fn, but not FnMut
@@ -376,6 +462,109 @@ mod tests {
         assert_eq!(cache.recently_accessed_epochs[0].0, 2);
     }
 
+    struct TestDagRoots;
+
+    impl DagRootsProvider for TestDagRoots {
+        fn dag_merkle_root(&self, epoch_length: u64, epoch: u64) -> Result<H128, Error> {
+            match epoch {
+                600 => Ok(H128::repeat_byte(1)),
+                _ => BuiltinDagRoots.dag_merkle_root(epoch_length, epoch),
+            }
+        }
+    }
+
+    #[test]
+    fn builtin_dag_roots_cover_compiled_epochs() {
+        assert_eq!(BuiltinDagRoots::end_epoch(EPOCH_LENGTH), Ok(512));
+        assert_eq!(BuiltinDagRoots::end_epoch(ETCHASH_EPOCH_LENGTH), Ok(145));
+        assert_eq!(
+            BuiltinDagRoots.dag_merkle_root(EPOCH_LENGTH, 0),
+            Ok(hex!("55b891e842e58f58956a847cbbf67821").into())
+        );
+        assert_eq!(
+            BuiltinDagRoots.dag_merkle_root(EPOCH_LENGTH, 512),
+            Err(Error::EpochOutOfRange)
+        );
+        assert_eq!(
+            BuiltinDagRoots.dag_merkle_root(1000, 0),
+            Err(Error::IncorrectEpochLength)
+        );
+    }
+
+    #[test]
+    fn hashimoto_merkle_uses_dag_roots_provider() {
+        let block_with_proofs = BlockWithProofs::from_file(&fixture_path("3.json"));
+        let proofs = block_with_proofs
+            .to_double_node_with_merkle_proof_vec(DoubleNodeWithMerkleProof::from_values);
+        let header_partial_hash: H256 =
+            hex!("481f55e00fd23652cb45ffba86a08b8d497f3b18cc2c0f14cbeb178b4c386e10").into();
+        let header_nonce: H64 = hex!("2e9344e0cbde83ce").into();
+        let prover = EthashProver::with_dag_roots(EPOCH_LENGTH, TestDagRoots);
+
+        assert!(prover
+            .hashimoto_merkle(header_partial_hash, header_nonce, 3, &proofs)
+            .is_ok());
+        // Root for epoch 600 is known but doesn't match the proofs
+        assert_eq!(
+            prover.hashimoto_merkle(
+                header_partial_hash,
+                header_nonce,
+                600 * EPOCH_LENGTH,
+                &proofs
+            ),
+            Err(Error::InvalidMerkleProof),
+        );
+    }
+
+    #[test]
+    fn merkle_root_matches_proofs() {
+        let dag_nodes: Vec<[H512; 2]> = (0..5u8)
+            .map(|i| [H512::repeat_byte(2 * i), H512::repeat_byte(2 * i + 1)])
+            .collect();
+        let mut levels = vec![dag_nodes
+            .iter()
+            .map(DoubleNodeWithMerkleProof::hash_dag_nodes)
+            .collect::<Vec<_>>()];
+        let root = calc_merkle_root(levels[0].clone());
+
+        while levels.last().unwrap().len() > 1 {
+            let mut level = levels.last().unwrap().clone();
+            if level.len() % 2 == 1 {
+                level.push(H128::zero());
+            }
+            levels.push(
+                level
+                    .chunks(2)
+                    .map(|pair| DoubleNodeWithMerkleProof::hash_h128(pair[0], pair[1]))
+                    .collect(),
+            );
+        }
+        for (index, nodes) in dag_nodes.into_iter().enumerate() {
+            let proof = levels[..levels.len() - 1]
+                .iter()
+                .enumerate()
+                .map(|(height, level)| {
+                    level
+                        .get((index >> height) ^ 1)
+                        .copied()
+                        .unwrap_or_default()
+                })
+                .collect();
+            let node = DoubleNodeWithMerkleProof::from_values(nodes, proof);
+            assert_eq!(node.apply_merkle_proof(index as u64), Ok(root));
+        }
+    }
+
+    #[cfg(feature = "expensive_tests")]
+    #[test]
+    fn dag_merkle_root_is_correct_epoch_0() {
+        let mut cache = EthashCache::new(EPOCH_LENGTH, 1);
+        assert_eq!(
+            cache.dag_merkle_roots(0..1),
+            vec![H128::from(DAGS_MERKLE_ROOTS[0])]
+        );
+    }
+
     #[cfg(feature = "expensive_tests")]
     #[test]
     fn hashimoto_light_is_correct_block_11090290() {
@@ -0,0 +1,303 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Typed decoding of EVM contract events.
+//!
+//! An app declares the event once by implementing [`EthEvent`] and decodes
+//! logs from proven receipts without dealing with topics and ABI data:
+//!
+//! ```ignore
+//! struct Transfer { from: H160, to: H160, value: U256 }
+//!
+//! impl EthEvent for Transfer {
+//!     const NAME: &'static str = "Transfer";
+//!
+//!     fn params() -> Vec<(ParamType, bool)> {
+//!         vec![(ParamType::Address, true), (ParamType::Address, true), (ParamType::Uint(256), false)]
+//!     }
+//!
+//!     fn from_tokens(tokens: Vec<Token>) -> Result<Self, DecodeError> { ... }
+//! }
+//!
+//! let transfers = Transfer::find_in(&receipt, token_address);
+//! ```
+
+use crate::{DecodeError, Log, Receipt, H160, H256};
+use ethabi::{Event, EventParam, ParamType, RawLog, Token};
+use ethbloom::{Bloom, Input};
+use sp_std::prelude::*;
+
+/// Decoder of the event with given signature emitted by given contract.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EventDecoder {
+    event: Event,
+    address: Option<H160>,
+}
+
+impl EventDecoder {
+    /// Creates decoder of the event `name` with `params` given as (type, is indexed) pairs.
+    pub fn new(name: &str, params: Vec<(ParamType, bool)>) -> Self {
+        let inputs = params
+            .into_iter()
+            .map(|(kind, indexed)| EventParam {
+                name: Default::default(),
+                kind,
+                indexed,
+            })
+            .collect();
+        EventDecoder {
+            event: Event {
+                name: name.into(),
+                inputs,
+                anonymous: false,
+            },
+            address: None,
+        }
+    }
+
+    /// Accepts only events emitted by the contract with given `address`.
+    pub fn emitted_by(mut self, address: H160) -> Self {
+        self.address = Some(address);
+        self
+    }
+
+    /// Event signature hash (topic0).
+    pub fn signature(&self) -> H256 {
+        self.event.signature()
+    }
+
+    /// Returns `false` if the bloom shows that the event is not in the receipt or block.
+    pub fn may_be_in(&self, bloom: &Bloom) -> bool {
+        let signature = self.signature();
+        if !bloom.contains_input(Input::Raw(signature.as_bytes())) {
+            return false;
+        }
+        match self.address {
+            Some(address) => bloom.contains_input(Input::Raw(address.as_bytes())),
+            None => true,
+        }
+    }
+
+    /// Checks whether the log is emitted by the expected contract and has the event signature.
+    pub fn matches(&self, log: &Log) -> bool {
+        self.check_origin(log).is_ok()
+    }
+
+    /// Decodes log params in the event params order.
+    pub fn decode(&self, log: &Log) -> Result<Vec<Token>, DecodeError> {
+        self.check_origin(log)?;
+        let log = self.event.parse_log(RawLog {
+            topics: log.topics.clone(),
+            data: log.data.clone(),
+        })?;
+        Ok(log.params.into_iter().map(|param| param.value).collect())
+    }
+
+    /// Decodes all matching logs of the receipt, skipping the receipt if bloom doesn't match.
+    pub fn decode_receipt(&self, receipt: &Receipt) -> Result<Vec<Vec<Token>>, DecodeError> {
+        if !self.may_be_in(&receipt.bloom) {
+            return Ok(Vec::new());
+        }
+        receipt
+            .logs
+            .iter()
+            .filter(|log| self.matches(log))
+            .map(|log| self.decode(log))
+            .collect()
+    }
+
+    fn check_origin(&self, log: &Log) -> Result<(), DecodeError> {
+        if let Some(address) = self.address {
+            if log.address != address {
+                return Err(DecodeError::InvalidEventAddress);
+            }
+        }
+        if log.topics.first() != Some(&self.signature()) {
+            return Err(DecodeError::InvalidEventSignature);
+        }
+        Ok(())
+    }
+}
+
+/// Event with typed params.
+pub trait EthEvent: Sized {
+    /// Event name as declared in the contract.
+    const NAME: &'static str;
+
+    /// Event param types with indexed flags, in the declaration order.
+    fn params() -> Vec<(ParamType, bool)>;
+
+    /// Converts decoded params (in the declaration order) into the event.
+    fn from_tokens(tokens: Vec<Token>) -> Result<Self, DecodeError>;
+
+    fn decoder(address: H160) -> EventDecoder {
+        EventDecoder::new(Self::NAME, Self::params()).emitted_by(address)
+    }
+
+    fn signature() -> H256 {
+        EventDecoder::new(Self::NAME, Self::params()).signature()
+    }
+
+    /// Decodes the event from the log emitted by the contract with given `address`.
+    fn decode(log: &Log, address: H160) -> Result<Self, DecodeError> {
+        Self::from_tokens(Self::decoder(address).decode(log)?)
+    }
+
+    /// Decodes all events emitted by the contract with given `address` in the receipt.
+    fn find_in(receipt: &Receipt, address: H160) -> Result<Vec<Self>, DecodeError> {
+        Self::decoder(address)
+            .decode_receipt(receipt)?
+            .into_iter()
+            .map(Self::from_tokens)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::U256;
+    use hex_literal::hex;
+
+    #[derive(Debug, PartialEq)]
+    struct Transfer {
+        from: H160,
+        to: H160,
+        value: U256,
+    }
+
+    impl EthEvent for Transfer {
+        const NAME: &'static str = "Transfer";
+
+        fn params() -> Vec<(ParamType, bool)> {
+            vec![
+                (ParamType::Address, true),
+                (ParamType::Address, true),
+                (ParamType::Uint(256), false),
+            ]
+        }
+
+        fn from_tokens(tokens: Vec<Token>) -> Result<Self, DecodeError> {
+            match tokens.as_slice() {
+                [Token::Address(from), Token::Address(to), Token::Uint(value)] => Ok(Transfer {
+                    from: *from,
+                    to: *to,
+                    value: *value,
+                }),
+                _ => Err(DecodeError::InvalidPayload),
+            }
+        }
+    }
+
+    const TOKEN: [u8; 20] = hex!("c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2");
+
+    fn transfer_log() -> Log {
+        Log {
+            address: TOKEN.into(),
+            topics: vec![
+                hex!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef").into(),
+                hex!("000000000000000000000000e9c1281aae66801fa35ec404d5f2aea393ff6988").into(),
+                hex!("0000000000000000000000007a250d5630b4cf539739df2c5dacb4c659f2488d").into(),
+            ],
+            data: hex!("00000000000000000000000000000000000000000000000003e973b5a5d1078e").to_vec(),
+        }
+    }
+
+    fn receipt(logs: Vec<Log>) -> Receipt {
+        let mut bloom = Bloom::default();
+        for log in &logs {
+            bloom.accrue(Input::Raw(log.address.as_bytes()));
+            for topic in &log.topics {
+                bloom.accrue(Input::Raw(topic.as_bytes()));
+            }
+        }
+        Receipt {
+            bloom,
+            logs,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn decode_typed_event() {
+        assert_eq!(
+            Transfer::signature(),
+            hex!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef").into()
+        );
+        let transfer = Transfer::decode(&transfer_log(), TOKEN.into()).unwrap();
+        assert_eq!(
+            transfer,
+            Transfer {
+                from: hex!("e9c1281aae66801fa35ec404d5f2aea393ff6988").into(),
+                to: hex!("7a250d5630b4cf539739df2c5dacb4c659f2488d").into(),
+                value: 0x03e973b5a5d1078eu64.into(),
+            }
+        );
+    }
+
+    #[test]
+    fn decode_rejects_mismatched_logs() {
+        assert!(matches!(
+            Transfer::decode(&transfer_log(), H160::repeat_byte(1)),
+            Err(DecodeError::InvalidEventAddress)
+        ));
+
+        let mut log = transfer_log();
+        log.topics[0] = H256::repeat_byte(1);
+        assert!(matches!(
+            Transfer::decode(&log, TOKEN.into()),
+            Err(DecodeError::InvalidEventSignature)
+        ));
+
+        // `value` is expected to be non-indexed
+        let mut log = transfer_log();
+        log.topics.push(H256::from_slice(&log.data));
+        log.data.clear();
+        assert!(matches!(
+            Transfer::decode(&log, TOKEN.into()),
+            Err(DecodeError::InvalidABI(_))
+        ));
+    }
+
+    #[test]
+    fn find_events_in_receipt() {
+        let mut other = transfer_log();
+        other.address = H160::repeat_byte(1);
+        let receipt = receipt(vec![other.clone(), transfer_log(), other]);
+
+        let transfers = Transfer::find_in(&receipt, TOKEN.into()).unwrap();
+        assert_eq!(transfers.len(), 1);
+        assert!(Transfer::decoder(TOKEN.into()).may_be_in(&receipt.bloom));
+
+        let unrelated = EventDecoder::new("Approval", Transfer::params());
+        assert!(!unrelated.may_be_in(&receipt.bloom));
+        assert_eq!(unrelated.decode_receipt(&receipt).unwrap().len(), 0);
+    }
+}
@@ -1,7 +1,8 @@
-use crate::MainnetAssetId;
+use crate::event::EthEvent;
+use crate::{DecodeError, Log, MainnetAssetId};
 use codec::{Decode, Encode};
 use derivative::Derivative;
-use ethabi::Token;
+use ethabi::{ParamType, Token};
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 use sp_core::{Get, RuntimeDebug, H160, H256, U256};
@@ -17,7 +18,7 @@ pub struct Proof {
     // The block hash of the block in which the receipt was included.
     pub block_hash: H256,
     // The index of the transaction (and receipt) within the block.
-    // !!! Untrusted value used just for logging purposes.
+    // Verified against the key path of the receipt proof.
     pub tx_index: u32,
     // Proof values
     pub data: Vec<Vec<u8>>,
@@ -220,6 +221,108 @@ impl<MaxMessages: Get<u32>, MaxPayload: Get<u32>> Commitment<MaxMessages, MaxPay
     }
 }
 
+/// Message sent to this chain through the outbound channel contract.
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct InboundMessage {
+    /// Application on the EVM side which sent the message.
+    pub source: H160,
+    /// Payload for target application.
+    pub payload: Vec<u8>,
+}
+
+/// Batch of messages committed by the outbound channel contract.
+///
+/// Emitted as
+/// `BatchCommitted(uint64 indexed nonce, address relayer, uint256 gasUsed, uint256 baseFee, (address,bytes)[] messages)`,
+/// where `relayer` is the account which paid `gasUsed` * `baseFee` for the commit transaction.
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct InboundBatch {
+    /// A batch nonce for replay protection and ordering.
+    pub nonce: u64,
+    pub relayer: H160,
+    pub gas_used: U256,
+    pub base_fee: U256,
+    pub messages: Vec<InboundMessage>,
+}
+
+impl InboundBatch {
+    /// Log of this batch emitted by the contract with given `address`.
+    pub fn to_log(&self, address: H160) -> Log {
+        let messages = self
+            .messages
+            .iter()
+            .map(|message| {
+                Token::Tuple(vec![
+                    Token::Address(message.source),
+                    Token::Bytes(message.payload.clone()),
+                ])
+            })
+            .collect();
+        let data = ethabi::encode(&[
+            Token::Address(self.relayer),
+            Token::Uint(self.gas_used),
+            Token::Uint(self.base_fee),
+            Token::Array(messages),
+        ]);
+        Log {
+            address,
+            topics: vec![Self::signature(), H256::from_low_u64_be(self.nonce)],
+            data,
+        }
+    }
+}
+
+impl EthEvent for InboundBatch {
+    const NAME: &'static str = "BatchCommitted";
+
+    fn params() -> Vec<(ParamType, bool)> {
+        vec![
+            (ParamType::Uint(64), true),
+            (ParamType::Address, false),
+            (ParamType::Uint(256), false),
+            (ParamType::Uint(256), false),
+            (
+                ParamType::Array(Box::new(ParamType::Tuple(vec![
+                    ParamType::Address,
+                    ParamType::Bytes,
+                ]))),
+                false,
+            ),
+        ]
+    }
+
+    fn from_tokens(tokens: Vec<Token>) -> Result<Self, DecodeError> {
+        let Ok([
+            Token::Uint(nonce),
+            Token::Address(relayer),
+            Token::Uint(gas_used),
+            Token::Uint(base_fee),
+            Token::Array(messages),
+        ]) = <[Token; 5]>::try_from(tokens) else {
+            return Err(DecodeError::InvalidPayload);
+        };
+        let messages = messages
+            .into_iter()
+            .map(|message| match message {
+                Token::Tuple(fields) => match <[Token; 2]>::try_from(fields) {
+                    Ok([Token::Address(source), Token::Bytes(payload)]) => {
+                        Ok(InboundMessage { source, payload })
+                    }
+                    _ => Err(DecodeError::InvalidPayload),
+                },
+                _ => Err(DecodeError::InvalidPayload),
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(InboundBatch {
+            nonce: nonce.try_into().map_err(|_| DecodeError::InvalidPayload)?,
+            relayer,
+            gas_used,
+            base_fee,
+            messages,
+        })
+    }
+}
+
 #[test]
 fn test_commitment_hash() {
     use hex_literal::hex;
@@ -238,3 +341,30 @@ fn test_commitment_hash() {
     ));
     assert_eq!(commitment.hash(), expected);
 }
+
+#[test]
+fn test_inbound_batch_log() {
+    let address = H160::repeat_byte(1);
+    let batch = InboundBatch {
+        nonce: 3,
+        relayer: H160::repeat_byte(2),
+        gas_used: 100_000.into(),
+        base_fee: 7.into(),
+        messages: vec![
+            InboundMessage {
+                source: H160::repeat_byte(3),
+                payload: vec![1, 2, 3],
+            },
+            InboundMessage {
+                source: H160::repeat_byte(4),
+                payload: vec![],
+            },
+        ],
+    };
+    let log = batch.to_log(address);
+    assert_eq!(InboundBatch::decode(&log, address).unwrap(), batch);
+    assert!(matches!(
+        InboundBatch::decode(&log, H160::repeat_byte(2)),
+        Err(DecodeError::InvalidEventAddress)
+    ));
+}
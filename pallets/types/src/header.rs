@@ -43,7 +43,7 @@ use serde::{Deserialize, Serialize};
 
 use ethereum_types::{Address, H256, H64, U256};
 
-use crate::{mpt, receipt};
+use crate::{mpt, receipt, state, transaction};
 
 /// Complete block header id.
 #[derive(
@@ -97,6 +97,64 @@ pub struct Header {
 
     // Base fee per gas (EIP-1559), only in headers from the London hardfork onwards.
     pub base_fee: Option<U256>,
+    // Withdrawals root (EIP-4895), only in headers from the Shanghai hardfork onwards.
+    pub withdrawals_root: Option<H256>,
+    // Blob gas used (EIP-4844), only in headers from the Cancun hardfork onwards.
+    pub blob_gas_used: Option<u64>,
+    // Excess blob gas (EIP-4844), only in headers from the Cancun hardfork onwards.
+    pub excess_blob_gas: Option<u64>,
+    // Parent beacon block root (EIP-4788), only in headers from the Cancun hardfork onwards.
+    pub parent_beacon_block_root: Option<H256>,
+    // Execution layer requests hash (EIP-7685), only in headers from the Prague hardfork onwards.
+    pub requests_hash: Option<H256>,
+}
+
+/// Header layout before the Shanghai and later fields were added,
+/// used to migrate the stored headers.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct LegacyHeader {
+    pub parent_hash: H256,
+    pub timestamp: u64,
+    pub number: u64,
+    pub author: Address,
+    pub transactions_root: H256,
+    pub ommers_hash: H256,
+    pub extra_data: Bytes,
+    pub state_root: H256,
+    pub receipts_root: H256,
+    pub logs_bloom: Bloom,
+    pub gas_used: U256,
+    pub gas_limit: U256,
+    pub difficulty: U256,
+    pub seal: Vec<Bytes>,
+    pub base_fee: Option<U256>,
+}
+
+impl From<LegacyHeader> for Header {
+    fn from(header: LegacyHeader) -> Self {
+        Header {
+            parent_hash: header.parent_hash,
+            timestamp: header.timestamp,
+            number: header.number,
+            author: header.author,
+            transactions_root: header.transactions_root,
+            ommers_hash: header.ommers_hash,
+            extra_data: header.extra_data,
+            state_root: header.state_root,
+            receipts_root: header.receipts_root,
+            logs_bloom: header.logs_bloom,
+            gas_used: header.gas_used,
+            gas_limit: header.gas_limit,
+            difficulty: header.difficulty,
+            seal: header.seal,
+            base_fee: header.base_fee,
+            withdrawals_root: None,
+            blob_gas_used: None,
+            excess_blob_gas: None,
+            parent_beacon_block_root: None,
+            requests_hash: None,
+        }
+    }
 }
 
 impl Header {
@@ -114,32 +172,43 @@ impl Header {
         &self,
         proof: &[Vec<u8>],
     ) -> Option<Result<receipt::Receipt, rlp::DecoderError>> {
-        match self.apply_merkle_proof(proof) {
-            Some((root, data)) if root == self.receipts_root => Some(rlp::decode(&data)),
-            Some((_, _)) => None,
-            None => None,
-        }
+        check_receipt_proof(self.receipts_root, proof)
     }
 
-    pub fn apply_merkle_proof(&self, proof: &[Vec<u8>]) -> Option<(H256, Vec<u8>)> {
-        let mut iter = proof.iter().rev();
-        let first_bytes = match iter.next() {
-            Some(b) => b,
-            None => return None,
-        };
-        let item_to_prove: mpt::ShortNode = rlp::decode(first_bytes).ok()?;
-
-        let final_hash: Option<[u8; 32]> =
-            iter.fold(Some(keccak_256(first_bytes)), |maybe_hash, bytes| {
-                let expected_hash = maybe_hash?;
-                let node: Box<dyn mpt::Node> = bytes.as_slice().try_into().ok()?;
-                if (*node).contains_hash(expected_hash.into()) {
-                    return Some(keccak_256(bytes));
-                }
-                None
-            });
+    /// Verifies the proof of the receipt with given transaction `index` against
+    /// the receipts root and decodes the proven receipt.
+    pub fn check_receipt_proof_at(
+        &self,
+        index: u64,
+        proof: &[Vec<u8>],
+    ) -> Option<Result<receipt::Receipt, rlp::DecoderError>> {
+        check_receipt_proof_at(self.receipts_root, index, proof)
+    }
 
-        final_hash.map(|hash| (hash.into(), item_to_prove.value))
+    /// Verifies the proof of the transaction with given `index` against
+    /// the transactions root and decodes the proven transaction.
+    pub fn check_transaction_proof_at(
+        &self,
+        index: u64,
+        proof: &[Vec<u8>],
+    ) -> Option<Result<transaction::Transaction, rlp::DecoderError>> {
+        let data =
+            mpt::verify_proof(self.transactions_root, &rlp::encode(&index), proof).ok()??;
+        Some(rlp::decode(&data))
+    }
+
+    /// Verifies the account proof against the state root.
+    /// Returns `None` if the proof shows that the account doesn't exist.
+    pub fn check_account_proof(
+        &self,
+        address: Address,
+        proof: &[Vec<u8>],
+    ) -> Result<Option<state::Account>, state::StateProofError> {
+        state::verify_account_proof(self.state_root, address, proof)
+    }
+
+    pub fn apply_merkle_proof(&self, proof: &[Vec<u8>]) -> Option<(H256, Vec<u8>)> {
+        apply_merkle_proof(proof)
     }
 
     pub fn mix_hash(&self) -> Option<H256> {
@@ -179,8 +248,21 @@ impl Header {
     /// https://github.com/openethereum/openethereum/blob/193b25a22d5ff07759c6431129e95235510516f9/crates/ethcore/types/src/header.rs#L341
     fn rlp(&self, with_seal: bool) -> Bytes {
         let mut s = RlpStream::new();
+        self.stream_rlp(&mut s, with_seal);
+        s.out().to_vec()
+    }
 
-        let stream_length_without_seal = if self.base_fee.is_some() { 14 } else { 13 };
+    fn stream_rlp(&self, s: &mut RlpStream, with_seal: bool) {
+        let optional_fields = [
+            self.base_fee.is_some(),
+            self.withdrawals_root.is_some(),
+            self.blob_gas_used.is_some(),
+            self.excess_blob_gas.is_some(),
+            self.parent_beacon_block_root.is_some(),
+            self.requests_hash.is_some(),
+        ];
+        let stream_length_without_seal =
+            HEADER_FIELDS + optional_fields.iter().filter(|is_some| **is_some).count();
 
         if with_seal {
             s.begin_list(stream_length_without_seal + self.seal.len());
@@ -211,11 +293,139 @@ impl Header {
         if let Some(base_fee) = self.base_fee {
             s.append(&base_fee);
         }
+        if let Some(withdrawals_root) = self.withdrawals_root {
+            s.append(&withdrawals_root);
+        }
+        if let Some(blob_gas_used) = self.blob_gas_used {
+            s.append(&blob_gas_used);
+        }
+        if let Some(excess_blob_gas) = self.excess_blob_gas {
+            s.append(&excess_blob_gas);
+        }
+        if let Some(parent_beacon_block_root) = self.parent_beacon_block_root {
+            s.append(&parent_beacon_block_root);
+        }
+        if let Some(requests_hash) = self.requests_hash {
+            s.append(&requests_hash);
+        }
+    }
+}
 
-        s.out().to_vec()
+/// Number of header fields preceding the seal.
+const HEADER_FIELDS: usize = 13;
+/// Number of seal fields (mix hash and nonce).
+const SEAL_FIELDS: usize = 2;
+/// Number of fields added to the header by hardforks (London, Shanghai, Cancun, Prague).
+const OPTIONAL_FIELDS: usize = 6;
+
+impl rlp::Encodable for Header {
+    fn rlp_append(&self, s: &mut RlpStream) {
+        self.stream_rlp(s, true);
+    }
+}
+
+impl rlp::Decodable for Header {
+    /// Decodes header with seal, as returned by `eth_getBlockByHash` RPC in RLP form.
+    fn decode(rlp: &rlp::Rlp) -> Result<Self, rlp::DecoderError> {
+        let item_count = rlp.item_count()?;
+        let min_items = HEADER_FIELDS + SEAL_FIELDS;
+        if item_count < min_items || item_count > min_items + OPTIONAL_FIELDS {
+            return Err(rlp::DecoderError::RlpIncorrectListLen);
+        }
+        let optional = |index: usize| -> Option<usize> {
+            let index = min_items + index;
+            if index < item_count {
+                Some(index)
+            } else {
+                None
+            }
+        };
+        Ok(Header {
+            parent_hash: rlp.val_at(0)?,
+            ommers_hash: rlp.val_at(1)?,
+            author: rlp.val_at(2)?,
+            state_root: rlp.val_at(3)?,
+            transactions_root: rlp.val_at(4)?,
+            receipts_root: rlp.val_at(5)?,
+            logs_bloom: rlp.val_at(6)?,
+            difficulty: rlp.val_at(7)?,
+            number: rlp.val_at(8)?,
+            gas_limit: rlp.val_at(9)?,
+            gas_used: rlp.val_at(10)?,
+            timestamp: rlp.val_at(11)?,
+            extra_data: rlp.val_at(12)?,
+            seal: (HEADER_FIELDS..min_items)
+                .map(|i| rlp.at(i).map(|field| field.as_raw().to_vec()))
+                .collect::<Result<_, _>>()?,
+            base_fee: optional(0).map(|i| rlp.val_at(i)).transpose()?,
+            withdrawals_root: optional(1).map(|i| rlp.val_at(i)).transpose()?,
+            blob_gas_used: optional(2).map(|i| rlp.val_at(i)).transpose()?,
+            excess_blob_gas: optional(3).map(|i| rlp.val_at(i)).transpose()?,
+            parent_beacon_block_root: optional(4).map(|i| rlp.val_at(i)).transpose()?,
+            requests_hash: optional(5).map(|i| rlp.val_at(i)).transpose()?,
+        })
     }
 }
 
+/// Verifies the receipt proof against `receipts_root` and decodes the proven receipt.
+/// Used for headers which are not stored as a whole, e.g. execution payload headers.
+pub fn check_receipt_proof(
+    receipts_root: H256,
+    proof: &[Vec<u8>],
+) -> Option<Result<receipt::Receipt, rlp::DecoderError>> {
+    match apply_merkle_proof(proof) {
+        Some((root, data)) if root == receipts_root => Some(rlp::decode(&data)),
+        Some((_, _)) => None,
+        None => None,
+    }
+}
+
+/// Verifies the proof of the receipt with given transaction `index` against
+/// `receipts_root` and decodes the proven receipt.
+/// Returns `None` if the proof is invalid or proves the receipt absence.
+pub fn check_receipt_proof_at(
+    receipts_root: H256,
+    index: u64,
+    proof: &[Vec<u8>],
+) -> Option<Result<receipt::Receipt, rlp::DecoderError>> {
+    check_receipt_proof_with_types(receipts_root, index, proof, receipt::ReceiptTypes::none())
+}
+
+/// Same as [`check_receipt_proof_at`], but also accepts chain-specific `receipt_types`.
+pub fn check_receipt_proof_with_types(
+    receipts_root: H256,
+    index: u64,
+    proof: &[Vec<u8>],
+    receipt_types: receipt::ReceiptTypes,
+) -> Option<Result<receipt::Receipt, rlp::DecoderError>> {
+    let data = mpt::verify_proof(receipts_root, &rlp::encode(&index), proof).ok()??;
+    Some(receipt::Receipt::decode_with_types(
+        &rlp::Rlp::new(&data),
+        receipt_types,
+    ))
+}
+
+fn apply_merkle_proof(proof: &[Vec<u8>]) -> Option<(H256, Vec<u8>)> {
+    let mut iter = proof.iter().rev();
+    let first_bytes = match iter.next() {
+        Some(b) => b,
+        None => return None,
+    };
+    let item_to_prove: mpt::ShortNode = rlp::decode(first_bytes).ok()?;
+
+    let final_hash: Option<[u8; 32]> =
+        iter.fold(Some(keccak_256(first_bytes)), |maybe_hash, bytes| {
+            let expected_hash = maybe_hash?;
+            let node: Box<dyn mpt::Node> = bytes.as_slice().try_into().ok()?;
+            if (*node).contains_hash(expected_hash.into()) {
+                return Some(keccak_256(bytes));
+            }
+            None
+        });
+
+    final_hash.map(|hash| (hash.into(), item_to_prove.value))
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -250,6 +460,7 @@ mod tests {
                 vec
             }],
             base_fee: None,
+            ..Default::default()
         };
         assert_eq!(
             header.compute_hash().as_bytes(),
@@ -289,6 +500,7 @@ mod tests {
                 rlp::encode(&nonce).to_vec(),
             ],
             base_fee: None,
+            ..Default::default()
         };
         assert_eq!(
             header.compute_hash().as_bytes(),
@@ -341,6 +553,11 @@ mod tests {
 			hex!("f904de20b904daf904d701830652f0b9010004200000000000000000000080020000000000010000000000010000000000000000000000000000000000000000000002000000080000000000000000200000000000000000000000000008000000220000000000400010000000000000000000000000000000000000000000000000000000000000040000000010000100000000000800000000004000000000000000000000000000080000004000000000020000000000020000000000000000000000000000000000000000000004000000000002000000000100000000000000000000000000001000000002000020000010200000000000010000000000000000000000000000000000000010000000f903ccf89b9421130f34829b4c343142047a28ce96ec07814b15f863a0ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3efa00000000000000000000000007d843005c7433c16b27ff939cb37471541561ebda0000000000000000000000000e9c1281aae66801fa35ec404d5f2aea393ff6988a000000000000000000000000000000000000000000000000000000005d09b7380f89b9421130f34829b4c343142047a28ce96ec07814b15f863a08c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925a00000000000000000000000007d843005c7433c16b27ff939cb37471541561ebda00000000000000000000000007a250d5630b4cf539739df2c5dacb4c659f2488da0ffffffffffffffffffffffffffffffffffffffffffffffffffffffcc840c6920f89b94c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2f863a0ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3efa0000000000000000000000000e9c1281aae66801fa35ec404d5f2aea393ff6988a00000000000000000000000007a250d5630b4cf539739df2c5dacb4c659f2488da000000000000000000000000000000000000000000000000003e973b5a5d1078ef87994e9c1281aae66801fa35ec404d5f2aea393ff6988e1a01c411e9a96e071241c2f21f7726b17ae89e3cab4c78be50e062b03a9fffbbad1b840000000000000000000000000000000000000000000000000000001f1420ad1d40000000000000000000000000000000000000000000000014ad400879d159a38f8fc94e9c1281aae66801fa35ec404d5f2aea393ff6988f863a0d78ad95fa46c994b6551d0da85fc275fe613ce37657fb8d5e3d130840159d822a00000000000000000000000007a250d5630b4cf539739df2c5dacb4c659f2488da00000000000000000000000007a250d5630b4cf539739df2c5dacb4c659f2488db88000000000000000000000000000000000000000000000000000000005d415f3320000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000003e973b5a5d1078ef87a94c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2f842a07fcf532c15f0a6db0bd6d0e038bea71d30d808c7d98cb3bf7268a95bf5081b65a00000000000000000000000007a250d5630b4cf539739df2c5dacb4c659f2488da000000000000000000000000000000000000000000000000003e973b5a5d1078e").to_vec(),
 		);
         assert!(header.check_receipt_proof(&proof_receipt5).is_some());
+        assert!(header
+            .check_receipt_proof_at(5, &proof_receipt5)
+            .unwrap()
+            .is_ok());
+        assert!(header.check_receipt_proof_at(4, &proof_receipt5).is_none());
 
         // Various invalid proofs
         let proof_empty: Vec<Vec<u8>> = vec![];
@@ -368,6 +585,12 @@ mod tests {
             .check_receipt_proof(&proof_invalid_encoding)
             .is_none());
         assert!(header.check_receipt_proof(&proof_no_full_node).is_none());
+        assert!(header
+            .check_receipt_proof_at(5, &proof_missing_short_node1)
+            .is_none());
+        assert!(header
+            .check_receipt_proof_at(5, &proof_no_full_node)
+            .is_none());
     }
 
     #[test]
@@ -385,5 +608,86 @@ mod tests {
 			hex!("f901ae20b901aaf901a70183bb444eb9010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000010000000000000001000000000000000000000000000100000000000008000000000000000000000000000000000000000000000000000000000000000000000000000000000200000000000010000000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000080000000000000000000000000000000000000000000000002000000000000000000081000000000000000000000000000000000000000400000000000000000000000000000000000000000000000000000000000f89df89b94dac17f958d2ee523a2206206994597c13d831ec7f863a0ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3efa00000000000000000000000002e514404ff6823f1b46a8318a709251db414e5e1a000000000000000000000000055021c55847c00d764357a352e5803237d328954a0000000000000000000000000000000000000000000000000000000000201c370").to_vec(),
 		];
         assert!(header.check_receipt_proof(&proof_receipt263).is_some());
+        assert!(header
+            .check_receipt_proof_at(263, &proof_receipt263)
+            .unwrap()
+            .is_ok());
+        assert!(header
+            .check_receipt_proof_at(262, &proof_receipt263)
+            .is_none());
+    }
+
+    #[test]
+    fn header_rlp_decode_genesis() {
+        // https://etherscan.io/block/0
+        let header = Header {
+            ommers_hash: EMPTY_OMMERS_HASH.into(),
+            state_root: hex!("d7f8974fb5ac78d9ac099b9ad5018bedc2ce0a72dad1827a1709da30580f0544")
+                .into(),
+            transactions_root: hex!(
+                "56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421"
+            )
+            .into(),
+            receipts_root: hex!("56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421")
+                .into(),
+            difficulty: 0x400000000u64.into(),
+            gas_limit: 5000.into(),
+            extra_data: hex!("11bbe8db4e347b4e8c937c1c8370e4b5ed33adb3db69cbdb7a38e1e50b1b82fa")
+                .to_vec(),
+            seal: vec![
+                rlp::encode(&H256::zero()).to_vec(),
+                rlp::encode(&hex!("0000000000000042").to_vec()).to_vec(),
+            ],
+            ..Default::default()
+        };
+        let encoded = rlp::encode(&header);
+        let decoded: Header = rlp::decode(&encoded).unwrap();
+        assert_eq!(decoded, header);
+        assert_eq!(
+            decoded.compute_hash(),
+            hex!("d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3").into()
+        );
+        assert_eq!(keccak_256(&encoded), decoded.compute_hash().0);
+    }
+
+    #[test]
+    fn header_rlp_round_trip_with_optional_fields() {
+        let mut header = Header {
+            number: 19_000_000,
+            timestamp: 1_705_473_612,
+            gas_limit: 30_000_000.into(),
+            extra_data: b"beaverbuild.org".to_vec(),
+            seal: vec![
+                rlp::encode(&H256::repeat_byte(1)).to_vec(),
+                rlp::encode(&vec![0u8; 8]).to_vec(),
+            ],
+            base_fee: Some(7.into()),
+            ..Default::default()
+        };
+        let mut hashes = vec![header.compute_hash()];
+        for index in 0..OPTIONAL_FIELDS - 1 {
+            match index {
+                0 => header.withdrawals_root = Some(H256::repeat_byte(2)),
+                1 => header.blob_gas_used = Some(131_072),
+                2 => header.excess_blob_gas = Some(0),
+                3 => header.parent_beacon_block_root = Some(H256::repeat_byte(3)),
+                _ => header.requests_hash = Some(H256::repeat_byte(4)),
+            }
+            let encoded = rlp::encode(&header);
+            let decoded: Header = rlp::decode(&encoded).unwrap();
+            assert_eq!(decoded, header);
+            assert_eq!(decoded.compute_hash(), keccak_256(&encoded).into());
+            assert!(!hashes.contains(&decoded.compute_hash()));
+            hashes.push(decoded.compute_hash());
+        }
+
+        let mut stream = RlpStream::new_list(HEADER_FIELDS + SEAL_FIELDS + OPTIONAL_FIELDS + 1);
+        for _ in 0..HEADER_FIELDS + SEAL_FIELDS + OPTIONAL_FIELDS + 1 {
+            stream.append_empty_data();
+        }
+        assert_eq!(
+            rlp::decode::<Header>(&stream.out()),
+            Err(rlp::DecoderError::RlpIncorrectListLen)
+        );
     }
 }
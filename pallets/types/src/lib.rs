@@ -30,20 +30,26 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+pub mod base_fee;
+pub mod beacon;
 pub mod channel_abi;
+pub mod clique;
 pub mod difficulty;
 pub mod ethashdata;
 pub mod ethashproof;
+pub mod event;
 pub mod evm;
 pub mod header;
 pub mod log;
-mod mpt;
+pub mod mpt;
 pub mod network_config;
 pub mod receipt;
+pub mod state;
 pub mod substrate;
 #[cfg(any(feature = "test", test))]
 pub mod test_utils;
 pub mod traits;
+pub mod transaction;
 pub mod types;
 pub mod utils;
 
@@ -70,6 +76,10 @@ pub enum DecodeError {
     InvalidABI(ethabi::Error),
     // Invalid message payload
     InvalidPayload,
+    // Log is not emitted by the expected contract
+    InvalidEventAddress,
+    // Log topic0 doesn't match the event signature
+    InvalidEventSignature,
 }
 
 impl From<rlp::DecoderError> for DecodeError {
@@ -217,6 +227,9 @@ pub enum GenericCommitment<MaxMessages: Get<u32>, MaxPayload: Get<u32>> {
     Sub(substrate::Commitment<MaxMessages, MaxPayload>),
     #[cfg_attr(feature = "std", serde(rename = "evm"))]
     EVM(evm::Commitment<MaxMessages, MaxPayload>),
+    SubMerkle(substrate::MerkleCommitment<MaxMessages, MaxPayload>),
+    SubV2(substrate::CommitmentV2<MaxMessages, MaxPayload>),
+    SubMerkleV2(substrate::MerkleCommitmentV2<MaxMessages, MaxPayload>),
 }
 
 impl<MaxMessages: Get<u32>, MaxPayload: Get<u32>> GenericCommitment<MaxMessages, MaxPayload> {
@@ -224,6 +237,9 @@ impl<MaxMessages: Get<u32>, MaxPayload: Get<u32>> GenericCommitment<MaxMessages,
         match self {
             GenericCommitment::EVM(commitment) => commitment.hash(),
             GenericCommitment::Sub(commitment) => commitment.hash(),
+            GenericCommitment::SubMerkle(commitment) => commitment.hash(),
+            GenericCommitment::SubV2(commitment) => commitment.hash(),
+            GenericCommitment::SubMerkleV2(commitment) => commitment.hash(),
         }
     }
 
@@ -231,6 +247,9 @@ impl<MaxMessages: Get<u32>, MaxPayload: Get<u32>> GenericCommitment<MaxMessages,
         match self {
             GenericCommitment::Sub(commitment) => commitment.nonce,
             GenericCommitment::EVM(commitment) => commitment.nonce,
+            GenericCommitment::SubMerkle(commitment) => commitment.nonce,
+            GenericCommitment::SubV2(commitment) => commitment.nonce,
+            GenericCommitment::SubMerkleV2(commitment) => commitment.nonce,
         }
     }
 }
@@ -31,9 +31,153 @@
 //! Helper types to work with Ethereum's Merkle Patricia Trie nodes
 
 use crate::H256;
+use codec::{Decode, Encode};
+use hex_literal::hex;
+use sp_io::hashing::keccak_256;
+use sp_runtime::RuntimeDebug;
 use sp_std::convert::TryFrom;
 use sp_std::prelude::*;
 
+/// Root of the trie without any items.
+pub const EMPTY_TRIE_ROOT: [u8; 32] =
+    hex!("56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421");
+
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub enum ProofError {
+    /// Proof ended before reaching the key.
+    MissingNode,
+    /// Proof node doesn't match the hash referenced by its parent.
+    InvalidNodeHash,
+    /// Proof node is not a valid trie node.
+    InvalidNode,
+    /// Proof contains nodes which are not on the key path.
+    UnusedNodes,
+}
+
+impl From<rlp::DecoderError> for ProofError {
+    fn from(_: rlp::DecoderError) -> Self {
+        ProofError::InvalidNode
+    }
+}
+
+/// Reference to a child node, nodes shorter than 32 bytes are inlined into the parent.
+enum NodeRef {
+    Hash(H256),
+    Inline(Vec<u8>),
+}
+
+impl NodeRef {
+    fn from_rlp(rlp: &rlp::Rlp) -> Result<Option<Self>, ProofError> {
+        if rlp.is_list() {
+            return Ok(Some(NodeRef::Inline(rlp.as_raw().to_vec())));
+        }
+        let data = rlp.data()?;
+        match data.len() {
+            0 => Ok(None),
+            32 => Ok(Some(NodeRef::Hash(H256::from_slice(data)))),
+            _ => Err(ProofError::InvalidNode),
+        }
+    }
+}
+
+/// Verifies the proof of `key` in the trie with given `root`.
+///
+/// `proof` contains the trie nodes on the key path starting from the root.
+/// Returns the value stored under `key` or `None` if the proof shows that
+/// the key is absent from the trie.
+pub fn verify_proof(
+    root: H256,
+    key: &[u8],
+    proof: &[Vec<u8>],
+) -> Result<Option<Vec<u8>>, ProofError> {
+    let mut nodes = proof.iter();
+    let result = if root == EMPTY_TRIE_ROOT.into() {
+        None
+    } else {
+        walk_proof(root, key, &mut nodes)?
+    };
+    if nodes.next().is_some() {
+        return Err(ProofError::UnusedNodes);
+    }
+    Ok(result)
+}
+
+fn walk_proof<'a>(
+    root: H256,
+    key: &[u8],
+    nodes: &mut impl Iterator<Item = &'a Vec<u8>>,
+) -> Result<Option<Vec<u8>>, ProofError> {
+    let nibbles = to_nibbles(key);
+    let mut path = &nibbles[..];
+    let mut next = NodeRef::Hash(root);
+    loop {
+        let node = match next {
+            NodeRef::Hash(hash) => {
+                let node = nodes.next().ok_or(ProofError::MissingNode)?;
+                if keccak_256(node) != hash.0 {
+                    return Err(ProofError::InvalidNodeHash);
+                }
+                node.clone()
+            }
+            NodeRef::Inline(node) => node,
+        };
+        let rlp = rlp::Rlp::new(&node);
+        let child = match rlp.item_count()? {
+            17 => {
+                let Some((nibble, rest)) = path.split_first() else {
+                    let value = rlp.at(16)?.data()?;
+                    return Ok((!value.is_empty()).then(|| value.to_vec()));
+                };
+                path = rest;
+                rlp.at(*nibble as usize)?
+            }
+            2 => {
+                let (node_path, is_leaf) = decode_compact_path(rlp.at(0)?.data()?)?;
+                if is_leaf {
+                    if path == node_path.as_slice() {
+                        return Ok(Some(rlp.at(1)?.data()?.to_vec()));
+                    }
+                    return Ok(None);
+                }
+                if !path.starts_with(&node_path) {
+                    return Ok(None);
+                }
+                path = &path[node_path.len()..];
+                rlp.at(1)?
+            }
+            _ => return Err(ProofError::InvalidNode),
+        };
+        next = match NodeRef::from_rlp(&child)? {
+            Some(next) => next,
+            None => return Ok(None),
+        };
+    }
+}
+
+fn to_nibbles(key: &[u8]) -> Vec<u8> {
+    key.iter()
+        .flat_map(|byte| [byte >> 4, byte & 0x0f])
+        .collect()
+}
+
+/// Decodes hex-prefix encoded path of a leaf or extension node.
+/// Returns path nibbles and whether the node is a leaf.
+fn decode_compact_path(encoded: &[u8]) -> Result<(Vec<u8>, bool), ProofError> {
+    let (first, rest) = encoded.split_first().ok_or(ProofError::InvalidNode)?;
+    let flag = first >> 4;
+    if flag > 3 {
+        return Err(ProofError::InvalidNode);
+    }
+    let mut nibbles = Vec::with_capacity(rest.len() * 2 + 1);
+    if flag & 1 == 1 {
+        nibbles.push(first & 0x0f);
+    } else if first & 0x0f != 0 {
+        return Err(ProofError::InvalidNode);
+    }
+    nibbles.extend(to_nibbles(rest));
+    Ok((nibbles, flag & 2 == 2))
+}
+
 pub trait Node {
     fn contains_hash(&self, hash: H256) -> bool;
 }
@@ -170,4 +314,96 @@ mod tests {
             hex!("4fff54398cad4d05ea6abfd8b0f3b4fe14c04d7ff5f5211c5b927d9cf72ac1d8").to_vec()
         );
     }
+
+    #[test]
+    fn verify_proof_of_receipt() {
+        let root = hex!("fd5e397a84884641f53c496804f24b5276cbb8c5c9cfc2342246be8e3ce5ad02").into();
+        let proof: Vec<Vec<u8>> = RAW_PROOF.iter().map(|node| node.to_vec()).collect();
+        let short_node: ShortNode = rlp::decode(RAW_PROOF[2]).unwrap();
+
+        assert_eq!(
+            verify_proof(root, &rlp::encode(&5u64), &proof),
+            Ok(Some(short_node.value))
+        );
+        // Key path leads to another receipt
+        assert_eq!(
+            verify_proof(root, &rlp::encode(&6u64), &proof),
+            Err(ProofError::InvalidNodeHash)
+        );
+        // Key path diverges in the leaf
+        assert_eq!(verify_proof(root, &[0x05, 0x10], &proof), Ok(None));
+        assert_eq!(
+            verify_proof(root, &rlp::encode(&5u64), &proof[..2]),
+            Err(ProofError::MissingNode)
+        );
+        assert_eq!(
+            verify_proof(root, &rlp::encode(&5u64), &proof[1..]),
+            Err(ProofError::InvalidNodeHash)
+        );
+        let mut extra = proof.clone();
+        extra.push(proof[2].clone());
+        assert_eq!(
+            verify_proof(root, &rlp::encode(&5u64), &extra),
+            Err(ProofError::UnusedNodes)
+        );
+        assert_eq!(
+            verify_proof(EMPTY_TRIE_ROOT.into(), &rlp::encode(&5u64), &[]),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn verify_proof_with_extension_node() {
+        let root = hex!("d128e3a57142d2bf15bc0cbcac7ad54f40750d571b5c3097e425882c10c9ba66").into();
+        let proof = vec![
+            hex!("f90131a00d3cb8d3f57ac1c0e12918a2ebe0cafed8c273577b9dd73e7ed1079b403ef494a0678b9835b834f8a287c0dd33a8fca9146e456ca688555ed4ec1361a2180b778da0fe42da181a46677a043b3d9d4b8bb05a6a17b7b5c010c17e7c1d31cfb7c4f911a0c89f0e2c53241cdb578e1f2b4caf6ba36e00500bdc57fecd66b84a6a58394c19a086c3c1fae5a0575940b5d38e111c469d07883106c26856f3ef608469a2081f13a06c5992ff00aab6226a70a032fd2f571ba22f797321f45e2daa73020d638d21b0a050861e9503ef68728f6c90a44f7fe1bceb2a9bdab6957bbe7136166bd849561ea006aa6eaca8a07e57176e9aa41e6a09edfb7678d1a112404e0ec779d7e567e82ea0bb0b430d303ba21b0af11c487b8a218bd75db54c98940b3f11bad8ff47cad3ef8080808080808080").to_vec(),
+            hex!("f871a0246de222036ee6a03329b0105da0a6b3f916fc95a9ed5a403a581a0c4d74242ca0ac108a49a88b57a05ac34a108b39f1e45f6f167f2b9fbc8d52fb58e2e5a6af1ea0fcfe07ac2ccd3c28b6eab68d1bce112f6f6dbd9023e4ec3c05b96615aa803d798080808080808080808080808080").to_vec(),
+            hex!("e4820001a04fff54398cad4d05ea6abfd8b0f3b4fe14c04d7ff5f5211c5b927d9cf72ac1d8").to_vec(),
+            hex!("f851a096d010643ca2d47412ca66898286b5f2412963b9ec051b33e570d575914c9c5ca028cd24c652989542fe89479ec6388eac4592432242af5ba97563b3ac7c71c019808080808080808080808080808080").to_vec(),
+            hex!("f90211a0bb35a84c5b1dcb78ec9d32614912c696e62df77bebf9ab326ee55b5d3acdde46a01084b30dac8df0accfcd0fd6330b7f6fc72a4651246d0694be9162151686a620a03eed50afdce7909d784c6157c445a444c806b5f23d31f3b63786f600c84a95b2a0af5232f1df6c6d41879804d081abe867002abe26ba3e5f8e0254a83a54769831a0607915fb13dd5da594256389a45007a67a7f7a86e95d38d8462792b6c98a722ea00e1260fda1730f2738c650ce2bfba83857bc10f8fb119ebc4fb39acba24e6fbaa0d11de17e417327457812675ca3b84ae8e1b64827abfe01420953697c8313d5b1a05fcaf2f7a88f76336a0c32ffc78acb87ae2005454bd25d658035331be3173b46a03f94f4952ab9e650f83cfd0e7f367b1bcc493aacf39a06f16c4a2e1b5605da48a0bdb4ec79785ca8ae22d60f1bbd42d707b4d7ec4aff231a3ebab755e315b35053a043a67c3f2bcef37c8f47a673adcb7061007a553696d1092408601c11b2e6846aa0c519d5af48cae87c7f4538845417c9735813bee892a6fe2dda79f5c414e8576aa0f7058256e09589501d7c231d739e61c84a850e139690989d24fda6058b432e98a081a52faab520978cb19ce14400dba0cd5bcdc4e5a3c0740678aa8f97ee0e5c56a0bcecc61cadeae52518e3b68a48af4b11603dfd9d99d99d7985efa6d2de44f904a02cba4accfc6f39bc5adb6d4440eb6358b4a5103ef93298e4e694f1f940f8b48280").to_vec(),
+            hex!("f901ae20b901aaf901a70183bb444eb9010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000010000000000000001000000000000000000000000000100000000000008000000000000000000000000000000000000000000000000000000000000000000000000000000000200000000000010000000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000080000000000000000000000000000000000000000000000002000000000000000000081000000000000000000000000000000000000000400000000000000000000000000000000000000000000000000000000000f89df89b94dac17f958d2ee523a2206206994597c13d831ec7f863a0ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3efa00000000000000000000000002e514404ff6823f1b46a8318a709251db414e5e1a000000000000000000000000055021c55847c00d764357a352e5803237d328954a0000000000000000000000000000000000000000000000000000000000201c370").to_vec(),
+        ];
+        assert!(verify_proof(root, &rlp::encode(&263u64), &proof)
+            .unwrap()
+            .is_some());
+        // Key path diverges in the extension node
+        assert_eq!(
+            verify_proof(root, &rlp::encode(&0x0202u64), &proof[..3]),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn verify_proof_with_inline_nodes() {
+        // Trie with keys 0x01 and 0x02 short enough to be inlined into the branch node
+        let mut leaf1 = rlp::RlpStream::new_list(2);
+        leaf1.append(&vec![0x20u8]).append(&vec![0xaau8]);
+        let mut leaf2 = rlp::RlpStream::new_list(2);
+        leaf2.append(&vec![0x20u8]).append(&vec![0xbbu8]);
+        let mut branch = rlp::RlpStream::new_list(17);
+        for i in 0..16 {
+            match i {
+                1 => branch.append_raw(&leaf1.out(), 1),
+                2 => branch.append_raw(&leaf2.out(), 1),
+                _ => branch.append_empty_data(),
+            };
+        }
+        branch.append_empty_data();
+        let branch = branch.out().to_vec();
+        let mut extension = rlp::RlpStream::new_list(2);
+        extension.append(&vec![0x10u8]).append_raw(&branch, 1);
+        let extension = extension.out().to_vec();
+        let root: H256 = keccak_256(&extension).into();
+
+        assert_eq!(
+            verify_proof(root, &[0x01], &[extension.clone()]),
+            Ok(Some(vec![0xaa]))
+        );
+        assert_eq!(
+            verify_proof(root, &[0x02], &[extension.clone()]),
+            Ok(Some(vec![0xbb]))
+        );
+        assert_eq!(verify_proof(root, &[0x03], &[extension.clone()]), Ok(None));
+        assert_eq!(verify_proof(root, &[0x11], &[extension]), Ok(None));
+    }
 }
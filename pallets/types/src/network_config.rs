@@ -29,7 +29,9 @@
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use crate::{
+    base_fee::BaseFeeParams,
     difficulty::{ClassicForkConfig, ForkConfig},
+    receipt::ReceiptTypes,
     EVMChainId,
 };
 use codec::{Decode, Encode};
@@ -41,17 +43,29 @@ use serde::{Deserialize, Serialize};
 #[derive(Copy, Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum Consensus {
-    Ethash { fork_config: ForkConfig },
-    Etchash { fork_config: ClassicForkConfig },
-    Clique { period: u64, epoch: u64 },
+    Ethash {
+        fork_config: ForkConfig,
+    },
+    Etchash {
+        fork_config: ClassicForkConfig,
+    },
+    Clique {
+        period: u64,
+        epoch: u64,
+    },
+    /// Post-merge networks finalized by the beacon chain.
+    ProofOfStake,
 }
 
 impl Consensus {
-    pub fn calc_epoch_length(&self, block_number: u64) -> u64 {
+    /// Returns `None` for the networks finalized by the beacon chain, which have no
+    /// execution layer epochs.
+    pub fn calc_epoch_length(&self, block_number: u64) -> Option<u64> {
         match self {
-            Consensus::Clique { epoch, .. } => *epoch,
-            Consensus::Ethash { fork_config } => fork_config.epoch_length(),
-            Consensus::Etchash { fork_config } => fork_config.calc_epoch_length(block_number),
+            Consensus::Clique { epoch, .. } => Some(*epoch),
+            Consensus::Ethash { fork_config } => Some(fork_config.epoch_length()),
+            Consensus::Etchash { fork_config } => Some(fork_config.calc_epoch_length(block_number)),
+            Consensus::ProofOfStake => None,
         }
     }
 }
@@ -70,19 +84,30 @@ pub enum NetworkConfig {
         chain_id: EVMChainId,
         consensus: Consensus,
     },
+    /// Ethereum mainnet after the merge, followed by the beacon light client.
+    BeaconMainnet,
+    /// Sepolia after the merge, followed by the beacon light client.
+    BeaconSepolia,
+    /// Custom network with chain-specific receipt types, e.g. deposit receipts on L2 chains.
+    CustomWithReceiptTypes {
+        chain_id: EVMChainId,
+        consensus: Consensus,
+        receipt_types: ReceiptTypes,
+    },
 }
 
 impl NetworkConfig {
     pub fn chain_id(&self) -> EVMChainId {
         match self {
-            NetworkConfig::Mainnet => 1u32.into(),
+            NetworkConfig::Mainnet | NetworkConfig::BeaconMainnet => 1u32.into(),
             NetworkConfig::Ropsten => 3u32.into(),
-            NetworkConfig::Sepolia => 11155111u32.into(),
+            NetworkConfig::Sepolia | NetworkConfig::BeaconSepolia => 11155111u32.into(),
             NetworkConfig::Rinkeby => 4u32.into(),
             NetworkConfig::Goerli => 5u32.into(),
             NetworkConfig::Classic => 61u32.into(),
             NetworkConfig::Mordor => 63u32.into(),
-            NetworkConfig::Custom { chain_id, .. } => *chain_id,
+            NetworkConfig::Custom { chain_id, .. }
+            | NetworkConfig::CustomWithReceiptTypes { chain_id, .. } => *chain_id,
         }
     }
 
@@ -114,7 +139,82 @@ impl NetworkConfig {
             NetworkConfig::Custom {
                 consensus: protocol,
                 ..
+            }
+            | NetworkConfig::CustomWithReceiptTypes {
+                consensus: protocol,
+                ..
             } => *protocol,
+            NetworkConfig::BeaconMainnet | NetworkConfig::BeaconSepolia => Consensus::ProofOfStake,
+        }
+    }
+
+    /// Receipt types accepted in addition to the standard ones.
+    pub fn receipt_types(&self) -> ReceiptTypes {
+        match self {
+            NetworkConfig::CustomWithReceiptTypes { receipt_types, .. } => *receipt_types,
+            _ => ReceiptTypes::none(),
         }
     }
+
+    /// EIP-1559 parameters of the network, `None` if it never activated London.
+    /// Custom Clique networks are expected to be post-London since genesis.
+    pub fn base_fee_params(&self) -> Option<BaseFeeParams> {
+        match self {
+            NetworkConfig::Rinkeby => Some(BaseFeeParams::ethereum(8_897_988)),
+            NetworkConfig::Goerli => Some(BaseFeeParams::ethereum(5_062_605)),
+            _ => match self.consensus() {
+                Consensus::Ethash { fork_config } => Some(fork_config.base_fee_params()),
+                Consensus::Clique { .. } => Some(BaseFeeParams::ethereum(0)),
+                Consensus::Etchash { .. } | Consensus::ProofOfStake => None,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_custom_config_encoding_is_unchanged() {
+        let chain_id: EVMChainId = 1338u32.into();
+        let consensus = Consensus::Clique {
+            period: 15,
+            epoch: 30000,
+        };
+        let mut encoded = vec![7u8];
+        encoded.extend(chain_id.encode());
+        encoded.extend(consensus.encode());
+        assert_eq!(
+            NetworkConfig::decode(&mut &encoded[..]).unwrap(),
+            NetworkConfig::Custom {
+                chain_id,
+                consensus
+            }
+        );
+    }
+
+    #[test]
+    fn test_base_fee_params() {
+        assert_eq!(
+            NetworkConfig::Mainnet.base_fee_params(),
+            Some(BaseFeeParams::ethereum(12_965_000))
+        );
+        assert_eq!(
+            NetworkConfig::Goerli.base_fee_params(),
+            Some(BaseFeeParams::ethereum(5_062_605))
+        );
+        assert_eq!(
+            NetworkConfig::Custom {
+                chain_id: 1338u32.into(),
+                consensus: Consensus::Clique {
+                    period: 15,
+                    epoch: 30000
+                },
+            }
+            .base_fee_params(),
+            Some(BaseFeeParams::ethereum(0))
+        );
+        assert_eq!(NetworkConfig::Classic.base_fee_params(), None);
+    }
 }
@@ -38,9 +38,82 @@ use sp_runtime::{
 };
 use sp_std::prelude::*;
 
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+/// Receipt type, matches the type of the transaction (EIP-2718).
+#[derive(
+    Clone, Copy, Default, Encode, Decode, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo,
+)]
+pub enum ReceiptKind {
+    #[default]
+    Legacy,
+    /// EIP-2930
+    AccessList,
+    /// EIP-1559
+    DynamicFee,
+    /// EIP-4844
+    Blob,
+    /// Chain-specific type, e.g. deposit transactions (0x7e) on OP-stack chains.
+    Custom(u8),
+}
+
+/// Set of chain-specific receipt types accepted in addition to the standard ones.
+/// Typed transaction types are in `0x00..=0x7f` range, so the set fits in a bitmask.
+#[derive(
+    Clone, Copy, Default, Encode, Decode, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo,
+)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ReceiptTypes(pub u128);
+
+impl ReceiptTypes {
+    pub const fn none() -> Self {
+        ReceiptTypes(0)
+    }
+
+    pub const fn with(self, receipt_type: u8) -> Self {
+        if receipt_type > 0x7f {
+            return self;
+        }
+        ReceiptTypes(self.0 | (1 << receipt_type))
+    }
+
+    pub fn contains(&self, receipt_type: u8) -> bool {
+        receipt_type <= 0x7f && self.0 & (1 << receipt_type) != 0
+    }
+}
+
+/// Receipt outcome, receipts before Byzantium contain the state root instead of the status.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub enum ReceiptOutcome {
+    PostState(H256),
+    Status(bool),
+}
+
+impl Default for ReceiptOutcome {
+    fn default() -> Self {
+        ReceiptOutcome::Status(true)
+    }
+}
+
+impl rlp::Decodable for ReceiptOutcome {
+    fn decode(rlp: &rlp::Rlp) -> Result<Self, rlp::DecoderError> {
+        let data = rlp.data()?;
+        match data {
+            [] => Ok(ReceiptOutcome::Status(false)),
+            [1] => Ok(ReceiptOutcome::Status(true)),
+            _ if data.len() == 32 => Ok(ReceiptOutcome::PostState(H256::from_slice(data))),
+            _ => Err(rlp::DecoderError::Custom(
+                "Expected receipt post state or status",
+            )),
+        }
+    }
+}
+
 #[derive(Clone, Default, Encode, Decode, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
 pub struct Receipt {
-    pub post_state_or_status: Vec<u8>,
+    pub kind: ReceiptKind,
+    pub outcome: ReceiptOutcome,
     pub cumulative_gas_used: u64,
     pub bloom: Bloom,
     pub logs: Vec<Log>,
@@ -55,10 +128,47 @@ impl Receipt {
         self.logs.iter().any(|l| Keccak256::hash_of(l) == log)
     }
 
-    fn decode_list(rlp: &rlp::Rlp) -> Result<Self, rlp::DecoderError> {
+    /// Returns whether the transaction succeeded, `None` for pre-Byzantium receipts.
+    pub fn is_successful(&self) -> Option<bool> {
+        match self.outcome {
+            ReceiptOutcome::Status(status) => Some(status),
+            ReceiptOutcome::PostState(_) => None,
+        }
+    }
+
+    /// Decodes receipt accepting chain-specific `custom_types` in addition to the standard ones.
+    pub fn decode_with_types(
+        rlp: &rlp::Rlp,
+        custom_types: ReceiptTypes,
+    ) -> Result<Self, rlp::DecoderError> {
+        if rlp.is_data() {
+            // Typed receipt
+            let data = rlp.as_raw();
+            let kind = match data[0] {
+                1 => ReceiptKind::AccessList,
+                2 => ReceiptKind::DynamicFee,
+                3 => ReceiptKind::Blob,
+                t if custom_types.contains(t) => ReceiptKind::Custom(t),
+                _ => return Err(rlp::DecoderError::Custom("Unsupported receipt type")),
+            };
+            let receipt_rlp = &rlp::Rlp::new(&data[1..]);
+            if !receipt_rlp.is_list() {
+                return Err(rlp::DecoderError::RlpExpectedToBeList);
+            }
+            Self::decode_list(kind, receipt_rlp)
+        } else if rlp.is_list() {
+            // Legacy receipt
+            Self::decode_list(ReceiptKind::Legacy, rlp)
+        } else {
+            Err(rlp::DecoderError::RlpExpectedToBeList)
+        }
+    }
+
+    /// Decodes receipt fields, chain-specific receipts may contain additional trailing fields.
+    fn decode_list(kind: ReceiptKind, rlp: &rlp::Rlp) -> Result<Self, rlp::DecoderError> {
         let mut iter = rlp.iter();
 
-        let post_state_or_status: Vec<u8> = match iter.next() {
+        let outcome: ReceiptOutcome = match iter.next() {
             Some(data) => data.as_val()?,
             None => {
                 return Err(rlp::DecoderError::Custom(
@@ -86,8 +196,13 @@ impl Receipt {
             None => return Err(rlp::DecoderError::Custom("Expected receipt logs")),
         };
 
+        if iter.next().is_some() && !matches!(kind, ReceiptKind::Custom(_)) {
+            return Err(rlp::DecoderError::RlpIncorrectListLen);
+        }
+
         Ok(Self {
-            post_state_or_status,
+            kind,
+            outcome,
             cumulative_gas_used,
             bloom,
             logs,
@@ -97,34 +212,14 @@ impl Receipt {
 
 impl rlp::Decodable for Receipt {
     fn decode(rlp: &rlp::Rlp) -> Result<Self, rlp::DecoderError> {
-        if rlp.is_data() {
-            // Typed receipt
-            let data = rlp.as_raw();
-            match data[0] {
-                // 1 = EIP-2930, 2 = EIP-1559
-                1 | 2 => {
-                    let receipt_rlp = &rlp::Rlp::new(&data[1..]);
-                    if !receipt_rlp.is_list() {
-                        return Err(rlp::DecoderError::RlpExpectedToBeList);
-                    }
-                    Self::decode_list(&rlp::Rlp::new(&data[1..]))
-                }
-                _ => Err(rlp::DecoderError::Custom("Unsupported receipt type")),
-            }
-        } else if rlp.is_list() {
-            // Legacy receipt
-            Self::decode_list(rlp)
-        } else {
-            Err(rlp::DecoderError::RlpExpectedToBeList)
-        }
+        Self::decode_with_types(rlp, ReceiptTypes::none())
     }
 }
 
 #[cfg(test)]
 mod tests {
 
-    use super::Receipt;
-    use ethbloom::Bloom;
+    use super::*;
     use hex_literal::hex;
 
     const RAW_RECEIPT: [u8; 1242] = hex!(
@@ -167,7 +262,9 @@ mod tests {
     #[test]
     fn decode_legacy_receipt() {
         let receipt: Receipt = rlp::decode(&RAW_RECEIPT).unwrap();
-        assert_eq!(receipt.post_state_or_status, vec!(1));
+        assert_eq!(receipt.kind, ReceiptKind::Legacy);
+        assert_eq!(receipt.outcome, ReceiptOutcome::Status(true));
+        assert_eq!(receipt.is_successful(), Some(true));
         assert_eq!(receipt.cumulative_gas_used, 414448);
         assert_eq!(
             receipt.bloom,
@@ -186,4 +283,64 @@ mod tests {
         );
         assert_eq!(receipt.logs.len(), 6);
     }
+
+    fn typed_receipt(receipt_type: u8, extra_fields: usize) -> Vec<u8> {
+        let legacy = rlp::Rlp::new(&RAW_RECEIPT);
+        let mut s = rlp::RlpStream::new_list(4 + extra_fields);
+        for item in legacy.iter() {
+            s.append_raw(item.as_raw(), 1);
+        }
+        for _ in 0..extra_fields {
+            s.append(&1u64);
+        }
+        let mut data = vec![receipt_type];
+        data.extend_from_slice(&s.out());
+        data
+    }
+
+    #[test]
+    fn decode_typed_receipts() {
+        for (receipt_type, kind) in [
+            (1, ReceiptKind::AccessList),
+            (2, ReceiptKind::DynamicFee),
+            (3, ReceiptKind::Blob),
+        ] {
+            let receipt: Receipt = rlp::decode(&typed_receipt(receipt_type, 0)).unwrap();
+            assert_eq!(receipt.kind, kind);
+            assert_eq!(receipt.cumulative_gas_used, 414448);
+            assert_eq!(receipt.logs.len(), 6);
+        }
+        assert_eq!(
+            rlp::decode::<Receipt>(&typed_receipt(2, 1)),
+            Err(rlp::DecoderError::RlpIncorrectListLen)
+        );
+    }
+
+    #[test]
+    fn decode_custom_typed_receipts() {
+        // OP-stack deposit receipt with deposit nonce and receipt version
+        let deposit = typed_receipt(0x7e, 2);
+        assert_eq!(
+            rlp::decode::<Receipt>(&deposit),
+            Err(rlp::DecoderError::Custom("Unsupported receipt type"))
+        );
+        let types = ReceiptTypes::none().with(0x7e);
+        let receipt = Receipt::decode_with_types(&rlp::Rlp::new(&deposit), types).unwrap();
+        assert_eq!(receipt.kind, ReceiptKind::Custom(0x7e));
+        assert_eq!(receipt.logs.len(), 6);
+        assert!(!types.contains(0x7d));
+        assert!(!types.with(0xff).contains(0xff));
+    }
+
+    #[test]
+    fn decode_receipt_outcome() {
+        let decode = |data: &[u8]| rlp::decode::<ReceiptOutcome>(&rlp::encode(&data.to_vec()));
+        assert_eq!(decode(&[]), Ok(ReceiptOutcome::Status(false)));
+        assert_eq!(decode(&[1]), Ok(ReceiptOutcome::Status(true)));
+        assert_eq!(
+            decode(&[2; 32]),
+            Ok(ReceiptOutcome::PostState(H256::repeat_byte(2)))
+        );
+        assert!(decode(&[2]).is_err());
+    }
 }
@@ -0,0 +1,261 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Verification of account and storage proofs (as returned by `eth_getProof`).
+
+use crate::mpt::{self, ProofError};
+use crate::{Address, H256, U256};
+use codec::{Decode, Encode};
+use hex_literal::hex;
+use sp_io::hashing::keccak_256;
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+
+/// Code hash of accounts without code.
+pub const EMPTY_CODE_HASH: [u8; 32] =
+    hex!("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470");
+
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub enum StateProofError {
+    /// Merkle Patricia proof is invalid.
+    InvalidProof(ProofError),
+    /// Proven account can't be decoded.
+    InvalidAccount,
+    /// Proven storage value can't be decoded.
+    InvalidStorageValue,
+}
+
+impl From<ProofError> for StateProofError {
+    fn from(err: ProofError) -> Self {
+        StateProofError::InvalidProof(err)
+    }
+}
+
+/// Account stored in the state trie.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct Account {
+    pub nonce: u64,
+    pub balance: U256,
+    /// Root of the account storage trie.
+    pub storage_root: H256,
+    pub code_hash: H256,
+}
+
+impl Default for Account {
+    fn default() -> Self {
+        Account {
+            nonce: 0,
+            balance: U256::zero(),
+            storage_root: mpt::EMPTY_TRIE_ROOT.into(),
+            code_hash: EMPTY_CODE_HASH.into(),
+        }
+    }
+}
+
+impl rlp::Decodable for Account {
+    fn decode(rlp: &rlp::Rlp) -> Result<Self, rlp::DecoderError> {
+        if rlp.item_count()? != 4 {
+            return Err(rlp::DecoderError::RlpIncorrectListLen);
+        }
+        Ok(Account {
+            nonce: rlp.val_at(0)?,
+            balance: rlp.val_at(1)?,
+            storage_root: rlp.val_at(2)?,
+            code_hash: rlp.val_at(3)?,
+        })
+    }
+}
+
+impl rlp::Encodable for Account {
+    fn rlp_append(&self, s: &mut rlp::RlpStream) {
+        s.begin_list(4);
+        s.append(&self.nonce);
+        s.append(&self.balance);
+        s.append(&self.storage_root);
+        s.append(&self.code_hash);
+    }
+}
+
+/// Verifies the account proof against `state_root`.
+/// Returns `None` if the proof shows that the account doesn't exist.
+pub fn verify_account_proof(
+    state_root: H256,
+    address: Address,
+    proof: &[Vec<u8>],
+) -> Result<Option<Account>, StateProofError> {
+    let Some(data) = mpt::verify_proof(state_root, &keccak_256(address.as_bytes()), proof)? else {
+        return Ok(None);
+    };
+    let account = rlp::decode(&data).map_err(|_| StateProofError::InvalidAccount)?;
+    Ok(Some(account))
+}
+
+/// Verifies the storage proof of `slot` against the account `storage_root`.
+/// Returns zero for the slots which are not set.
+pub fn verify_storage_proof(
+    storage_root: H256,
+    slot: H256,
+    proof: &[Vec<u8>],
+) -> Result<U256, StateProofError> {
+    let Some(data) = mpt::verify_proof(storage_root, &keccak_256(slot.as_bytes()), proof)? else {
+        return Ok(U256::zero());
+    };
+    let value: Vec<u8> = rlp::decode(&data).map_err(|_| StateProofError::InvalidStorageValue)?;
+    if value.is_empty() || value.len() > 32 || value[0] == 0 {
+        return Err(StateProofError::InvalidStorageValue);
+    }
+    Ok(U256::from_big_endian(&value))
+}
+
+/// Storage slot of the Solidity `mapping` value with the ABI-encoded `key`
+/// for the mapping declared at `slot`.
+pub fn mapping_slot(key: H256, slot: H256) -> H256 {
+    let mut data = [0u8; 64];
+    data[..32].copy_from_slice(key.as_bytes());
+    data[32..].copy_from_slice(slot.as_bytes());
+    keccak_256(&data).into()
+}
+
+/// Storage slot of the Solidity `mapping(address => ...)` value.
+pub fn address_mapping_slot(key: Address, slot: U256) -> H256 {
+    mapping_slot(key.into(), uint_to_slot(slot))
+}
+
+/// Storage slot of the Solidity `mapping(uint256 => ...)` value.
+pub fn uint_mapping_slot(key: U256, slot: U256) -> H256 {
+    mapping_slot(uint_to_slot(key), uint_to_slot(slot))
+}
+
+/// Storage slot of the Solidity dynamic array item at `index` for the array declared at `slot`.
+/// Items are assumed to occupy a single slot each.
+pub fn array_item_slot(slot: U256, index: U256) -> H256 {
+    let start = U256::from_big_endian(&keccak_256(uint_to_slot(slot).as_bytes()));
+    uint_to_slot(start.overflowing_add(index).0)
+}
+
+fn uint_to_slot(value: U256) -> H256 {
+    let mut slot = [0u8; 32];
+    value.to_big_endian(&mut slot);
+    slot.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Trie with a single item consisting of a leaf node with the full key path.
+    fn single_leaf_trie(key: &[u8], value: &[u8]) -> (H256, Vec<Vec<u8>>) {
+        let mut path = vec![0x20];
+        path.extend_from_slice(&keccak_256(key));
+        let mut leaf = rlp::RlpStream::new_list(2);
+        leaf.append(&path).append(&value.to_vec());
+        let leaf = leaf.out().to_vec();
+        (keccak_256(&leaf).into(), vec![leaf])
+    }
+
+    #[test]
+    fn verify_account_proof_works() {
+        let address = Address::repeat_byte(1);
+        let account = Account {
+            nonce: 3,
+            balance: 1_000_000_000_000_000_000u64.into(),
+            ..Default::default()
+        };
+        let (state_root, proof) = single_leaf_trie(address.as_bytes(), &rlp::encode(&account));
+
+        assert_eq!(
+            verify_account_proof(state_root, address, &proof),
+            Ok(Some(account))
+        );
+        assert_eq!(
+            verify_account_proof(state_root, Address::repeat_byte(2), &proof),
+            Ok(None)
+        );
+        assert_eq!(
+            verify_account_proof(H256::repeat_byte(1), address, &proof),
+            Err(StateProofError::InvalidProof(ProofError::InvalidNodeHash))
+        );
+
+        let (state_root, proof) = single_leaf_trie(address.as_bytes(), &[0xc0]);
+        assert_eq!(
+            verify_account_proof(state_root, address, &proof),
+            Err(StateProofError::InvalidAccount)
+        );
+    }
+
+    #[test]
+    fn verify_storage_proof_works() {
+        let slot = address_mapping_slot(Address::repeat_byte(1), 2.into());
+        let (storage_root, proof) =
+            single_leaf_trie(slot.as_bytes(), &rlp::encode(&U256::from(12345)));
+
+        assert_eq!(
+            verify_storage_proof(storage_root, slot, &proof),
+            Ok(12345.into())
+        );
+        assert_eq!(
+            verify_storage_proof(storage_root, H256::zero(), &proof),
+            Ok(U256::zero())
+        );
+        assert_eq!(
+            verify_storage_proof(mpt::EMPTY_TRIE_ROOT.into(), slot, &[]),
+            Ok(U256::zero())
+        );
+
+        let (storage_root, proof) = single_leaf_trie(slot.as_bytes(), &rlp::encode(&vec![0u8, 1]));
+        assert_eq!(
+            verify_storage_proof(storage_root, slot, &proof),
+            Err(StateProofError::InvalidStorageValue)
+        );
+    }
+
+    #[test]
+    fn storage_slots_are_computed() {
+        assert_eq!(
+            uint_mapping_slot(U256::zero(), U256::zero()),
+            hex!("ad3228b676f7d3cd4284a5443f17f1962b36e491b30a40b2405849e597ba5fb5").into()
+        );
+        assert_eq!(
+            array_item_slot(U256::zero(), U256::zero()),
+            hex!("290decd9548b62a8d60345a988386fc84ba6bc95484008f6362f93160ef3e563").into()
+        );
+        assert_eq!(
+            array_item_slot(U256::zero(), U256::one()),
+            hex!("290decd9548b62a8d60345a988386fc84ba6bc95484008f6362f93160ef3e564").into()
+        );
+        let address = Address::repeat_byte(1);
+        let mut key = [0u8; 32];
+        key[12..].copy_from_slice(address.as_bytes());
+        assert_eq!(
+            address_mapping_slot(address, 5.into()),
+            mapping_slot(key.into(), uint_to_slot(5.into()))
+        );
+    }
+}
@@ -70,6 +70,8 @@ pub enum SubstrateAppCall {
         message_id: H256,
         transfer_status: XCMAppTransferStatus,
     },
+    /// The message is rejected by the destination because its deadline is expired.
+    ReportMessageTimeout { message_id: H256 },
 }
 
 #[derive(Clone, RuntimeDebug, Encode, Decode, PartialEq, Eq, scale_info::TypeInfo)]
@@ -137,6 +139,46 @@ impl SubstrateBridgeMessageEncode for MultisigVerifierCall {
     }
 }
 
+/// Result of the message dispatch on the destination, reported back to the source.
+#[derive(
+    Clone,
+    Copy,
+    RuntimeDebug,
+    Encode,
+    Decode,
+    PartialEq,
+    Eq,
+    scale_info::TypeInfo,
+    codec::MaxEncodedLen,
+)]
+pub enum MessageDispatchResult {
+    /// The message call is dispatched successfully.
+    Dispatched,
+    /// The message call failed.
+    Failed,
+    /// The message is not dispatched because its deadline is expired,
+    /// the timeout is reported separately.
+    Expired,
+    /// The message is a delivery report, its own delivery is not reported.
+    DeliveryReport,
+}
+
+/// Message to SubstrateBridgeOutboundChannel pallet
+#[derive(Clone, RuntimeDebug, Encode, Decode, PartialEq, Eq, scale_info::TypeInfo)]
+pub enum OutboundChannelCall {
+    /// All messages of the batch are delivered, `results` are in the order of the batch messages.
+    ReportDelivery {
+        batch_nonce: u64,
+        results: Vec<MessageDispatchResult>,
+    },
+}
+
+impl SubstrateBridgeMessageEncode for OutboundChannelCall {
+    fn prepare_message(self) -> Vec<u8> {
+        BridgeCall::OutboundChannel(self).encode()
+    }
+}
+
 /// Substrate bridge message payload
 #[derive(Clone, RuntimeDebug, Encode, Decode, PartialEq, Eq, scale_info::TypeInfo)]
 pub enum BridgeCall {
@@ -144,6 +186,7 @@ pub enum BridgeCall {
     XCMApp(XCMAppCall),
     DataSigner(DataSignerCall),
     MultisigVerifier(MultisigVerifierCall),
+    OutboundChannel(OutboundChannelCall),
 }
 
 impl SubstrateBridgeMessageEncode for BridgeCall {
@@ -168,6 +211,66 @@ pub struct BridgeMessage<MaxPayload: Get<u32>> {
     pub timepoint: GenericTimepoint,
 }
 
+impl<MaxPayload: Get<u32>> BridgeMessage<MaxPayload> {
+    /// Leaf of the messages Merkle tree in [`MerkleCommitment`].
+    pub fn hash(&self) -> H256 {
+        sp_runtime::traits::Keccak256::hash_of(self)
+    }
+}
+
+/// Substrate bridge message with the deadline, passed in [`CommitmentV2`]
+/// and [`MerkleCommitmentV2`].
+#[derive(Encode, Decode, scale_info::TypeInfo, codec::MaxEncodedLen, Derivative)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derivative(
+    Debug(bound = ""),
+    Clone(bound = ""),
+    PartialEq(bound = ""),
+    Eq(bound = "")
+)]
+#[scale_info(skip_type_params(MaxPayload))]
+#[cfg_attr(feature = "std", serde(bound = ""))]
+pub struct BridgeMessageV2<MaxPayload: Get<u32>> {
+    pub payload: BoundedVec<u8, MaxPayload>,
+    pub timepoint: GenericTimepoint,
+    /// Timestamp in milliseconds after which the message is rejected by the destination.
+    pub deadline: Option<u64>,
+}
+
+impl<MaxPayload: Get<u32>> BridgeMessageV2<MaxPayload> {
+    /// Leaf of the messages Merkle tree in [`MerkleCommitmentV2`].
+    ///
+    /// The message without deadline has the same leaf as [`BridgeMessage`],
+    /// so the inclusion proofs of both commitment versions are verified the same way.
+    pub fn hash(&self) -> H256 {
+        match self.deadline {
+            // Same encoding as `BridgeMessage`
+            None => sp_runtime::traits::Keccak256::hash_of(&(&self.payload, &self.timepoint)),
+            Some(_) => sp_runtime::traits::Keccak256::hash_of(self),
+        }
+    }
+}
+
+impl<MaxPayload: Get<u32>> From<BridgeMessage<MaxPayload>> for BridgeMessageV2<MaxPayload> {
+    fn from(message: BridgeMessage<MaxPayload>) -> Self {
+        Self {
+            payload: message.payload,
+            timepoint: message.timepoint,
+            deadline: None,
+        }
+    }
+}
+
+impl<MaxPayload: Get<u32>> From<BridgeMessageV2<MaxPayload>> for BridgeMessage<MaxPayload> {
+    /// Drops the message deadline.
+    fn from(message: BridgeMessageV2<MaxPayload>) -> Self {
+        Self {
+            payload: message.payload,
+            timepoint: message.timepoint,
+        }
+    }
+}
+
 #[derive(
     Clone,
     Copy,
@@ -210,3 +313,156 @@ impl<MaxMessages: Get<u32>, MaxPayload: Get<u32>> Commitment<MaxMessages, MaxPay
         sp_runtime::traits::Keccak256::hash_of(self)
     }
 }
+
+/// Wire-format for commitment of the messages Merkle tree.
+///
+/// Unlike [`Commitment`], allows to prove inclusion of a single message
+/// without passing the whole batch.
+#[derive(Encode, Decode, scale_info::TypeInfo, codec::MaxEncodedLen, Derivative)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derivative(
+    Debug(bound = ""),
+    Clone(bound = ""),
+    PartialEq(bound = ""),
+    Eq(bound = "")
+)]
+#[scale_info(skip_type_params(MaxMessages, MaxPayload))]
+#[cfg_attr(feature = "std", serde(bound = ""))]
+pub struct MerkleCommitment<MaxMessages: Get<u32>, MaxPayload: Get<u32>> {
+    /// Messages passed through the channel in the current commit.
+    pub messages: BoundedVec<BridgeMessage<MaxPayload>, MaxMessages>,
+    pub nonce: u64,
+}
+
+impl<MaxMessages: Get<u32>, MaxPayload: Get<u32>> MerkleCommitment<MaxMessages, MaxPayload> {
+    pub fn messages_root(&self) -> H256 {
+        merkle_root(self.messages.iter().map(BridgeMessage::hash).collect())
+    }
+
+    pub fn hash(&self) -> H256 {
+        merkle_commitment_hash(self.nonce, self.messages.len() as u32, self.messages_root())
+    }
+}
+
+/// Wire-format for commitment of the messages with deadlines.
+#[derive(Encode, Decode, scale_info::TypeInfo, codec::MaxEncodedLen, Derivative)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derivative(
+    Debug(bound = ""),
+    Clone(bound = ""),
+    PartialEq(bound = ""),
+    Eq(bound = "")
+)]
+#[scale_info(skip_type_params(MaxMessages, MaxPayload))]
+#[cfg_attr(feature = "std", serde(bound = ""))]
+pub struct CommitmentV2<MaxMessages: Get<u32>, MaxPayload: Get<u32>> {
+    /// Messages passed through the channel in the current commit.
+    pub messages: BoundedVec<BridgeMessageV2<MaxPayload>, MaxMessages>,
+    pub nonce: u64,
+}
+
+impl<MaxMessages: Get<u32>, MaxPayload: Get<u32>> CommitmentV2<MaxMessages, MaxPayload> {
+    pub fn hash(&self) -> H256 {
+        sp_runtime::traits::Keccak256::hash_of(self)
+    }
+}
+
+/// Wire-format for commitment of the messages with deadlines Merkle tree.
+#[derive(Encode, Decode, scale_info::TypeInfo, codec::MaxEncodedLen, Derivative)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derivative(
+    Debug(bound = ""),
+    Clone(bound = ""),
+    PartialEq(bound = ""),
+    Eq(bound = "")
+)]
+#[scale_info(skip_type_params(MaxMessages, MaxPayload))]
+#[cfg_attr(feature = "std", serde(bound = ""))]
+pub struct MerkleCommitmentV2<MaxMessages: Get<u32>, MaxPayload: Get<u32>> {
+    /// Messages passed through the channel in the current commit.
+    pub messages: BoundedVec<BridgeMessageV2<MaxPayload>, MaxMessages>,
+    pub nonce: u64,
+}
+
+impl<MaxMessages: Get<u32>, MaxPayload: Get<u32>> MerkleCommitmentV2<MaxMessages, MaxPayload> {
+    pub fn messages_root(&self) -> H256 {
+        merkle_root(self.messages.iter().map(BridgeMessageV2::hash).collect())
+    }
+
+    pub fn hash(&self) -> H256 {
+        merkle_commitment_hash(self.nonce, self.messages.len() as u32, self.messages_root())
+    }
+}
+
+/// Hash of the [`MerkleCommitment`] with `messages_count` messages and `messages_root`.
+pub fn merkle_commitment_hash(nonce: u64, messages_count: u32, messages_root: H256) -> H256 {
+    sp_runtime::traits::Keccak256::hash_of(&(nonce, messages_count, messages_root))
+}
+
+/// Hash of the messages Merkle tree node.
+pub fn merkle_node_hash(left: H256, right: H256) -> H256 {
+    sp_runtime::traits::Keccak256::hash_of(&(left, right))
+}
+
+/// Number of the messages Merkle tree levels above the leaves.
+pub fn merkle_depth(leaves_count: u32) -> usize {
+    leaves_count.max(1).next_power_of_two().trailing_zeros() as usize
+}
+
+/// Root of the messages Merkle tree.
+///
+/// The last node of a level with odd number of nodes is paired with zero hash.
+pub fn merkle_root(mut level: Vec<H256>) -> H256 {
+    if level.is_empty() {
+        return H256::zero();
+    }
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| merkle_node_hash(pair[0], pair.get(1).copied().unwrap_or_default()))
+            .collect();
+    }
+    level[0]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sp_core::ConstU32;
+
+    #[test]
+    fn test_merkle_root() {
+        let leaves: Vec<H256> = (1..=3).map(H256::repeat_byte).collect();
+        assert_eq!(merkle_root(vec![]), H256::zero());
+        assert_eq!(merkle_root(leaves[..1].to_vec()), leaves[0]);
+        assert_eq!(
+            merkle_root(leaves.clone()),
+            merkle_node_hash(
+                merkle_node_hash(leaves[0], leaves[1]),
+                merkle_node_hash(leaves[2], H256::zero())
+            )
+        );
+        assert_eq!(merkle_depth(0), 0);
+        assert_eq!(merkle_depth(1), 0);
+        assert_eq!(merkle_depth(3), 2);
+        assert_eq!(merkle_depth(4), 2);
+        assert_eq!(merkle_depth(5), 3);
+    }
+
+    #[test]
+    fn test_message_v2_leaf() {
+        let message = BridgeMessage::<ConstU32<16>> {
+            payload: vec![1, 2, 3].try_into().unwrap(),
+            timepoint: GenericTimepoint::Sora(5),
+        };
+        let mut message_v2 = BridgeMessageV2::from(message.clone());
+        assert_eq!(message_v2.hash(), message.hash());
+
+        message_v2.deadline = Some(1_000);
+        assert_ne!(message_v2.hash(), message.hash());
+        assert_eq!(
+            message_v2.hash(),
+            sp_runtime::traits::Keccak256::hash_of(&message_v2)
+        );
+    }
+}
@@ -36,6 +36,7 @@ use core::fmt::Debug;
 
 use crate::types::AssetKind;
 use crate::types::AuxiliaryDigestItem;
+use crate::types::MessageDirection;
 use crate::H256;
 use crate::U256;
 use crate::{
@@ -48,7 +49,7 @@ use ethereum_types::Address;
 use frame_support::weights::Weight;
 use frame_support::{
     dispatch::{DispatchError, DispatchResult},
-    Parameter,
+    PalletId, Parameter,
 };
 use frame_system::{Config, RawOrigin};
 use scale_info::TypeInfo;
@@ -82,21 +83,40 @@ pub trait OutboundChannel<NetworkId, AccountId, Additional> {
         additional: Additional,
     ) -> Result<H256, DispatchError>;
 
+    /// Submits the message which is rejected by the destination after `deadline`,
+    /// timestamp in milliseconds. Fails for the channels which don't support deadlines.
+    fn submit_with_deadline(
+        _network_id: NetworkId,
+        _who: &RawOrigin<AccountId>,
+        _payload: &[u8],
+        _additional: Additional,
+        _deadline: u64,
+    ) -> Result<H256, DispatchError> {
+        Err(DispatchError::Other("Message deadlines are not supported"))
+    }
+
     fn submit_weight() -> Weight;
 }
 
 /// Dispatch a message
 pub trait MessageDispatch<T: Config, NetworkId, MessageId, Additional> {
+    /// Dispatches the message, returns the result of the message call.
     fn dispatch(
         network_id: NetworkId,
         id: MessageId,
         timepoint: GenericTimepoint,
         payload: &[u8],
         additional: Additional,
-    );
+    ) -> DispatchResult;
 
     fn dispatch_weight(payload: &[u8]) -> Weight;
 
+    /// Returns error if the message is paused, so the channel can reject it
+    /// before the message nonce is consumed and the message can be retried later.
+    fn ensure_not_paused(_network_id: NetworkId, _payload: &[u8]) -> DispatchResult {
+        Ok(())
+    }
+
     #[cfg(feature = "runtime-benchmarks")]
     fn successful_dispatch_event(id: MessageId) -> Option<<T as Config>::RuntimeEvent>;
 }
@@ -435,3 +455,84 @@ impl<AssetId, Balance> BridgeAssetLockChecker<AssetId, Balance> for () {
         Ok(())
     }
 }
+
+/// Checks whether the bridge messages are paused.
+pub trait BridgePauseChecker {
+    /// Returns error if messages of the network in the direction are paused,
+    /// transfers of the `app` are checked as well if it is set.
+    fn ensure_not_paused(
+        network_id: GenericNetworkId,
+        direction: MessageDirection,
+        app: Option<PalletId>,
+    ) -> DispatchResult;
+
+    /// Weight of the check with the app set, the most expensive one.
+    fn ensure_not_paused_weight() -> Weight;
+}
+
+impl BridgePauseChecker for () {
+    fn ensure_not_paused(
+        _network_id: GenericNetworkId,
+        _direction: MessageDirection,
+        _app: Option<PalletId>,
+    ) -> DispatchResult {
+        Ok(())
+    }
+
+    fn ensure_not_paused_weight() -> Weight {
+        Weight::zero()
+    }
+}
+
+/// Returns the app which handles the bridge message call, so the app pause can be checked.
+pub trait BridgeCallApp<Call> {
+    fn call_app(call: &Call) -> Option<PalletId>;
+}
+
+impl<Call> BridgeCallApp<Call> for () {
+    fn call_app(_call: &Call) -> Option<PalletId> {
+        None
+    }
+}
+
+/// Reports to the source network that the message is rejected because its deadline is expired,
+/// so the source can refund the message.
+pub trait MessageTimeoutReporter<NetworkId> {
+    fn report_timeout(network_id: NetworkId, message_id: H256) -> DispatchResult;
+
+    fn report_timeout_weight() -> Weight;
+}
+
+impl<NetworkId> MessageTimeoutReporter<NetworkId> for () {
+    fn report_timeout(_network_id: NetworkId, _message_id: H256) -> DispatchResult {
+        Ok(())
+    }
+
+    fn report_timeout_weight() -> Weight {
+        Weight::zero()
+    }
+}
+
+/// Handles the delivery results reported by the destination network, so the app can
+/// release the dispatched messages and refund the failed ones.
+pub trait MessageDeliveryHandler<NetworkId> {
+    fn on_message_dispatched(network_id: NetworkId, message_id: H256);
+
+    /// Returns `true` if the message is refunded.
+    fn on_message_failed(network_id: NetworkId, message_id: H256) -> bool;
+
+    /// Weight of the most expensive of the handlers.
+    fn on_message_delivered_weight() -> Weight;
+}
+
+impl<NetworkId> MessageDeliveryHandler<NetworkId> for () {
+    fn on_message_dispatched(_network_id: NetworkId, _message_id: H256) {}
+
+    fn on_message_failed(_network_id: NetworkId, _message_id: H256) -> bool {
+        false
+    }
+
+    fn on_message_delivered_weight() -> Weight {
+        Weight::zero()
+    }
+}
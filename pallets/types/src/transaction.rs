@@ -0,0 +1,396 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Ethereum transactions as stored in the transactions trie.
+
+use crate::{Address, H256, U256};
+use codec::{Decode, Encode};
+use rlp::RlpStream;
+use sp_io::hashing::keccak_256;
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub enum TransactionKind {
+    Legacy,
+    /// EIP-2930
+    AccessList,
+    /// EIP-1559
+    DynamicFee,
+    /// EIP-4844
+    Blob,
+}
+
+impl TransactionKind {
+    /// Returns transaction kind and number of its fields (with signature).
+    fn from_type(tx_type: u8) -> Result<(Self, usize), rlp::DecoderError> {
+        match tx_type {
+            1 => Ok((TransactionKind::AccessList, 11)),
+            2 => Ok((TransactionKind::DynamicFee, 12)),
+            3 => Ok((TransactionKind::Blob, 14)),
+            _ => Err(rlp::DecoderError::Custom("Unsupported transaction type")),
+        }
+    }
+}
+
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+pub struct Transaction {
+    pub kind: TransactionKind,
+    /// Chain id, `None` for legacy transactions without replay protection (EIP-155).
+    pub chain_id: Option<u64>,
+    pub nonce: U256,
+    pub gas_limit: U256,
+    /// Call recipient, `None` for contract creation.
+    pub to: Option<Address>,
+    pub value: U256,
+    /// Call data or contract init code.
+    pub input: Vec<u8>,
+    /// Signature recovery id (0 or 1).
+    pub recovery_id: u8,
+    pub r: H256,
+    pub s: H256,
+    /// Hash of the transaction payload signed by the sender.
+    pub signing_hash: H256,
+    /// Transaction hash.
+    pub hash: H256,
+}
+
+impl Transaction {
+    /// Recovers the transaction sender from the signature.
+    pub fn sender(&self) -> Option<Address> {
+        let mut signature = [0u8; 65];
+        signature[..32].copy_from_slice(self.r.as_bytes());
+        signature[32..64].copy_from_slice(self.s.as_bytes());
+        signature[64] = self.recovery_id;
+        let public =
+            sp_io::crypto::secp256k1_ecdsa_recover(&signature, &self.signing_hash.0).ok()?;
+        Some(Address::from_slice(&keccak_256(&public)[12..]))
+    }
+
+    /// Decodes the transaction fields in legacy or typed transaction order.
+    fn decode_fields(
+        kind: TransactionKind,
+        rlp: &rlp::Rlp,
+        signing_hash: H256,
+        hash: H256,
+    ) -> Result<Self, rlp::DecoderError> {
+        // Typed transactions start with chain id, dynamic fee ones have two fee fields
+        let (chain_id, offset) = match kind {
+            TransactionKind::Legacy => (None, 0),
+            _ => (Some(rlp.val_at(0)?), 1),
+        };
+        let fee_fields = match kind {
+            TransactionKind::DynamicFee | TransactionKind::Blob => 2,
+            _ => 1,
+        };
+        let gas_limit_index = offset + 1 + fee_fields;
+        let to: Vec<u8> = rlp.val_at(gas_limit_index + 1)?;
+        let to = match to.len() {
+            0 if kind != TransactionKind::Blob => None,
+            20 => Some(Address::from_slice(&to)),
+            _ => return Err(rlp::DecoderError::Custom("Invalid transaction recipient")),
+        };
+        let signature_index = rlp.item_count()? - 3;
+        let v: u64 = rlp.val_at(signature_index)?;
+        let (chain_id, recovery_id) = match kind {
+            TransactionKind::Legacy => match v {
+                27 | 28 => (None, (v - 27) as u8),
+                v if v >= 35 => (Some((v - 35) / 2), ((v - 35) % 2) as u8),
+                _ => return Err(rlp::DecoderError::Custom("Invalid transaction signature")),
+            },
+            _ if v <= 1 => (chain_id, v as u8),
+            _ => return Err(rlp::DecoderError::Custom("Invalid transaction signature")),
+        };
+        Ok(Transaction {
+            kind,
+            chain_id,
+            nonce: rlp.val_at(offset)?,
+            gas_limit: rlp.val_at(gas_limit_index)?,
+            to,
+            value: rlp.val_at(gas_limit_index + 2)?,
+            input: rlp.val_at(gas_limit_index + 3)?,
+            recovery_id,
+            r: u256_to_h256(rlp.val_at(signature_index + 1)?),
+            s: u256_to_h256(rlp.val_at(signature_index + 2)?),
+            signing_hash,
+            hash,
+        })
+    }
+
+    fn decode_legacy(rlp: &rlp::Rlp) -> Result<Self, rlp::DecoderError> {
+        if rlp.item_count()? != 9 {
+            return Err(rlp::DecoderError::RlpIncorrectListLen);
+        }
+        let v: u64 = rlp.val_at(6)?;
+        let mut s = RlpStream::new();
+        if v >= 35 {
+            // EIP-155 replay protected transaction
+            s.begin_list(9);
+            append_raw_items(&mut s, rlp, 6)?;
+            s.append(&((v - 35) / 2));
+            s.append_empty_data();
+            s.append_empty_data();
+        } else {
+            s.begin_list(6);
+            append_raw_items(&mut s, rlp, 6)?;
+        }
+        let signing_hash = keccak_256(&s.out()).into();
+        let hash = keccak_256(rlp.as_raw()).into();
+        Self::decode_fields(TransactionKind::Legacy, rlp, signing_hash, hash)
+    }
+
+    fn decode_typed(data: &[u8]) -> Result<Self, rlp::DecoderError> {
+        let (tx_type, payload) = data.split_first().ok_or(rlp::DecoderError::RlpIsTooShort)?;
+        let (kind, fields) = TransactionKind::from_type(*tx_type)?;
+        let rlp = rlp::Rlp::new(payload);
+        if !rlp.is_list() {
+            return Err(rlp::DecoderError::RlpExpectedToBeList);
+        }
+        if rlp.item_count()? != fields {
+            return Err(rlp::DecoderError::RlpIncorrectListLen);
+        }
+        let mut s = RlpStream::new();
+        s.begin_list(fields - 3);
+        append_raw_items(&mut s, &rlp, fields - 3)?;
+        let mut unsigned = vec![*tx_type];
+        unsigned.extend_from_slice(&s.out());
+        let signing_hash = keccak_256(&unsigned).into();
+        let hash = keccak_256(data).into();
+        Self::decode_fields(kind, &rlp, signing_hash, hash)
+    }
+}
+
+impl rlp::Decodable for Transaction {
+    fn decode(rlp: &rlp::Rlp) -> Result<Self, rlp::DecoderError> {
+        if rlp.is_data() {
+            // Typed transaction
+            Self::decode_typed(rlp.as_raw())
+        } else if rlp.is_list() {
+            Self::decode_legacy(rlp)
+        } else {
+            Err(rlp::DecoderError::RlpExpectedToBeList)
+        }
+    }
+}
+
+fn append_raw_items(
+    s: &mut RlpStream,
+    rlp: &rlp::Rlp,
+    count: usize,
+) -> Result<(), rlp::DecoderError> {
+    for i in 0..count {
+        s.append_raw(rlp.at(i)?.as_raw(), 1);
+    }
+    Ok(())
+}
+
+fn u256_to_h256(value: U256) -> H256 {
+    let mut bytes = [0u8; 32];
+    value.to_big_endian(&mut bytes);
+    bytes.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hex_literal::hex;
+    use sp_core::{ecdsa, Pair};
+
+    #[test]
+    fn decode_eip155_transaction() {
+        // Example from EIP-155
+        let raw = hex!("f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83");
+        let tx: Transaction = rlp::decode(&raw).unwrap();
+        assert_eq!(tx.kind, TransactionKind::Legacy);
+        assert_eq!(tx.chain_id, Some(1));
+        assert_eq!(tx.nonce, 9.into());
+        assert_eq!(tx.gas_limit, 21000.into());
+        assert_eq!(tx.to, Some(Address::repeat_byte(0x35)));
+        assert_eq!(tx.value, 1_000_000_000_000_000_000u64.into());
+        assert!(tx.input.is_empty());
+        assert_eq!(
+            tx.signing_hash,
+            hex!("daf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53").into()
+        );
+        assert_eq!(tx.hash, keccak_256(&raw).into());
+        assert_eq!(
+            tx.sender(),
+            Some(hex!("9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f").into())
+        );
+    }
+
+    fn signed_typed_transaction(tx_type: u8, fields: &[Vec<u8>]) -> (Vec<u8>, Address) {
+        let pair = ecdsa::Pair::from_seed(&[7; 32]);
+        let mut s = RlpStream::new_list(fields.len());
+        for field in fields {
+            s.append_raw(field, 1);
+        }
+        let mut unsigned = vec![tx_type];
+        unsigned.extend_from_slice(&s.out());
+        let signature = pair.sign_prehashed(&keccak_256(&unsigned));
+
+        let mut s = RlpStream::new_list(fields.len() + 3);
+        for field in fields {
+            s.append_raw(field, 1);
+        }
+        s.append(&signature.0[64]);
+        s.append(&U256::from_big_endian(&signature.0[..32]));
+        s.append(&U256::from_big_endian(&signature.0[32..64]));
+        let mut raw = vec![tx_type];
+        raw.extend_from_slice(&s.out());
+
+        let public =
+            sp_io::crypto::secp256k1_ecdsa_recover(&signature.0, &keccak_256(&unsigned)).unwrap();
+        (raw, Address::from_slice(&keccak_256(&public)[12..]))
+    }
+
+    fn encode<T: rlp::Encodable>(value: &T) -> Vec<u8> {
+        rlp::encode(value).to_vec()
+    }
+
+    #[test]
+    fn decode_typed_transactions() {
+        let to = Address::repeat_byte(0x11);
+        let input = hex!("a9059cbb").to_vec();
+        let empty_list = rlp::EMPTY_LIST_RLP.to_vec();
+        let cases = [
+            (
+                1u8,
+                TransactionKind::AccessList,
+                vec![
+                    encode(&5u64),
+                    encode(&1u64),
+                    encode(&10u64),
+                    encode(&50_000u64),
+                    encode(&to),
+                    encode(&100u64),
+                    encode(&input),
+                    empty_list.clone(),
+                ],
+            ),
+            (
+                2,
+                TransactionKind::DynamicFee,
+                vec![
+                    encode(&5u64),
+                    encode(&1u64),
+                    encode(&1u64),
+                    encode(&10u64),
+                    encode(&50_000u64),
+                    encode(&to),
+                    encode(&100u64),
+                    encode(&input),
+                    empty_list.clone(),
+                ],
+            ),
+            (
+                3,
+                TransactionKind::Blob,
+                vec![
+                    encode(&5u64),
+                    encode(&1u64),
+                    encode(&1u64),
+                    encode(&10u64),
+                    encode(&50_000u64),
+                    encode(&to),
+                    encode(&100u64),
+                    encode(&input),
+                    empty_list.clone(),
+                    encode(&1u64),
+                    empty_list.clone(),
+                ],
+            ),
+        ];
+        for (tx_type, kind, fields) in cases {
+            let (raw, sender) = signed_typed_transaction(tx_type, &fields);
+            let tx: Transaction = rlp::decode(&raw).unwrap();
+            assert_eq!(tx.kind, kind);
+            assert_eq!(tx.chain_id, Some(5));
+            assert_eq!(tx.nonce, 1.into());
+            assert_eq!(tx.gas_limit, 50_000.into());
+            assert_eq!(tx.to, Some(to));
+            assert_eq!(tx.value, 100.into());
+            assert_eq!(tx.input, input);
+            assert_eq!(tx.hash, keccak_256(&raw).into());
+            assert_eq!(tx.sender(), Some(sender));
+        }
+    }
+
+    #[test]
+    fn decode_invalid_transactions() {
+        let (mut raw, _) = signed_typed_transaction(
+            2,
+            &[
+                encode(&5u64),
+                encode(&1u64),
+                encode(&1u64),
+                encode(&10u64),
+                encode(&50_000u64),
+                encode(&Vec::<u8>::new()),
+                encode(&100u64),
+                encode(&Vec::<u8>::new()),
+                rlp::EMPTY_LIST_RLP.to_vec(),
+            ],
+        );
+        let tx: Transaction = rlp::decode(&raw).unwrap();
+        assert_eq!(tx.to, None);
+
+        raw[0] = 3;
+        assert_eq!(
+            rlp::decode::<Transaction>(&raw),
+            Err(rlp::DecoderError::RlpIncorrectListLen)
+        );
+        raw[0] = 4;
+        assert_eq!(
+            rlp::decode::<Transaction>(&raw),
+            Err(rlp::DecoderError::Custom("Unsupported transaction type"))
+        );
+    }
+
+    #[test]
+    fn check_transaction_proof() {
+        let raw = hex!("f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83").to_vec();
+        // Single transaction trie, the key is RLP encoded index 0
+        let mut leaf = RlpStream::new_list(2);
+        leaf.append(&vec![0x20u8, 0x80]).append(&raw);
+        let leaf = leaf.out().to_vec();
+        let header = crate::Header {
+            transactions_root: keccak_256(&leaf).into(),
+            ..Default::default()
+        };
+
+        let tx = header
+            .check_transaction_proof_at(0, &[leaf.clone()])
+            .unwrap()
+            .unwrap();
+        assert_eq!(tx.hash, keccak_256(&raw).into());
+        assert!(header.check_transaction_proof_at(1, &[leaf]).is_none());
+    }
+}
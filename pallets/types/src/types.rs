@@ -35,6 +35,7 @@ use crate::substrate::SubAssetInfo;
 use crate::{GenericTimepoint, H256};
 use codec::{Decode, Encode};
 use derivative::Derivative;
+use frame_support::PalletId;
 use frame_support::RuntimeDebug;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
@@ -46,12 +47,45 @@ use sp_std::vec::Vec;
 
 use crate::GenericNetworkId;
 
-#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+#[derive(
+    Encode,
+    Decode,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    RuntimeDebug,
+    scale_info::TypeInfo,
+    codec::MaxEncodedLen,
+)]
 pub enum MessageDirection {
     Inbound,
     Outbound,
 }
 
+/// Part of the bridge which can be paused.
+#[derive(
+    Encode,
+    Decode,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    RuntimeDebug,
+    scale_info::TypeInfo,
+    codec::MaxEncodedLen,
+)]
+pub enum PauseScope {
+    /// All networks of the bridge.
+    Global,
+    /// All messages to and from the network.
+    Network(GenericNetworkId),
+    /// Messages of the network in one direction.
+    Direction(GenericNetworkId, MessageDirection),
+    /// Transfers of the app with the network.
+    App(GenericNetworkId, PalletId),
+}
+
 #[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
 pub struct MessageId {
     sender: GenericNetworkId,
@@ -202,6 +236,31 @@ pub enum MessageStatus {
     Approved,
 }
 
+/// Lifecycle state of a bridge channel with a network.
+#[derive(
+    Clone,
+    Copy,
+    RuntimeDebug,
+    Encode,
+    Decode,
+    PartialEq,
+    Eq,
+    scale_info::TypeInfo,
+    codec::MaxEncodedLen,
+)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum ChannelState {
+    /// Channel is known, but doesn't accept messages yet.
+    Registered,
+    /// Channel accepts messages.
+    Active,
+    /// Channel doesn't accept new messages, already accepted ones are still delivered.
+    Draining,
+    /// Channel doesn't accept messages anymore.
+    Closed,
+}
+
 #[derive(
     Clone,
     Copy,
@@ -44,3 +44,13 @@ pub fn threshold(peers: u32) -> u32 {
 pub fn make_offchain_key(network_id: GenericNetworkId, batch_nonce: u64) -> Vec<u8> {
     (CHANNEL_INDEXING_PREFIX, network_id, batch_nonce).encode()
 }
+
+/// Key of the latest batch nonce committed to the network.
+pub fn make_offchain_latest_nonce_key(network_id: GenericNetworkId) -> Vec<u8> {
+    (CHANNEL_INDEXING_PREFIX, b"latest", network_id).encode()
+}
+
+/// Key of the last batch nonce pruned from the network commitments.
+pub fn make_offchain_pruned_nonce_key(network_id: GenericNetworkId) -> Vec<u8> {
+    (CHANNEL_INDEXING_PREFIX, b"pruned", network_id).encode()
+}